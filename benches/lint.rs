@@ -0,0 +1,80 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use criterion::{
+  criterion_group, criterion_main, BenchmarkId, Criterion, Throughput,
+};
+use deno_lint::linter::LinterBuilder;
+use deno_lint::rules::{get_all_rules, get_recommended_rules, LintRule};
+use deno_lint::swc_util::get_syntax_for_file_name;
+use std::fs;
+use std::path::Path;
+
+struct Input {
+  file_name: String,
+  source: String,
+}
+
+/// Loads the corpus from `benches/testdata`, sorted by file name.
+fn load_corpus() -> Vec<Input> {
+  let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("benches/testdata");
+  let mut inputs = fs::read_dir(&dir)
+    .expect("Failed to read benches/testdata")
+    .map(|entry| entry.unwrap().path())
+    .filter(|path| {
+      matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("js") | Some("ts")
+      )
+    })
+    .map(|path| Input {
+      file_name: path.file_name().unwrap().to_string_lossy().into_owned(),
+      source: fs::read_to_string(&path).unwrap(),
+    })
+    .collect::<Vec<_>>();
+  inputs.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+  inputs
+}
+
+fn lint(input: &Input, rules: Vec<Box<dyn LintRule>>) -> usize {
+  let mut linter = LinterBuilder::default()
+    .syntax(get_syntax_for_file_name(&input.file_name))
+    .rules(rules)
+    .build();
+  linter
+    .lint(input.file_name.clone(), input.source.clone())
+    .expect("Failed to lint")
+    .len()
+}
+
+/// Creates the rules linted with in a scenario.
+type RulesFn = fn() -> Vec<Box<dyn LintRule>>;
+
+fn single_rule() -> Vec<Box<dyn LintRule>> {
+  get_all_rules()
+    .into_iter()
+    .filter(|rule| rule.code() == "no-unused-vars")
+    .collect()
+}
+
+fn bench_lint(c: &mut Criterion) {
+  let scenarios: Vec<(&str, RulesFn)> = vec![
+    // Parsing and scope / control flow analysis, without any rules.
+    ("parse", Vec::new),
+    ("single rule", single_rule),
+    ("recommended rules", get_recommended_rules),
+    ("all rules", get_all_rules),
+  ];
+
+  for input in &load_corpus() {
+    let mut group = c.benchmark_group(&input.file_name);
+    group.throughput(Throughput::Bytes(input.source.len() as u64));
+    for (scenario, rules) in &scenarios {
+      group.bench_function(BenchmarkId::from_parameter(scenario), |b| {
+        b.iter(|| lint(input, rules()))
+      });
+    }
+    group.finish();
+  }
+}
+
+criterion_group!(benches, bench_lint);
+criterion_main!(benches);
@@ -0,0 +1,123 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+//! String-in, string-out entry point meant to be wrapped by bindings to
+//! other languages. With the `wasm` feature it's exported to JavaScript
+//! through wasm-bindgen.
+use crate::config::LintConfig;
+use crate::swc_util::get_syntax_for_file_name;
+use std::panic;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Lints `source` with rules selected by `config_json`, in the format of
+/// `LintConfig`, and returns the diagnostics serialized to JSON. Syntax
+/// errors are reported as `parse-error` diagnostics. Invalid configs and
+/// panics are returned as error messages.
+pub fn lint_to_json(
+  file_name: &str,
+  source: &str,
+  config_json: &str,
+) -> Result<String, String> {
+  let config: LintConfig =
+    serde_json::from_str(config_json).map_err(|err| err.to_string())?;
+  let mut linter = config
+    .linter_builder()
+    .map_err(|err| err.to_string())?
+    .syntax(get_syntax_for_file_name(file_name))
+    .report_parse_errors(true)
+    .build();
+
+  let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+    linter.lint(file_name.to_string(), source.to_string())
+  }));
+  let diagnostics = match result {
+    Ok(diagnostics) => diagnostics.map_err(|err| err.to_string())?,
+    Err(panic) => {
+      let message = match panic.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => match panic.downcast_ref::<String>() {
+          Some(message) => message.clone(),
+          None => "unknown error".to_string(),
+        },
+      };
+      return Err(format!("Linter panicked: {}", message));
+    }
+  };
+  serde_json::to_string(&diagnostics).map_err(|err| err.to_string())
+}
+
+/// JavaScript binding of `lint_to_json`, returning the diagnostics as a
+/// JSON string. Errors are thrown as JavaScript `Error`s.
+///
+/// `wasm32-unknown-unknown` aborts on panic, so there panics surface as a
+/// `WebAssembly.RuntimeError` instead of being caught by `lint_to_json`.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn lint(
+  file_name: &str,
+  source: &str,
+  config_json: &str,
+) -> Result<String, JsValue> {
+  lint_to_json(file_name, source, config_json)
+    .map_err(|err| js_sys::Error::new(&err).into())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::Value;
+
+  fn lint(file_name: &str, source: &str, config_json: &str) -> Value {
+    let json = lint_to_json(file_name, source, config_json).unwrap();
+    serde_json::from_str(&json).unwrap()
+  }
+
+  #[test]
+  fn lint_with_config() {
+    let config = r#"{ "rules": { "tags": [], "include": ["no-var"] } }"#;
+    let diagnostics = lint("file.js", "var a = 1;\ndebugger;\n", config);
+    let diagnostics = diagnostics.as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["code"], "no-var");
+    assert_eq!(diagnostics[0]["filename"], "file.js");
+    assert_eq!(
+      diagnostics[0]["range"]["start"],
+      serde_json::json!({ "line": 1, "col": 0, "col_utf16": 0, "byte_pos": 0 })
+    );
+  }
+
+  #[test]
+  fn lint_with_parse_error() {
+    let diagnostics = lint("file.ts", "let a = ;", "{}");
+    assert_eq!(diagnostics[0]["code"], "parse-error");
+  }
+
+  #[test]
+  fn invalid_config() {
+    let err = lint_to_json("file.ts", "", r#"{ "rules": { "include": 1 } }"#)
+      .unwrap_err();
+    assert!(err.contains("invalid type"), "{}", err);
+    let err = lint_to_json(
+      "file.ts",
+      "",
+      r#"{ "rules": { "include": ["no-such-rule"] } }"#,
+    )
+    .unwrap_err();
+    assert_eq!(err, "Unknown rule \"no-such-rule\"");
+  }
+}
+
+#[cfg(all(test, feature = "wasm", target_arch = "wasm32"))]
+mod wasm_tests {
+  use wasm_bindgen::JsCast;
+  use wasm_bindgen_test::*;
+
+  #[wasm_bindgen_test]
+  fn lint() {
+    let json = super::lint("file.js", "debugger;\n", "{}").unwrap();
+    let diagnostics: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(diagnostics[0]["code"], "no-debugger");
+
+    let err = super::lint("file.js", "", "[]").unwrap_err();
+    assert!(err.is_instance_of::<js_sys::Error>());
+  }
+}
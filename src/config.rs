@@ -0,0 +1,574 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use crate::diagnostic::Severity;
+use crate::globals::{env_globals, DEFAULT_ENV};
+use crate::linter::LinterBuilder;
+use crate::rules::get_all_rules;
+use crate::rules::resolve_deprecated_code;
+use crate::rules::LintRule;
+use crate::rules::RuleConfigError;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// Configuration of the linter, usually deserialized from a JSON file.
+///
+/// ```json
+/// {
+///   "rules": {
+///     "tags": ["recommended"],
+///     "include": ["no-cond-assign"],
+///     "exclude": ["no-fallthrough"],
+///     "options": { "no-cond-assign": "always" },
+///     "no-fallthrough": "warn",
+///     "no-empty": { "level": "hint", "options": null }
+///   },
+///   "env": ["es2021", "deno"],
+///   "globals": ["myInjectedGlobal"]
+/// }
+/// ```
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LintConfig {
+  pub rules: RulesConfig,
+  /// Environments whose globals are predefined.
+  pub env: Vec<String>,
+  /// Additional predefined globals.
+  pub globals: Vec<String>,
+}
+
+impl Default for LintConfig {
+  fn default() -> Self {
+    Self {
+      rules: RulesConfig::default(),
+      env: DEFAULT_ENV.iter().map(|s| s.to_string()).collect(),
+      globals: vec![],
+    }
+  }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct RulesConfig {
+  /// Rules having any of these tags are enabled.
+  pub tags: Vec<String>,
+  /// Rules enabled regardless of their tags.
+  pub include: Vec<String>,
+  /// Rules disabled even if they are tagged or included.
+  pub exclude: Vec<String>,
+  /// Per-rule options passed to `LintRule::new_with_config`.
+  pub options: HashMap<String, serde_json::Value>,
+  /// Rules configured by their code, e.g. `"no-fallthrough": "warn"`.
+  /// Listed rules are enabled unless their level is `"off"`.
+  #[serde(flatten)]
+  pub levels: HashMap<String, RuleLevel>,
+}
+
+/// Configuration of a single rule, either just its level or an object
+/// with an optional level and options. Levels are `"error"`, `"warn"`,
+/// `"hint"` and `"off"`. Rules without a level keep their default severity.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum RuleLevel {
+  Level(String),
+  Detailed {
+    #[serde(default)]
+    level: Option<String>,
+    #[serde(default)]
+    options: Option<serde_json::Value>,
+  },
+}
+
+impl Default for RulesConfig {
+  fn default() -> Self {
+    Self {
+      tags: vec!["recommended".to_string()],
+      include: vec![],
+      exclude: vec![],
+      options: HashMap::new(),
+      levels: HashMap::new(),
+    }
+  }
+}
+
+/// Codes of diagnostics reported by the linter itself rather than by rules.
+/// They are enabled by default and can be turned off with `exclude`.
+pub(crate) const BUILTIN_CODES: &[&str] =
+  &["ban-unknown-rule-code", "ban-unused-ignore"];
+
+#[derive(Clone, Debug)]
+pub enum ConfigError {
+  UnknownRule(String),
+  UnknownEnv(String),
+  /// Invalid level of the rule with the first code.
+  InvalidLevel(String, String),
+  InvalidRuleConfig(RuleConfigError),
+}
+
+impl Error for ConfigError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      ConfigError::InvalidRuleConfig(err) => Some(err),
+      _ => None,
+    }
+  }
+}
+
+impl fmt::Display for ConfigError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ConfigError::UnknownRule(code) => write!(f, "Unknown rule \"{}\"", code),
+      ConfigError::UnknownEnv(env) => {
+        write!(f, "Unknown environment \"{}\"", env)
+      }
+      ConfigError::InvalidLevel(code, level) => write!(
+        f,
+        "Invalid level \"{}\" for rule \"{}\", expected \"error\", \"warn\", \"hint\" or \"off\"",
+        level, code
+      ),
+      ConfigError::InvalidRuleConfig(err) => err.fmt(f),
+    }
+  }
+}
+
+impl From<RuleConfigError> for ConfigError {
+  fn from(err: RuleConfigError) -> Self {
+    ConfigError::InvalidRuleConfig(err)
+  }
+}
+
+impl RulesConfig {
+  /// Replaces deprecated camelCase rule codes with the current ones,
+  /// logging a warning for each of them, and merges `levels` into the
+  /// other fields. Returns the resulting config and configured severities.
+  fn resolve(
+    &self,
+  ) -> Result<(RulesConfig, HashMap<String, Severity>), ConfigError> {
+    let resolve = |code: &String| match resolve_deprecated_code(code) {
+      Some(new_code) => {
+        warn!(
+          "Rule code \"{}\" is deprecated, use \"{}\" instead",
+          code, new_code
+        );
+        new_code.to_string()
+      }
+      None => code.clone(),
+    };
+
+    let mut config = RulesConfig {
+      tags: self.tags.clone(),
+      include: self.include.iter().map(resolve).collect(),
+      exclude: self.exclude.iter().map(resolve).collect(),
+      options: self
+        .options
+        .iter()
+        .map(|(code, options)| (resolve(code), options.clone()))
+        .collect(),
+      levels: HashMap::new(),
+    };
+
+    let mut severities = HashMap::new();
+    for (code, entry) in &self.levels {
+      let code = resolve(code);
+      let (level, options) = match entry {
+        RuleLevel::Level(level) => (Some(level), &None),
+        RuleLevel::Detailed { level, options } => (level.as_ref(), options),
+      };
+      if let Some(options) = options {
+        config.options.insert(code.clone(), options.clone());
+      }
+      let severity = match level.map(|level| level.as_str()) {
+        // Without a level the rule keeps its default severity.
+        None => None,
+        Some("error") => Some(Severity::Error),
+        Some("warn") => Some(Severity::Warning),
+        Some("hint") => Some(Severity::Hint),
+        Some("off") => {
+          config.exclude.push(code);
+          continue;
+        }
+        Some(level) => {
+          return Err(ConfigError::InvalidLevel(code, level.to_string()))
+        }
+      };
+      config.include.push(code.clone());
+      if let Some(severity) = severity {
+        severities.insert(code, severity);
+      }
+    }
+
+    Ok((config, severities))
+  }
+}
+
+impl LintConfig {
+  /// Creates instances of all rules selected by this config.
+  pub fn get_rules(&self) -> Result<Vec<Box<dyn LintRule>>, ConfigError> {
+    let all_rules = get_all_rules();
+    let codes = all_rules.iter().map(|r| r.code()).collect::<Vec<_>>();
+
+    let (config, _) = &self.rules.resolve()?;
+    for code in config
+      .include
+      .iter()
+      .chain(config.exclude.iter())
+      .chain(config.options.keys())
+    {
+      if !codes.contains(&code.as_str())
+        && !BUILTIN_CODES.contains(&code.as_str())
+      {
+        return Err(ConfigError::UnknownRule(code.to_string()));
+      }
+    }
+
+    let mut rules = vec![];
+    for rule in all_rules {
+      let code = rule.code();
+      let is_tagged = rule
+        .tags()
+        .iter()
+        .any(|tag| config.tags.iter().any(|t| t == tag));
+      let is_included = config.include.iter().any(|c| c == code);
+      let is_excluded = config.exclude.iter().any(|c| c == code);
+
+      if is_excluded || !(is_tagged || is_included) {
+        continue;
+      }
+
+      let rule = match config.options.get(code) {
+        Some(options) if !options.is_null() => {
+          rule.new_with_config(options.clone())?
+        }
+        _ => rule,
+      };
+      rules.push(rule);
+    }
+
+    Ok(rules)
+  }
+
+  /// Creates a `LinterBuilder` with rules, built-in checks and globals
+  /// selected by this config.
+  pub fn linter_builder(&self) -> Result<LinterBuilder, ConfigError> {
+    let rules = self.get_rules()?;
+    if let Some(env) = self.env.iter().find(|env| env_globals(env).is_none()) {
+      return Err(ConfigError::UnknownEnv(env.to_string()));
+    }
+    let (config, severities) = self.rules.resolve()?;
+    let is_excluded = |code: &str| config.exclude.iter().any(|c| c == code);
+
+    Ok(
+      LinterBuilder::default()
+        .rules(rules)
+        .env(self.env.iter().map(|s| s.as_str()).collect())
+        .globals(self.globals.iter().map(|s| s.as_str()).collect())
+        .lint_unknown_rules(!is_excluded("ban-unknown-rule-code"))
+        .lint_unused_ignore_directives(!is_excluded("ban-unused-ignore"))
+        .severity_overrides(severities),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::get_recommended_rules;
+  use crate::test_util::lint_with_rule;
+
+  fn parse(json: &str) -> LintConfig {
+    serde_json::from_str(json).unwrap()
+  }
+
+  fn codes(config: &LintConfig) -> Vec<&'static str> {
+    config
+      .get_rules()
+      .unwrap()
+      .iter()
+      .map(|r| r.code())
+      .collect()
+  }
+
+  #[test]
+  fn default_config() {
+    let recommended = get_recommended_rules()
+      .iter()
+      .map(|r| r.code())
+      .collect::<Vec<_>>();
+    assert_eq!(codes(&parse("{}")), recommended);
+  }
+
+  #[test]
+  fn include_only() {
+    let config = parse(
+      r#"{ "rules": { "tags": [], "include": ["no-var", "no-debugger"] } }"#,
+    );
+    assert_eq!(codes(&config), vec!["no-debugger", "no-var"]);
+
+    let config = parse(r#"{ "rules": { "include": ["no-var"] } }"#);
+    let codes = codes(&config);
+    assert!(codes.contains(&"no-var"));
+    assert!(codes.contains(&"no-debugger"));
+  }
+
+  #[test]
+  fn exclude_only() {
+    let config = parse(r#"{ "rules": { "exclude": ["no-fallthrough"] } }"#);
+    let codes = codes(&config);
+    assert!(!codes.contains(&"no-fallthrough"));
+    assert_eq!(codes.len(), get_recommended_rules().len() - 1);
+
+    let config =
+      parse(r#"{ "rules": { "include": ["no-var"], "exclude": ["no-var"] } }"#);
+    assert!(!self::codes(&config).contains(&"no-var"));
+  }
+
+  #[test]
+  fn unknown_rule() {
+    for json in &[
+      r#"{ "rules": { "include": ["no-such-rule"] } }"#,
+      r#"{ "rules": { "exclude": ["no-such-rule"] } }"#,
+      r#"{ "rules": { "options": { "no-such-rule": true } } }"#,
+      r#"{ "rules": { "no-such-rule": "warn" } }"#,
+    ] {
+      match parse(json).get_rules() {
+        Err(ConfigError::UnknownRule(code)) => assert_eq!(code, "no-such-rule"),
+        _ => panic!("expected unknown rule error for {}", json),
+      }
+    }
+  }
+
+  #[test]
+  fn options_forwarded() {
+    let source = "if ((a = b)) {}";
+    let config = parse(
+      r#"{ "rules": {
+        "tags": [],
+        "include": ["no-cond-assign"],
+        "options": { "no-cond-assign": "always" }
+      } }"#,
+    );
+    let mut rules = config.get_rules().unwrap();
+    assert_eq!(rules.len(), 1);
+    assert_eq!(lint_with_rule(rules.remove(0), source).len(), 1);
+
+    let config =
+      parse(r#"{ "rules": { "options": { "no-cond-assign": "sometimes" } } }"#);
+    match config.get_rules() {
+      Err(ConfigError::InvalidRuleConfig(err)) => {
+        assert_eq!(err.code, "no-cond-assign")
+      }
+      _ => panic!("expected invalid rule config error"),
+    }
+  }
+
+  #[test]
+  fn severity_levels() {
+    let source = "switch (a) {\n  case 1:\n    b();\n  case 2:\n    c();\n}\n";
+    let lint = |json: &str| {
+      parse(json)
+        .linter_builder()
+        .unwrap()
+        .build()
+        .lint("config_test.ts".to_string(), source.to_string())
+        .unwrap()
+    };
+
+    let errors =
+      lint(r#"{ "rules": { "tags": [], "no-fallthrough": "error" } }"#);
+    let warnings = lint(
+      r#"{ "rules": { "tags": [], "no-fallthrough": { "level": "warn" } } }"#,
+    );
+    assert_eq!(errors.len(), 1);
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(errors[0].severity, Severity::Error);
+    assert_eq!(warnings[0].severity, Severity::Warning);
+    assert_eq!(errors[0].code, warnings[0].code);
+    assert_eq!(errors[0].message, warnings[0].message);
+    assert_eq!(errors[0].range, warnings[0].range);
+
+    assert!(lint(
+      r#"{ "rules": { "include": ["no-fallthrough"], "no-fallthrough": "off" } }"#
+    )
+    .iter()
+    .all(|d| d.code != "no-fallthrough"));
+  }
+
+  #[test]
+  fn level_with_options() {
+    let config = parse(
+      r#"{ "rules": {
+        "tags": [],
+        "no-cond-assign": { "level": "hint", "options": "always" }
+      } }"#,
+    );
+    let diagnostics = config
+      .linter_builder()
+      .unwrap()
+      .build()
+      .lint("config_test.ts".to_string(), "if ((a = b)) {}".to_string())
+      .unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Hint);
+  }
+
+  /// Reports every module, with a `Warning` severity by default.
+  struct WarningRule;
+
+  impl LintRule for WarningRule {
+    fn new() -> Box<Self> {
+      Box::new(WarningRule)
+    }
+
+    fn code(&self) -> &'static str {
+      "warning-rule"
+    }
+
+    fn severity(&self) -> Severity {
+      Severity::Warning
+    }
+
+    fn lint_module(
+      &self,
+      context: std::sync::Arc<crate::linter::Context>,
+      module: &swc_ecmascript::ast::Module,
+    ) {
+      context.add_diagnostic(module.span, "warning-rule", "Warning");
+    }
+  }
+
+  #[test]
+  fn default_level() {
+    let severity = |json: &str| {
+      let (config, severities) = parse(json).rules.resolve().unwrap();
+      assert_eq!(config.include, vec!["warning-rule"]);
+      let diagnostics = LinterBuilder::default()
+        .rules(vec![WarningRule::new()])
+        .severity_overrides(severities)
+        .build()
+        .lint("config_test.ts".to_string(), "a;".to_string())
+        .unwrap();
+      assert_eq!(diagnostics.len(), 1);
+      diagnostics[0].severity
+    };
+
+    assert_eq!(
+      severity(r#"{ "rules": { "warning-rule": { "options": {} } } }"#),
+      Severity::Warning
+    );
+    assert_eq!(
+      severity(r#"{ "rules": { "warning-rule": { "levle": "error" } } }"#),
+      Severity::Warning
+    );
+    assert_eq!(
+      severity(r#"{ "rules": { "warning-rule": { "level": "error" } } }"#),
+      Severity::Error
+    );
+  }
+
+  #[test]
+  fn invalid_level() {
+    match parse(r#"{ "rules": { "no-fallthrough": "fatal" } }"#).get_rules() {
+      Err(ConfigError::InvalidLevel(code, level)) => {
+        assert_eq!(code, "no-fallthrough");
+        assert_eq!(level, "fatal");
+      }
+      _ => panic!("expected invalid level error"),
+    }
+  }
+
+  #[test]
+  fn unknown_field() {
+    assert!(serde_json::from_str::<LintConfig>(r#"{ "rulez": {} }"#).is_err());
+  }
+
+  #[test]
+  fn builtin_checks() {
+    let source = "// deno-lint-ignore no-debugger no-debuger\nconst a = 1;\n";
+    let lint = |config: LintConfig| {
+      config
+        .linter_builder()
+        .unwrap()
+        .build()
+        .lint("config_test.ts".to_string(), source.to_string())
+        .unwrap()
+        .into_iter()
+        .map(|d| d.code)
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+      lint(parse("{}")),
+      vec!["ban-unknown-rule-code", "ban-unused-ignore"]
+    );
+    assert_eq!(
+      lint(parse(
+        r#"{ "rules": { "exclude": ["ban-unused-ignore"] } }"#
+      )),
+      vec!["ban-unknown-rule-code"]
+    );
+    assert!(lint(parse(
+      r#"{ "rules": {
+        "exclude": ["ban-unused-ignore", "ban-unknown-rule-code"]
+      } }"#
+    ))
+    .is_empty());
+  }
+
+  #[test]
+  fn env_and_globals() {
+    let source = "Deno.readTextFile(\"a.txt\");\nmyInjectedGlobal();\n";
+    let lint = |json: &str| {
+      parse(json)
+        .linter_builder()
+        .unwrap()
+        .build()
+        .lint("config_test.ts".to_string(), source.to_string())
+        .unwrap()
+        .into_iter()
+        .map(|d| d.message)
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(lint("{}"), vec!["myInjectedGlobal is not defined"]);
+    assert!(lint(
+      r#"{ "env": ["deno", "es2021"], "globals": ["myInjectedGlobal"] }"#
+    )
+    .is_empty());
+    assert_eq!(
+      lint(r#"{ "env": ["es2021"], "globals": ["myInjectedGlobal"] }"#),
+      vec!["Deno is not defined"]
+    );
+  }
+
+  #[test]
+  fn unknown_env() {
+    match parse(r#"{ "env": ["deno", "node"] }"#).linter_builder() {
+      Err(err) => assert_eq!(err.to_string(), "Unknown environment \"node\""),
+      Ok(_) => panic!("expected unknown env error"),
+    }
+  }
+
+  #[test]
+  fn deprecated_codes() {
+    let config = parse(
+      r#"{ "rules": {
+        "tags": [],
+        "include": ["noVar", "noCondAssign", "no-debugger"],
+        "exclude": ["noDebugger"],
+        "options": { "noCondAssign": "always" }
+      } }"#,
+    );
+    assert_eq!(codes(&config), vec!["no-cond-assign", "no-var"]);
+    let rules = config.get_rules().unwrap();
+    assert_eq!(
+      lint_with_rule(rules.into_iter().next().unwrap(), "if ((a = b)) {}")
+        .len(),
+      1
+    );
+
+    match parse(r#"{ "rules": { "include": ["noSuchRule"] } }"#).get_rules() {
+      Err(ConfigError::UnknownRule(code)) => assert_eq!(code, "noSuchRule"),
+      _ => panic!("expected unknown rule error"),
+    }
+  }
+}
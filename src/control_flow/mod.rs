@@ -30,6 +30,29 @@ impl ControlFlow {
   pub fn meta(&self, lo: BytePos) -> Option<&Metadata> {
     self.meta.get(&lo)
   }
+
+  /// Returns true if the statement, or the `export` declaration at the top
+  /// level of a module, can't be executed.
+  pub fn is_unreachable<N: Spanned>(&self, node: &N) -> bool {
+    match self.meta(node.span().lo) {
+      Some(meta) => meta.unreachable,
+      None => false,
+    }
+  }
+
+  /// Returns how execution of the statement list finishes, or `None` if
+  /// the end of the list is reachable.
+  pub fn stmts_end(&self, stmts: &[Stmt]) -> Option<End> {
+    stmts
+      .iter()
+      .find_map(|stmt| self.meta(stmt.span().lo).and_then(Metadata::end))
+  }
+
+  /// Returns true if execution can reach the end of the statement list,
+  /// e.g. the end of a function body or a switch case.
+  pub fn is_end_reachable(&self, stmts: &[Stmt]) -> bool {
+    self.stmts_end(stmts).is_none()
+  }
 }
 
 /// Kind of a basic block.
@@ -49,13 +72,14 @@ pub enum BlockKind {
 #[derive(Debug, Default, Clone)]
 pub struct Metadata {
   pub unreachable: bool,
-  done: Option<Done>,
+  done: Option<End>,
 }
 
 impl Metadata {
-  /// Returns true if a node prevents further execution.
-  pub fn stops_execution(&self) -> bool {
-    self.done.is_some()
+  /// Returns how the node finishes execution, or `None` if execution
+  /// continues after it.
+  pub fn end(&self) -> Option<End> {
+    self.done
   }
 }
 
@@ -75,7 +99,7 @@ struct Scope<'a> {
   _kind: BlockKind,
 
   /// Unconditionally ends with return, throw
-  done: Option<Done>,
+  done: Option<End>,
 
   may_throw: bool,
 
@@ -86,14 +110,66 @@ struct Scope<'a> {
   found_break: Option<Option<Id>>,
   found_continue: bool,
 }
-#[derive(Debug, Copy, Clone)]
-enum Done {
-  /// Return, Throw, or infinite loop
-  Forced,
-  // Break or continue
+/// How a statement finishes execution.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum End {
+  /// Return, throw, or infinite loop. The flags tell which of them can
+  /// finish the statement, e.g. both `ret` and `throw` are set for
+  /// `if (a) { return; } else { throw e; }`.
+  Forced {
+    ret: bool,
+    throw: bool,
+    infinite_loop: bool,
+  },
+  /// Break or continue
   Break,
 }
 
+impl End {
+  const RETURN: End = End::Forced {
+    ret: true,
+    throw: false,
+    infinite_loop: false,
+  };
+  const THROW: End = End::Forced {
+    ret: false,
+    throw: true,
+    infinite_loop: false,
+  };
+  const INFINITE_LOOP: End = End::Forced {
+    ret: false,
+    throw: false,
+    infinite_loop: true,
+  };
+
+  pub fn is_forced(self) -> bool {
+    matches!(self, End::Forced { .. })
+  }
+
+  /// Combines ends of branches which all finish execution.
+  fn merge(self, other: End) -> End {
+    match (self, other) {
+      (
+        End::Forced {
+          ret,
+          throw,
+          infinite_loop,
+        },
+        End::Forced {
+          ret: other_ret,
+          throw: other_throw,
+          infinite_loop: other_infinite_loop,
+        },
+      ) => End::Forced {
+        ret: ret || other_ret,
+        throw: throw || other_throw,
+        infinite_loop: infinite_loop || other_infinite_loop,
+      },
+      _ => End::Break,
+    }
+  }
+}
+
 impl<'a> Scope<'a> {
   pub fn new(parent: Option<&'a Scope<'a>>, kind: BlockKind) -> Self {
     Self {
@@ -127,8 +203,8 @@ impl Analyzer<'_> {
       match kind {
         BlockKind::Function => {}
         _ => {
-          if let Some(Done::Forced) = prev_done {
-            child.scope.done = Some(Done::Forced);
+          if let Some(done @ End::Forced { .. }) = prev_done {
+            child.scope.done = Some(done);
           }
         }
       }
@@ -147,63 +223,88 @@ impl Analyzer<'_> {
 
     self.scope.used_hoistable_ids.extend(hoist);
 
+    // A break statement never escapes a function, and a break statement
+    // without label is consumed by the closest loop or switch.
+    let mut breaks_to_label = false;
+    let found_break = match (&kind, found_break) {
+      (BlockKind::Function, _) => None,
+      (BlockKind::Loop, Some(None)) | (BlockKind::Case, Some(None)) => None,
+      // Labeled statement consumes a break statement with its label.
+      (BlockKind::Label(label), Some(Some(id))) if id == *label => {
+        breaks_to_label = true;
+        None
+      }
+      (_, found_break) => found_break,
+    };
+
     // Preserve information about visited ast nodes.
     self.scope.may_throw |= may_throw;
     if self.scope.found_break.is_none() {
       self.scope.found_break = found_break;
     }
     self.scope.found_continue |= found_continue;
+    self.info = info;
 
     if let Some(done) = done {
       match kind {
         BlockKind::Function => {}
         BlockKind::Block => {
-          if let Done::Forced = done {
+          if done.is_forced() {
             self.mark_as_done(lo, done);
           } else if self.scope.done.is_none() {
-            self.scope.done = Some(Done::Break)
-          }
-        }
-        BlockKind::Case => {
-          if let Done::Forced = done {
-            self.mark_as_done(lo, done);
+            self.scope.done = Some(End::Break)
           }
         }
+        // Marked by visit_switch_case, which knows about break statements.
+        BlockKind::Case => {}
         BlockKind::If => {}
         BlockKind::Loop => {}
-        BlockKind::Label(label) => {
-          if let Some(Some(id)) = &self.scope.found_break {
-            if *id == label {
-              // Eat break statemnt
-              self.scope.found_break = None;
-            }
+        BlockKind::Label(_) => {
+          // Execution continues after the labeled statement if its body
+          // breaks to the label.
+          if !breaks_to_label {
+            self.mark_as_done(lo, done);
           }
         }
       }
     }
-
-    self.info = info;
   }
 
   fn is_forced_done(&self, lo: BytePos) -> bool {
-    match self.get_done_reason(lo) {
-      Some(Done::Forced) => true,
-      _ => false,
-    }
+    matches!(self.get_done_reason(lo), Some(End::Forced { .. }))
   }
 
-  fn get_done_reason(&self, lo: BytePos) -> Option<Done> {
+  fn get_done_reason(&self, lo: BytePos) -> Option<End> {
     self.info.get(&lo).map(|md| md.done).flatten()
   }
 
   /// Mark a statement as finisher - finishes execution - and expose it.
-  fn mark_as_done(&mut self, lo: BytePos, done: Done) {
+  fn mark_as_done(&mut self, lo: BytePos, done: End) {
     if self.scope.done.is_none() {
       self.scope.done = Some(done);
     }
     self.info.entry(lo).or_default().done = Some(done);
   }
 
+  /// Returns true if the declaration can't be executed. Although execution
+  /// is done, hoisted declarations are reachable.
+  fn is_decl_unreachable(&self, decl: &Decl) -> bool {
+    if self.scope.done.is_none() {
+      return false;
+    }
+    match decl {
+      Decl::Fn(FnDecl { ident, .. }) => {
+        !self.scope.used_hoistable_ids.contains(&ident.to_id())
+      }
+      Decl::Var(VarDecl {
+        kind: VarDeclKind::Var,
+        decls,
+        ..
+      }) => decls.iter().any(|decl| decl.init.is_some()),
+      _ => true,
+    }
+  }
+
   /// Visits statement or block. This method handles break and continue.
   ///
   /// This cannot be done in visit_stmt of Visit because
@@ -214,7 +315,7 @@ impl Analyzer<'_> {
     // break, continue **may** make execution done
     match s {
       Stmt::Break(..) | Stmt::Continue(..) => {
-        self.mark_as_done(s.span().lo, Done::Break)
+        self.mark_as_done(s.span().lo, End::Break)
       }
       _ => {}
     }
@@ -222,11 +323,11 @@ impl Analyzer<'_> {
 }
 
 macro_rules! mark_as_done {
-  ($name:ident, $T:ty) => {
+  ($name:ident, $T:ty, $end:expr) => {
     fn $name(&mut self, s: &$T, _: &dyn Node) {
       s.visit_children_with(self);
 
-      self.mark_as_done(s.span().lo, Done::Forced);
+      self.mark_as_done(s.span().lo, $end);
     }
   };
 }
@@ -234,8 +335,14 @@ macro_rules! mark_as_done {
 impl Visit for Analyzer<'_> {
   noop_visit_type!();
 
-  mark_as_done!(visit_return_stmt, ReturnStmt);
-  mark_as_done!(visit_throw_stmt, ThrowStmt);
+  mark_as_done!(visit_return_stmt, ReturnStmt, End::RETURN);
+
+  fn visit_throw_stmt(&mut self, n: &ThrowStmt, _: &dyn Node) {
+    n.visit_children_with(self);
+
+    self.scope.may_throw = true;
+    self.mark_as_done(n.span.lo, End::THROW);
+  }
 
   fn visit_break_stmt(&mut self, n: &BreakStmt, _: &dyn Node) {
     if let Some(label) = &n.label {
@@ -339,7 +446,15 @@ impl Visit for Analyzer<'_> {
 
     // A switch statement is finisher or not.
     if is_done {
-      self.mark_as_done(n.span.lo, Done::Forced);
+      let end = n
+        .cases
+        .iter()
+        .filter_map(|case| self.get_done_reason(case.span.lo))
+        .fold(None, |acc: Option<End>, end| {
+          Some(acc.map_or(end, |acc| acc.merge(end)))
+        })
+        .unwrap();
+      self.mark_as_done(n.span.lo, end);
     } else {
       self.scope.done = prev_done;
     }
@@ -353,14 +468,14 @@ impl Visit for Analyzer<'_> {
       n.cons.visit_with(n, a);
 
       if a.scope.found_break.is_some() {
-        case_done = Some(Done::Break);
-      } else if let Some(Done::Forced) = a.scope.done {
-        case_done = Some(Done::Forced);
+        case_done = Some(End::Break);
+      } else if let Some(done @ End::Forced { .. }) = a.scope.done {
+        case_done = Some(done);
       }
     });
 
-    if let Some(Done::Forced) = case_done {
-      self.mark_as_done(n.span.lo, Done::Forced);
+    if let Some(done @ End::Forced { .. }) = case_done {
+      self.mark_as_done(n.span.lo, done);
     }
     self.scope.done = prev_done;
   }
@@ -385,12 +500,15 @@ impl Visit for Analyzer<'_> {
         let alt_reason = self.get_done_reason(alt.span().lo);
 
         match (cons_reason, alt_reason) {
-          (Some(Done::Forced), Some(Done::Forced)) => {
-            self.mark_as_done(n.span.lo, Done::Forced);
+          (
+            Some(cons_done @ End::Forced { .. }),
+            Some(alt_done @ End::Forced { .. }),
+          ) => {
+            self.mark_as_done(n.span.lo, cons_done.merge(alt_done));
           }
-          (Some(Done::Break), another) | (another, Some(Done::Break)) => {
+          (Some(End::Break), another) | (another, Some(End::Break)) => {
             if another.is_some() {
-              self.mark_as_done(n.span.lo, Done::Break);
+              self.mark_as_done(n.span.lo, End::Break);
             } else {
               self.scope.done = None
             }
@@ -406,29 +524,10 @@ impl Visit for Analyzer<'_> {
   }
 
   fn visit_stmt(&mut self, n: &Stmt, _: &dyn Node) {
-    let unreachable = if self.scope.done.is_some() {
-      // Although execution is done, we should handle hoisting.
-      match n {
-        Stmt::Empty(..) => false,
-        Stmt::Decl(Decl::Fn(FnDecl { ident, .. }))
-          if self.scope.used_hoistable_ids.contains(&ident.to_id()) =>
-        {
-          false
-        }
-        Stmt::Decl(Decl::Var(VarDecl {
-          kind: VarDeclKind::Var,
-          decls,
-          ..
-        }))
-          if decls.iter().all(|decl| decl.init.is_none()) =>
-        {
-          false
-        }
-        // It's unreachable
-        _ => true,
-      }
-    } else {
-      false
+    let unreachable = match n {
+      Stmt::Empty(..) => false,
+      Stmt::Decl(decl) => self.is_decl_unreachable(decl),
+      _ => self.scope.done.is_some(),
     };
 
     if unreachable {
@@ -438,6 +537,14 @@ impl Visit for Analyzer<'_> {
     n.visit_children_with(self);
   }
 
+  fn visit_export_decl(&mut self, n: &ExportDecl, _: &dyn Node) {
+    if self.is_decl_unreachable(&n.decl) {
+      self.info.entry(n.span.lo).or_default().unreachable = true;
+    }
+
+    n.visit_children_with(self);
+  }
+
   // loops
 
   fn visit_for_stmt(&mut self, n: &ForStmt, _: &dyn Node) {
@@ -453,14 +560,14 @@ impl Visit for Analyzer<'_> {
       if a.scope.found_break.is_none() {
         if n.test.is_none() {
           // Infinite loop
-          a.mark_as_done(n.span.lo, Done::Forced);
-          stmt_done = Some(Done::Forced);
+          a.mark_as_done(n.span.lo, End::INFINITE_LOOP);
+          stmt_done = Some(End::INFINITE_LOOP);
         } else if let (_, Value::Known(true)) =
           n.test.as_ref().unwrap().as_bool()
         {
           // Infinite loop
-          a.mark_as_done(n.span.lo, Done::Forced);
-          stmt_done = Some(Done::Forced);
+          a.mark_as_done(n.span.lo, End::INFINITE_LOOP);
+          stmt_done = Some(End::INFINITE_LOOP);
         }
       }
     });
@@ -494,15 +601,15 @@ impl Visit for Analyzer<'_> {
     self.with_child_scope(BlockKind::Loop, n.body.span().lo, |a| {
       n.body.visit_with(n, a);
       if let (_, Value::Known(true)) = n.test.as_bool() {
-        if let Some(Done::Forced) = a.get_done_reason(n.body.span().lo) {
-          a.mark_as_done(n.span.lo, Done::Forced);
-          stmt_done = Some(Done::Forced);
-        }
-
-        if a.scope.found_break.is_none() {
+        if let Some(done @ End::Forced { .. }) =
+          a.get_done_reason(n.body.span().lo)
+        {
+          a.mark_as_done(n.span.lo, done);
+          stmt_done = Some(done);
+        } else if a.scope.found_break.is_none() {
           // Infinite loop
-          a.mark_as_done(n.span.lo, Done::Forced);
-          stmt_done = Some(Done::Forced);
+          a.mark_as_done(n.span.lo, End::INFINITE_LOOP);
+          stmt_done = Some(End::INFINITE_LOOP);
         }
       }
     });
@@ -530,17 +637,17 @@ impl Visit for Analyzer<'_> {
   }
 
   fn visit_try_stmt(&mut self, n: &TryStmt, _: &dyn Node) {
-    n.finalizer.visit_with(n, self);
     let old_throw = self.scope.may_throw;
 
     let prev_done = self.scope.done;
 
     self.scope.may_throw = false;
     n.block.visit_with(n, self);
+    let block_may_throw = self.scope.may_throw;
 
     let mut block_done = None;
 
-    if self.scope.may_throw {
+    if block_may_throw {
       if let Some(done) = self.scope.done {
         block_done = Some(done);
         self.scope.done = prev_done;
@@ -550,20 +657,43 @@ impl Visit for Analyzer<'_> {
       self.mark_as_done(n.span.lo, done);
     }
 
-    n.handler.visit_with(n, self);
-    match (block_done, self.scope.done) {
-      (Some(Done::Forced), Some(Done::Forced)) => {
-        self.mark_as_done(n.span.lo, Done::Forced);
-      }
-      (Some(_try_done), Some(_catch_done)) => {
-        self.mark_as_done(n.span.lo, Done::Break);
+    let mut try_done = match &n.handler {
+      Some(handler) => {
+        handler.visit_with(n, self);
+        match (block_done, self.scope.done) {
+          (
+            Some(block_done @ End::Forced { .. }),
+            Some(catch_done @ End::Forced { .. }),
+          ) => Some(block_done.merge(catch_done)),
+          (Some(_try_done), Some(_catch_done)) => Some(End::Break),
+          _ => None,
+        }
       }
-      _ => {
-        self.scope.done = prev_done;
+      // Without catch clause, an exception thrown in the try block
+      // propagates, so the statement finishes whenever the block does.
+      None => block_done,
+    };
+
+    if let Some(finalizer) = &n.finalizer {
+      // Finalizer is executed regardless of how the try block finishes.
+      self.scope.done = prev_done;
+      finalizer.visit_with(n, self);
+
+      if prev_done.is_none() {
+        if let Some(done) = self.scope.done {
+          try_done = Some(done);
+        }
       }
     }
 
-    self.scope.may_throw = old_throw;
+    self.scope.done = prev_done;
+    if let Some(done) = try_done {
+      self.mark_as_done(n.span.lo, done);
+    }
+
+    // Exceptions are not caught if there's no catch clause.
+    self.scope.may_throw =
+      old_throw || (n.handler.is_none() && block_may_throw);
   }
 
   fn visit_labeled_stmt(&mut self, n: &LabeledStmt, _: &dyn Node) {
@@ -572,3 +702,132 @@ impl Visit for Analyzer<'_> {
     });
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::swc_util::{self, AstParser};
+
+  /// Returns how the body of the first function in `src` finishes.
+  fn body_end(src: &str) -> Option<End> {
+    let ast_parser = AstParser::new();
+    let syntax = swc_util::get_default_ts_config();
+    let (parse_result, _comments) =
+      ast_parser.parse_module("file_name.ts", syntax, src);
+    let module = parse_result.unwrap();
+    let control_flow = ControlFlow::analyze(&module);
+
+    let body = match &module.body[0] {
+      ModuleItem::Stmt(Stmt::Decl(Decl::Fn(decl))) => {
+        decl.function.body.as_ref().unwrap()
+      }
+      _ => panic!("expected a function declaration"),
+    };
+    assert_eq!(
+      control_flow.is_end_reachable(&body.stmts),
+      control_flow.stmts_end(&body.stmts).is_none()
+    );
+    control_flow.stmts_end(&body.stmts)
+  }
+
+  fn forced(ret: bool, throw: bool, infinite_loop: bool) -> Option<End> {
+    Some(End::Forced {
+      ret,
+      throw,
+      infinite_loop,
+    })
+  }
+
+  #[test]
+  fn return_and_throw() {
+    assert_eq!(body_end("function f() { a(); }"), None);
+    assert_eq!(
+      body_end("function f() { a(); return; b(); }"),
+      forced(true, false, false)
+    );
+    assert_eq!(
+      body_end("function f() { throw new Error(); }"),
+      forced(false, true, false)
+    );
+    assert_eq!(
+      body_end("function f() { if (a) { return 1; } else { throw e; } }"),
+      forced(true, true, false)
+    );
+    assert_eq!(body_end("function f() { if (a) { return 1; } }"), None);
+  }
+
+  #[test]
+  fn switch_with_default() {
+    assert_eq!(
+      body_end(
+        "function f() { switch (a) { case 1: return 1; default: throw e; } }"
+      ),
+      forced(true, true, false)
+    );
+    assert_eq!(
+      body_end("function f() { switch (a) { case 1: return 1; } }"),
+      None
+    );
+    assert_eq!(
+      body_end(
+        "function f() { switch (a) { case 1: break; default: return 2; } }"
+      ),
+      None
+    );
+  }
+
+  #[test]
+  fn try_catch_finally() {
+    assert_eq!(
+      body_end("function f() { try { return a(); } catch { return 1; } }"),
+      forced(true, false, false)
+    );
+    assert_eq!(
+      body_end("function f() { try { return a(); } catch (e) { log(e); } }"),
+      None
+    );
+    assert_eq!(
+      body_end("function f() { try { a(); } finally { throw e; } }"),
+      forced(false, true, false)
+    );
+    assert_eq!(
+      body_end("function f() { try { return a(); } finally { b(); } }"),
+      forced(true, false, false)
+    );
+  }
+
+  #[test]
+  fn loops() {
+    assert_eq!(
+      body_end("function f() { while (true) { a(); } }"),
+      forced(false, false, true)
+    );
+    assert_eq!(
+      body_end("function f() { for (;;) { if (a) break; } }"),
+      None
+    );
+    assert_eq!(
+      body_end("function f() { while (true) { return 1; } }"),
+      forced(true, false, false)
+    );
+    assert_eq!(body_end("function f() { while (a) { return 1; } }"), None);
+  }
+
+  #[test]
+  fn labeled_break() {
+    assert_eq!(
+      body_end("function f() { outer: { if (a) break outer; return 1; } }"),
+      None
+    );
+    assert_eq!(
+      body_end("function f() { outer: { return 1; } }"),
+      forced(true, false, false)
+    );
+    assert_eq!(
+      body_end(
+        "function f() { outer: while (true) { while (true) { break outer; } } }"
+      ),
+      None
+    );
+  }
+}
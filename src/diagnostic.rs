@@ -1,40 +1,418 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
-#[cfg(feature = "json")]
-use serde::Serialize;
+use crate::linter::LintError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+use swc_common::{BytePos, SourceMap};
 
-#[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+/// Location in a file. `line` is 1-based. `col` is 0-based and counted in
+/// chars, `col_utf16` in UTF-16 code units as used by LSP. `byte_pos` is
+/// the 0-based byte offset from the start of the file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Position {
   pub line: usize,
   pub col: usize,
+  pub col_utf16: usize,
+  pub byte_pos: usize,
 }
 
-impl Into<Position> for swc_common::Loc {
-  fn into(self) -> Position {
+impl Position {
+  pub(crate) fn new(source_map: &SourceMap, pos: BytePos) -> Self {
+    let loc = source_map.lookup_char_pos(pos);
+    let file = &loc.file;
+    let line_start = (file.lines[loc.line - 1] - file.start_pos).0 as usize;
+    let byte_pos = (pos - file.start_pos).0 as usize;
     Position {
-      line: self.line,
-      // Using self.col instead of self.col_display
+      line: loc.line,
+      // Using loc.col instead of loc.col_display
       // because it leads to out-of-bounds columns if file
       // contains non-narrow chars (like tabs).
       // See: https://github.com/denoland/deno_lint/issues/139
-      col: self.col.0,
+      col: loc.col.0,
+      col_utf16: file.src[line_start..byte_pos].encode_utf16().count(),
+      byte_pos,
     }
   }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Range {
   pub start: Position,
   pub end: Position,
 }
 
-#[derive(Clone, Debug)]
-#[cfg_attr(feature = "json", derive(Serialize))]
-pub struct LintDiagnostic {
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+  Error,
+  Warning,
+  Hint,
+}
+
+/// Machine-applicable fix replacing `start..end` bytes of the source.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LintFix {
+  pub start: usize,
+  pub end: usize,
+  pub replacement: String,
+}
+
+/// Possible remediation of a diagnostic, which is never applied
+/// automatically.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LintSuggestion {
+  pub description: String,
+  pub fix: LintFix,
+}
+
+/// Secondary location of a diagnostic, e.g. where a duplicated name was
+/// first defined.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RelatedInformation {
   pub range: Range,
-  pub filename: String,
   pub message: String,
+}
+
+/// A problem reported by a rule.
+///
+/// It serializes to an object with the following fields, in this order:
+/// `code`, `message`, `severity`, `filename`, `range` (`{ start, end }`,
+/// each with `line`, `col`, `col_utf16` and `byte_pos`), `hint`, `fix`,
+/// `suggestions` and `related` (`{ range, message }` entries). Lines are
+/// 1-based, columns and byte offsets are 0-based.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LintDiagnostic {
   pub code: String,
+  pub message: String,
+  pub severity: Severity,
+  pub filename: String,
+  pub range: Range,
   pub hint: Option<String>,
+  pub fix: Option<LintFix>,
+  pub suggestions: Vec<LintSuggestion>,
+  pub related: Vec<RelatedInformation>,
+}
+
+/// Formats the diagnostic as `file:line:col code message`, with a 1-based
+/// column.
+impl fmt::Display for LintDiagnostic {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{}:{}:{} {} {}",
+      self.filename,
+      self.range.start.line,
+      self.range.start.col + 1,
+      self.code,
+      self.message
+    )
+  }
+}
+
+pub trait DiagnosticsExt: Sized {
+  /// Returns true if any of the diagnostics has `Severity::Error`.
+  fn has_errors(&self) -> bool;
+  /// Returns `LintError::Diagnostics` if any of the diagnostics has
+  /// `Severity::Error`, so that lint failures can be propagated with `?`.
+  fn into_result(self) -> Result<Self, LintError>;
+}
+
+impl DiagnosticsExt for Vec<LintDiagnostic> {
+  fn has_errors(&self) -> bool {
+    self.iter().any(|d| d.severity == Severity::Error)
+  }
+
+  fn into_result(self) -> Result<Self, LintError> {
+    if self.has_errors() {
+      Err(LintError::Diagnostics(self))
+    } else {
+      Ok(self)
+    }
+  }
+}
+
+/// Diagnostics of one or more files together with summary counts. The
+/// counts include diagnostics dropped because of `max_diagnostics`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LintReport {
+  pub diagnostics: Vec<LintDiagnostic>,
+  pub files_checked: usize,
+  pub error_count: usize,
+  pub warning_count: usize,
+  pub hint_count: usize,
+  pub code_counts: BTreeMap<String, usize>,
+  /// Number of diagnostics dropped after reaching `max_diagnostics`.
+  pub truncated_count: usize,
+  /// Time spent parsing, if `collect_timings` is enabled.
+  pub parse_time: Option<Duration>,
+  /// Time spent in each rule that ran, in the order of the rules, if
+  /// `collect_timings` is enabled. Rules sharing the traversal of the
+  /// module are timed per callback.
+  pub rule_timings: Vec<(&'static str, Duration)>,
+  /// Codes of the rules that ran to completion.
+  pub completed_rules: Vec<&'static str>,
+  /// Whether linting stopped after exceeding the linter's `timeout`, in
+  /// which case rules missing from `completed_rules` may have reported
+  /// only part of their diagnostics.
+  pub timed_out: bool,
+  max_diagnostics: Option<usize>,
+}
+
+impl LintReport {
+  /// Creates an empty report keeping at most `max_diagnostics`
+  /// diagnostics, or all of them if `None`.
+  pub fn new(max_diagnostics: Option<usize>) -> Self {
+    Self {
+      max_diagnostics,
+      ..Self::default()
+    }
+  }
+
+  /// Adds sorted diagnostics of a single file. Once the limit is reached
+  /// the remaining diagnostics are only counted.
+  pub fn add_file(&mut self, diagnostics: Vec<LintDiagnostic>) {
+    self.files_checked += 1;
+    for diagnostic in diagnostics {
+      match diagnostic.severity {
+        Severity::Error => self.error_count += 1,
+        Severity::Warning => self.warning_count += 1,
+        Severity::Hint => self.hint_count += 1,
+      }
+      *self.code_counts.entry(diagnostic.code.clone()).or_insert(0) += 1;
+
+      match self.max_diagnostics {
+        Some(max) if self.diagnostics.len() >= max => self.truncated_count += 1,
+        _ => self.diagnostics.push(diagnostic),
+      }
+    }
+  }
+
+  pub fn is_truncated(&self) -> bool {
+    self.truncated_count > 0
+  }
+}
+
+/// Applies fixes of `diagnostics` to `source`. Fixes overlapping an
+/// earlier fix, or not falling on char boundaries, are skipped.
+pub fn apply_fixes(source: &str, diagnostics: &[LintDiagnostic]) -> String {
+  let mut fixes = diagnostics
+    .iter()
+    .filter_map(|d| d.fix.as_ref())
+    .filter(|fix| {
+      fix.start <= fix.end
+        && fix.end <= source.len()
+        && source.is_char_boundary(fix.start)
+        && source.is_char_boundary(fix.end)
+    })
+    .collect::<Vec<_>>();
+  fixes.sort_by_key(|fix| (fix.start, fix.end));
+
+  let mut applied: Vec<&LintFix> = vec![];
+  for fix in fixes {
+    if let Some(last) = applied.last() {
+      if fix.start < last.end || fix.start == last.start {
+        continue;
+      }
+    }
+    applied.push(fix);
+  }
+
+  let mut fixed = source.to_string();
+  for fix in applied.iter().rev() {
+    fixed.replace_range(fix.start..fix.end, &fix.replacement);
+  }
+  fixed
+}
+
+/// Applies the suggestion of `diagnostic` with given index to `source`.
+/// Returns `None` if there's no such suggestion or it can't be applied.
+pub fn apply_suggestion(
+  source: &str,
+  diagnostic: &LintDiagnostic,
+  index: usize,
+) -> Option<String> {
+  let fix = &diagnostic.suggestions.get(index)?.fix;
+  if fix.start > fix.end
+    || fix.end > source.len()
+    || !source.is_char_boundary(fix.start)
+    || !source.is_char_boundary(fix.end)
+  {
+    return None;
+  }
+
+  let mut fixed = source.to_string();
+  fixed.replace_range(fix.start..fix.end, &fix.replacement);
+  Some(fixed)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn diagnostic_with_fix(
+    start: usize,
+    end: usize,
+    text: &str,
+  ) -> LintDiagnostic {
+    let position = Position {
+      line: 1,
+      col: 0,
+      col_utf16: 0,
+      byte_pos: 0,
+    };
+    LintDiagnostic {
+      range: Range {
+        start: position.clone(),
+        end: position,
+      },
+      filename: "test.ts".to_string(),
+      message: "message".to_string(),
+      code: "code".to_string(),
+      hint: None,
+      severity: Severity::Error,
+      fix: Some(LintFix {
+        start,
+        end,
+        replacement: text.to_string(),
+      }),
+      suggestions: vec![],
+      related: vec![],
+    }
+  }
+
+  #[test]
+  fn apply_fixes_in_reverse_order() {
+    let diagnostics = vec![
+      diagnostic_with_fix(0, 3, "let"),
+      diagnostic_with_fix(10, 11, ""),
+      diagnostic_with_fix(8, 8, " "),
+    ];
+    assert_eq!(apply_fixes("var a = 1;;", &diagnostics), "let a =  1;");
+  }
+
+  #[test]
+  fn apply_fixes_skips_conflicts() {
+    let diagnostics = vec![
+      diagnostic_with_fix(4, 9, "b"),
+      diagnostic_with_fix(0, 6, "const x"),
+      diagnostic_with_fix(0, 3, "let"),
+    ];
+    assert_eq!(apply_fixes("var a = 1;", &diagnostics), "let b;");
+  }
+
+  #[test]
+  fn apply_fixes_multi_byte_chars() {
+    let source = "const a = \"🦕\";;";
+    let semi = source.len() - 1;
+    let diagnostics = vec![
+      diagnostic_with_fix(semi, semi + 1, ""),
+      // Points into the middle of the emoji.
+      diagnostic_with_fix(12, 13, "x"),
+    ];
+    assert_eq!(apply_fixes(source, &diagnostics), "const a = \"🦕\";");
+  }
+
+  #[test]
+  fn json_round_trip() {
+    let mut diagnostic = diagnostic_with_fix(0, 3, "let");
+    diagnostic.hint = Some("Use `let` instead".to_string());
+    diagnostic.suggestions = vec![LintSuggestion {
+      description: "Use `const`".to_string(),
+      fix: LintFix {
+        start: 0,
+        end: 3,
+        replacement: "const".to_string(),
+      },
+    }];
+
+    let json = serde_json::to_string(&diagnostic).unwrap();
+    let deserialized: LintDiagnostic = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, diagnostic);
+  }
+
+  #[test]
+  fn json_layout() {
+    let diagnostic = diagnostic_with_fix(4, 5, "");
+    assert_eq!(
+      serde_json::to_string(&diagnostic).unwrap(),
+      concat!(
+        r#"{"code":"code","message":"message","severity":"error","#,
+        r#""filename":"test.ts","range":{"#,
+        r#""start":{"line":1,"col":0,"col_utf16":0,"byte_pos":0},"#,
+        r#""end":{"line":1,"col":0,"col_utf16":0,"byte_pos":0}},"#,
+        r#""hint":null,"#,
+        r#""fix":{"start":4,"end":5,"replacement":""},"suggestions":[],"#,
+        r#""related":[]}"#
+      )
+    );
+  }
+
+  fn diagnostic(code: &str, severity: Severity) -> LintDiagnostic {
+    LintDiagnostic {
+      code: code.to_string(),
+      severity,
+      fix: None,
+      ..diagnostic_with_fix(0, 0, "")
+    }
+  }
+
+  #[test]
+  fn report_counts() {
+    let mut report = LintReport::new(None);
+    report.add_file(vec![
+      diagnostic("no-var", Severity::Error),
+      diagnostic("no-debugger", Severity::Warning),
+      diagnostic("no-var", Severity::Error),
+    ]);
+    report.add_file(vec![]);
+    report.add_file(vec![diagnostic("no-empty", Severity::Hint)]);
+
+    assert_eq!(report.files_checked, 3);
+    assert_eq!(report.diagnostics.len(), 4);
+    assert_eq!(report.error_count, 2);
+    assert_eq!(report.warning_count, 1);
+    assert_eq!(report.hint_count, 1);
+    assert!(!report.is_truncated());
+    assert_eq!(
+      report.code_counts.into_iter().collect::<Vec<_>>(),
+      vec![
+        ("no-debugger".to_string(), 1),
+        ("no-empty".to_string(), 1),
+        ("no-var".to_string(), 2),
+      ]
+    );
+  }
+
+  #[test]
+  fn report_truncation() {
+    let mut report = LintReport::new(Some(2));
+    report.add_file(vec![diagnostic("a", Severity::Error)]);
+    report.add_file(vec![
+      diagnostic("b", Severity::Error),
+      diagnostic("c", Severity::Error),
+    ]);
+    report.add_file(vec![diagnostic("d", Severity::Warning)]);
+
+    let codes = report
+      .diagnostics
+      .iter()
+      .map(|d| d.code.as_str())
+      .collect::<Vec<_>>();
+    assert_eq!(codes, vec!["a", "b"]);
+    assert!(report.is_truncated());
+    assert_eq!(report.truncated_count, 2);
+    assert_eq!(report.files_checked, 3);
+    assert_eq!(report.error_count, 3);
+    assert_eq!(report.warning_count, 1);
+    assert_eq!(report.code_counts["d"], 1);
+  }
+
+  #[test]
+  fn display() {
+    let mut diagnostic = diagnostic_with_fix(0, 0, "");
+    diagnostic.range.start.line = 3;
+    diagnostic.range.start.col = 4;
+    assert_eq!(diagnostic.to_string(), "test.ts:3:5 code message");
+  }
 }
@@ -0,0 +1,109 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+//! Markdown documentation of rules, generated from their metadata.
+use crate::rules::{LintRule, RuleRegistry};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Renders the documentation of a single rule.
+pub fn rule_markdown(rule: &dyn LintRule) -> String {
+  let mut markdown = format!("# {}\n\n", rule.code());
+  if is_recommended(rule) {
+    markdown.push_str("Recommended: yes\n\n");
+  } else {
+    markdown.push_str("Recommended: no\n\n");
+  }
+  markdown.push_str(rule.docs().trim());
+  markdown.push('\n');
+
+  if let Some(schema) = rule.options_schema() {
+    markdown.push_str("\n## Options\n\n```json\n");
+    markdown.push_str(&serde_json::to_string_pretty(&schema).unwrap());
+    markdown.push_str("\n```\n");
+  }
+
+  let examples = rule.examples();
+  for (title, samples) in
+    &[("Valid", examples.valid), ("Invalid", examples.invalid)]
+  {
+    if samples.is_empty() {
+      continue;
+    }
+    markdown.push_str(&format!("\n## {} examples\n", title));
+    for sample in samples.iter() {
+      markdown.push_str(&format!("\n```typescript\n{}\n```\n", sample));
+    }
+  }
+  markdown
+}
+
+/// Renders a list of the rules, linking to the pages written by
+/// `write_docs`.
+pub fn index_markdown(registry: &RuleRegistry) -> String {
+  let mut codes = registry.codes();
+  codes.sort_unstable();
+  let mut markdown = "# Rules\n\n".to_string();
+  for code in codes {
+    let rule = registry.get(code).unwrap();
+    let summary = rule.docs().lines().next().unwrap_or("");
+    markdown.push_str(&format!("- [{}]({}.md)", code, code));
+    if is_recommended(rule) {
+      markdown.push_str(" (recommended)");
+    }
+    if !summary.is_empty() {
+      markdown.push_str(&format!(": {}", summary));
+    }
+    markdown.push('\n');
+  }
+  markdown
+}
+
+/// Writes `<code>.md` for every rule of `registry` and an `index.md`
+/// to `dir`, creating it if needed.
+pub fn write_docs(dir: &Path, registry: &RuleRegistry) -> io::Result<()> {
+  fs::create_dir_all(dir)?;
+  for code in registry.codes() {
+    let rule = registry.get(code).unwrap();
+    fs::write(dir.join(format!("{}.md", code)), rule_markdown(rule))?;
+  }
+  fs::write(dir.join("index.md"), index_markdown(registry))
+}
+
+fn is_recommended(rule: &dyn LintRule) -> bool {
+  rule.tags().contains(&"recommended")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rule_page() {
+    let registry = RuleRegistry::with_builtin_rules();
+    let markdown = rule_markdown(registry.get("no-fallthrough").unwrap());
+    assert!(markdown.starts_with("# no-fallthrough\n\nRecommended: yes\n"));
+    assert!(markdown.contains("\"commentPattern\""));
+    assert!(markdown.contains("## Valid examples"));
+    assert!(markdown.contains("## Invalid examples"));
+
+    let markdown = rule_markdown(registry.get("no-eval").unwrap());
+    assert!(markdown.contains("Recommended: no"));
+    assert!(!markdown.contains("## Options"));
+  }
+
+  #[test]
+  fn writes_every_rule() {
+    let registry = RuleRegistry::with_builtin_rules();
+    let dir = std::env::temp_dir()
+      .join(format!("deno_lint_docs_{}", std::process::id()));
+    write_docs(&dir, &registry).unwrap();
+
+    let index = fs::read_to_string(dir.join("index.md")).unwrap();
+    for code in registry.codes() {
+      assert!(index.contains(&format!("[{}]({}.md)", code, code)));
+      let page = fs::read_to_string(dir.join(format!("{}.md", code))).unwrap();
+      assert!(page.starts_with(&format!("# {}\n", code)));
+    }
+    fs::remove_dir_all(&dir).unwrap();
+  }
+}
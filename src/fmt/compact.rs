@@ -0,0 +1,54 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::{DiagnosticFormatter, SourceProvider};
+use crate::diagnostic::LintDiagnostic;
+
+/// Formats each diagnostic on a single line as
+/// `file:line:col: message [code]`, with 1-based columns.
+#[derive(Default)]
+pub struct CompactFormatter;
+
+impl DiagnosticFormatter for CompactFormatter {
+  fn format_diagnostics(
+    &self,
+    diagnostics: &[LintDiagnostic],
+    _sources: &dyn SourceProvider,
+  ) -> String {
+    diagnostics
+      .iter()
+      .map(|d| {
+        format!(
+          "{}:{}:{}: {} [{}]",
+          d.filename,
+          d.range.start.line,
+          d.range.start.col + 1,
+          d.message,
+          d.code
+        )
+      })
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::linter::LinterBuilder;
+  use crate::rules::get_recommended_rules;
+
+  #[test]
+  fn compact() {
+    let source = "const a = 1;\nif (a) {} debugger;\n";
+    let diagnostics = LinterBuilder::default()
+      .rules(get_recommended_rules())
+      .build()
+      .lint("src/file.ts".to_string(), source.to_string())
+      .unwrap();
+
+    assert_eq!(
+      CompactFormatter.format_diagnostics(&diagnostics, &()),
+      "src/file.ts:2:8: Empty block statement [no-empty]
+src/file.ts:2:11: `debugger` statement is not allowed [no-debugger]"
+    );
+  }
+}
@@ -0,0 +1,109 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::{DiagnosticFormatter, SourceProvider};
+use crate::diagnostic::{LintDiagnostic, Severity};
+
+/// Formats diagnostics as GitHub Actions workflow commands, which show up
+/// as annotations on pull requests.
+#[derive(Default)]
+pub struct GithubFormatter;
+
+impl DiagnosticFormatter for GithubFormatter {
+  fn format_diagnostics(
+    &self,
+    diagnostics: &[LintDiagnostic],
+    _sources: &dyn SourceProvider,
+  ) -> String {
+    diagnostics
+      .iter()
+      .map(|d| {
+        let command = match d.severity {
+          Severity::Error => "error",
+          Severity::Warning => "warning",
+          Severity::Hint => "notice",
+        };
+        format!(
+          "::{} file={},line={},col={},endLine={},endColumn={}::{}",
+          command,
+          escape_property(&d.filename),
+          d.range.start.line,
+          d.range.start.col + 1,
+          d.range.end.line,
+          d.range.end.col + 1,
+          escape_data(&format!("{} ({})", d.message, d.code))
+        )
+      })
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
+}
+
+/// Escapes the message of a workflow command, including `::` which
+/// separates the message from the command.
+fn escape_data(s: &str) -> String {
+  s.replace('%', "%25")
+    .replace('\r', "%0D")
+    .replace('\n', "%0A")
+    .replace("::", "%3A%3A")
+}
+
+/// Escapes a property value, where `:` and `,` are separators.
+fn escape_property(s: &str) -> String {
+  escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::diagnostic::{Position, Range};
+
+  fn diagnostic(filename: &str, message: &str) -> LintDiagnostic {
+    LintDiagnostic {
+      code: "some-rule".to_string(),
+      message: message.to_string(),
+      severity: Severity::Error,
+      filename: filename.to_string(),
+      range: Range {
+        start: Position {
+          line: 2,
+          col: 4,
+          col_utf16: 4,
+          byte_pos: 20,
+        },
+        end: Position {
+          line: 3,
+          col: 0,
+          col_utf16: 0,
+          byte_pos: 30,
+        },
+      },
+      hint: None,
+      fix: None,
+      suggestions: vec![],
+      related: vec![],
+    }
+  }
+
+  #[test]
+  fn github() {
+    let mut warning = diagnostic("src/file.ts", "Something is wrong");
+    warning.severity = Severity::Warning;
+    let diagnostics = vec![diagnostic("src/file.ts", "Bad"), warning];
+
+    assert_eq!(
+      GithubFormatter.format_diagnostics(&diagnostics, &()),
+      "::error file=src/file.ts,line=2,col=5,endLine=3,endColumn=1::Bad (some-rule)
+::warning file=src/file.ts,line=2,col=5,endLine=3,endColumn=1::Something is wrong (some-rule)"
+    );
+  }
+
+  #[test]
+  fn github_escaping() {
+    let diagnostics =
+      vec![diagnostic("C:\\a,b.ts", "100% wrong\nuse Foo::bar")];
+
+    assert_eq!(
+      GithubFormatter.format_diagnostics(&diagnostics, &()),
+      "::error file=C%3A\\a%2Cb.ts,line=2,col=5,endLine=3,endColumn=1::100%25 wrong%0Ause Foo%3A%3Abar (some-rule)"
+    );
+  }
+}
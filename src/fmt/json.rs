@@ -0,0 +1,17 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::{DiagnosticFormatter, SourceProvider};
+use crate::diagnostic::LintDiagnostic;
+
+/// Formats diagnostics as a pretty-printed JSON array.
+#[derive(Default)]
+pub struct JsonFormatter;
+
+impl DiagnosticFormatter for JsonFormatter {
+  fn format_diagnostics(
+    &self,
+    diagnostics: &[LintDiagnostic],
+    _sources: &dyn SourceProvider,
+  ) -> String {
+    serde_json::to_string_pretty(diagnostics).unwrap()
+  }
+}
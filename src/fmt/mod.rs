@@ -0,0 +1,568 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use crate::diagnostic::{LintDiagnostic, Range};
+use crate::linter::FileDiagnostics;
+use crate::rules::get_all_rules;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::io::Write;
+use termcolor::Color::{Ansi256, Red};
+use termcolor::{Ansi, ColorSpec, WriteColor};
+
+mod compact;
+mod github;
+mod json;
+pub mod sarif;
+
+pub use compact::CompactFormatter;
+pub use github::GithubFormatter;
+pub use json::JsonFormatter;
+pub use sarif::SarifFormatter;
+
+/// Looks up the source text of a file by its name, for formatters that
+/// show source excerpts.
+pub trait SourceProvider {
+  fn source(&self, file_name: &str) -> Option<&str>;
+}
+
+/// Provides no sources.
+impl SourceProvider for () {
+  fn source(&self, _file_name: &str) -> Option<&str> {
+    None
+  }
+}
+
+impl SourceProvider for HashMap<String, String> {
+  fn source(&self, file_name: &str) -> Option<&str> {
+    self.get(file_name).map(|s| s.as_str())
+  }
+}
+
+/// Provides the sources of `(file_name, source_code)` pairs, as passed to
+/// `Linter::lint_files`.
+impl SourceProvider for [(String, String)] {
+  fn source(&self, file_name: &str) -> Option<&str> {
+    self
+      .iter()
+      .find(|(name, _)| name == file_name)
+      .map(|(_, source)| source.as_str())
+  }
+}
+
+impl SourceProvider for Vec<(String, String)> {
+  fn source(&self, file_name: &str) -> Option<&str> {
+    self.as_slice().source(file_name)
+  }
+}
+
+pub trait DiagnosticFormatter {
+  /// Formats a list of diagnostics.
+  fn format_diagnostics(
+    &self,
+    diagnostics: &[LintDiagnostic],
+    sources: &dyn SourceProvider,
+  ) -> String;
+
+  /// Formats the diagnostics of linted files. Files that failed to parse
+  /// are skipped.
+  fn format(
+    &self,
+    files: &[FileDiagnostics],
+    sources: &dyn SourceProvider,
+  ) -> String {
+    let diagnostics = files
+      .iter()
+      .filter_map(|file| file.diagnostics.as_ref().ok())
+      .flatten()
+      .cloned()
+      .collect::<Vec<_>>();
+    self.format_diagnostics(&diagnostics, sources)
+  }
+}
+
+/// Formatters selectable by name, e.g. from a CLI flag.
+#[derive(Default)]
+pub struct FormatterRegistry {
+  formatters: Vec<(String, Box<dyn DiagnosticFormatter>)>,
+}
+
+impl FormatterRegistry {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Creates a registry containing "pretty", "compact", "github", "sarif"
+  /// and "json".
+  pub fn with_builtin_formatters() -> Self {
+    let mut registry = Self::new();
+    let formatters: Vec<(&str, Box<dyn DiagnosticFormatter>)> = vec![
+      ("pretty", Box::new(PrettyFormatter::new())),
+      ("compact", Box::new(CompactFormatter)),
+      ("github", Box::new(GithubFormatter)),
+      ("sarif", Box::new(SarifFormatter::new(get_all_rules()))),
+      ("json", Box::new(JsonFormatter)),
+    ];
+    for (name, formatter) in formatters {
+      registry.register(name, formatter).unwrap();
+    }
+    registry
+  }
+
+  /// Adds a formatter. Fails if its name is already taken.
+  pub fn register(
+    &mut self,
+    name: &str,
+    formatter: Box<dyn DiagnosticFormatter>,
+  ) -> Result<(), DuplicateFormatterError> {
+    if self.get(name).is_some() {
+      return Err(DuplicateFormatterError {
+        name: name.to_string(),
+      });
+    }
+    self.formatters.push((name.to_string(), formatter));
+    Ok(())
+  }
+
+  pub fn get(&self, name: &str) -> Option<&dyn DiagnosticFormatter> {
+    self
+      .formatters
+      .iter()
+      .find(|(n, _)| n == name)
+      .map(|(_, formatter)| formatter.as_ref())
+  }
+
+  pub fn names(&self) -> Vec<&str> {
+    self
+      .formatters
+      .iter()
+      .map(|(name, _)| name.as_str())
+      .collect()
+  }
+}
+
+/// Error returned when registering a formatter whose name is already taken.
+#[derive(Clone, Debug)]
+pub struct DuplicateFormatterError {
+  pub name: String,
+}
+
+impl Error for DuplicateFormatterError {}
+
+impl fmt::Display for DuplicateFormatterError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "Formatter \"{}\" is already registered", self.name)
+  }
+}
+
+/// Number of columns a tab is rendered with.
+const TAB_WIDTH: usize = 4;
+
+/// Formats diagnostics for the terminal, showing the offending source line
+/// with the span underlined.
+///
+/// ```text
+/// (no-debugger) `debugger` statement is not allowed
+///  --> file.ts:2:3
+///   |
+/// 2 |   debugger;
+///   |   ^^^^^^^^^
+///   = hint: Remove the `debugger` statement
+/// ```
+pub struct PrettyFormatter {
+  use_color: bool,
+}
+
+impl Default for PrettyFormatter {
+  fn default() -> Self {
+    Self { use_color: true }
+  }
+}
+
+impl PrettyFormatter {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn use_color(mut self, use_color: bool) -> Self {
+    self.use_color = use_color;
+    self
+  }
+
+  pub fn format(&self, diagnostic: &LintDiagnostic, source: &str) -> String {
+    self.format_with_source(diagnostic, Some(source))
+  }
+
+  /// Formats the diagnostic without the source excerpt if `source` is `None`.
+  fn format_with_source(
+    &self,
+    diagnostic: &LintDiagnostic,
+    source: Option<&str>,
+  ) -> String {
+    let start = &diagnostic.range.start;
+    let max_line = diagnostic
+      .related
+      .iter()
+      .map(|related| related.range.start.line)
+      .fold(start.line, usize::max);
+    let gutter = " ".repeat(max_line.to_string().len());
+
+    let mut lines = vec![
+      format!(
+        "({}) {}",
+        self.paint(&diagnostic.code, gray()),
+        diagnostic.message
+      ),
+      self.paint(
+        &format!(
+          "{}--> {}:{}:{}",
+          gutter,
+          diagnostic.filename,
+          start.line,
+          start.col + 1
+        ),
+        cyan(),
+      ),
+    ];
+
+    if let Some(source) = source {
+      lines.push(format!("{} |", gutter));
+      lines.extend(self.excerpt(
+        source,
+        &diagnostic.range,
+        &gutter,
+        ("^", red()),
+        "",
+      ));
+      for related in &diagnostic.related {
+        lines.push(format!("{} |", gutter));
+        lines.extend(self.excerpt(
+          source,
+          &related.range,
+          &gutter,
+          ("-", cyan()),
+          &related.message,
+        ));
+      }
+    }
+
+    if let Some(hint) = &diagnostic.hint {
+      lines.push(format!(
+        "{} = {} {}",
+        gutter,
+        self.paint("hint:", bold()),
+        hint
+      ));
+    }
+
+    lines.join("\n")
+  }
+
+  /// Returns the first source line of `range` with the range underlined
+  /// by repeating the colored `marker`, followed by `label`.
+  fn excerpt(
+    &self,
+    source: &str,
+    range: &Range,
+    gutter: &str,
+    marker: (&str, ColorSpec),
+    label: &str,
+  ) -> Vec<String> {
+    let start = &range.start;
+    let end = &range.end;
+    let line = source.lines().nth(start.line - 1).unwrap_or("");
+    let is_multiline = end.line > start.line;
+    let end_col = if is_multiline {
+      line.chars().count()
+    } else {
+      end.col
+    };
+    let (line, underline_start, underline_len) =
+      expand_tabs(line, start.col, end_col);
+    let ellipsis = if is_multiline { "..." } else { "" };
+    let label = if label.is_empty() {
+      String::new()
+    } else {
+      format!(" {}", label)
+    };
+
+    vec![
+      format!("{:>width$} | {}", start.line, line, width = gutter.len()),
+      format!(
+        "{} | {}{}{}{}",
+        gutter,
+        " ".repeat(underline_start),
+        self.paint(&marker.0.repeat(underline_len.max(1)), marker.1),
+        ellipsis,
+        label
+      ),
+    ]
+  }
+
+  fn paint(&self, s: &str, color_spec: ColorSpec) -> String {
+    if !self.use_color {
+      return s.to_string();
+    }
+
+    let mut v = Vec::new();
+    let mut ansi_writer = Ansi::new(&mut v);
+    ansi_writer.set_color(&color_spec).unwrap();
+    ansi_writer.write_all(s.as_bytes()).unwrap();
+    ansi_writer.reset().unwrap();
+    String::from_utf8_lossy(&v).into_owned()
+  }
+}
+
+impl DiagnosticFormatter for PrettyFormatter {
+  fn format_diagnostics(
+    &self,
+    diagnostics: &[LintDiagnostic],
+    sources: &dyn SourceProvider,
+  ) -> String {
+    diagnostics
+      .iter()
+      .map(|d| self.format_with_source(d, sources.source(&d.filename)))
+      .collect::<Vec<_>>()
+      .join("\n\n")
+  }
+}
+
+/// Replaces tabs in `line` with spaces and converts the `start_col..end_col`
+/// char range to the start and length of the underline in the result.
+fn expand_tabs(
+  line: &str,
+  start_col: usize,
+  end_col: usize,
+) -> (String, usize, usize) {
+  let mut expanded = String::new();
+  let mut underline_start = 0;
+  let mut underline_len = 0;
+
+  for (col, c) in line.chars().enumerate() {
+    let width = if c == '\t' { TAB_WIDTH } else { 1 };
+    if c == '\t' {
+      expanded.push_str(&" ".repeat(TAB_WIDTH));
+    } else {
+      expanded.push(c);
+    }
+
+    if col < start_col {
+      underline_start += width;
+    } else if col < end_col {
+      underline_len += width;
+    }
+  }
+
+  (expanded, underline_start, underline_len)
+}
+
+fn gray() -> ColorSpec {
+  let mut color_spec = ColorSpec::new();
+  color_spec.set_fg(Some(Ansi256(8)));
+  color_spec
+}
+
+fn red() -> ColorSpec {
+  let mut color_spec = ColorSpec::new();
+  color_spec.set_fg(Some(Red));
+  color_spec
+}
+
+fn cyan() -> ColorSpec {
+  let mut color_spec = ColorSpec::new();
+  color_spec.set_fg(Some(Ansi256(14)));
+  color_spec
+}
+
+fn bold() -> ColorSpec {
+  let mut color_spec = ColorSpec::new();
+  color_spec.set_bold(true);
+  color_spec
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::diagnostic::{Position, RelatedInformation, Severity};
+
+  fn diagnostic(
+    start: (usize, usize),
+    end: (usize, usize),
+    hint: Option<&str>,
+  ) -> LintDiagnostic {
+    LintDiagnostic {
+      range: Range {
+        start: Position {
+          line: start.0,
+          col: start.1,
+          col_utf16: start.1,
+          byte_pos: 0,
+        },
+        end: Position {
+          line: end.0,
+          col: end.1,
+          col_utf16: end.1,
+          byte_pos: 0,
+        },
+      },
+      filename: "file.ts".to_string(),
+      message: "Something is wrong".to_string(),
+      code: "some-rule".to_string(),
+      hint: hint.map(|h| h.to_string()),
+      severity: Severity::Error,
+      fix: None,
+      suggestions: vec![],
+      related: vec![],
+    }
+  }
+
+  fn format(diagnostic: &LintDiagnostic, source: &str) -> String {
+    PrettyFormatter::new()
+      .use_color(false)
+      .format(diagnostic, source)
+  }
+
+  #[test]
+  fn single_line() {
+    let source = "const a = 1;\nif (a) debugger;\n";
+    assert_eq!(
+      format(&diagnostic((2, 7), (2, 16), None), source),
+      r#"(some-rule) Something is wrong
+ --> file.ts:2:8
+  |
+2 | if (a) debugger;
+  |        ^^^^^^^^^"#
+    );
+  }
+
+  #[test]
+  fn with_hint() {
+    let source = "debugger;";
+    assert_eq!(
+      format(&diagnostic((1, 0), (1, 9), Some("Remove it")), source),
+      r#"(some-rule) Something is wrong
+ --> file.ts:1:1
+  |
+1 | debugger;
+  | ^^^^^^^^^
+  = hint: Remove it"#
+    );
+  }
+
+  #[test]
+  fn multi_line() {
+    let source = "function foo() {\n  return;\n}\n";
+    assert_eq!(
+      format(&diagnostic((1, 9), (3, 1), None), source),
+      r#"(some-rule) Something is wrong
+ --> file.ts:1:10
+  |
+1 | function foo() {
+  |          ^^^^^^^..."#
+    );
+  }
+
+  #[test]
+  fn tabs() {
+    let source = "if (a) {\n\t\tdebugger;\n}";
+    assert_eq!(
+      format(&diagnostic((2, 2), (2, 11), None), source),
+      "(some-rule) Something is wrong
+ --> file.ts:2:3
+  |
+2 |         debugger;
+  |         ^^^^^^^^^"
+    );
+  }
+
+  #[test]
+  fn wide_gutter() {
+    let source = format!("{}debugger;", "\n".repeat(10));
+    assert_eq!(
+      format(&diagnostic((11, 0), (11, 9), None), &source),
+      r#"(some-rule) Something is wrong
+  --> file.ts:11:1
+   |
+11 | debugger;
+   | ^^^^^^^^^"#
+    );
+  }
+
+  #[test]
+  fn related() {
+    let source = "const a = {\n  b: 1,\n  c: 2,\n  b: 3,\n};\n";
+    let mut diagnostic = diagnostic((4, 2), (4, 3), Some("Rename it"));
+    let first = self::diagnostic((2, 2), (2, 3), None).range;
+    diagnostic.related.push(RelatedInformation {
+      range: first,
+      message: "first defined here".to_string(),
+    });
+    assert_eq!(
+      format(&diagnostic, source),
+      r#"(some-rule) Something is wrong
+ --> file.ts:4:3
+  |
+4 |   b: 3,
+  |   ^
+  |
+2 |   b: 1,
+  |   - first defined here
+  = hint: Rename it"#
+    );
+  }
+
+  #[test]
+  fn color() {
+    let formatted = PrettyFormatter::new()
+      .format(&diagnostic((1, 0), (1, 9), None), "debugger;");
+    assert!(formatted.contains("\u{1b}["));
+  }
+
+  #[test]
+  fn format_diagnostics() {
+    let mut first = diagnostic((1, 0), (1, 9), None);
+    first.filename = "a.ts".to_string();
+    let mut second = diagnostic((1, 2), (1, 3), None);
+    second.filename = "b.ts".to_string();
+    let sources = vec![("a.ts".to_string(), "debugger;".to_string())];
+
+    assert_eq!(
+      PrettyFormatter::new()
+        .use_color(false)
+        .format_diagnostics(&[first, second], &sources),
+      r#"(some-rule) Something is wrong
+ --> a.ts:1:1
+  |
+1 | debugger;
+  | ^^^^^^^^^
+
+(some-rule) Something is wrong
+ --> b.ts:1:3"#
+    );
+  }
+
+  #[test]
+  fn registry() {
+    let mut registry = FormatterRegistry::with_builtin_formatters();
+    let names = registry.names();
+    for name in &["pretty", "compact", "github", "sarif"] {
+      assert!(names.contains(name));
+    }
+    assert!(registry.get("checkstyle").is_none());
+    assert_eq!(
+      registry
+        .register("compact", Box::new(CompactFormatter))
+        .unwrap_err()
+        .name,
+      "compact"
+    );
+
+    let files = vec![FileDiagnostics {
+      file_name: "file.ts".to_string(),
+      diagnostics: Ok(vec![diagnostic((1, 0), (1, 9), None)]),
+    }];
+    assert_eq!(
+      registry.get("compact").unwrap().format(&files, &()),
+      "file.ts:1:1: Something is wrong [some-rule]"
+    );
+  }
+}
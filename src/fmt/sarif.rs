@@ -0,0 +1,282 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::{DiagnosticFormatter, SourceProvider};
+use crate::diagnostic::{LintDiagnostic, Severity};
+use crate::rules::LintRule;
+use serde::Serialize;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+#[derive(Debug, Serialize)]
+pub struct SarifLog {
+  #[serde(rename = "$schema")]
+  pub schema: &'static str,
+  pub version: &'static str,
+  pub runs: Vec<Run>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Run {
+  pub tool: Tool,
+  pub results: Vec<SarifResult>,
+  /// Unit of the columns of `Region`.
+  pub column_kind: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Tool {
+  pub driver: Driver,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Driver {
+  pub name: &'static str,
+  pub version: &'static str,
+  pub information_uri: &'static str,
+  pub rules: Vec<ReportingDescriptor>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportingDescriptor {
+  pub id: String,
+  pub short_description: Message,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Message {
+  pub text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+  pub rule_id: String,
+  pub rule_index: usize,
+  pub level: &'static str,
+  pub message: Message,
+  pub locations: Vec<Location>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+  pub physical_location: PhysicalLocation,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicalLocation {
+  pub artifact_location: ArtifactLocation,
+  pub region: Region,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArtifactLocation {
+  pub uri: String,
+}
+
+/// Lines and columns are 1-based, columns count Unicode code points.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Region {
+  pub start_line: usize,
+  pub start_column: usize,
+  pub end_line: usize,
+  pub end_column: usize,
+}
+
+/// Formats diagnostics as a SARIF log, see `to_sarif`.
+pub struct SarifFormatter {
+  rules: Vec<Box<dyn LintRule>>,
+}
+
+impl SarifFormatter {
+  /// `rules` provide the descriptors of the diagnostics' codes.
+  pub fn new(rules: Vec<Box<dyn LintRule>>) -> Self {
+    Self { rules }
+  }
+}
+
+impl DiagnosticFormatter for SarifFormatter {
+  fn format_diagnostics(
+    &self,
+    diagnostics: &[LintDiagnostic],
+    _sources: &dyn SourceProvider,
+  ) -> String {
+    let log = to_sarif(&[diagnostics.to_vec()], &self.rules);
+    serde_json::to_string_pretty(&log).unwrap()
+  }
+}
+
+/// Creates a SARIF log with a single run from diagnostics of each linted
+/// file. `rules` provides the rule descriptors; codes of diagnostics not
+/// coming from any of the rules get a descriptor without docs.
+pub fn to_sarif(
+  files: &[Vec<LintDiagnostic>],
+  rules: &[Box<dyn LintRule>],
+) -> SarifLog {
+  let mut descriptors = rules
+    .iter()
+    .map(|rule| ReportingDescriptor {
+      id: rule.code().to_string(),
+      short_description: Message {
+        text: rule.docs().lines().next().unwrap_or("").to_string(),
+      },
+    })
+    .collect::<Vec<_>>();
+
+  let mut results = vec![];
+  for diagnostic in files.iter().flatten() {
+    let rule_index =
+      match descriptors.iter().position(|d| d.id == diagnostic.code) {
+        Some(index) => index,
+        None => {
+          descriptors.push(ReportingDescriptor {
+            id: diagnostic.code.clone(),
+            short_description: Message {
+              text: diagnostic.code.clone(),
+            },
+          });
+          descriptors.len() - 1
+        }
+      };
+
+    let range = &diagnostic.range;
+    results.push(SarifResult {
+      rule_id: diagnostic.code.clone(),
+      rule_index,
+      level: match diagnostic.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Hint => "note",
+      },
+      message: Message {
+        text: diagnostic.message.clone(),
+      },
+      locations: vec![Location {
+        physical_location: PhysicalLocation {
+          artifact_location: ArtifactLocation {
+            uri: diagnostic.filename.clone(),
+          },
+          region: Region {
+            start_line: range.start.line,
+            start_column: range.start.col + 1,
+            end_line: range.end.line,
+            end_column: range.end.col + 1,
+          },
+        },
+      }],
+    });
+  }
+
+  SarifLog {
+    schema: SARIF_SCHEMA,
+    version: "2.1.0",
+    runs: vec![Run {
+      tool: Tool {
+        driver: Driver {
+          name: "deno_lint",
+          version: env!("CARGO_PKG_VERSION"),
+          information_uri: "https://github.com/denoland/deno_lint",
+          rules: descriptors,
+        },
+      },
+      results,
+      column_kind: "unicodeCodePoints",
+    }],
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::linter::LinterBuilder;
+  use crate::rules::no_debugger::NoDebugger;
+  use crate::rules::no_var::NoVar;
+
+  fn lint(file_name: &str, source: &str) -> Vec<LintDiagnostic> {
+    LinterBuilder::default()
+      .rules(vec![NoDebugger::new(), NoVar::new()])
+      .build()
+      .lint(file_name.to_string(), source.to_string())
+      .unwrap()
+  }
+
+  #[test]
+  fn sarif_for_two_files() {
+    let files = vec![
+      lint("a.ts", "debugger;\n"),
+      lint(
+        "b.ts",
+        "const a = 1;\n  var b = 2;\n// deno-lint-ignore no-var\n",
+      ),
+    ];
+    let rules: Vec<Box<dyn LintRule>> = vec![NoDebugger::new(), NoVar::new()];
+    let sarif = serde_json::to_value(to_sarif(&files, &rules)).unwrap();
+
+    assert_eq!(sarif["version"], "2.1.0");
+    assert_eq!(sarif["$schema"], SARIF_SCHEMA);
+    let run = &sarif["runs"][0];
+    assert_eq!(run["columnKind"], "unicodeCodePoints");
+    let descriptors = run["tool"]["driver"]["rules"].as_array().unwrap();
+    assert_eq!(descriptors.len(), 3);
+    assert_eq!(descriptors[0]["id"], "no-debugger");
+    assert_eq!(
+      descriptors[0]["shortDescription"]["text"],
+      "Disallows the use of the `debugger` statement."
+    );
+    assert_eq!(descriptors[1]["id"], "no-var");
+    assert_eq!(descriptors[2]["id"], "ban-unused-ignore");
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0]["ruleId"], "no-debugger");
+    assert_eq!(results[0]["ruleIndex"], 0);
+    assert_eq!(
+      results[0]["locations"][0]["physicalLocation"],
+      serde_json::json!({
+        "artifactLocation": { "uri": "a.ts" },
+        "region": {
+          "startLine": 1,
+          "startColumn": 1,
+          "endLine": 1,
+          "endColumn": 10,
+        },
+      })
+    );
+    assert_eq!(results[1]["ruleId"], "no-var");
+    assert_eq!(results[1]["ruleIndex"], 1);
+    assert_eq!(
+      results[1]["locations"][0]["physicalLocation"]["region"],
+      serde_json::json!({
+        "startLine": 2,
+        "startColumn": 3,
+        "endLine": 2,
+        "endColumn": 6,
+      })
+    );
+    assert_eq!(results[2]["ruleId"], "ban-unused-ignore");
+    assert_eq!(results[2]["ruleIndex"], 2);
+    assert_eq!(results[2]["level"], "error");
+  }
+
+  #[test]
+  fn sarif_columns_count_code_points() {
+    let files = vec![lint("a.ts", "/* 😀 */ var a = 1;\n")];
+    let rules: Vec<Box<dyn LintRule>> = vec![NoVar::new()];
+    let sarif = serde_json::to_value(to_sarif(&files, &rules)).unwrap();
+
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(
+      results[0]["locations"][0]["physicalLocation"]["region"],
+      serde_json::json!({
+        "startLine": 1,
+        "startColumn": 9,
+        "endLine": 1,
+        "endColumn": 12,
+      })
+    );
+  }
+}
@@ -1,61 +1,49 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 
-/// List of globals available in Deno environment
+/// Environments enabled when none are configured.
+pub static DEFAULT_ENV: &[&str] = &["es2021", "deno"];
+
+/// Returns globals defined in the environment with given name, or `None`
+/// if there's no such environment.
 ///
 /// Adapted from https://www.npmjs.com/package/globals
-#[allow(unused)]
-pub static GLOBALS: &[&str] = &[
-  "AbortController",
-  "AbortSignal",
-  "addEventListener",
+pub fn env_globals(env: &str) -> Option<Vec<&'static str>> {
+  let sets: &[&[&str]] = match env {
+    "es2021" => &[ES_GLOBALS],
+    "browser" => &[WEB_GLOBALS, BROWSER_GLOBALS],
+    "deno" => &[WEB_GLOBALS, DENO_GLOBALS],
+    "worker" => &[WEB_GLOBALS, WORKER_GLOBALS],
+    _ => return None,
+  };
+  Some(sets.concat())
+}
+
+/// Built-in objects of ECMAScript 2021.
+static ES_GLOBALS: &[&str] = &[
+  "AggregateError",
   "Array",
   "ArrayBuffer",
-  "atob",
   "Atomics",
   "BigInt",
   "BigInt64Array",
   "BigUint64Array",
-  "Blob",
   "Boolean",
-  "btoa",
-  "clearInterval",
-  "clearTimeout",
-  "close",
-  "closed",
-  "CloseEvent",
-  "console",
   "constructor",
-  "CountQueuingStrategy",
-  "crypto",
-  "CustomEvent",
   "DataView",
   "Date",
   "decodeURI",
   "decodeURIComponent",
-  "Deno",
-  "dispatchEvent",
-  "DOMException",
   "encodeURI",
   "encodeURIComponent",
   "Error",
-  "ErrorEvent",
   "escape",
   "eval",
   "EvalError",
-  "event",
-  "Event",
-  "EventSource",
-  "EventTarget",
-  "fetch",
-  "File",
-  "FileReader",
   "FinalizationRegistry",
   "Float32Array",
   "Float64Array",
-  "FormData",
   "Function",
   "globalThis",
-  "Headers",
   "hasOwnProperty",
   "Infinity",
   "Int16Array",
@@ -70,44 +58,22 @@ pub static GLOBALS: &[&str] = &[
   "NaN",
   "Number",
   "Object",
-  "onmessage",
-  "onmessageerror",
   "parseFloat",
   "parseInt",
-  "performance",
-  "Performance",
-  "PerformanceEntry",
-  "PerformanceMark",
-  "PerformanceMeasure",
-  "Permissions",
-  "PermissionStatus",
-  "postMessage",
-  "ProgressEvent",
   "Promise",
   "propertyIsEnumerable",
   "Proxy",
-  "queueMicrotask",
   "RangeError",
-  "ReadableStream",
   "ReferenceError",
   "Reflect",
   "RegExp",
-  "removeEventListener",
-  "Request",
-  "Response",
-  "self",
   "Set",
-  "setInterval",
-  "setTimeout",
   "SharedArrayBuffer",
   "String",
   "Symbol",
   "SyntaxError",
-  "TextDecoder",
-  "TextEncoder",
   "toLocaleString",
   "toString",
-  "TransformStream",
   "TypeError",
   "Uint16Array",
   "Uint32Array",
@@ -116,16 +82,116 @@ pub static GLOBALS: &[&str] = &[
   "undefined",
   "unescape",
   "URIError",
-  "URL",
-  "URLSearchParams",
   "valueOf",
   "WeakMap",
   "WeakRef",
   "WeakSet",
+];
+
+/// Web APIs available in browsers, web workers and Deno.
+static WEB_GLOBALS: &[&str] = &[
+  "AbortController",
+  "AbortSignal",
+  "addEventListener",
+  "atob",
+  "Blob",
+  "btoa",
+  "clearInterval",
+  "clearTimeout",
+  "CloseEvent",
+  "console",
+  "CountQueuingStrategy",
+  "crypto",
+  "CustomEvent",
+  "dispatchEvent",
+  "DOMException",
+  "ErrorEvent",
+  "Event",
+  "EventSource",
+  "EventTarget",
+  "fetch",
+  "File",
+  "FileReader",
+  "FormData",
+  "Headers",
+  "performance",
+  "Performance",
+  "PerformanceEntry",
+  "PerformanceMark",
+  "PerformanceMeasure",
+  "ProgressEvent",
+  "queueMicrotask",
+  "ReadableStream",
+  "removeEventListener",
+  "Request",
+  "Response",
+  "self",
+  "setInterval",
+  "setTimeout",
+  "TextDecoder",
+  "TextEncoder",
+  "TransformStream",
+  "URL",
+  "URLSearchParams",
   "WebAssembly",
   "WebSocket",
-  "window",
-  "Window",
   "Worker",
   "WritableStream",
 ];
+
+/// Globals specific to the main thread of a browser.
+static BROWSER_GLOBALS: &[&str] = &[
+  "alert",
+  "cancelAnimationFrame",
+  "close",
+  "closed",
+  "confirm",
+  "customElements",
+  "document",
+  "Document",
+  "Element",
+  "event",
+  "getComputedStyle",
+  "history",
+  "HTMLElement",
+  "localStorage",
+  "location",
+  "MutationObserver",
+  "navigator",
+  "Node",
+  "NodeList",
+  "open",
+  "postMessage",
+  "prompt",
+  "requestAnimationFrame",
+  "sessionStorage",
+  "window",
+  "Window",
+  "XMLHttpRequest",
+];
+
+/// Globals specific to Deno, including the `Deno` namespace.
+static DENO_GLOBALS: &[&str] = &[
+  "close",
+  "closed",
+  "Deno",
+  "event",
+  "onmessage",
+  "onmessageerror",
+  "Permissions",
+  "PermissionStatus",
+  "postMessage",
+  "window",
+  "Window",
+];
+
+/// Globals specific to web workers.
+static WORKER_GLOBALS: &[&str] = &[
+  "close",
+  "DedicatedWorkerGlobalScope",
+  "importScripts",
+  "onmessage",
+  "onmessageerror",
+  "postMessage",
+  "WorkerGlobalScope",
+];
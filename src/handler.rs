@@ -0,0 +1,428 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+//! Single traversal of a module shared by all rules.
+//!
+//! Rules implement `Handler` instead of walking the module themselves. The
+//! `CombinedVisitor` walks the AST once and calls every handler on entering
+//! and exiting each node, so the cost of the walk doesn't grow with the
+//! number of rules.
+use crate::time::Instant;
+use std::time::Duration;
+use swc_common::DUMMY_SP;
+use swc_ecmascript::ast::*;
+use swc_ecmascript::visit::{Node, Visit, VisitWith};
+
+/// Number of nodes visited between two checks of the deadline.
+const DEADLINE_CHECK_INTERVAL: usize = 256;
+
+/// Passed to the `visit_*` methods of handlers to control the traversal.
+#[derive(Default)]
+pub struct Traversal {
+  skip_children: bool,
+}
+
+impl Traversal {
+  /// Don't call the handler for descendants of the current node.
+  pub fn skip_children(&mut self) {
+    self.skip_children = true;
+  }
+}
+
+/// Whether the handler was suspended on entering a node.
+struct Suspension {
+  depth: usize,
+  entered: bool,
+}
+
+struct Slot<'a> {
+  handler: &'a mut dyn Handler,
+  visit_types: bool,
+  suspension: Option<Suspension>,
+  time: Duration,
+}
+
+/// Walks a module once, calling every handler for each node.
+pub struct CombinedVisitor<'a> {
+  slots: Vec<Slot<'a>>,
+  depth: usize,
+  collect_timings: bool,
+  deadline: Option<Instant>,
+  nodes_until_check: usize,
+  timed_out: bool,
+}
+
+impl<'a> CombinedVisitor<'a> {
+  pub fn new(handlers: Vec<&'a mut dyn Handler>) -> Self {
+    let slots = handlers
+      .into_iter()
+      .map(|handler| Slot {
+        visit_types: handler.visit_types(),
+        handler,
+        suspension: None,
+        time: Duration::default(),
+      })
+      .collect();
+    Self {
+      slots,
+      depth: 0,
+      collect_timings: false,
+      deadline: None,
+      nodes_until_check: 0,
+      timed_out: false,
+    }
+  }
+
+  /// Measures the time spent in the callbacks of each handler.
+  pub fn collect_timings(mut self, collect_timings: bool) -> Self {
+    self.collect_timings = collect_timings;
+    self
+  }
+
+  /// Stops the traversal once `deadline` is exceeded.
+  pub fn deadline(mut self, deadline: Option<Instant>) -> Self {
+    self.deadline = deadline;
+    self
+  }
+
+  /// Time spent in the callbacks of each handler, in the order they were
+  /// passed to `new`, if `collect_timings` is enabled.
+  pub fn timings(&self) -> Vec<Duration> {
+    self.slots.iter().map(|slot| slot.time).collect()
+  }
+
+  /// Whether the traversal stopped before visiting all nodes as the
+  /// deadline was exceeded.
+  pub fn timed_out(&self) -> bool {
+    self.timed_out
+  }
+
+  fn check_deadline(&mut self) -> bool {
+    if let Some(deadline) = self.deadline {
+      if self.nodes_until_check == 0 {
+        if Instant::now() >= deadline {
+          self.timed_out = true;
+        }
+        self.nodes_until_check = DEADLINE_CHECK_INTERVAL;
+      }
+      self.nodes_until_check -= 1;
+    }
+    !self.timed_out
+  }
+
+  fn call<F>(collect_timings: bool, slot: &mut Slot, f: F)
+  where
+    F: FnOnce(&mut dyn Handler),
+  {
+    if collect_timings {
+      let start = Instant::now();
+      f(slot.handler);
+      slot.time += start.elapsed();
+    } else {
+      f(slot.handler);
+    }
+  }
+
+  fn visit_node<N: ?Sized>(
+    &mut self,
+    n: &N,
+    parent: &dyn Node,
+    is_type: bool,
+    enter: impl Fn(&mut dyn Handler, &N, &mut Traversal),
+    exit: impl Fn(&mut dyn Handler, &N),
+    visit_children: impl FnOnce(&mut Self, &N, &dyn Node),
+  ) {
+    if !self.check_deadline() {
+      return;
+    }
+
+    let depth = self.depth;
+    let collect_timings = self.collect_timings;
+    let mut has_active = false;
+    for slot in &mut self.slots {
+      if slot.suspension.is_some() {
+        continue;
+      }
+      if is_type && !slot.visit_types {
+        slot.suspension = Some(Suspension {
+          depth,
+          entered: false,
+        });
+        continue;
+      }
+      let mut traversal = Traversal::default();
+      Self::call(collect_timings, slot, |handler| {
+        enter(handler, n, &mut traversal)
+      });
+      if traversal.skip_children {
+        slot.suspension = Some(Suspension {
+          depth,
+          entered: true,
+        });
+      } else {
+        has_active = true;
+      }
+    }
+
+    // Descendants of nodes no handler is interested in are skipped.
+    if has_active {
+      self.depth += 1;
+      visit_children(self, n, parent);
+      self.depth -= 1;
+    }
+
+    for slot in &mut self.slots {
+      let entered = match &slot.suspension {
+        None => true,
+        Some(suspension) if suspension.depth == depth => {
+          let entered = suspension.entered;
+          slot.suspension = None;
+          entered
+        }
+        Some(_) => false,
+      };
+      if entered {
+        Self::call(collect_timings, slot, |handler| exit(handler, n));
+      }
+    }
+  }
+}
+
+/// Walks `node` calling only `handler`, e.g. to visit some children of a
+/// node after calling `Traversal::skip_children`.
+pub fn walk<N>(handler: &mut dyn Handler, node: &N)
+where
+  N: ?Sized + for<'a> VisitWith<CombinedVisitor<'a>>,
+{
+  let mut visitor = CombinedVisitor::new(vec![handler]);
+  node.visit_with(&Invalid { span: DUMMY_SP }, &mut visitor);
+}
+
+macro_rules! define_handler {
+  (
+    nodes { $($visit:ident, $exit:ident: $ty:ty;)* }
+    types { $($type_visit:ident, $type_exit:ident: $type_ty:ty;)* }
+  ) => {
+    /// Callbacks of a rule, called by `CombinedVisitor` on entering
+    /// (`visit_*`) and exiting (`exit_*`) each node of the module.
+    pub trait Handler {
+      /// Whether to receive TypeScript types and declarations that swc's
+      /// `noop_visit_type!` skips, including nodes inside of them.
+      fn visit_types(&self) -> bool {
+        false
+      }
+
+      $(
+        fn $visit(&mut self, _n: &$ty, _traversal: &mut Traversal) {}
+        fn $exit(&mut self, _n: &$ty) {}
+      )*
+      $(
+        fn $type_visit(&mut self, _n: &$type_ty, _traversal: &mut Traversal) {}
+        fn $type_exit(&mut self, _n: &$type_ty) {}
+      )*
+    }
+
+    impl<'a> Visit for CombinedVisitor<'a> {
+      $(
+        fn $visit(&mut self, n: &$ty, parent: &dyn Node) {
+          self.visit_node(
+            n,
+            parent,
+            false,
+            |handler, n, traversal| handler.$visit(n, traversal),
+            |handler, n| handler.$exit(n),
+            |visitor, n, parent| {
+              swc_ecmascript::visit::$visit(visitor, n, parent)
+            },
+          );
+        }
+      )*
+      $(
+        fn $type_visit(&mut self, n: &$type_ty, parent: &dyn Node) {
+          self.visit_node(
+            n,
+            parent,
+            true,
+            |handler, n, traversal| handler.$type_visit(n, traversal),
+            |handler, n| handler.$type_exit(n),
+            |visitor, n, parent| {
+              swc_ecmascript::visit::$type_visit(visitor, n, parent)
+            },
+          );
+        }
+      )*
+    }
+  };
+}
+
+define_handler! {
+  nodes {
+    visit_array_lit, exit_array_lit: ArrayLit;
+    visit_array_pat, exit_array_pat: ArrayPat;
+    visit_arrow_expr, exit_arrow_expr: ArrowExpr;
+    visit_assign_expr, exit_assign_expr: AssignExpr;
+    visit_assign_pat, exit_assign_pat: AssignPat;
+    visit_assign_pat_prop, exit_assign_pat_prop: AssignPatProp;
+    visit_await_expr, exit_await_expr: AwaitExpr;
+    visit_bin_expr, exit_bin_expr: BinExpr;
+    visit_block_stmt, exit_block_stmt: BlockStmt;
+    visit_break_stmt, exit_break_stmt: BreakStmt;
+    visit_call_expr, exit_call_expr: CallExpr;
+    visit_catch_clause, exit_catch_clause: CatchClause;
+    visit_class, exit_class: Class;
+    visit_class_decl, exit_class_decl: ClassDecl;
+    visit_class_expr, exit_class_expr: ClassExpr;
+    visit_class_method, exit_class_method: ClassMethod;
+    visit_class_prop, exit_class_prop: ClassProp;
+    visit_cond_expr, exit_cond_expr: CondExpr;
+    visit_constructor, exit_constructor: Constructor;
+    visit_continue_stmt, exit_continue_stmt: ContinueStmt;
+    visit_debugger_stmt, exit_debugger_stmt: DebuggerStmt;
+    visit_decl, exit_decl: Decl;
+    visit_do_while_stmt, exit_do_while_stmt: DoWhileStmt;
+    visit_empty_stmt, exit_empty_stmt: EmptyStmt;
+    visit_export_decl, exit_export_decl: ExportDecl;
+    visit_export_named_specifier, exit_export_named_specifier:
+      ExportNamedSpecifier;
+    visit_expr, exit_expr: Expr;
+    visit_expr_stmt, exit_expr_stmt: ExprStmt;
+    visit_fn_decl, exit_fn_decl: FnDecl;
+    visit_fn_expr, exit_fn_expr: FnExpr;
+    visit_for_in_stmt, exit_for_in_stmt: ForInStmt;
+    visit_for_of_stmt, exit_for_of_stmt: ForOfStmt;
+    visit_for_stmt, exit_for_stmt: ForStmt;
+    visit_function, exit_function: Function;
+    visit_getter_prop, exit_getter_prop: GetterProp;
+    visit_ident, exit_ident: Ident;
+    visit_if_stmt, exit_if_stmt: IfStmt;
+    visit_import_default_specifier, exit_import_default_specifier:
+      ImportDefaultSpecifier;
+    visit_import_named_specifier, exit_import_named_specifier:
+      ImportNamedSpecifier;
+    visit_import_star_as_specifier, exit_import_star_as_specifier:
+      ImportStarAsSpecifier;
+    visit_key_value_prop, exit_key_value_prop: KeyValueProp;
+    visit_labeled_stmt, exit_labeled_stmt: LabeledStmt;
+    visit_lit, exit_lit: Lit;
+    visit_member_expr, exit_member_expr: MemberExpr;
+    visit_method_prop, exit_method_prop: MethodProp;
+    visit_module, exit_module: Module;
+    visit_module_decl, exit_module_decl: ModuleDecl;
+    visit_module_item, exit_module_item: ModuleItem;
+    visit_module_items, exit_module_items: [ModuleItem];
+    visit_named_export, exit_named_export: NamedExport;
+    visit_new_expr, exit_new_expr: NewExpr;
+    visit_number, exit_number: Number;
+    visit_object_lit, exit_object_lit: ObjectLit;
+    visit_object_pat, exit_object_pat: ObjectPat;
+    visit_opt_chain_expr, exit_opt_chain_expr: OptChainExpr;
+    visit_param, exit_param: Param;
+    visit_params, exit_params: [Param];
+    visit_pat, exit_pat: Pat;
+    visit_private_method, exit_private_method: PrivateMethod;
+    visit_prop, exit_prop: Prop;
+    visit_regex, exit_regex: Regex;
+    visit_rest_pat, exit_rest_pat: RestPat;
+    visit_return_stmt, exit_return_stmt: ReturnStmt;
+    visit_setter_prop, exit_setter_prop: SetterProp;
+    visit_stmt, exit_stmt: Stmt;
+    visit_stmts, exit_stmts: [Stmt];
+    visit_str, exit_str: Str;
+    visit_super, exit_super: Super;
+    visit_switch_case, exit_switch_case: SwitchCase;
+    visit_switch_cases, exit_switch_cases: [SwitchCase];
+    visit_switch_stmt, exit_switch_stmt: SwitchStmt;
+    visit_this_expr, exit_this_expr: ThisExpr;
+    visit_throw_stmt, exit_throw_stmt: ThrowStmt;
+    visit_tpl, exit_tpl: Tpl;
+    visit_try_stmt, exit_try_stmt: TryStmt;
+    visit_ts_as_expr, exit_ts_as_expr: TsAsExpr;
+    visit_ts_expr_with_type_args, exit_ts_expr_with_type_args:
+      TsExprWithTypeArgs;
+    visit_ts_non_null_expr, exit_ts_non_null_expr: TsNonNullExpr;
+    visit_unary_expr, exit_unary_expr: UnaryExpr;
+    visit_update_expr, exit_update_expr: UpdateExpr;
+    visit_var_decl, exit_var_decl: VarDecl;
+    visit_var_decl_or_pat, exit_var_decl_or_pat: VarDeclOrPat;
+    visit_var_declarator, exit_var_declarator: VarDeclarator;
+    visit_while_stmt, exit_while_stmt: WhileStmt;
+    visit_with_stmt, exit_with_stmt: WithStmt;
+    visit_yield_expr, exit_yield_expr: YieldExpr;
+  }
+  types {
+    visit_accessibility, exit_accessibility: Accessibility;
+    visit_true_plus_minus, exit_true_plus_minus: TruePlusMinus;
+    visit_ts_array_type, exit_ts_array_type: TsArrayType;
+    visit_ts_call_signature_decl, exit_ts_call_signature_decl:
+      TsCallSignatureDecl;
+    visit_ts_conditional_type, exit_ts_conditional_type: TsConditionalType;
+    visit_ts_construct_signature_decl, exit_ts_construct_signature_decl:
+      TsConstructSignatureDecl;
+    visit_ts_constructor_type, exit_ts_constructor_type: TsConstructorType;
+    visit_ts_entity_name, exit_ts_entity_name: TsEntityName;
+    visit_ts_enum_decl, exit_ts_enum_decl: TsEnumDecl;
+    visit_ts_enum_member, exit_ts_enum_member: TsEnumMember;
+    visit_ts_enum_member_id, exit_ts_enum_member_id: TsEnumMemberId;
+    visit_ts_external_module_ref, exit_ts_external_module_ref:
+      TsExternalModuleRef;
+    visit_ts_fn_or_constructor_type, exit_ts_fn_or_constructor_type:
+      TsFnOrConstructorType;
+    visit_ts_fn_param, exit_ts_fn_param: TsFnParam;
+    visit_ts_fn_type, exit_ts_fn_type: TsFnType;
+    visit_ts_import_equals_decl, exit_ts_import_equals_decl:
+      TsImportEqualsDecl;
+    visit_ts_import_type, exit_ts_import_type: TsImportType;
+    visit_ts_index_signature, exit_ts_index_signature: TsIndexSignature;
+    visit_ts_indexed_access_type, exit_ts_indexed_access_type:
+      TsIndexedAccessType;
+    visit_ts_infer_type, exit_ts_infer_type: TsInferType;
+    visit_ts_interface_body, exit_ts_interface_body: TsInterfaceBody;
+    visit_ts_interface_decl, exit_ts_interface_decl: TsInterfaceDecl;
+    visit_ts_intersection_type, exit_ts_intersection_type:
+      TsIntersectionType;
+    visit_ts_keyword_type, exit_ts_keyword_type: TsKeywordType;
+    visit_ts_keyword_type_kind, exit_ts_keyword_type_kind: TsKeywordTypeKind;
+    visit_ts_mapped_type, exit_ts_mapped_type: TsMappedType;
+    visit_ts_method_signature, exit_ts_method_signature: TsMethodSignature;
+    visit_ts_module_block, exit_ts_module_block: TsModuleBlock;
+    visit_ts_module_decl, exit_ts_module_decl: TsModuleDecl;
+    visit_ts_module_name, exit_ts_module_name: TsModuleName;
+    visit_ts_module_ref, exit_ts_module_ref: TsModuleRef;
+    visit_ts_namespace_body, exit_ts_namespace_body: TsNamespaceBody;
+    visit_ts_namespace_decl, exit_ts_namespace_decl: TsNamespaceDecl;
+    visit_ts_namespace_export_decl, exit_ts_namespace_export_decl:
+      TsNamespaceExportDecl;
+    visit_ts_optional_type, exit_ts_optional_type: TsOptionalType;
+    visit_ts_param_prop, exit_ts_param_prop: TsParamProp;
+    visit_ts_param_prop_param, exit_ts_param_prop_param: TsParamPropParam;
+    visit_ts_parenthesized_type, exit_ts_parenthesized_type:
+      TsParenthesizedType;
+    visit_ts_property_signature, exit_ts_property_signature:
+      TsPropertySignature;
+    visit_ts_qualified_name, exit_ts_qualified_name: TsQualifiedName;
+    visit_ts_rest_type, exit_ts_rest_type: TsRestType;
+    visit_ts_signature_decl, exit_ts_signature_decl: TsSignatureDecl;
+    visit_ts_this_type, exit_ts_this_type: TsThisType;
+    visit_ts_this_type_or_ident, exit_ts_this_type_or_ident:
+      TsThisTypeOrIdent;
+    visit_ts_tuple_type, exit_ts_tuple_type: TsTupleType;
+    visit_ts_type, exit_ts_type: TsType;
+    visit_ts_type_alias_decl, exit_ts_type_alias_decl: TsTypeAliasDecl;
+    visit_ts_type_ann, exit_ts_type_ann: TsTypeAnn;
+    visit_ts_type_assertion, exit_ts_type_assertion: TsTypeAssertion;
+    visit_ts_type_cast_expr, exit_ts_type_cast_expr: TsTypeCastExpr;
+    visit_ts_type_element, exit_ts_type_element: TsTypeElement;
+    visit_ts_type_lit, exit_ts_type_lit: TsTypeLit;
+    visit_ts_type_operator, exit_ts_type_operator: TsTypeOperator;
+    visit_ts_type_operator_op, exit_ts_type_operator_op: TsTypeOperatorOp;
+    visit_ts_type_param, exit_ts_type_param: TsTypeParam;
+    visit_ts_type_param_decl, exit_ts_type_param_decl: TsTypeParamDecl;
+    visit_ts_type_param_instantiation, exit_ts_type_param_instantiation:
+      TsTypeParamInstantiation;
+    visit_ts_type_predicate, exit_ts_type_predicate: TsTypePredicate;
+    visit_ts_type_query, exit_ts_type_query: TsTypeQuery;
+    visit_ts_type_query_expr, exit_ts_type_query_expr: TsTypeQueryExpr;
+    visit_ts_type_ref, exit_ts_type_ref: TsTypeRef;
+    visit_ts_union_or_intersection_type, exit_ts_union_or_intersection_type:
+      TsUnionOrIntersectionType;
+    visit_ts_union_type, exit_ts_union_type: TsUnionType;
+  }
+}
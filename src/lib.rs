@@ -8,21 +8,36 @@ extern crate lazy_static;
 #[macro_use]
 extern crate log;
 
+#[cfg(test)]
+#[macro_use]
+mod test_util;
+
+pub mod bindings;
+pub mod config;
 mod control_flow;
 pub mod diagnostic;
+pub mod docs;
+pub mod fmt;
 mod globals;
+pub mod handler;
 mod js_regex;
 pub mod linter;
+#[cfg(feature = "lsp")]
+pub mod lsp;
 pub mod rules;
 mod scopes;
 pub mod swc_util;
+mod time;
+pub mod walk;
 
-#[cfg(test)]
-mod test_util;
+// Re-exported so that rules defined outside of this crate use the same
+// AST and visitor types as the linter.
+pub use swc_common;
+pub use swc_ecmascript;
 
 #[cfg(test)]
 mod lint_tests {
-  use crate::diagnostic::LintDiagnostic;
+  use crate::diagnostic::{DiagnosticsExt, LintDiagnostic, Position, Severity};
   use crate::linter::*;
   use crate::rules::get_recommended_rules;
   use crate::test_util::assert_diagnostic;
@@ -43,6 +58,142 @@ mod lint_tests {
       .expect("Failed to lint")
   }
 
+  #[test]
+  fn diagnostic_range() {
+    let src = "function foo() {\n  return;\n  a();\n  b();\n}\n";
+    let diagnostics = lint(src, false, false);
+    let diagnostic = diagnostics
+      .iter()
+      .find(|d| d.code == "no-unreachable")
+      .unwrap();
+
+    let range = &diagnostic.range;
+    assert_eq!(
+      range.start,
+      Position {
+        line: 3,
+        col: 2,
+        col_utf16: 2,
+        byte_pos: 29
+      }
+    );
+    assert_eq!(
+      range.end,
+      Position {
+        line: 4,
+        col: 6,
+        col_utf16: 6,
+        byte_pos: 40
+      }
+    );
+  }
+
+  #[test]
+  fn diagnostic_range_with_non_ascii_chars() {
+    let src = "// 中文\nconst s = \"😀中\"; debugger;\n";
+    let diagnostics = lint(src, false, false);
+    assert_eq!(diagnostics.len(), 1);
+
+    // 😀 is 1 char, 2 UTF-16 code units and 4 bytes; 中 is 1 char,
+    // 1 UTF-16 code unit and 3 bytes.
+    let range = &diagnostics[0].range;
+    assert_eq!(
+      range.start,
+      Position {
+        line: 2,
+        col: 16,
+        col_utf16: 17,
+        byte_pos: 31,
+      }
+    );
+    assert_eq!(
+      range.end,
+      Position {
+        line: 2,
+        col: 25,
+        col_utf16: 26,
+        byte_pos: 40,
+      }
+    );
+    assert_eq!(&src[range.start.byte_pos..range.end.byte_pos], "debugger;");
+  }
+
+  #[test]
+  fn diagnostic_range_json() {
+    let src = "function foo() {\n  return;\n  a();\n  b();\n}\n";
+    let diagnostics = lint(src, false, false);
+    let diagnostic = diagnostics
+      .iter()
+      .find(|d| d.code == "no-unreachable")
+      .unwrap();
+    let json = serde_json::to_value(diagnostic).unwrap();
+
+    assert_eq!(
+      json["range"],
+      serde_json::json!({
+        "start": { "line": 3, "col": 2, "col_utf16": 2, "byte_pos": 29 },
+        "end": { "line": 4, "col": 6, "col_utf16": 6, "byte_pos": 40 },
+      })
+    );
+  }
+
+  #[test]
+  fn diagnostics_sorted_by_position() {
+    let diagnostics = lint("const a = 1;\nif (a) {} debugger;\n", false, false);
+
+    assert_eq!(diagnostics.len(), 2);
+    assert_diagnostic(&diagnostics[0], "no-empty", 2, 7, "");
+    assert_diagnostic(&diagnostics[1], "no-debugger", 2, 10, "");
+  }
+
+  #[test]
+  fn diagnostic_suggestions_json() {
+    let diagnostics = lint("let x = 1;\nif (x = 2) {}\n", false, false);
+    let diagnostic = diagnostics
+      .iter()
+      .find(|d| d.code == "no-cond-assign")
+      .unwrap();
+    let json = serde_json::to_value(diagnostic).unwrap();
+
+    assert_eq!(
+      json["suggestions"][0],
+      serde_json::json!({
+        "description": "Use '===' to compare values",
+        "fix": { "start": 16, "end": 19, "replacement": " === " },
+      })
+    );
+    assert_eq!(json["fix"], serde_json::Value::Null);
+    assert_eq!(json["hint"], "Did you mean to use '===' instead of '='?");
+  }
+
+  #[test]
+  fn diagnostic_severity() {
+    let diagnostics = lint("// TODO\nfunction foo() {}\n", false, false);
+    assert!(!diagnostics.has_errors());
+
+    let diagnostics = lint("let a = 1;\ndebugger;\n", false, false);
+    assert!(diagnostics.has_errors());
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+  }
+
+  #[test]
+  fn diagnostics_into_result() {
+    let diagnostics = lint("// TODO\nfunction foo() {}\n", false, false);
+    assert_eq!(diagnostics.clone().into_result().unwrap(), diagnostics);
+
+    let diagnostics = lint("const a = 1;\ndebugger;\n", false, false);
+    let err = diagnostics.into_result().unwrap_err();
+    assert!(matches!(&err, LintError::Diagnostics(d) if d.len() == 1));
+    assert_eq!(
+      err.to_string(),
+      "Found 1 problem\nlint_test.ts:2:1 no-debugger `debugger` statement is not allowed"
+    );
+
+    let diagnostics = lint("debugger;\ndebugger;\n", false, false);
+    let err = diagnostics.into_result().unwrap_err();
+    assert!(err.to_string().starts_with("Found 2 problems\n"));
+  }
+
   #[test]
   fn empty_file() {
     let diagnostics = lint("", true, false);
@@ -112,6 +263,83 @@ mod lint_tests {
     assert_eq!(diagnostics.len(), 0);
   }
 
+  #[test]
+  fn ignore_one_of_codes_on_line() {
+    let src = r#"
+const a = 1;
+// deno-lint-ignore no-debugger
+if (a) {} debugger;
+      "#;
+    let diagnostics = lint(src, false, true);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-empty", 4, 7, src);
+  }
+
+  #[test]
+  fn ignore_with_explanation() {
+    let src = r#"
+// deno-lint-ignore no-debugger -- needed to debug flaky test
+debugger;
+      "#;
+    let diagnostics = lint(src, true, true);
+
+    assert!(diagnostics.is_empty());
+  }
+
+  #[test]
+  fn ignore_in_nested_blocks() {
+    let src = r#"
+function foo() {
+  if (foo) {
+    while (foo) {
+      // deno-lint-ignore no-debugger
+      debugger;
+      debugger;
+    }
+  }
+}
+      "#;
+    let diagnostics = lint(src, false, true);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-debugger", 7, 6, src);
+  }
+
+  #[test]
+  fn ignore_all_codes_on_line() {
+    let src = r#"
+const a = 1;
+// deno-lint-ignore
+if (a) {} debugger;
+debugger;
+      "#;
+    let diagnostics = lint(src, false, false)
+      .into_iter()
+      .filter(|d| d.code != "ban-untagged-ignore")
+      .collect::<Vec<_>>();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-debugger", 5, 0, src);
+  }
+
+  #[test]
+  fn warn_unused_dir_without_codes() {
+    let src = r#"
+// deno-lint-ignore
+Math.max(1, 2);
+// deno-lint-ignore
+debugger;
+      "#;
+    let diagnostics = lint(src, false, true)
+      .into_iter()
+      .filter(|d| d.code != "ban-untagged-ignore")
+      .collect::<Vec<_>>();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "ban-unused-ignore", 2, 0, src);
+  }
+
   #[test]
   fn file_directive_with_code() {
     let diagnostics = lint(
@@ -159,4 +387,532 @@ mod lint_tests {
     assert_eq!(diagnostics.len(), 1);
     assert_diagnostic(&diagnostics[0], "ban-unused-ignore", 4, 1, src);
   }
+
+  #[test]
+  fn file_directive() {
+    let src = r#"
+// Generated file, do not edit.
+// deno-lint-ignore-file
+
+function bar(p: any) {
+  debugger;
+}
+      "#;
+    let diagnostics = lint(src, true, true);
+
+    assert!(diagnostics.is_empty());
+  }
+
+  #[test]
+  fn file_directive_ignores_only_listed_codes() {
+    let src = r#"
+// deno-lint-ignore-file no-explicit-any
+
+function bar(p: any) {
+  debugger;
+}
+      "#;
+    let diagnostics = lint(src, false, true);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-debugger", 5, 2, src);
+  }
+
+  #[test]
+  fn misplaced_file_directive() {
+    let src = r#"
+function foo() {}
+
+// deno-lint-ignore-file
+
+function bar(p: any) {
+  debugger;
+}
+      "#;
+    let diagnostics = lint(src, false, false);
+
+    assert_eq!(diagnostics.len(), 2);
+    assert_diagnostic(&diagnostics[0], "no-explicit-any", 6, 16, src);
+    assert_diagnostic(&diagnostics[1], "no-debugger", 7, 2, src);
+  }
+
+  fn lint_with_eslint_directives(source: &str) -> Vec<LintDiagnostic> {
+    let mut linter = LinterBuilder::default()
+      .honor_eslint_directives(true)
+      .rules(get_recommended_rules())
+      .build();
+
+    linter
+      .lint("lint_test.ts".to_string(), source.to_string())
+      .expect("Failed to lint")
+  }
+
+  #[test]
+  fn eslint_disable_next_line() {
+    let src = r#"
+// eslint-disable-next-line no-debugger
+debugger;
+// eslint-disable-next-line @typescript-eslint/no-explicit-any, no-debugger
+function foo(a: any) { debugger; }
+// eslint-disable-next-line
+debugger;
+// eslint-disable-next-line some-plugin/some-rule
+debugger;
+      "#;
+    let diagnostics = lint_with_eslint_directives(src);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-debugger", 9, 0, src);
+  }
+
+  #[test]
+  fn eslint_disable_line() {
+    let src = r#"
+debugger; // eslint-disable-line no-debugger
+debugger; // eslint-disable-line no-empty
+debugger; /* eslint-disable-line */
+      "#;
+    let diagnostics = lint_with_eslint_directives(src);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-debugger", 3, 0, src);
+  }
+
+  #[test]
+  fn eslint_disable_enable() {
+    let src = r#"
+/* eslint-disable no-debugger */
+debugger;
+debugger;
+/* eslint-enable no-debugger */
+debugger;
+/* eslint-disable */
+debugger;
+      "#;
+    let diagnostics = lint_with_eslint_directives(src);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-debugger", 6, 0, src);
+  }
+
+  #[test]
+  fn eslint_enable_listed_codes_only() {
+    let src = r#"
+/* eslint-disable */
+debugger;
+/* eslint-enable no-debugger */
+debugger;
+if (a) {}
+/* eslint-disable no-debugger, no-empty */
+/* eslint-enable no-debugger */
+debugger;
+if (a) {}
+      "#;
+    let diagnostics = lint_with_eslint_directives(src);
+
+    assert_eq!(diagnostics.len(), 2);
+    assert_diagnostic(&diagnostics[0], "no-debugger", 5, 0, src);
+    assert_diagnostic(&diagnostics[1], "no-debugger", 9, 0, src);
+  }
+
+  #[test]
+  fn eslint_disable_line_comment_ignored() {
+    let src = r#"
+// eslint-disable no-debugger
+debugger;
+      "#;
+    let diagnostics = lint_with_eslint_directives(src);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-debugger", 3, 0, src);
+  }
+
+  #[test]
+  fn eslint_directives_disabled_by_default() {
+    let src = "// eslint-disable-next-line no-debugger\ndebugger;\n";
+    let diagnostics = lint(src, false, false);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-debugger", 2, 0, src);
+  }
+
+  #[test]
+  fn combined_rules_match_individual_rules() {
+    use crate::rules::get_all_rules;
+
+    let src = r#"
+var a = 1;
+let b: any = a == 2 ? "x" : 'y';
+function foo(x = 1, y) {
+  if (x = y) {}
+  debugger;
+  return;
+  console.log(b);
+}
+switch (a) {
+  case 1:
+    foo();
+  case 2:
+    break;
+}
+class A extends Object {
+  constructor() { this.a = 1; }
+  get x() {}
+  set x(v) { return v; }
+}
+while (true) { const c = 1; c = 2; }
+namespace N {
+  export const n: number = 1;
+  let unused = [, 1];
+}
+interface I { f(a: any): String; }
+enum E { A = typeof a === "strnig" ? 1 : 2 }
+const d = <const>{ k: async function* () { with (a) {} } };
+label: for (let i = 0; i < 10; i--) { if (i !== NaN) continue; }
+try {} finally { return; }
+export function bar(p) { let q = p; return new Symbol(q); }
+    "#;
+    let key = |d: &LintDiagnostic| {
+      (d.range.start.line, d.range.start.col, d.code.clone())
+    };
+
+    let mut linter = LinterBuilder::default()
+      .lint_unused_ignore_directives(false)
+      .lint_unknown_rules(false)
+      .rules(get_all_rules())
+      .build();
+    let mut combined = linter
+      .lint("lint_test.ts".to_string(), src.to_string())
+      .unwrap()
+      .iter()
+      .map(key)
+      .collect::<Vec<_>>();
+
+    let mut individual = vec![];
+    for rule in get_all_rules() {
+      let mut linter = LinterBuilder::default()
+        .lint_unused_ignore_directives(false)
+        .lint_unknown_rules(false)
+        .rules(vec![rule])
+        .build();
+      let diagnostics = linter
+        .lint("lint_test.ts".to_string(), src.to_string())
+        .unwrap();
+      individual.extend(diagnostics.iter().map(key));
+    }
+
+    combined.sort();
+    individual.sort();
+    let mut codes = combined.iter().map(|d| &d.2).collect::<Vec<_>>();
+    codes.sort();
+    codes.dedup();
+    assert!(codes.len() > 20);
+    assert_eq!(combined, individual);
+  }
+
+  #[test]
+  fn lint_files() {
+    let inputs = (0..16)
+      .map(|i| {
+        let source = if i == 5 {
+          "const = ;".to_string()
+        } else {
+          format!("{}debugger;\n", "\n".repeat(i))
+        };
+        (format!("file_{}.ts", i), source)
+      })
+      .collect::<Vec<_>>();
+
+    let results = Linter::lint_files(inputs, || {
+      LinterBuilder::default()
+        .rules(get_recommended_rules())
+        .build()
+    });
+
+    assert_eq!(results.len(), 16);
+    for (i, result) in results.iter().enumerate() {
+      assert_eq!(result.file_name, format!("file_{}.ts", i));
+      if i == 5 {
+        assert!(result.diagnostics.is_err());
+        continue;
+      }
+      let diagnostics = result.diagnostics.as_ref().unwrap();
+      assert_eq!(diagnostics.len(), 1);
+      assert_eq!(diagnostics[0].filename, result.file_name);
+      assert_eq!(diagnostics[0].range.start.line, i + 1);
+    }
+  }
+
+  fn lint_ts(source: &str) -> Vec<LintDiagnostic> {
+    lint_file_name("file.ts", source)
+  }
+
+  fn lint_file_name(file_name: &str, source: &str) -> Vec<LintDiagnostic> {
+    let mut linter = LinterBuilder::default()
+      .syntax(crate::swc_util::get_syntax_for_file_name(file_name))
+      .rules(get_recommended_rules())
+      .build();
+    linter
+      .lint(file_name.to_string(), source.to_string())
+      .expect("Failed to lint")
+  }
+
+  #[test]
+  fn shebang_only() {
+    assert_eq!(lint_ts("#!/usr/bin/env -S deno run\n"), vec![]);
+    assert_eq!(lint_ts("#!/usr/bin/env -S deno run"), vec![]);
+  }
+
+  #[test]
+  fn shebang_with_code() {
+    let diagnostics =
+      lint_file_name("script.js", "#!/usr/bin/env -S deno run\ndebugger;\n");
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-debugger", 2, 0, "");
+  }
+
+  #[test]
+  fn shebang_with_ignore_file_directive() {
+    let source = r#"#!/usr/bin/env -S deno run --allow-read
+// deno-lint-ignore-file
+debugger;
+"#;
+    assert_eq!(lint_ts(source), vec![]);
+
+    let source = r#"#!/usr/bin/env -S deno run --allow-read
+// deno-lint-ignore-file no-debugger
+debugger;
+const a = 1;
+if (a) {}
+"#;
+    let diagnostics = lint_ts(source);
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-empty", 5, 7, "");
+  }
+
+  #[test]
+  fn typescript_cond_assign() {
+    let source = r#"
+function check(a: number, b: number): boolean {
+  if (a = b) {
+    return true;
+  }
+  return false;
+}
+"#;
+    let diagnostics = lint_ts(source);
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-cond-assign", 3, 6, source);
+  }
+
+  #[test]
+  fn typescript_unreachable_after_as() {
+    let source = r#"
+interface Foo {
+  bar: string;
+}
+function foo(x: unknown): Foo {
+  return x as Foo;
+  console.log(x);
+}
+"#;
+    let diagnostics = lint_ts(source);
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-unreachable", 7, 2, source);
+  }
+
+  #[test]
+  fn typescript_decorators_and_enums() {
+    let diagnostics = lint_ts(
+      r#"
+enum Color {
+  Red,
+  Green = "green",
+}
+function sealed(constructor: new () => unknown) {
+  Object.seal(constructor);
+}
+@sealed
+export class Greeter<T> {
+  constructor(private readonly color: Color = Color.Red) {}
+  greet(value: T): string {
+    return `${value} ${this.color}`;
+  }
+}
+"#,
+    );
+    assert_eq!(diagnostics, vec![]);
+  }
+
+  #[test]
+  fn syntax_for_file_name() {
+    use crate::swc_util::get_syntax_for_file_name;
+    use swc_ecmascript::parser::{EsConfig, Syntax, TsConfig};
+
+    assert!(matches!(
+      get_syntax_for_file_name("a.ts"),
+      Syntax::Typescript(_)
+    ));
+    assert!(matches!(
+      get_syntax_for_file_name("a/b.d.ts"),
+      Syntax::Typescript(_)
+    ));
+    assert!(matches!(get_syntax_for_file_name("a.mjs"), Syntax::Es(_)));
+    assert!(matches!(get_syntax_for_file_name("a.js"), Syntax::Es(_)));
+    assert!(matches!(
+      get_syntax_for_file_name("a.tsx"),
+      Syntax::Typescript(TsConfig { tsx: true, .. })
+    ));
+    assert!(matches!(
+      get_syntax_for_file_name("a.jsx"),
+      Syntax::Es(EsConfig { jsx: true, .. })
+    ));
+    assert!(matches!(
+      get_syntax_for_file_name("README"),
+      Syntax::Typescript(_)
+    ));
+  }
+
+  #[test]
+  fn adapt_syntax_keeps_options() {
+    use crate::swc_util::adapt_syntax_to_file_name;
+    use swc_ecmascript::parser::{EsConfig, Syntax, TsConfig};
+
+    let es = Syntax::Es(EsConfig {
+      decorators: true,
+      ..EsConfig::default()
+    });
+    assert!(matches!(
+      adapt_syntax_to_file_name(es, "a.jsx"),
+      Syntax::Es(EsConfig {
+        jsx: true,
+        decorators: true,
+        ..
+      })
+    ));
+    assert!(matches!(
+      adapt_syntax_to_file_name(es, "a.js"),
+      Syntax::Es(EsConfig {
+        jsx: false,
+        decorators: true,
+        ..
+      })
+    ));
+    assert!(matches!(
+      adapt_syntax_to_file_name(es, "a.ts"),
+      Syntax::Typescript(TsConfig { tsx: false, .. })
+    ));
+    assert!(matches!(
+      adapt_syntax_to_file_name(es, "README"),
+      Syntax::Es(EsConfig {
+        decorators: true,
+        ..
+      })
+    ));
+  }
+
+  #[test]
+  fn tsx_component() {
+    let source = r#"
+export function Button(props: { label: string }) {
+  return <button class="btn">{props.label}</button>;
+}
+"#;
+    assert_eq!(lint_file_name("button.tsx", source), vec![]);
+  }
+
+  #[test]
+  fn tsx_diagnostic_in_attribute() {
+    let source = r#"
+export function Counter(dead: () => void) {
+  return (
+    <div>
+      Zählerstand — ändern:
+      <button onClick={() => { return; dead(); }}>+</button>
+    </div>
+  );
+}
+"#;
+    let diagnostics = lint_file_name("counter.tsx", source);
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-unreachable", 6, 39, source);
+  }
+
+  #[test]
+  fn jsx_component() {
+    let source = "const b = 1;\nexport const a = <div>{b}</div>;\n";
+    assert_eq!(lint_file_name("a.jsx", source), vec![]);
+  }
+
+  #[test]
+  fn parse_error_unterminated_string() {
+    let mut linter = LinterBuilder::default()
+      .rules(get_recommended_rules())
+      .build();
+    let err = linter
+      .lint(
+        "a.ts".to_string(),
+        "const a = 1;\nconst b = \"abc;\n".to_string(),
+      )
+      .unwrap_err();
+    assert_eq!(err.parse_diagnostics.len(), 1);
+    let range = &err.parse_diagnostics[0].range;
+    assert_eq!(
+      range.start,
+      Position {
+        line: 2,
+        col: 10,
+        col_utf16: 10,
+        byte_pos: 23
+      }
+    );
+  }
+
+  #[test]
+  fn parse_error_as_diagnostic() {
+    let mut linter = LinterBuilder::default()
+      .report_parse_errors(true)
+      .rules(get_recommended_rules())
+      .build();
+    let diagnostics = linter
+      .lint("a.ts".to_string(), "function foo() {\n}\n}\n".to_string())
+      .unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, "parse-error");
+    assert_eq!(diagnostics[0].filename, "a.ts");
+    assert_eq!(
+      diagnostics[0].range.start,
+      Position {
+        line: 3,
+        col: 0,
+        col_utf16: 0,
+        byte_pos: 19
+      }
+    );
+  }
+
+  #[test]
+  fn recovered_parse_error() {
+    let source = "export const a = 010;\ndebugger;\n";
+    let mut linter = LinterBuilder::default()
+      .report_parse_errors(true)
+      .rules(get_recommended_rules())
+      .build();
+    let diagnostics =
+      linter.lint("a.ts".to_string(), source.to_string()).unwrap();
+    let codes: Vec<_> = diagnostics.iter().map(|d| d.code.as_str()).collect();
+    assert_eq!(
+      codes,
+      vec!["no-octal", "parse-error", "parse-error", "no-debugger"]
+    );
+    assert_eq!(
+      diagnostics[1].range.start,
+      Position {
+        line: 1,
+        col: 17,
+        col_utf16: 17,
+        byte_pos: 17
+      }
+    );
+  }
 }
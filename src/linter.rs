@@ -1,22 +1,43 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
-use crate::diagnostic::{LintDiagnostic, Position, Range};
-use crate::rules::LintRule;
+use crate::config::{ConfigError, BUILTIN_CODES};
+use crate::diagnostic::{
+  LintDiagnostic, LintFix, LintReport, LintSuggestion, Position, Range,
+  RelatedInformation, Severity,
+};
+use crate::globals::{env_globals, DEFAULT_ENV};
+use crate::handler::{CombinedVisitor, Handler};
+use crate::rules::{DuplicateRuleError, LintRule};
 use crate::scopes::{analyze, Scope};
-use crate::swc_util::get_default_ts_config;
-use crate::swc_util::AstParser;
+use crate::swc_util::{adapt_syntax_to_file_name, get_syntax_for_file_name};
+use crate::swc_util::{AstParser, ModuleKind, SyntaxConfig};
+use crate::time::Instant;
+use crate::walk::{collect_files, WalkOptions};
 use crate::{control_flow::ControlFlow, swc_util::SwcDiagnosticBuffer};
+use rayon::prelude::*;
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::Mutex;
-use std::time::Instant;
+use std::time::Duration;
+use swc_atoms::JsWord;
 use swc_common::comments::CommentKind;
 use swc_common::comments::SingleThreadedComments;
+use swc_common::sync::Lrc;
 use swc_common::BytePos;
+use swc_common::SourceFile;
 use swc_common::SourceMap;
 use swc_common::Span;
+use swc_common::Spanned;
+use swc_common::DUMMY_SP;
 use swc_common::{comments::Comment, SyntaxContext};
+use swc_ecmascript::ast::{Invalid, Module};
 use swc_ecmascript::parser::Syntax;
+use swc_ecmascript::transforms::resolver::ts_resolver;
+use swc_ecmascript::visit::{FoldWith, VisitWith};
 
 lazy_static! {
   static ref IGNORE_COMMENT_CODE_RE: regex::Regex =
@@ -28,6 +49,7 @@ pub struct Context {
   pub file_name: String,
   pub diagnostics: Arc<Mutex<Vec<LintDiagnostic>>>,
   pub source_map: Arc<SourceMap>,
+  pub(crate) source_file: Lrc<SourceFile>,
   pub(crate) leading_comments: HashMap<BytePos, Vec<Comment>>,
   pub(crate) trailing_comments: HashMap<BytePos, Vec<Comment>>,
   pub ignore_directives: Vec<IgnoreDirective>,
@@ -35,24 +57,200 @@ pub struct Context {
   pub(crate) scope: Arc<Scope>,
   pub(crate) control_flow: Arc<ControlFlow>,
   pub(crate) top_level_ctxt: SyntaxContext,
+  /// Default severities of the rules, keyed by rule code.
+  pub(crate) severities: HashMap<&'static str, Severity>,
+  pub(crate) eslint_directives: Vec<EslintDirective>,
+  pub(crate) is_module: bool,
 }
 
 impl Context {
-  pub(crate) fn add_diagnostic(&self, span: Span, code: &str, message: &str) {
-    let diagnostic = self.create_diagnostic(span, code, message);
+  /// Whether the file was parsed as an ES module rather than as a script,
+  /// see `SyntaxConfig::kind`.
+  pub fn is_module(&self) -> bool {
+    self.is_module
+  }
+
+  /// Source code of the file being linted.
+  pub fn source_text(&self) -> &str {
+    &self.source_file.src
+  }
+
+  /// Slices the source code covered by `span`.
+  pub fn span_text(&self, span: Span) -> &str {
+    let start = (span.lo() - self.source_file.start_pos).0 as usize;
+    let end = (span.hi() - self.source_file.start_pos).0 as usize;
+    &self.source_text()[start..end]
+  }
+
+  /// Text of the given 1-based line, without the line break.
+  pub fn line_text(&self, line: usize) -> Option<&str> {
+    if line == 0 {
+      return None;
+    }
+    self.source_text().lines().nth(line - 1)
+  }
+
+  /// Comments directly before the node starting at `lo`.
+  pub fn leading_comments(&self, lo: BytePos) -> &[Comment] {
+    self.leading_comments.get(&lo).map_or(&[], |c| c.as_slice())
+  }
+
+  /// Comments directly after the node ending at `hi`.
+  pub fn trailing_comments(&self, hi: BytePos) -> &[Comment] {
+    self
+      .trailing_comments
+      .get(&hi)
+      .map_or(&[], |c| c.as_slice())
+  }
+
+  /// All comments in the file, ordered by position.
+  pub fn all_comments(&self) -> Vec<&Comment> {
+    let mut comments = self
+      .leading_comments
+      .values()
+      .chain(self.trailing_comments.values())
+      .flatten()
+      .collect::<Vec<_>>();
+    comments.sort_by_key(|comment| comment.span.lo());
+    comments
+  }
+
+  pub fn add_diagnostic(&self, span: Span, code: &str, message: &str) {
+    let diagnostic = self.create_default_diagnostic(span, code, message);
+    self.push_diagnostic(diagnostic);
+  }
+
+  /// Adds diagnostic with a severity different from the rule's default.
+  pub fn add_diagnostic_with_severity(
+    &self,
+    span: Span,
+    code: &str,
+    message: &str,
+    severity: Severity,
+  ) {
+    let diagnostic = self.create_diagnostic(span, code, message, severity);
+    self.push_diagnostic(diagnostic);
+  }
+
+  /// Adds diagnostic with a hint on how to fix the problem.
+  pub fn add_diagnostic_with_hint(
+    &self,
+    span: Span,
+    code: &str,
+    message: &str,
+    hint: &str,
+  ) {
+    let mut diagnostic = self.create_default_diagnostic(span, code, message);
+    diagnostic.hint = Some(hint.to_string());
+    self.push_diagnostic(diagnostic);
+  }
+
+  /// Adds diagnostic with a fix replacing `fix_span` with `replacement`.
+  pub fn add_diagnostic_with_fix(
+    &self,
+    span: Span,
+    code: &str,
+    message: &str,
+    fix_span: Span,
+    replacement: &str,
+  ) {
+    let mut diagnostic = self.create_default_diagnostic(span, code, message);
+    diagnostic.fix = Some(self.create_fix(fix_span, replacement));
+    self.push_diagnostic(diagnostic);
+  }
+
+  /// Adds diagnostic with suggestions created by `create_suggestion`.
+  pub fn add_diagnostic_with_suggestions(
+    &self,
+    span: Span,
+    code: &str,
+    message: &str,
+    suggestions: Vec<LintSuggestion>,
+  ) {
+    let mut diagnostic = self.create_default_diagnostic(span, code, message);
+    diagnostic.suggestions = suggestions;
+    self.push_diagnostic(diagnostic);
+  }
+
+  /// Adds diagnostic with secondary locations, each given by a span and
+  /// a short label such as "first defined here".
+  pub fn add_diagnostic_with_related(
+    &self,
+    span: Span,
+    code: &str,
+    message: &str,
+    related: Vec<(Span, &str)>,
+  ) {
+    let mut diagnostic = self.create_default_diagnostic(span, code, message);
+    diagnostic.related = related
+      .into_iter()
+      .map(|(span, message)| RelatedInformation {
+        range: Range {
+          start: Position::new(&self.source_map, span.lo()),
+          end: Position::new(&self.source_map, span.hi()),
+        },
+        message: message.to_string(),
+      })
+      .collect();
+    self.push_diagnostic(diagnostic);
+  }
+
+  /// Adds diagnostic created with `create_default_diagnostic`, for rules
+  /// that need to set several optional fields.
+  pub(crate) fn push_diagnostic(&self, diagnostic: LintDiagnostic) {
     let mut diags = self.diagnostics.lock().unwrap();
     diags.push(diagnostic);
   }
 
+  /// Creates diagnostic with the rule's default severity.
+  pub(crate) fn create_default_diagnostic(
+    &self,
+    span: Span,
+    code: &str,
+    message: &str,
+  ) -> LintDiagnostic {
+    let severity = self.default_severity(code);
+    self.create_diagnostic(span, code, message, severity)
+  }
+
+  pub fn create_suggestion(
+    &self,
+    description: &str,
+    span: Span,
+    replacement: &str,
+  ) -> LintSuggestion {
+    LintSuggestion {
+      description: description.to_string(),
+      fix: self.create_fix(span, replacement),
+    }
+  }
+
+  fn create_fix(&self, span: Span, replacement: &str) -> LintFix {
+    LintFix {
+      start: self.source_map.lookup_byte_offset(span.lo()).pos.0 as usize,
+      end: self.source_map.lookup_byte_offset(span.hi()).pos.0 as usize,
+      replacement: replacement.to_string(),
+    }
+  }
+
+  fn default_severity(&self, code: &str) -> Severity {
+    self
+      .severities
+      .get(code)
+      .copied()
+      .unwrap_or(Severity::Error)
+  }
+
   fn create_diagnostic(
     &self,
     span: Span,
     code: &str,
     message: &str,
+    severity: Severity,
   ) -> LintDiagnostic {
     let time_start = Instant::now();
-    let start: Position = self.source_map.lookup_char_pos(span.lo()).into();
-    let end: Position = self.source_map.lookup_char_pos(span.hi()).into();
+    let start = Position::new(&self.source_map, span.lo());
+    let end = Position::new(&self.source_map, span.hi());
 
     let diagnostic = LintDiagnostic {
       range: Range { start, end },
@@ -60,6 +258,10 @@ impl Context {
       message: message.to_string(),
       code: code.to_string(),
       hint: None,
+      severity,
+      fix: None,
+      suggestions: vec![],
+      related: vec![],
     };
 
     let time_end = Instant::now();
@@ -77,6 +279,8 @@ pub struct IgnoreDirective {
   pub span: Span,
   pub codes: Vec<String>,
   pub used_codes: HashMap<String, bool>,
+  /// Whether a directive without codes ignored any diagnostic.
+  pub used: bool,
   pub is_global: bool,
 }
 
@@ -94,6 +298,12 @@ impl IgnoreDirective {
       return false;
     }
 
+    // Directive without codes ignores all diagnostics on the next line.
+    if self.codes.is_empty() {
+      self.used = true;
+      return true;
+    }
+
     let mut should_ignore = false;
     for code in self.codes.iter() {
       // `ends_with` allows to skip `@typescript-eslint` prefix - not ideal
@@ -108,12 +318,52 @@ impl IgnoreDirective {
   }
 }
 
+/// ESLint rule names that differ from codes of the corresponding rules.
+const ESLINT_RULE_ALIASES: &[(&str, &str)] = &[
+  ("no-native-reassign", "no-global-assign"),
+  ("no-negated-in-lhs", "no-unsafe-negation"),
+];
+
+/// Suppression comment in ESLint syntax, like `eslint-disable-next-line`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct EslintDirective {
+  /// First and last line (inclusive) the directive applies to.
+  start_line: usize,
+  end_line: usize,
+  /// Disabled codes, empty if all codes are disabled.
+  codes: Vec<String>,
+  /// Codes re-enabled by `eslint-enable` while all codes are disabled.
+  enabled_codes: Vec<String>,
+}
+
+impl EslintDirective {
+  fn ignores(&self, diagnostic: &LintDiagnostic) -> bool {
+    let line = diagnostic.range.start.line;
+    let code = &diagnostic.code;
+    self.start_line <= line
+      && line <= self.end_line
+      && if self.codes.is_empty() {
+        !self.enabled_codes.contains(code)
+      } else {
+        self.codes.contains(code)
+      }
+  }
+}
+
 pub struct LinterBuilder {
   ignore_file_directives: Vec<String>,
   ignore_diagnostic_directives: Vec<String>,
   lint_unused_ignore_directives: bool,
   lint_unknown_rules: bool,
-  syntax: swc_ecmascript::parser::Syntax,
+  honor_eslint_directives: bool,
+  report_parse_errors: bool,
+  max_diagnostics: Option<usize>,
+  collect_timings: bool,
+  timeout: Option<Duration>,
+  severity_overrides: HashMap<String, Severity>,
+  env: Vec<String>,
+  globals: Vec<String>,
+  syntax_config: SyntaxConfig,
   rules: Vec<Box<dyn LintRule>>,
 }
 
@@ -124,20 +374,38 @@ impl LinterBuilder {
       ignore_diagnostic_directives: vec!["deno-lint-ignore".to_string()],
       lint_unused_ignore_directives: true,
       lint_unknown_rules: true,
-      syntax: get_default_ts_config(),
+      honor_eslint_directives: false,
+      report_parse_errors: false,
+      max_diagnostics: None,
+      collect_timings: false,
+      timeout: None,
+      severity_overrides: HashMap::new(),
+      env: DEFAULT_ENV.iter().map(|s| s.to_string()).collect(),
+      globals: vec![],
+      syntax_config: SyntaxConfig::default(),
       rules: vec![],
     }
   }
 
   pub fn build(self) -> Linter {
-    Linter::new(
-      self.ignore_file_directives,
-      self.ignore_diagnostic_directives,
-      self.lint_unused_ignore_directives,
-      self.lint_unknown_rules,
-      self.syntax,
-      self.rules,
-    )
+    Linter {
+      has_linted: false,
+      ast_parser: AstParser::new(),
+      ignore_file_directives: self.ignore_file_directives,
+      ignore_diagnostic_directives: self.ignore_diagnostic_directives,
+      lint_unused_ignore_directives: self.lint_unused_ignore_directives,
+      lint_unknown_rules: self.lint_unknown_rules,
+      honor_eslint_directives: self.honor_eslint_directives,
+      report_parse_errors: self.report_parse_errors,
+      max_diagnostics: self.max_diagnostics,
+      collect_timings: self.collect_timings,
+      timeout: self.timeout,
+      severity_overrides: self.severity_overrides,
+      env: self.env,
+      globals: self.globals,
+      syntax_config: self.syntax_config,
+      rules: self.rules,
+    }
   }
 
   pub fn ignore_file_directives(mut self, directives: Vec<&str>) -> Self {
@@ -165,8 +433,77 @@ impl LinterBuilder {
     self
   }
 
+  /// Honor `eslint-disable-next-line`, `eslint-disable-line` and
+  /// `/* eslint-disable */`/`/* eslint-enable */` block comments. Unknown
+  /// ESLint rule names are ignored.
+  pub fn honor_eslint_directives(
+    mut self,
+    honor_eslint_directives: bool,
+  ) -> Self {
+    self.honor_eslint_directives = honor_eslint_directives;
+    self
+  }
+
+  /// Report syntax errors as diagnostics with the `parse-error` code
+  /// instead of failing with `SwcDiagnosticBuffer`.
+  pub fn report_parse_errors(mut self, report_parse_errors: bool) -> Self {
+    self.report_parse_errors = report_parse_errors;
+    self
+  }
+
+  /// Keep only the first `max_diagnostics` diagnostics, in sorted order.
+  pub fn max_diagnostics(mut self, max_diagnostics: usize) -> Self {
+    self.max_diagnostics = Some(max_diagnostics);
+    self
+  }
+
+  /// Record the parse time and time spent in each rule in `LintReport`.
+  pub fn collect_timings(mut self, collect_timings: bool) -> Self {
+    self.collect_timings = collect_timings;
+    self
+  }
+
+  /// Stop running rules on a file once linting it took longer than
+  /// `timeout`. It's checked between rules and every 256 nodes of the
+  /// traversal shared by rules, which interrupts them. Diagnostics reported
+  /// before the timeout are kept, but interrupted rules are missing from
+  /// `completed_rules` of `LintReport`.
+  pub fn timeout(mut self, timeout: Duration) -> Self {
+    self.timeout = Some(timeout);
+    self
+  }
+
+  /// Severities replacing the ones of all diagnostics with given codes.
+  pub fn severity_overrides(
+    mut self,
+    severity_overrides: HashMap<String, Severity>,
+  ) -> Self {
+    self.severity_overrides = severity_overrides;
+    self
+  }
+
+  /// Environments whose globals are predefined, e.g. `"es2021"`,
+  /// `"browser"`, `"deno"` or `"worker"`. Unknown names are ignored.
+  pub fn env(mut self, env: Vec<&str>) -> Self {
+    self.env = env.iter().map(|s| s.to_string()).collect();
+    self
+  }
+
+  /// Additional predefined globals.
+  pub fn globals(mut self, globals: Vec<&str>) -> Self {
+    self.globals = globals.iter().map(|s| s.to_string()).collect();
+    self
+  }
+
   pub fn syntax(mut self, syntax: Syntax) -> Self {
-    self.syntax = syntax;
+    self.syntax_config.syntax = syntax;
+    self
+  }
+
+  /// Replaces the whole parser configuration, including the syntax set
+  /// with `syntax`.
+  pub fn syntax_config(mut self, syntax_config: SyntaxConfig) -> Self {
+    self.syntax_config = syntax_config;
     self
   }
 
@@ -176,6 +513,88 @@ impl LinterBuilder {
   }
 }
 
+/// Result of linting a single file with `Linter::lint_files`.
+#[derive(Debug)]
+pub struct FileDiagnostics {
+  pub file_name: String,
+  pub diagnostics: Result<Vec<LintDiagnostic>, SwcDiagnosticBuffer>,
+}
+
+/// Result of linting a single file with `Linter::lint_paths`.
+#[derive(Debug)]
+pub struct PathDiagnostics {
+  pub path: PathBuf,
+  pub diagnostics: Result<Vec<LintDiagnostic>, LintError>,
+}
+
+/// Error returned by `Linter::lint_file`, or by
+/// `DiagnosticsExt::into_result` when linting found errors.
+#[derive(Debug)]
+pub enum LintError {
+  Io(io::Error),
+  Parse(SwcDiagnosticBuffer),
+  Config(ConfigError),
+  /// Diagnostics including at least one with `Severity::Error`.
+  Diagnostics(Vec<LintDiagnostic>),
+}
+
+impl Error for LintError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      LintError::Io(err) => Some(err),
+      LintError::Parse(err) => Some(err),
+      LintError::Config(err) => Some(err),
+      LintError::Diagnostics(_) => None,
+    }
+  }
+}
+
+impl fmt::Display for LintError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      LintError::Io(err) => err.fmt(f),
+      LintError::Parse(err) => err.fmt(f),
+      LintError::Config(err) => err.fmt(f),
+      LintError::Diagnostics(diagnostics) => {
+        let count = diagnostics.len();
+        let noun = if count == 1 { "problem" } else { "problems" };
+        write!(f, "Found {} {}", count, noun)?;
+        for diagnostic in diagnostics {
+          write!(f, "\n{}", diagnostic)?;
+        }
+        Ok(())
+      }
+    }
+  }
+}
+
+impl From<ConfigError> for LintError {
+  fn from(err: ConfigError) -> Self {
+    LintError::Config(err)
+  }
+}
+
+impl From<io::Error> for LintError {
+  fn from(err: io::Error) -> Self {
+    LintError::Io(err)
+  }
+}
+
+impl From<SwcDiagnosticBuffer> for LintError {
+  fn from(err: SwcDiagnosticBuffer) -> Self {
+    LintError::Parse(err)
+  }
+}
+
+/// Reads the file at `path`, dropping a leading BOM.
+fn read_source(path: &Path) -> Result<String, LintError> {
+  let source_code = fs::read_to_string(path)?;
+  Ok(match source_code.strip_prefix('\u{feff}') {
+    Some(stripped) => stripped.to_string(),
+    None => source_code,
+  })
+}
+
 pub struct Linter {
   has_linted: bool,
   ast_parser: AstParser,
@@ -183,29 +602,115 @@ pub struct Linter {
   ignore_diagnostic_directives: Vec<String>,
   lint_unused_ignore_directives: bool,
   lint_unknown_rules: bool,
-  syntax: Syntax,
+  honor_eslint_directives: bool,
+  report_parse_errors: bool,
+  max_diagnostics: Option<usize>,
+  collect_timings: bool,
+  timeout: Option<Duration>,
+  severity_overrides: HashMap<String, Severity>,
+  env: Vec<String>,
+  globals: Vec<String>,
+  syntax_config: SyntaxConfig,
   rules: Vec<Box<dyn LintRule>>,
 }
 
 impl Linter {
-  fn new(
-    ignore_file_directives: Vec<String>,
-    ignore_diagnostic_directives: Vec<String>,
-    lint_unused_ignore_directives: bool,
-    lint_unknown_rules: bool,
-    syntax: Syntax,
+  /// Lints `(file_name, source_code)` pairs in parallel, using a new
+  /// linter created by `create_linter` for each file. The syntax of the
+  /// linter is adapted to the extension, see `adapt_syntax_to_file_name`.
+  /// Results are returned in the same order as `inputs`.
+  pub fn lint_files<F>(
+    inputs: Vec<(String, String)>,
+    create_linter: F,
+  ) -> Vec<FileDiagnostics>
+  where
+    F: Fn() -> Linter + Sync,
+  {
+    inputs
+      .into_par_iter()
+      .map(|(file_name, source_code)| {
+        let mut linter = create_linter();
+        linter.adapt_syntax_to_file_name(&file_name);
+        let diagnostics = linter.lint(file_name.clone(), source_code);
+        FileDiagnostics {
+          file_name,
+          diagnostics,
+        }
+      })
+      .collect()
+  }
+
+  /// Lints the files found by walking `roots` in parallel, using a new
+  /// linter created by `create_linter` for each file. The syntax of the
+  /// linter is adapted to the extension, see `adapt_syntax_to_file_name`.
+  /// Results are sorted by path, with unreadable files and directories
+  /// reported as `LintError::Io`.
+  pub fn lint_paths<F>(
+    roots: &[PathBuf],
+    options: WalkOptions,
+    create_linter: F,
+  ) -> Vec<PathDiagnostics>
+  where
+    F: Fn() -> Linter + Sync,
+  {
+    let (files, errors) = collect_files(roots, &options);
+    let mut results = files
+      .into_par_iter()
+      .map(|path| {
+        let diagnostics = read_source(&path).and_then(|source_code| {
+          let file_name = path.display().to_string();
+          let mut linter = create_linter();
+          linter.adapt_syntax_to_file_name(&file_name);
+          Ok(linter.lint(file_name, source_code)?)
+        });
+        PathDiagnostics { path, diagnostics }
+      })
+      .collect::<Vec<_>>();
+
+    results.extend(errors.into_iter().map(|(path, err)| PathDiagnostics {
+      path,
+      diagnostics: Err(LintError::Io(err)),
+    }));
+    results.sort_by(|a, b| a.path.cmp(&b.path));
+    results
+  }
+
+  /// Reads and lints the file at `path` with the default options. The
+  /// syntax is picked based on the extension and a leading BOM is ignored.
+  pub fn lint_file(
+    path: &Path,
     rules: Vec<Box<dyn LintRule>>,
-  ) -> Self {
-    Linter {
-      has_linted: false,
-      ast_parser: AstParser::new(),
-      ignore_file_directives,
-      ignore_diagnostic_directives,
-      lint_unused_ignore_directives,
-      lint_unknown_rules,
-      syntax,
-      rules,
+  ) -> Result<Vec<LintDiagnostic>, LintError> {
+    let source_code = read_source(path)?;
+    let file_name = path.display().to_string();
+    let mut linter = LinterBuilder::default()
+      .syntax(get_syntax_for_file_name(&file_name))
+      .rules(rules)
+      .build();
+    Ok(linter.lint(file_name, source_code)?)
+  }
+
+  fn adapt_syntax_to_file_name(&mut self, file_name: &str) {
+    self.syntax_config.syntax =
+      adapt_syntax_to_file_name(self.syntax_config.syntax, file_name);
+  }
+
+  /// Adds a rule to the ones the linter was built with. Fails if a rule
+  /// with the same code was already added.
+  pub fn add_rule(
+    &mut self,
+    rule: Box<dyn LintRule>,
+  ) -> Result<(), DuplicateRuleError> {
+    let code = rule.code();
+    if self.rules.iter().any(|r| r.code() == code)
+      || BUILTIN_CODES.contains(&code)
+    {
+      return Err(DuplicateRuleError {
+        code: code.to_string(),
+      });
     }
+    self.rules.push(rule);
+    Ok(())
   }
 
   pub fn lint(
@@ -213,37 +718,125 @@ impl Linter {
     file_name: String,
     source_code: String,
   ) -> Result<Vec<LintDiagnostic>, SwcDiagnosticBuffer> {
+    Ok(self.lint_with_report(file_name, source_code)?.diagnostics)
+  }
+
+  /// Like `lint`, but also returns counts of the diagnostics, including
+  /// the ones dropped because of `max_diagnostics`.
+  pub fn lint_with_report(
+    &mut self,
+    file_name: String,
+    source_code: String,
+  ) -> Result<LintReport, SwcDiagnosticBuffer> {
     assert!(
       !self.has_linted,
       "Linter can be used only on a single module."
     );
     self.has_linted = true;
     let start = Instant::now();
+    let deadline = self.timeout.map(|timeout| start + timeout);
+    let mut parse_time = None;
+    let mut results = RuleResults::default();
     let diagnostics = if source_code.is_empty() {
+      results.completed_rules = self.rules.iter().map(|r| r.code()).collect();
       vec![]
     } else {
       let (parse_result, comments) =
         self
           .ast_parser
-          .parse_module(&file_name, self.syntax, &source_code);
+          .parse(&file_name, self.syntax_config, &source_code);
       let end_parse_module = Instant::now();
       debug!(
         "ast_parser.parse_module took {:#?}",
         end_parse_module - start
       );
-      let module = parse_result?;
-      self.lint_module(file_name, module, comments)
+      if self.collect_timings {
+        parse_time = Some(end_parse_module - start);
+      }
+      match parse_result {
+        Ok(module) => {
+          results = self.lint_resolved_module(
+            file_name.clone(),
+            &module,
+            &comments,
+            self.ast_parser.source_map.clone(),
+            deadline,
+          );
+          let mut diagnostics = std::mem::take(&mut results.diagnostics);
+          if self.report_parse_errors {
+            // The parser may have recovered from some errors.
+            let recovered = SwcDiagnosticBuffer::from_swc_error(
+              self.ast_parser.buffered_error.clone(),
+              &self.ast_parser,
+            );
+            diagnostics.extend(parse_error_diagnostics(&file_name, recovered));
+            sort_diagnostics(&mut diagnostics);
+          }
+          diagnostics
+        }
+        Err(err) if self.report_parse_errors => {
+          parse_error_diagnostics(&file_name, err)
+        }
+        Err(err) => return Err(err),
+      }
     };
 
+    let mut report = LintReport::new(self.max_diagnostics);
+    report.add_file(diagnostics);
+    report.parse_time = parse_time;
+    report.rule_timings = results.rule_timings;
+    report.completed_rules = results.completed_rules;
+    report.timed_out = results.timed_out;
+
     let end = Instant::now();
     debug!("Linter::lint took {:#?}", end - start);
-    Ok(diagnostics)
+    Ok(report)
+  }
+
+  /// Lints a module the caller has already parsed, e.g. for transpiling,
+  /// instead of parsing `file_name` again.
+  ///
+  /// `source_map` must be the map the module was parsed with, so that all
+  /// spans of `module` and `comments` point into the same source file of
+  /// it. The module must not have been processed by swc's resolver or
+  /// any other transform, as the linter applies its own resolver to a copy
+  /// of it; comments are copied as well.
+  pub fn lint_parsed(
+    &mut self,
+    file_name: String,
+    module: &Module,
+    comments: &SingleThreadedComments,
+    source_map: Arc<SourceMap>,
+  ) -> Vec<LintDiagnostic> {
+    assert!(
+      !self.has_linted,
+      "Linter can be used only on a single module."
+    );
+    self.has_linted = true;
+    let start = Instant::now();
+
+    let top_level_mark = self.ast_parser.top_level_mark;
+    let module = swc_common::GLOBALS.set(&self.ast_parser.globals, || {
+      module.clone().fold_with(&mut ts_resolver(top_level_mark))
+    });
+    let deadline = self.timeout.map(|timeout| start + timeout);
+    let results = self
+      .lint_resolved_module(file_name, &module, comments, source_map, deadline);
+    let mut report = LintReport::new(self.max_diagnostics);
+    report.add_file(results.diagnostics);
+
+    let end = Instant::now();
+    debug!("Linter::lint_parsed took {:#?}", end - start);
+    report.diagnostics
   }
 
+  /// Applies ignore directives to the diagnostics of `context`. Unused
+  /// ignore directives are only reported for `completed_rules`.
   fn filter_diagnostics(
     &self,
     context: Arc<Context>,
     rules: &[Box<dyn LintRule>],
+    completed_rules: &[&'static str],
   ) -> Vec<LintDiagnostic> {
     let start = Instant::now();
     let mut ignore_directives = context.ignore_directives.clone();
@@ -263,19 +856,40 @@ impl Linter {
           ignore_directive.maybe_ignore_diagnostic(&diagnostic)
         })
       })
+      .filter(|diagnostic| {
+        !context
+          .eslint_directives
+          .iter()
+          .any(|directive| directive.ignores(diagnostic))
+      })
       .collect();
 
     if self.lint_unused_ignore_directives || self.lint_unknown_rules {
       for ignore_directive in ignore_directives {
-        for (code, used) in ignore_directive.used_codes.iter() {
+        // Rules that didn't complete may have reported the ignored line.
+        if self.lint_unused_ignore_directives
+          && ignore_directive.codes.is_empty()
+          && !ignore_directive.used
+          && completed_rules.len() == rules.len()
+        {
+          filtered_diagnostics.push(context.create_diagnostic(
+            ignore_directive.span,
+            "ban-unused-ignore",
+            "Ignore directive was not used.",
+            Severity::Error,
+          ));
+        }
+        for code in ignore_directive.codes.iter() {
+          let used = ignore_directive.used_codes[code];
           if self.lint_unused_ignore_directives
             && !used
-            && rule_codes.contains(code)
+            && completed_rules.contains(&code.as_str())
           {
             let diagnostic = context.create_diagnostic(
               ignore_directive.span,
               "ban-unused-ignore",
               &format!("Ignore for code \"{}\" was not used.", code),
+              Severity::Error,
             );
             filtered_diagnostics.push(diagnostic);
           }
@@ -285,14 +899,20 @@ impl Linter {
               ignore_directive.span,
               "ban-unknown-rule-code",
               &format!("Unknown rule for code \"{}\"", code),
+              Severity::Error,
             ))
           }
         }
       }
     }
 
-    filtered_diagnostics
-      .sort_by(|a, b| a.range.start.line.cmp(&b.range.start.line));
+    for diagnostic in filtered_diagnostics.iter_mut() {
+      if let Some(severity) = self.severity_overrides.get(&diagnostic.code) {
+        diagnostic.severity = *severity;
+      }
+    }
+
+    sort_diagnostics(&mut filtered_diagnostics);
 
     let end = Instant::now();
     debug!("Linter::filter_diagnostics took {:#?}", end - start);
@@ -300,20 +920,29 @@ impl Linter {
     filtered_diagnostics
   }
 
-  fn lint_module(
+  /// Lints a module that was already processed by the resolver, running
+  /// rules until `deadline` is exceeded.
+  fn lint_resolved_module(
     &self,
     file_name: String,
-    module: swc_ecmascript::ast::Module,
-    comments: SingleThreadedComments,
-  ) -> Vec<LintDiagnostic> {
+    module: &Module,
+    comments: &SingleThreadedComments,
+    source_map: Arc<SourceMap>,
+    deadline: Option<Instant>,
+  ) -> RuleResults {
     let start = Instant::now();
-    let file_ignore_directive = comments.with_leading(module.span.lo(), |c| {
+    // The module span includes a shebang, which comments can't precede.
+    let first_item_lo = match module.body.first() {
+      Some(item) => item.span().lo(),
+      None => module.span.lo(),
+    };
+    let file_ignore_directive = comments.with_leading(first_item_lo, |c| {
       let directives = c
         .iter()
         .filter_map(|comment| {
           parse_ignore_comment(
             &self.ignore_file_directives,
-            &*self.ast_parser.source_map,
+            &source_map,
             comment,
             true,
           )
@@ -331,23 +960,18 @@ impl Linter {
     // whole file and skip linting it.
     if let Some(ignore_directive) = &file_ignore_directive {
       if ignore_directive.codes.is_empty() {
-        return vec![];
+        return RuleResults::default();
       }
     }
 
-    let (leading, trailing) = comments.take_all();
-    let leading_coms = Rc::try_unwrap(leading)
-      .expect("Failed to get leading comments")
-      .into_inner();
-    let leading = leading_coms.into_iter().collect();
-    let trailing_coms = Rc::try_unwrap(trailing)
-      .expect("Failed to get leading comments")
-      .into_inner();
-    let trailing = trailing_coms.into_iter().collect();
+    // The maps are shared with `comments`, so they are copied.
+    let (leading, trailing) = comments.clone().take_all();
+    let leading = leading.borrow().clone().into_iter().collect();
+    let trailing = trailing.borrow().clone().into_iter().collect();
 
     let mut ignore_directives = parse_ignore_directives(
       &self.ignore_diagnostic_directives,
-      &self.ast_parser.source_map,
+      &source_map,
       &leading,
       &trailing,
     );
@@ -356,13 +980,30 @@ impl Linter {
       ignore_directives.insert(0, ignore_directive);
     }
 
-    let scope = Arc::new(analyze(&module));
-    let control_flow = Arc::new(ControlFlow::analyze(&module));
+    let eslint_directives = if self.honor_eslint_directives {
+      let rule_codes = self.rules.iter().map(|r| r.code()).collect::<Vec<_>>();
+      parse_eslint_directives(&source_map, &leading, &trailing, &rule_codes)
+    } else {
+      vec![]
+    };
 
+    let globals = self
+      .env
+      .iter()
+      .filter_map(|env| env_globals(env))
+      .flatten()
+      .map(JsWord::from)
+      .chain(self.globals.iter().map(|g| JsWord::from(g.as_str())))
+      .collect();
+    let scope = Arc::new(analyze(module, globals));
+    let control_flow = Arc::new(ControlFlow::analyze(module));
+
+    let source_file = source_map.lookup_byte_offset(module.span.lo()).sf;
     let context = Arc::new(Context {
       file_name,
       diagnostics: Arc::new(Mutex::new(vec![])),
-      source_map: self.ast_parser.source_map.clone(),
+      source_map,
+      source_file,
       leading_comments: leading,
       trailing_comments: trailing,
       ignore_directives,
@@ -371,18 +1012,132 @@ impl Linter {
       top_level_ctxt: swc_common::GLOBALS.set(&self.ast_parser.globals, || {
         SyntaxContext::empty().apply_mark(self.ast_parser.top_level_mark)
       }),
+      severities: self
+        .rules
+        .iter()
+        .map(|rule| (rule.code(), rule.severity()))
+        .collect(),
+      eslint_directives,
+      is_module: self.syntax_config.kind == ModuleKind::Module,
     });
 
-    for rule in &self.rules {
-      rule.lint_module(context.clone(), &module);
+    let mut results = RuleResults::default();
+    let mut rule_timings = vec![None; self.rules.len()];
+    let mut completed = vec![false; self.rules.len()];
+    let mut handlers = vec![];
+    // Rules that walk the module themselves run first, one after another.
+    for (index, rule) in self.rules.iter().enumerate() {
+      if let Some(handler) = rule.handler(context.clone()) {
+        handlers.push((index, handler));
+        continue;
+      }
+      let rule_start = Instant::now();
+      if matches!(deadline, Some(deadline) if rule_start >= deadline) {
+        results.timed_out = true;
+        break;
+      }
+      rule.lint_module(context.clone(), module);
+      rule_timings[index] = Some(rule_start.elapsed());
+      completed[index] = true;
+    }
+
+    // The remaining rules share a single traversal of the module.
+    if !results.timed_out && !handlers.is_empty() {
+      let mut visitor = CombinedVisitor::new(
+        handlers
+          .iter_mut()
+          .map(|(_, handler)| handler.as_mut() as &mut dyn Handler)
+          .collect(),
+      )
+      .collect_timings(self.collect_timings)
+      .deadline(deadline);
+      module.visit_with(&Invalid { span: DUMMY_SP }, &mut visitor);
+      results.timed_out = visitor.timed_out();
+      let timings = visitor.timings();
+      for ((index, _), time) in handlers.iter().zip(timings) {
+        rule_timings[*index] = Some(time);
+        completed[*index] = !results.timed_out;
+      }
     }
+    drop(handlers);
 
-    let d = self.filter_diagnostics(context, &self.rules);
+    for (index, rule) in self.rules.iter().enumerate() {
+      if let (true, Some(time)) = (self.collect_timings, rule_timings[index]) {
+        results.rule_timings.push((rule.code(), time));
+      }
+      if completed[index] {
+        results.completed_rules.push(rule.code());
+      }
+    }
+
+    results.diagnostics =
+      self.filter_diagnostics(context, &self.rules, &results.completed_rules);
     let end = Instant::now();
     debug!("Linter::lint_module took {:#?}", end - start);
 
-    d
+    results
+  }
+}
+
+/// Outcome of running the rules on a module, see `LintReport`.
+#[derive(Default)]
+struct RuleResults {
+  diagnostics: Vec<LintDiagnostic>,
+  rule_timings: Vec<(&'static str, Duration)>,
+  completed_rules: Vec<&'static str>,
+  timed_out: bool,
+}
+
+/// Sorts diagnostics by file, position and code, and removes duplicates
+/// reported for the same range with the same code and message.
+fn sort_diagnostics(diagnostics: &mut Vec<LintDiagnostic>) {
+  diagnostics.sort_by(|a, b| {
+    a.filename
+      .cmp(&b.filename)
+      .then(a.range.start.line.cmp(&b.range.start.line))
+      .then(a.range.start.col.cmp(&b.range.start.col))
+      .then(a.code.cmp(&b.code))
+  });
+
+  let mut deduped: Vec<LintDiagnostic> = Vec::with_capacity(diagnostics.len());
+  for diagnostic in diagnostics.drain(..) {
+    let is_duplicate = deduped
+      .iter()
+      .rev()
+      .take_while(|d| {
+        d.filename == diagnostic.filename
+          && d.range.start == diagnostic.range.start
+          && d.code == diagnostic.code
+      })
+      .any(|d| {
+        d.range.end == diagnostic.range.end && d.message == diagnostic.message
+      });
+    if !is_duplicate {
+      deduped.push(diagnostic);
+    }
   }
+  *diagnostics = deduped;
+}
+
+fn parse_error_diagnostics(
+  file_name: &str,
+  buffer: SwcDiagnosticBuffer,
+) -> Vec<LintDiagnostic> {
+  buffer
+    .parse_diagnostics
+    .into_iter()
+    .map(|d| LintDiagnostic {
+      range: d.range,
+      filename: file_name.to_string(),
+      message: d.message,
+      code: "parse-error".to_string(),
+      hint: None,
+      severity: Severity::Error,
+      fix: None,
+      suggestions: vec![],
+      related: vec![],
+    })
+    .collect()
 }
 
 fn parse_ignore_directives(
@@ -424,6 +1179,144 @@ fn parse_ignore_directives(
   ignore_directives
 }
 
+fn parse_eslint_directives(
+  source_map: &SourceMap,
+  leading_comments: &HashMap<BytePos, Vec<Comment>>,
+  trailing_comments: &HashMap<BytePos, Vec<Comment>>,
+  rule_codes: &[&str],
+) -> Vec<EslintDirective> {
+  let mut comments = leading_comments
+    .values()
+    .chain(trailing_comments.values())
+    .flatten()
+    .collect::<Vec<&Comment>>();
+  comments.sort_by_key(|comment| comment.span.lo());
+
+  let mut directives = vec![];
+  // Indices of `eslint-disable` directives not closed by `eslint-enable` yet.
+  let mut open_directives: Vec<usize> = vec![];
+
+  for comment in comments {
+    let text = comment.text.trim();
+    let mut words = text.splitn(2, char::is_whitespace);
+    let kind = words.next().unwrap_or("");
+    let rest = words.next().unwrap_or("");
+    if !kind.starts_with("eslint-") {
+      continue;
+    }
+
+    let names = rest
+      .split(" --")
+      .next()
+      .unwrap()
+      .split(|c: char| c == ',' || c.is_whitespace())
+      .filter(|name| !name.is_empty())
+      .collect::<Vec<&str>>();
+    let codes = names
+      .iter()
+      .filter_map(|name| eslint_rule_to_code(name, rule_codes))
+      .collect::<Vec<String>>();
+    // None of the listed rules is known; nothing to suppress.
+    if !names.is_empty() && codes.is_empty() {
+      continue;
+    }
+
+    // Like ESLint, only block comments can disable or enable a region.
+    let is_block = comment.kind == CommentKind::Block;
+    let start_line = source_map.lookup_char_pos(comment.span.lo()).line;
+    let end_line = source_map.lookup_char_pos(comment.span.hi()).line;
+    match kind {
+      "eslint-disable-next-line" => directives.push(EslintDirective {
+        start_line: end_line + 1,
+        end_line: end_line + 1,
+        codes,
+        enabled_codes: vec![],
+      }),
+      "eslint-disable-line" => directives.push(EslintDirective {
+        start_line,
+        end_line,
+        codes,
+        enabled_codes: vec![],
+      }),
+      "eslint-disable" if is_block => {
+        open_directives.push(directives.len());
+        directives.push(EslintDirective {
+          start_line,
+          end_line: usize::MAX,
+          codes,
+          enabled_codes: vec![],
+        });
+      }
+      "eslint-enable" if is_block => {
+        let mut reopened = vec![];
+        for index in open_directives.drain(..) {
+          let directive = &mut directives[index];
+          if codes.is_empty() {
+            directive.end_line = start_line;
+            continue;
+          }
+
+          // Close the region and reopen it without the enabled codes.
+          let remaining = if directive.codes.is_empty() {
+            let mut enabled_codes = directive.enabled_codes.clone();
+            enabled_codes.extend(codes.iter().cloned());
+            Some(EslintDirective {
+              start_line: start_line + 1,
+              end_line: usize::MAX,
+              codes: vec![],
+              enabled_codes,
+            })
+          } else if directive.codes.iter().any(|code| codes.contains(code)) {
+            let remaining_codes = directive
+              .codes
+              .iter()
+              .filter(|code| !codes.contains(code))
+              .cloned()
+              .collect::<Vec<String>>();
+            if remaining_codes.is_empty() {
+              None
+            } else {
+              Some(EslintDirective {
+                start_line: start_line + 1,
+                end_line: usize::MAX,
+                codes: remaining_codes,
+                enabled_codes: vec![],
+              })
+            }
+          } else {
+            reopened.push(index);
+            continue;
+          };
+
+          directive.end_line = start_line;
+          if let Some(remaining) = remaining {
+            reopened.push(directives.len());
+            directives.push(remaining);
+          }
+        }
+        open_directives = reopened;
+      }
+      _ => {}
+    }
+  }
+
+  directives
+}
+
+fn eslint_rule_to_code(name: &str, rule_codes: &[&str]) -> Option<String> {
+  let code = ESLINT_RULE_ALIASES
+    .iter()
+    .find(|(eslint_name, _)| *eslint_name == name)
+    .map(|(_, code)| *code)
+    .unwrap_or_else(|| name.trim_start_matches("@typescript-eslint/"));
+
+  if rule_codes.contains(&code) {
+    Some(code.to_string())
+  } else {
+    None
+  }
+}
+
 fn parse_ignore_comment(
   ignore_diagnostic_directives: &[String],
   source_map: &SourceMap,
@@ -440,6 +1333,8 @@ fn parse_ignore_comment(
     if let Some(prefix) = comment_text.split_whitespace().next() {
       if prefix == ignore_dir {
         let comment_text = comment_text.strip_prefix(ignore_dir).unwrap();
+        // Anything after ` --` is an explanation, not a list of codes.
+        let comment_text = comment_text.split(" --").next().unwrap();
         let comment_text =
           IGNORE_COMMENT_CODE_RE.replace_all(comment_text, ",");
         let codes = comment_text
@@ -448,18 +1343,17 @@ fn parse_ignore_comment(
           .map(|code| String::from(code.trim()))
           .collect::<Vec<String>>();
 
-        let location = source_map.lookup_char_pos(comment.span.lo());
-
         let mut used_codes = HashMap::new();
         codes.iter().for_each(|code| {
           used_codes.insert(code.to_string(), false);
         });
 
         return Some(IgnoreDirective {
-          position: location.into(),
+          position: Position::new(source_map, comment.span.lo()),
           span: comment.span,
           codes,
           used_codes,
+          used: false,
           is_global,
         });
       }
@@ -473,6 +1367,8 @@ fn parse_ignore_comment(
 mod tests {
   use super::*;
   use crate::swc_util;
+  use std::rc::Rc;
+  use swc_ecmascript::parser::EsConfig;
 
   #[test]
   fn test_parse_ignore_comments() {
@@ -520,16 +1416,725 @@ object | undefined {}
 
     assert_eq!(directives.len(), 4);
     let d = &directives[0];
-    assert_eq!(d.position, Position { line: 2, col: 0 });
+    assert_eq!(
+      d.position,
+      Position {
+        line: 2,
+        col: 0,
+        col_utf16: 0,
+        byte_pos: 1
+      }
+    );
     assert_eq!(d.codes, vec!["no-explicit-any", "no-empty", "no-debugger"]);
     let d = &directives[1];
-    assert_eq!(d.position, Position { line: 8, col: 0 });
+    assert_eq!(
+      d.position,
+      Position {
+        line: 8,
+        col: 0,
+        col_utf16: 0,
+        byte_pos: 146
+      }
+    );
     assert_eq!(d.codes, vec!["no-explicit-any", "no-empty", "no-debugger"]);
     let d = &directives[2];
-    assert_eq!(d.position, Position { line: 11, col: 0 });
+    assert_eq!(
+      d.position,
+      Position {
+        line: 11,
+        col: 0,
+        col_utf16: 0,
+        byte_pos: 229
+      }
+    );
     assert_eq!(d.codes, vec!["no-explicit-any", "no-empty", "no-debugger"]);
     let d = &directives[3];
-    assert_eq!(d.position, Position { line: 17, col: 3 });
+    assert_eq!(
+      d.position,
+      Position {
+        line: 17,
+        col: 3,
+        col_utf16: 3,
+        byte_pos: 392
+      }
+    );
     assert_eq!(d.codes, vec!["ban-types"]);
   }
+
+  /// Reports every comment it can reach through `Context`.
+  struct CommentsRule;
+
+  impl LintRule for CommentsRule {
+    fn new() -> Box<Self> {
+      Box::new(CommentsRule)
+    }
+
+    fn code(&self) -> &'static str {
+      "comments"
+    }
+
+    fn lint_module(
+      &self,
+      context: Arc<Context>,
+      module: &swc_ecmascript::ast::Module,
+    ) {
+      use swc_common::Spanned;
+
+      for comment in context.all_comments() {
+        let kind = match comment.kind {
+          CommentKind::Line => "line",
+          CommentKind::Block => "block",
+        };
+        context.add_diagnostic(
+          comment.span,
+          "comments",
+          &format!("{}:{}", kind, comment.text.trim()),
+        );
+      }
+
+      let first = module.body[0].span();
+      for comment in context.leading_comments(first.lo()) {
+        context.add_diagnostic(comment.span, "comments", "leading");
+      }
+      for comment in context.trailing_comments(first.hi()) {
+        context.add_diagnostic(comment.span, "comments", "trailing");
+      }
+    }
+  }
+
+  #[test]
+  fn comments_in_context() {
+    let source = "// a\nfoo(); /* b */\nbar(); // c\n";
+    let diagnostics =
+      crate::test_util::lint_with_rule(CommentsRule::new(), source);
+    let found = diagnostics
+      .iter()
+      .map(|d| {
+        (
+          d.message.as_str(),
+          d.range.start.line,
+          d.range.start.col,
+          d.range.end.col,
+        )
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(
+      found,
+      vec![
+        ("line:a", 1, 0, 4),
+        ("leading", 1, 0, 4),
+        ("block:b", 2, 7, 14),
+        ("trailing", 2, 7, 14),
+        ("line:c", 3, 7, 11),
+      ]
+    );
+  }
+
+  /// Reports the source text of each top-level statement.
+  struct SourceTextRule;
+
+  impl LintRule for SourceTextRule {
+    fn new() -> Box<Self> {
+      Box::new(SourceTextRule)
+    }
+
+    fn code(&self) -> &'static str {
+      "source-text"
+    }
+
+    fn lint_module(
+      &self,
+      context: Arc<Context>,
+      module: &swc_ecmascript::ast::Module,
+    ) {
+      use swc_common::Spanned;
+
+      assert_eq!(context.span_text(module.span), context.source_text());
+      for item in &module.body {
+        context.add_diagnostic(
+          item.span(),
+          "source-text",
+          context.span_text(item.span()),
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn span_text() {
+    let source = "const a = \"日本語\";\n\"ü\" + a;\nfoo(\"🦕\")";
+    let diagnostics =
+      crate::test_util::lint_with_rule(SourceTextRule::new(), source);
+    let texts = diagnostics
+      .iter()
+      .map(|d| d.message.as_str())
+      .collect::<Vec<_>>();
+    assert_eq!(
+      texts,
+      vec!["const a = \"日本語\";", "\"ü\" + a;", "foo(\"🦕\")"]
+    );
+  }
+
+  /// Reports the text of the first lines of the file.
+  struct LineTextRule;
+
+  impl LintRule for LineTextRule {
+    fn new() -> Box<Self> {
+      Box::new(LineTextRule)
+    }
+
+    fn code(&self) -> &'static str {
+      "line-text"
+    }
+
+    fn lint_module(
+      &self,
+      context: Arc<Context>,
+      module: &swc_ecmascript::ast::Module,
+    ) {
+      for line in 0..5 {
+        context.add_diagnostic(
+          module.span,
+          "line-text",
+          &format!("{}: {:?}", line, context.line_text(line)),
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn line_text() {
+    let source = "const a = 1;\r\n\n  foo(\"🦕\");";
+    let diagnostics =
+      crate::test_util::lint_with_rule(LineTextRule::new(), source);
+    let texts = diagnostics
+      .iter()
+      .map(|d| d.message.as_str())
+      .collect::<Vec<_>>();
+    assert_eq!(
+      texts,
+      vec![
+        "0: None",
+        "1: Some(\"const a = 1;\")",
+        "2: Some(\"\")",
+        "3: Some(\"  foo(\\\"🦕\\\");\")",
+        "4: None",
+      ]
+    );
+  }
+
+  /// Reports the given top-level statements.
+  struct StmtsRule(&'static str, &'static [usize]);
+
+  impl LintRule for StmtsRule {
+    fn new() -> Box<Self> {
+      Box::new(StmtsRule("stmts", &[]))
+    }
+
+    fn code(&self) -> &'static str {
+      self.0
+    }
+
+    fn lint_module(
+      &self,
+      context: Arc<Context>,
+      module: &swc_ecmascript::ast::Module,
+    ) {
+      use swc_common::Spanned;
+
+      for index in self.1 {
+        context.add_diagnostic(module.body[*index].span(), self.0, "Bad");
+      }
+    }
+  }
+
+  #[test]
+  fn diagnostics_sorted_and_deduplicated() {
+    let mut linter = LinterBuilder::default()
+      .lint_unknown_rules(false)
+      .rules(vec![
+        Box::new(StmtsRule("rule-b", &[2, 0, 1, 2])),
+        Box::new(StmtsRule("rule-a", &[2, 1])),
+      ])
+      .build();
+    let diagnostics = linter
+      .lint("file.ts".to_string(), "a;\nb;\nc;\n".to_string())
+      .unwrap();
+    let found = diagnostics
+      .iter()
+      .map(|d| (d.range.start.line, d.code.as_str()))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      found,
+      vec![
+        (1, "rule-b"),
+        (2, "rule-a"),
+        (2, "rule-b"),
+        (3, "rule-a"),
+        (3, "rule-b")
+      ]
+    );
+  }
+
+  #[test]
+  fn max_diagnostics_keeps_earliest() {
+    let mut linter = LinterBuilder::default()
+      .max_diagnostics(3)
+      .rules(vec![
+        Box::new(StmtsRule("rule-b", &[3, 2, 0])),
+        Box::new(StmtsRule("rule-a", &[3, 1])),
+      ])
+      .build();
+    let report = linter
+      .lint_with_report("file.ts".to_string(), "a;\nb;\nc;\nd;\n".to_string())
+      .unwrap();
+    let found = report
+      .diagnostics
+      .iter()
+      .map(|d| (d.range.start.line, d.code.as_str()))
+      .collect::<Vec<_>>();
+    assert_eq!(found, vec![(1, "rule-b"), (2, "rule-a"), (3, "rule-b")]);
+    assert_eq!(report.truncated_count, 2);
+    assert_eq!(report.files_checked, 1);
+    assert_eq!(report.error_count, 5);
+    assert_eq!(report.code_counts["rule-a"], 2);
+    assert_eq!(report.code_counts["rule-b"], 3);
+  }
+
+  #[test]
+  fn lint_parsed_module_twice() {
+    use crate::rules::{
+      no_debugger::NoDebugger, no_undef::NoUndef, no_var::NoVar,
+    };
+    use swc_common::FileName;
+    use swc_ecmascript::parser::{
+      lexer::Lexer, JscTarget, Parser, StringInput,
+    };
+
+    let source = "var a = 1;\n// deno-lint-ignore no-debugger\ndebugger;\ndebugger;\na + b;\n";
+    let source_map = AstParser::new().source_map;
+    // Another file in the map, so that spans of the module don't start at 0.
+    source_map.new_source_file(
+      FileName::Custom("other.ts".to_string()),
+      "other();\n".to_string(),
+    );
+    let source_file = source_map.new_source_file(
+      FileName::Real("/src/file.ts".into()),
+      source.to_string(),
+    );
+    let comments = SingleThreadedComments::default();
+    let lexer = Lexer::new(
+      crate::swc_util::get_default_ts_config(),
+      JscTarget::Es2019,
+      StringInput::from(&*source_file),
+      Some(&comments),
+    );
+    let module = Parser::new_from(lexer).parse_module().unwrap();
+
+    let lint = |rules: Vec<Box<dyn LintRule>>| {
+      LinterBuilder::default()
+        .rules(rules)
+        .build()
+        .lint_parsed(
+          "file.ts".to_string(),
+          &module,
+          &comments,
+          source_map.clone(),
+        )
+        .into_iter()
+        .map(|d| {
+          assert_eq!(d.filename, "file.ts");
+          (d.code, d.range.start.line, d.range.start.col)
+        })
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+      lint(vec![NoDebugger::new()]),
+      vec![("no-debugger".to_string(), 4, 0)]
+    );
+    assert_eq!(
+      lint(vec![NoVar::new(), NoUndef::new()]),
+      vec![
+        ("no-var".to_string(), 1, 0),
+        ("ban-unknown-rule-code".to_string(), 2, 0),
+        ("no-undef".to_string(), 5, 4),
+      ]
+    );
+  }
+
+  #[test]
+  fn timings() {
+    use crate::rules::{
+      ban_untagged_todo::BanUntaggedTodo, no_debugger::NoDebugger,
+      no_var::NoVar,
+    };
+
+    let lint = |collect_timings: bool| {
+      LinterBuilder::default()
+        .collect_timings(collect_timings)
+        .rules(vec![
+          NoVar::new(),
+          BanUntaggedTodo::new(),
+          NoDebugger::new(),
+        ])
+        .build()
+        .lint_with_report("file.ts".to_string(), "var a;\n".to_string())
+        .unwrap()
+    };
+
+    let report = lint(true);
+    assert!(report.parse_time.is_some());
+    let codes = report
+      .rule_timings
+      .iter()
+      .map(|(code, _)| *code)
+      .collect::<Vec<_>>();
+    // Rules sharing the traversal are timed alongside the others.
+    assert_eq!(codes, vec!["no-var", "ban-untagged-todo", "no-debugger"]);
+
+    let report = lint(false);
+    assert_eq!(report.parse_time, None);
+    assert!(report.rule_timings.is_empty());
+  }
+
+  #[test]
+  fn timeout() {
+    use crate::rules::{no_debugger::NoDebugger, no_var::NoVar};
+
+    let source = "var a;\ndebugger;\n".repeat(5000);
+    let lint = |timeout: Option<Duration>| {
+      let mut builder = LinterBuilder::default()
+        .lint_unused_ignore_directives(true)
+        .rules(vec![NoVar::new(), NoDebugger::new()]);
+      if let Some(timeout) = timeout {
+        builder = builder.timeout(timeout);
+      }
+      builder
+        .build()
+        .lint_with_report("file.ts".to_string(), source.clone())
+        .unwrap()
+    };
+
+    let report = lint(None);
+    assert!(!report.timed_out);
+    assert_eq!(report.completed_rules, vec!["no-var", "no-debugger"]);
+    assert_eq!(report.diagnostics.len(), 10000);
+
+    // Parsing alone exceeds the budget.
+    let report = lint(Some(Duration::from_nanos(1)));
+    assert!(report.timed_out);
+    assert!(report.completed_rules.is_empty());
+    assert!(report.diagnostics.is_empty());
+  }
+
+  /// Reports the module and then takes longer than `timeout_partial`
+  /// allows.
+  struct SlowRule;
+
+  impl LintRule for SlowRule {
+    fn new() -> Box<Self> {
+      Box::new(SlowRule)
+    }
+
+    fn code(&self) -> &'static str {
+      "slow-rule"
+    }
+
+    fn lint_module(&self, context: Arc<Context>, module: &Module) {
+      context.add_diagnostic(module.span, "slow-rule", "Slow");
+      std::thread::sleep(Duration::from_millis(300));
+    }
+  }
+
+  #[test]
+  fn timeout_partial() {
+    let source = "// deno-lint-ignore no-debugger\ndebugger;\n";
+    let report = LinterBuilder::default()
+      .timeout(Duration::from_millis(200))
+      .rules(vec![
+        SlowRule::new(),
+        crate::rules::no_debugger::NoDebugger::new(),
+      ])
+      .build()
+      .lint_with_report("file.ts".to_string(), source.to_string())
+      .unwrap();
+
+    assert!(report.timed_out);
+    assert_eq!(report.completed_rules, vec!["slow-rule"]);
+    // The ignore directive of the skipped rule is not reported as unused.
+    let codes = report
+      .diagnostics
+      .iter()
+      .map(|d| d.code.as_str())
+      .collect::<Vec<_>>();
+    assert_eq!(codes, vec!["slow-rule"]);
+  }
+
+  /// Takes a millisecond on each `debugger` statement during the shared
+  /// traversal.
+  struct SlowHandlerRule;
+
+  impl LintRule for SlowHandlerRule {
+    fn new() -> Box<Self> {
+      Box::new(SlowHandlerRule)
+    }
+
+    fn code(&self) -> &'static str {
+      "slow-handler-rule"
+    }
+
+    fn handler<'a>(
+      &'a self,
+      _context: Arc<Context>,
+    ) -> Option<Box<dyn Handler + 'a>> {
+      Some(Box::new(SlowHandlerRule))
+    }
+  }
+
+  impl Handler for SlowHandlerRule {
+    fn visit_debugger_stmt(
+      &mut self,
+      _debugger_stmt: &swc_ecmascript::ast::DebuggerStmt,
+      _traversal: &mut crate::handler::Traversal,
+    ) {
+      std::thread::sleep(Duration::from_millis(1));
+    }
+  }
+
+  #[test]
+  fn timeout_during_traversal() {
+    use crate::rules::{ban_untagged_todo::BanUntaggedTodo, no_var::NoVar};
+
+    let source = "var a;\ndebugger;\n".repeat(2000);
+    let report = LinterBuilder::default()
+      .timeout(Duration::from_millis(200))
+      .rules(vec![
+        SlowHandlerRule::new(),
+        BanUntaggedTodo::new(),
+        NoVar::new(),
+      ])
+      .build()
+      .lint_with_report("file.ts".to_string(), source)
+      .unwrap();
+
+    assert!(report.timed_out);
+    assert_eq!(report.completed_rules, vec!["ban-untagged-todo"]);
+    // The traversal stopped before reaching the end of the module.
+    assert!(!report.diagnostics.is_empty());
+    assert!(report.diagnostics.len() < 2000);
+  }
+
+  /// Writes `source` to a file named `name` in a fresh temp directory.
+  fn temp_file(name: &str, source: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+      "deno_lint_{}_{}",
+      std::process::id(),
+      name
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    fs::write(&path, source).unwrap();
+    path
+  }
+
+  fn lint_file(path: &Path) -> Result<Vec<LintDiagnostic>, LintError> {
+    Linter::lint_file(path, vec![crate::rules::no_debugger::NoDebugger::new()])
+  }
+
+  #[test]
+  fn lint_file_js() {
+    let path = temp_file("file.js", "function f() {\n  debugger;\n}\n");
+    let diagnostics = lint_file(&path).unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].filename, path.display().to_string());
+    assert_eq!(
+      diagnostics[0].range.start,
+      Position {
+        line: 2,
+        col: 2,
+        col_utf16: 2,
+        byte_pos: 17
+      }
+    );
+
+    // Type annotations are not valid JavaScript.
+    let path = temp_file("types.js", "function f(a: number) {}\n");
+    assert!(matches!(lint_file(&path), Err(LintError::Parse(_))));
+  }
+
+  #[test]
+  fn lint_file_ts() {
+    let path = temp_file("file.ts", "const a: number = 1;\ndebugger;\n");
+    let diagnostics = lint_file(&path).unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].range.start,
+      Position {
+        line: 2,
+        col: 0,
+        col_utf16: 0,
+        byte_pos: 21
+      }
+    );
+  }
+
+  #[test]
+  fn lint_file_missing() {
+    let path = std::env::temp_dir().join("deno_lint_missing_file.ts");
+    match lint_file(&path) {
+      Err(LintError::Io(err)) => {
+        assert_eq!(err.kind(), io::ErrorKind::NotFound)
+      }
+      result => panic!("Expected an IO error, got {:?}", result),
+    }
+  }
+
+  #[test]
+  fn lint_error_source() {
+    let path = std::env::temp_dir().join("deno_lint_missing_file.ts");
+    let err = lint_file(&path).unwrap_err();
+    let source = err.source().unwrap();
+    assert_eq!(source.to_string(), err.to_string());
+    assert!(source.downcast_ref::<io::Error>().is_some());
+
+    let config: crate::config::LintConfig = serde_json::from_str(
+      r#"{ "rules": { "options": { "no-cond-assign": "sometimes" } } }"#,
+    )
+    .unwrap();
+    let err = LintError::from(config.get_rules().err().unwrap());
+    let source = err.source().unwrap();
+    assert!(source.downcast_ref::<ConfigError>().is_some());
+    let rule_err = source.source().unwrap();
+    assert!(rule_err
+      .downcast_ref::<crate::rules::RuleConfigError>()
+      .is_some());
+    assert!(err.to_string().starts_with("Invalid config for rule"));
+  }
+
+  #[test]
+  fn lint_file_bom() {
+    let path = temp_file("bom.ts", "\u{feff}debugger;\n  debugger;\n");
+    let diagnostics = lint_file(&path).unwrap();
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(
+      diagnostics[0].range.start,
+      Position {
+        line: 1,
+        col: 0,
+        col_utf16: 0,
+        byte_pos: 0
+      }
+    );
+    assert_eq!(
+      diagnostics[0].range.end,
+      Position {
+        line: 1,
+        col: 9,
+        col_utf16: 9,
+        byte_pos: 9
+      }
+    );
+    assert_eq!(
+      diagnostics[1].range.start,
+      Position {
+        line: 2,
+        col: 2,
+        col_utf16: 2,
+        byte_pos: 12
+      }
+    );
+  }
+
+  /// Codes of the diagnostics of `source`, including parse errors.
+  fn lint_with_syntax(
+    syntax_config: SyntaxConfig,
+    source: &str,
+  ) -> Vec<String> {
+    LinterBuilder::default()
+      .syntax_config(syntax_config)
+      .report_parse_errors(true)
+      .rules(vec![crate::rules::no_with::NoWith::new()])
+      .build()
+      .lint("file.js".to_string(), source.to_string())
+      .unwrap()
+      .into_iter()
+      .map(|d| d.code)
+      .collect()
+  }
+
+  #[test]
+  fn script_and_module() {
+    let script = SyntaxConfig {
+      kind: ModuleKind::Script,
+      syntax: swc_util::get_default_es_config(),
+      ..SyntaxConfig::default()
+    };
+    assert_eq!(lint_with_syntax(script, "with (x) {}\n"), vec!["no-with"]);
+
+    // `with` is not allowed in strict mode code, like modules.
+    let module = SyntaxConfig {
+      syntax: swc_util::get_default_es_config(),
+      ..SyntaxConfig::default()
+    };
+    assert!(lint_with_syntax(module, "with (x) {}\n")
+      .contains(&"parse-error".to_string()));
+  }
+
+  #[test]
+  fn decorators_proposal() {
+    let source = "@observable\nclass Foo {}\n";
+    let mut es_config = EsConfig::default();
+    let config = |es_config: EsConfig| SyntaxConfig {
+      syntax: Syntax::Es(es_config),
+      ..SyntaxConfig::default()
+    };
+    assert_eq!(
+      lint_with_syntax(config(es_config), source),
+      vec!["parse-error"]
+    );
+
+    es_config.decorators = true;
+    assert!(lint_with_syntax(config(es_config), source).is_empty());
+  }
+
+  struct ModuleKindRule;
+
+  impl LintRule for ModuleKindRule {
+    fn new() -> Box<Self> {
+      Box::new(ModuleKindRule)
+    }
+
+    fn code(&self) -> &'static str {
+      "module-kind"
+    }
+
+    fn lint_module(&self, context: Arc<Context>, module: &Module) {
+      let message = if context.is_module() {
+        "module"
+      } else {
+        "script"
+      };
+      context.add_diagnostic(module.span, "module-kind", message);
+    }
+  }
+
+  #[test]
+  fn context_is_module() {
+    let lint = |kind: ModuleKind| {
+      let mut linter = LinterBuilder::default()
+        .syntax_config(SyntaxConfig {
+          kind,
+          ..SyntaxConfig::default()
+        })
+        .rules(vec![ModuleKindRule::new()])
+        .build();
+      let diagnostics = linter
+        .lint("file.ts".to_string(), "foo();\n".to_string())
+        .unwrap();
+      diagnostics[0].message.clone()
+    };
+    assert_eq!(lint(ModuleKind::Module), "module");
+    assert_eq!(lint(ModuleKind::Script), "script");
+  }
 }
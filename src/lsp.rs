@@ -0,0 +1,147 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+//! Conversion of diagnostics to `lsp_types` structures.
+use crate::diagnostic::{LintDiagnostic, LintFix, Position, Severity};
+use lsp_types::{
+  Diagnostic, DiagnosticSeverity, NumberOrString, Range, TextEdit,
+};
+
+/// Name of the linter in the `source` field of diagnostics.
+const SOURCE: &str = "deno-lint";
+
+/// Converts the diagnostic to an LSP `Diagnostic`. Lines are 0-based and
+/// columns counted in UTF-16 code units as required by LSP. The hint is
+/// appended to the message.
+impl From<&LintDiagnostic> for Diagnostic {
+  fn from(diagnostic: &LintDiagnostic) -> Self {
+    let severity = match diagnostic.severity {
+      Severity::Error => DiagnosticSeverity::Error,
+      Severity::Warning => DiagnosticSeverity::Warning,
+      Severity::Hint => DiagnosticSeverity::Hint,
+    };
+    let message = match &diagnostic.hint {
+      Some(hint) => format!("{}\n\nhint: {}", diagnostic.message, hint),
+      None => diagnostic.message.clone(),
+    };
+    Diagnostic {
+      range: Range::new(
+        lsp_position(&diagnostic.range.start),
+        lsp_position(&diagnostic.range.end),
+      ),
+      severity: Some(severity),
+      code: Some(NumberOrString::String(diagnostic.code.clone())),
+      source: Some(SOURCE.to_string()),
+      message,
+      related_information: None,
+      tags: None,
+    }
+  }
+}
+
+/// Converts the fix of the diagnostic to LSP `TextEdit`s. Fixes store byte
+/// offsets, so `source` is needed to compute the positions.
+pub fn to_lsp_text_edits(
+  diagnostic: &LintDiagnostic,
+  source: &str,
+) -> Vec<TextEdit> {
+  diagnostic
+    .fix
+    .iter()
+    .filter_map(|fix| text_edit(fix, source))
+    .collect()
+}
+
+fn text_edit(fix: &LintFix, source: &str) -> Option<TextEdit> {
+  let start = position_at(source, fix.start)?;
+  let end = position_at(source, fix.end)?;
+  Some(TextEdit::new(
+    Range::new(start, end),
+    fix.replacement.clone(),
+  ))
+}
+
+fn lsp_position(position: &Position) -> lsp_types::Position {
+  lsp_types::Position::new(
+    (position.line - 1) as u64,
+    position.col_utf16 as u64,
+  )
+}
+
+/// LSP position of the byte offset, or `None` if it's out of bounds or not
+/// on a char boundary.
+fn position_at(source: &str, offset: usize) -> Option<lsp_types::Position> {
+  if offset > source.len() || !source.is_char_boundary(offset) {
+    return None;
+  }
+  let before = &source[..offset];
+  let line_start = match before.rfind('\n') {
+    Some(i) => i + 1,
+    None => 0,
+  };
+  let character = before[line_start..].encode_utf16().count();
+  Some(lsp_types::Position::new(
+    before.matches('\n').count() as u64,
+    character as u64,
+  ))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::linter::LinterBuilder;
+  use crate::rules::get_recommended_rules;
+
+  fn lint(source: &str) -> Vec<LintDiagnostic> {
+    LinterBuilder::default()
+      .rules(get_recommended_rules())
+      .build()
+      .lint("file.ts".to_string(), source.to_string())
+      .unwrap()
+  }
+
+  #[test]
+  fn utf16_columns() {
+    let source = "const a = 1;\nconst s = \"😀é\"; debugger;\n";
+    let diagnostics = lint(source);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.col, 16);
+
+    assert_eq!(
+      Diagnostic::from(&diagnostics[0]),
+      Diagnostic {
+        range: Range::new(
+          lsp_types::Position::new(1, 17),
+          lsp_types::Position::new(1, 26),
+        ),
+        severity: Some(DiagnosticSeverity::Error),
+        code: Some(NumberOrString::String("no-debugger".to_string())),
+        source: Some("deno-lint".to_string()),
+        message: "`debugger` statement is not allowed\n\nhint: Remove the `debugger` statement".to_string(),
+        related_information: None,
+        tags: None,
+      }
+    );
+  }
+
+  #[test]
+  fn text_edits() {
+    let source = "const s = \"😀\";\nif (s == \"a\") {}\n";
+    let mut diagnostic = lint("debugger;").remove(0);
+    assert!(to_lsp_text_edits(&diagnostic, source).is_empty());
+
+    diagnostic.fix = Some(LintFix {
+      start: 24,
+      end: 26,
+      replacement: "===".to_string(),
+    });
+    assert_eq!(
+      to_lsp_text_edits(&diagnostic, source),
+      vec![TextEdit::new(
+        Range::new(
+          lsp_types::Position::new(1, 6),
+          lsp_types::Position::new(1, 8),
+        ),
+        "===".to_string(),
+      )]
+    );
+  }
+}
@@ -1,6 +1,7 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use crate::swc_util::Key;
 use std::collections::HashSet;
 use std::sync::Arc;
@@ -11,7 +12,6 @@ use swc_ecmascript::ast::{
   Module, ModuleDecl, ModuleItem, Stmt, Str, TsInterfaceBody,
   TsMethodSignature, TsModuleBlock, TsTypeElement, TsTypeLit,
 };
-use swc_ecmascript::visit::{Node, Visit};
 
 pub struct AdjacentOverloadSignatures;
 
@@ -24,9 +24,19 @@ impl LintRule for AdjacentOverloadSignatures {
     "adjacent-overload-signatures"
   }
 
-  fn lint_module(&self, context: Arc<Context>, module: &Module) {
-    let mut visitor = AdjacentOverloadSignaturesVisitor::new(context);
-    visitor.visit_module(module, module);
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Requires overload signatures of a member to be placed next to each other."
+  }
+
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(AdjacentOverloadSignaturesVisitor::new(context)))
   }
 }
 
@@ -141,42 +151,41 @@ impl ExtractMethod for TsTypeElement {
   }
 }
 
-impl Visit for AdjacentOverloadSignaturesVisitor {
-  fn visit_module(&mut self, module: &Module, parent: &dyn Node) {
+impl Handler for AdjacentOverloadSignaturesVisitor {
+  fn visit_types(&self) -> bool {
+    true
+  }
+
+  fn visit_module(&mut self, module: &Module, _traversal: &mut Traversal) {
     self.check(&module.body);
-    swc_ecmascript::visit::visit_module(self, module, parent);
   }
 
   fn visit_ts_module_block(
     &mut self,
     ts_module_block: &TsModuleBlock,
-    parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     self.check(&ts_module_block.body);
-    swc_ecmascript::visit::visit_ts_module_block(self, ts_module_block, parent);
   }
 
-  fn visit_class(&mut self, class: &Class, parent: &dyn Node) {
+  fn visit_class(&mut self, class: &Class, _traversal: &mut Traversal) {
     self.check(&class.body);
-    swc_ecmascript::visit::visit_class(self, class, parent);
   }
 
-  fn visit_ts_type_lit(&mut self, ts_type_lit: &TsTypeLit, parent: &dyn Node) {
+  fn visit_ts_type_lit(
+    &mut self,
+    ts_type_lit: &TsTypeLit,
+    _traversal: &mut Traversal,
+  ) {
     self.check(&ts_type_lit.members);
-    swc_ecmascript::visit::visit_ts_type_lit(self, ts_type_lit, parent);
   }
 
   fn visit_ts_interface_body(
     &mut self,
     ts_inteface_body: &TsInterfaceBody,
-    parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     self.check(&ts_inteface_body.body);
-    swc_ecmascript::visit::visit_ts_interface_body(
-      self,
-      ts_inteface_body,
-      parent,
-    );
   }
 }
 
@@ -38,21 +38,22 @@ impl LintRule for BanTsComment {
     "ban-ts-comment"
   }
 
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows `@ts-<directive>` comments that suppress type checking errors."
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
     _module: &swc_ecmascript::ast::Module,
   ) {
-    context.leading_comments.values().for_each(|comments| {
-      for comment in comments {
-        self.lint_comment(&context, comment);
-      }
-    });
-    context.trailing_comments.values().for_each(|comments| {
-      for comment in comments {
-        self.lint_comment(&context, comment);
-      }
-    });
+    for comment in context.all_comments() {
+      self.lint_comment(&context, comment);
+    }
   }
 }
 
@@ -1,8 +1,7 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
+use crate::handler::{Handler, Traversal};
 
 use std::sync::Arc;
 
@@ -17,13 +16,19 @@ impl LintRule for BanTypes {
     "ban-types"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows the use of primitive wrapper types and other problematic types, such as `String`, `Object` or `Function`."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = BanTypesVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(BanTypesVisitor::new(context)))
   }
 }
 
@@ -47,11 +52,15 @@ const BANNED_TYPES: [(&str, &str); 6] = [
 or if you want a type meaning `any value`, you probably want `unknown` instead."),
 ];
 
-impl Visit for BanTypesVisitor {
+impl Handler for BanTypesVisitor {
+  fn visit_types(&self) -> bool {
+    true
+  }
+
   fn visit_ts_type_ref(
     &mut self,
     ts_type_ref: &swc_ecmascript::ast::TsTypeRef,
-    _parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     if let swc_ecmascript::ast::TsEntityName::Ident(ident) =
       &ts_type_ref.type_name
@@ -65,31 +74,24 @@ impl Visit for BanTypesVisitor {
           .add_diagnostic(ts_type_ref.span, "ban-types", message);
       }
     }
-    if let Some(type_param) = &ts_type_ref.type_params {
-      self.visit_ts_type_param_instantiation(type_param, ts_type_ref);
-    }
   }
   fn visit_ts_type_lit(
     &mut self,
     ts_type_lit: &swc_ecmascript::ast::TsTypeLit,
-    _parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
-    if !ts_type_lit.members.is_empty() {
-      for element in ts_type_lit.members.iter() {
-        self.visit_ts_type_element(element, ts_type_lit);
-      }
-      return;
+    if ts_type_lit.members.is_empty() {
+      self.context.add_diagnostic(
+        ts_type_lit.span,
+        "ban-types",
+        BANNED_TYPES[5].1, // `Object` message
+      );
     }
-    self.context.add_diagnostic(
-      ts_type_lit.span,
-      "ban-types",
-      BANNED_TYPES[5].1, // `Object` message
-    );
   }
   fn visit_ts_keyword_type(
     &mut self,
     ts_keyword_type: &swc_ecmascript::ast::TsKeywordType,
-    _parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     if let swc_ecmascript::ast::TsKeywordTypeKind::TsObjectKeyword =
       ts_keyword_type.kind
@@ -101,15 +103,6 @@ impl Visit for BanTypesVisitor {
       );
     }
   }
-  fn visit_ts_type_param_instantiation(
-    &mut self,
-    ts_type_param_instantiation: &swc_ecmascript::ast::TsTypeParamInstantiation,
-    _parent: &dyn Node,
-  ) {
-    for param in ts_type_param_instantiation.params.iter() {
-      self.visit_ts_type(&param, ts_type_param_instantiation);
-    }
-  }
 }
 
 #[cfg(test)]
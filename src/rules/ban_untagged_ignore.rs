@@ -15,6 +15,14 @@ impl LintRule for BanUntaggedIgnore {
     "ban-untagged-ignore"
   }
 
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Requires `deno-lint-ignore` directives to specify the codes of the rules they ignore."
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
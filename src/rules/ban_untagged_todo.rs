@@ -43,27 +43,25 @@ impl LintRule for BanUntaggedTodo {
     "ban-untagged-todo"
   }
 
+  fn docs(&self) -> &'static str {
+    "Requires TODO comments to be tagged with an issue reference or a user, e.g. `TODO(#1234)` or `TODO(@user)`."
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
     _module: &swc_ecmascript::ast::Module,
   ) {
-    context.leading_comments.values().for_each(|comments| {
-      for comment in comments {
-        self.lint_comment(&context, comment);
-      }
-    });
-    context.trailing_comments.values().for_each(|comments| {
-      for comment in comments {
-        self.lint_comment(&context, comment);
-      }
-    });
+    for comment in context.all_comments() {
+      self.lint_comment(&context, comment);
+    }
   }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::diagnostic::Severity;
   use crate::test_util::*;
 
   #[test]
@@ -97,4 +95,14 @@ const a = "a";
       0,
     );
   }
+
+  #[test]
+  fn ban_untagged_todo_severity() {
+    assert_lint_err_with_severity::<BanUntaggedTodo>(
+      "// TODO\nconst a = 1;",
+      1,
+      0,
+      Severity::Error,
+    );
+  }
 }
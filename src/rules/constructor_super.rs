@@ -1,9 +1,12 @@
+use crate::handler::{Handler, Traversal};
 use std::sync::Arc;
 
 use super::Context;
 use super::LintRule;
 use swc_ecmascript::ast::{
-  Class, ClassMember, Constructor, Expr, ExprOrSuper, Stmt,
+  ArrowExpr, BinExpr, BinaryOp, CallExpr, Class, ClassMember, CondExpr,
+  Constructor, DoWhileStmt, Expr, ExprOrSuper, ForInStmt, ForOfStmt, ForStmt,
+  Function, IfStmt, ReturnStmt, SwitchStmt, ThrowStmt, TryStmt, WhileStmt,
 };
 use swc_ecmascript::visit::noop_visit_type;
 use swc_ecmascript::visit::Node;
@@ -11,8 +14,6 @@ use swc_ecmascript::visit::Visit;
 
 pub struct ConstructorSuper;
 
-// This rule currently differs from the ESlint implementation
-// as there is currently no way of handling code paths in dlint
 impl LintRule for ConstructorSuper {
   fn new() -> Box<Self> {
     Box::new(ConstructorSuper)
@@ -22,16 +23,36 @@ impl LintRule for ConstructorSuper {
     "constructor-super"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Verifies that constructors of derived classes call `super()` and that non-derived constructors don't.
+
+Derived constructors must call `super()` exactly once on every path, unless
+the path returns a value. Calling it in only one branch of an `if` or in a
+loop body which may not run is reported. Classes extending `null` or other
+literals can't call `super()`."#
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = ConstructorSuperVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(ConstructorSuperVisitor::new(context)))
   }
 }
 
+#[derive(Clone, Copy, PartialEq)]
+enum ClassKind {
+  /// No `extends` clause.
+  Base,
+  Derived,
+  /// Extends `null` or another literal.
+  NonConstructor,
+}
+
 struct ConstructorSuperVisitor {
   context: Arc<Context>,
 }
@@ -40,96 +61,277 @@ impl ConstructorSuperVisitor {
   fn new(context: Arc<Context>) -> Self {
     Self { context }
   }
+
   fn check_constructor(&self, constructor: &Constructor, class: &Class) {
-    let mut sup = None;
-    let mut span = constructor.span;
-    if let Some(block_stmt) = &constructor.body {
-      span = block_stmt.span;
-      for stmt in &block_stmt.stmts {
-        if let Stmt::Expr(expr) = stmt {
-          if let Expr::Call(call) = &*expr.expr {
-            if let ExprOrSuper::Super(s) = &call.callee {
-              if sup.is_none() {
-                sup = Some(s)
-              } else {
-                self.context.add_diagnostic(
-                  span,
-                  "constructor-super",
-                  "Constructors of derived classes must call super() only once",
-                );
-              }
-            }
-          }
-        } else if let Stmt::Return(ret) = stmt {
-          // returning value is a substitute of 'super()'.
-          if sup.is_none() {
-            if ret.arg.is_none() && class.super_class.is_some() {
-              self.context.add_diagnostic(
-                span,
-                "constructor-super",
-                "Constructors of derived classes must call super()",
-              );
-            }
-            return;
-          }
-        }
+    // Overload signatures have no body.
+    let body = match &constructor.body {
+      Some(body) => body,
+      None => return,
+    };
+    let kind = match class.super_class.as_deref() {
+      None => ClassKind::Base,
+      Some(Expr::Lit(_)) => ClassKind::NonConstructor,
+      Some(_) => ClassKind::Derived,
+    };
+
+    let mut checker = SuperCallChecker::default();
+    for stmt in &body.stmts {
+      checker.visit_stmt(stmt, body);
+    }
+    checker.check_end();
+
+    let message = match kind {
+      ClassKind::Base if checker.called => {
+        "Constructors of non derived classes must not call super()"
+      }
+      ClassKind::NonConstructor if checker.called => {
+        "Constructors of classes which inherit from a non constructor must not call super()"
+      }
+      ClassKind::NonConstructor if checker.missing => {
+        "Classes which inherit from a non constructor must not define a constructor"
+      }
+      ClassKind::Derived if checker.missing => {
+        "Constructors of derived classes must call super()"
+      }
+      ClassKind::Derived if checker.duplicate => {
+        "Constructors of derived classes must call super() only once"
+      }
+      _ => return,
+    };
+    self
+      .context
+      .add_diagnostic(body.span, "constructor-super", message);
+  }
+}
+
+impl Handler for ConstructorSuperVisitor {
+  fn visit_class(&mut self, class: &Class, _traversal: &mut Traversal) {
+    for member in &class.body {
+      if let ClassMember::Constructor(constructor) = member {
+        self.check_constructor(constructor, class);
       }
     }
+  }
+}
 
-    if let Some(expr) = &class.super_class {
-      if let Expr::Lit(_) = &**expr {
-        if constructor.body.is_none()
-          || constructor.body.as_ref().unwrap().stmts.is_empty()
-        {
-          self.context.add_diagnostic(
-            span,
-            "constructor-super",
-            "Classes which inherit from a non constructor must not define a constructor",
-          );
-        } else {
-          self.context.add_diagnostic(
-            span,
-            "constructor-super",
-            "Constructors of classes which inherit from a non constructor must not call super()",
-          );
+/// Walks a constructor body in execution order, tracking the `super()`
+/// calls on each path.
+#[derive(Default)]
+struct SuperCallChecker {
+  /// Whether `super()` has been called on every path to the current node.
+  /// Paths which returned or threw count as having called it.
+  definitely: bool,
+  /// Whether `super()` has been called on some path to the current node.
+  possibly: bool,
+  /// Whether `super()` is called anywhere.
+  called: bool,
+  /// Whether some path finishes without calling `super()`.
+  missing: bool,
+  /// Whether some path calls `super()` twice.
+  duplicate: bool,
+}
+
+impl SuperCallChecker {
+  /// The state after the path finished, so it doesn't affect merges.
+  fn finish_path(&mut self) {
+    self.definitely = true;
+    self.possibly = false;
+  }
+
+  fn check_end(&mut self) {
+    if !self.definitely {
+      self.missing = true;
+    }
+  }
+
+  /// Visits code that may not run, like a loop body.
+  fn visit_maybe<F>(&mut self, f: F)
+  where
+    F: FnOnce(&mut Self),
+  {
+    let (definitely, possibly) = (self.definitely, self.possibly);
+    f(self);
+    self.definitely = definitely;
+    self.possibly |= possibly;
+  }
+
+  /// Visits both branches from the current state and merges their states.
+  fn visit_branches<F, G>(&mut self, cons: F, alt: G)
+  where
+    F: FnOnce(&mut Self),
+    G: FnOnce(&mut Self),
+  {
+    let (definitely, possibly) = (self.definitely, self.possibly);
+    cons(self);
+    let (cons_definitely, cons_possibly) = (self.definitely, self.possibly);
+    self.definitely = definitely;
+    self.possibly = possibly;
+    alt(self);
+    self.definitely &= cons_definitely;
+    self.possibly |= cons_possibly;
+  }
+}
+
+impl Visit for SuperCallChecker {
+  noop_visit_type!();
+
+  // Nested classes are checked separately.
+  fn visit_class(&mut self, _class: &Class, _parent: &dyn Node) {}
+
+  fn visit_function(&mut self, _function: &Function, _parent: &dyn Node) {}
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, parent: &dyn Node) {
+    self.visit_maybe(|v| {
+      swc_ecmascript::visit::visit_arrow_expr(v, arrow_expr, parent)
+    });
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+    if let ExprOrSuper::Super(_) = call_expr.callee {
+      self.called = true;
+      if self.possibly {
+        self.duplicate = true;
+      }
+      self.definitely = true;
+      self.possibly = true;
+    }
+  }
+
+  fn visit_if_stmt(&mut self, if_stmt: &IfStmt, _parent: &dyn Node) {
+    self.visit_expr(&if_stmt.test, if_stmt);
+    self.visit_branches(
+      |v| v.visit_stmt(&if_stmt.cons, if_stmt),
+      |v| {
+        if let Some(alt) = &if_stmt.alt {
+          v.visit_stmt(alt, if_stmt);
         }
-        return;
+      },
+    );
+  }
+
+  fn visit_cond_expr(&mut self, cond_expr: &CondExpr, _parent: &dyn Node) {
+    self.visit_expr(&cond_expr.test, cond_expr);
+    self.visit_branches(
+      |v| v.visit_expr(&cond_expr.cons, cond_expr),
+      |v| v.visit_expr(&cond_expr.alt, cond_expr),
+    );
+  }
+
+  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, _parent: &dyn Node) {
+    self.visit_expr(&bin_expr.left, bin_expr);
+    match bin_expr.op {
+      BinaryOp::LogicalAnd
+      | BinaryOp::LogicalOr
+      | BinaryOp::NullishCoalescing => {
+        self.visit_maybe(|v| v.visit_expr(&bin_expr.right, bin_expr));
       }
+      _ => self.visit_expr(&bin_expr.right, bin_expr),
     }
+  }
 
-    if sup.is_some() {
-      if class.super_class.is_none() {
-        self.context.add_diagnostic(
-          span,
-          "constructor-super",
-          "Constructors of non derived classes must not call super()",
-        );
+  fn visit_switch_stmt(
+    &mut self,
+    switch_stmt: &SwitchStmt,
+    _parent: &dyn Node,
+  ) {
+    self.visit_expr(&switch_stmt.discriminant, switch_stmt);
+    let (definitely, possibly) = (self.definitely, self.possibly);
+    let mut all_definitely = true;
+    let mut any_possibly = possibly;
+    for case in &switch_stmt.cases {
+      self.definitely = definitely;
+      self.possibly = possibly;
+      if let Some(test) = &case.test {
+        self.visit_expr(test, case);
+      }
+      for stmt in &case.cons {
+        self.visit_stmt(stmt, case);
+      }
+      // Empty cases fall through to the next one.
+      if !case.cons.is_empty() {
+        all_definitely &= self.definitely;
+        any_possibly |= self.possibly;
       }
-    } else if class.super_class.is_some() {
-      self.context.add_diagnostic(
-        span,
-        "constructor-super",
-        "Constructors of derived classes must call super()",
-      );
     }
+    let has_default = switch_stmt.cases.iter().any(|case| case.test.is_none());
+    self.definitely = definitely || (has_default && all_definitely);
+    self.possibly = any_possibly;
   }
-}
 
-impl Visit for ConstructorSuperVisitor {
-  noop_visit_type!();
+  fn visit_try_stmt(&mut self, try_stmt: &TryStmt, _parent: &dyn Node) {
+    let (definitely, possibly) = (self.definitely, self.possibly);
+    self.visit_block_stmt(&try_stmt.block, try_stmt);
+    if let Some(handler) = &try_stmt.handler {
+      // The block may throw anywhere, before or after calling `super()`.
+      let block_definitely = self.definitely;
+      let block_possibly = self.possibly;
+      self.definitely = definitely;
+      self.possibly |= possibly;
+      self.visit_catch_clause(handler, try_stmt);
+      self.definitely &= block_definitely;
+      self.possibly |= block_possibly;
+    }
+    if let Some(finalizer) = &try_stmt.finalizer {
+      self.visit_block_stmt(finalizer, try_stmt);
+    }
+  }
 
-  fn visit_class(&mut self, class: &Class, parent: &dyn Node) {
-    for member in &class.body {
-      if let ClassMember::Constructor(constructor) = member {
-        self.check_constructor(constructor, class);
+  fn visit_while_stmt(&mut self, while_stmt: &WhileStmt, _parent: &dyn Node) {
+    self.visit_expr(&while_stmt.test, while_stmt);
+    self.visit_maybe(|v| v.visit_stmt(&while_stmt.body, while_stmt));
+  }
+
+  fn visit_do_while_stmt(
+    &mut self,
+    do_while_stmt: &DoWhileStmt,
+    _parent: &dyn Node,
+  ) {
+    self.visit_stmt(&do_while_stmt.body, do_while_stmt);
+    self.visit_expr(&do_while_stmt.test, do_while_stmt);
+  }
+
+  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, _parent: &dyn Node) {
+    if let Some(init) = &for_stmt.init {
+      self.visit_var_decl_or_expr(init, for_stmt);
+    }
+    if let Some(test) = &for_stmt.test {
+      self.visit_expr(test, for_stmt);
+    }
+    self.visit_maybe(|v| {
+      v.visit_stmt(&for_stmt.body, for_stmt);
+      if let Some(update) = &for_stmt.update {
+        v.visit_expr(update, for_stmt);
       }
+    });
+  }
+
+  fn visit_for_in_stmt(&mut self, for_in_stmt: &ForInStmt, _parent: &dyn Node) {
+    self.visit_expr(&for_in_stmt.right, for_in_stmt);
+    self.visit_maybe(|v| v.visit_stmt(&for_in_stmt.body, for_in_stmt));
+  }
+
+  fn visit_for_of_stmt(&mut self, for_of_stmt: &ForOfStmt, _parent: &dyn Node) {
+    self.visit_expr(&for_of_stmt.right, for_of_stmt);
+    self.visit_maybe(|v| v.visit_stmt(&for_of_stmt.body, for_of_stmt));
+  }
+
+  fn visit_return_stmt(&mut self, return_stmt: &ReturnStmt, parent: &dyn Node) {
+    swc_ecmascript::visit::visit_return_stmt(self, return_stmt, parent);
+    // Returning a value is a substitute for `super()`.
+    if return_stmt.arg.is_none() {
+      self.check_end();
     }
-    swc_ecmascript::visit::visit_class(self, class, parent);
+    self.finish_path();
+  }
+
+  fn visit_throw_stmt(&mut self, throw_stmt: &ThrowStmt, parent: &dyn Node) {
+    swc_ecmascript::visit::visit_throw_stmt(self, throw_stmt, parent);
+    self.finish_path();
   }
 }
 
-// most tests are taken from ESlint, commenting those
-// requiring code path support
+// most tests are taken from ESlint
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -152,7 +354,7 @@ class A extends null { }
 // derived classes.
 class A extends B { }
 class A extends B { constructor() { super(); } }
-// class A extends B { constructor() { if (true) { super(); } else { super(); } } }
+class A extends B { constructor() { if (true) { super(); } else { super(); } } }
 class A extends (class B {}) { constructor() { super(); } }
 class A extends (B = C) { constructor() { super(); } }
 class A extends (B || C) { constructor() { super(); } }
@@ -165,11 +367,11 @@ class A extends B { constructor() { super(); class C extends D { constructor() {
 class A extends B { constructor() { super(); class C { constructor() { } } } }
 
 // multi code path.
-// class A extends B { constructor() { a ? super() : super(); } }
-// class A extends B { constructor() { if (a) super(); else super(); } }
-// class A extends B { constructor() { switch (a) { case 0: super(); break; default: super(); } } }
-// class A extends B { constructor() { try {} finally { super(); } } }
-// class A extends B { constructor() { if (a) throw Error(); super(); } }
+class A extends B { constructor() { a ? super() : super(); } }
+class A extends B { constructor() { if (a) super(); else super(); } }
+class A extends B { constructor() { switch (a) { case 0: super(); break; default: super(); } } }
+class A extends B { constructor() { try {} finally { super(); } } }
+class A extends B { constructor() { if (a) throw Error(); super(); } }
 
 // returning value is a substitute of 'super()'.
 class A extends B { constructor() { if (true) return a; super(); } }
@@ -301,4 +503,60 @@ class A extends B {
       20,
     );
   }
+
+  #[test]
+  fn constructor_super_code_paths() {
+    assert_lint_ok_n::<ConstructorSuper>(vec![
+      "class A extends getBase() { constructor() { super(); } }",
+      "class A extends B { constructor() { if (a) { return b; } super(); } }",
+      "class A extends B { constructor() { do { foo(); } while (a); super(); } }",
+      "class A extends B { constructor() { try { super(); } catch (e) { throw e; } } }",
+      "class A extends B { constructor() { function foo() { super(); } super(); } }",
+      "class A { constructor(); constructor(a) {} }",
+    ]);
+    assert_lint_err::<ConstructorSuper>(
+      "class A extends getBase() { constructor() { } }",
+      42,
+    );
+    assert_lint_err::<ConstructorSuper>(
+      "class A extends B { constructor() { if (a) { super(); } } }",
+      34,
+    );
+    assert_lint_err::<ConstructorSuper>(
+      "class A extends B { constructor() { if (a) { super(); } else { foo(); } } }",
+      34,
+    );
+    assert_lint_err::<ConstructorSuper>(
+      "class A extends B { constructor() { a && super(); } }",
+      34,
+    );
+    assert_lint_err::<ConstructorSuper>(
+      "class A extends B { constructor() { while (a) { super(); } } }",
+      34,
+    );
+    assert_lint_err::<ConstructorSuper>(
+      "class A extends B { constructor() { switch (a) { case 0: super(); } } }",
+      34,
+    );
+    assert_lint_err::<ConstructorSuper>(
+      "class A extends B { constructor() { try { super(); } catch (e) {} } }",
+      34,
+    );
+    assert_lint_err::<ConstructorSuper>(
+      "class A extends B { constructor() { if (a) { super(); } super(); } }",
+      34,
+    );
+    assert_lint_err::<ConstructorSuper>(
+      "class A extends B { constructor() { if (a) { return; } super(); } }",
+      34,
+    );
+    assert_lint_err::<ConstructorSuper>(
+      "class A { constructor() { super(); } }",
+      24,
+    );
+    assert_lint_err::<ConstructorSuper>(
+      "class A { constructor() { if (a) { super(); } } }",
+      24,
+    );
+  }
 }
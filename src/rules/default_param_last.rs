@@ -1,11 +1,9 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_common::Span;
 use swc_ecmascript::ast::{ArrowExpr, Function, Pat};
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
-use swc_ecmascript::visit::{self, noop_visit_type};
 
 use std::sync::Arc;
 
@@ -20,13 +18,15 @@ impl LintRule for DefaultParamLast {
     "default-param-last"
   }
 
-  fn lint_module(
-    &self,
+  fn docs(&self) -> &'static str {
+    "Enforces default parameters to be last in a parameter list."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = DefaultParamLastVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(DefaultParamLastVisitor::new(context)))
   }
 }
 
@@ -68,17 +68,21 @@ impl DefaultParamLastVisitor {
   }
 }
 
-impl Visit for DefaultParamLastVisitor {
-  noop_visit_type!();
-
-  fn visit_function(&mut self, function: &Function, parent: &dyn Node) {
+impl Handler for DefaultParamLastVisitor {
+  fn visit_function(
+    &mut self,
+    function: &Function,
+    _traversal: &mut Traversal,
+  ) {
     self.check_params(function.params.iter().rev().map(|p| &p.pat));
-    visit::visit_function(self, function, parent);
   }
 
-  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, parent: &dyn Node) {
+  fn visit_arrow_expr(
+    &mut self,
+    arrow_expr: &ArrowExpr,
+    _traversal: &mut Traversal,
+  ) {
     self.check_params(arrow_expr.params.iter().rev());
-    visit::visit_arrow_expr(self, arrow_expr, parent);
   }
 }
 
@@ -122,11 +126,11 @@ class Foo {
     assert_lint_err::<DefaultParamLast>("const f = function (a = 2, b) {}", 20);
     assert_lint_err_n::<DefaultParamLast>(
       "function f(a = 5, b = 6, c) {}",
-      vec![18, 11],
+      vec![11, 18],
     );
     assert_lint_err_n::<DefaultParamLast>(
       "function f(a = 5, b, c = 6, d) {}",
-      vec![21, 11],
+      vec![11, 21],
     );
     assert_lint_err::<DefaultParamLast>("function f(a = 5, b, c = 5) {}", 11);
     assert_lint_err::<DefaultParamLast>("const f = (a = 5, b, ...c) => {}", 11);
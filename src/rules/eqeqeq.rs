@@ -1,59 +1,209 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
-use super::LintRule;
-use swc_ecmascript::ast::{BinExpr, BinaryOp};
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
+use super::{LintRule, RuleConfigError, RuleExamples};
+use crate::handler::{Handler, Traversal};
+use swc_common::{BytePos, Span, Spanned};
+use swc_ecmascript::ast::{BinExpr, BinaryOp, Expr, Lit, UnaryOp};
 
 use std::sync::Arc;
 
-pub struct Eqeqeq;
+/// How comparisons against `null` are treated.
+#[derive(Clone, Copy, PartialEq)]
+enum NullOption {
+  /// `===` and `!==` are required.
+  Always,
+  /// Both `==` and `===` are allowed.
+  Ignore,
+  /// `==` and `!=` are required, as they also match `undefined`.
+  Never,
+}
+
+pub struct Eqeqeq {
+  /// Allow `==` where it can't behave differently from `===`: comparisons
+  /// of two literals, of `typeof` results and against `null`.
+  smart: bool,
+  null: NullOption,
+}
 
 impl LintRule for Eqeqeq {
   fn new() -> Box<Self> {
-    Box::new(Eqeqeq)
+    Box::new(Eqeqeq {
+      smart: false,
+      null: NullOption::Always,
+    })
+  }
+
+  fn new_with_config(
+    &self,
+    config: serde_json::Value,
+  ) -> Result<Box<dyn LintRule>, RuleConfigError> {
+    let error = |message: String| RuleConfigError {
+      code: "eqeqeq".to_string(),
+      message,
+    };
+
+    let smart = match config.get("mode") {
+      None => false,
+      Some(mode) if *mode == "always" => false,
+      Some(mode) if *mode == "smart" => true,
+      Some(mode) => {
+        return Err(error(format!(
+          "expected mode to be \"always\" or \"smart\", got {}",
+          mode
+        )))
+      }
+    };
+
+    let null = match config.get("null") {
+      None => NullOption::Always,
+      Some(null) if *null == "always" => NullOption::Always,
+      Some(null) if *null == "ignore" => NullOption::Ignore,
+      Some(null) if *null == "never" => NullOption::Never,
+      Some(null) => {
+        return Err(error(format!(
+          "expected null to be \"always\", \"ignore\" or \"never\", got {}",
+          null
+        )))
+      }
+    };
+
+    Ok(Box::new(Eqeqeq { smart, null }))
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(serde_json::json!({
+      "type": "object",
+      "properties": {
+        "mode": { "enum": ["always", "smart"] },
+        "null": { "enum": ["always", "ignore", "never"] }
+      },
+      "additionalProperties": false
+    }))
   }
 
   fn code(&self) -> &'static str {
     "eqeqeq"
   }
 
-  fn lint_module(
-    &self,
+  fn docs(&self) -> &'static str {
+    r#"Requires the use of type-safe equality operators `===` and `!==`.
+
+Options:
+- `"mode"`: `"always"` (default) or `"smart"`. The latter allows `==` and
+  `!=` when comparing two literals, `typeof` results or against `null`.
+- `"null"`: `"always"` (default) requires `===` against `null` too,
+  `"ignore"` allows both and `"never"` requires `==`, which also matches
+  `undefined`."#
+  }
+
+  fn examples(&self) -> RuleExamples {
+    RuleExamples {
+      valid: &["if (a === 5) {}", "if (\"hello world\" !== input) {}"],
+      invalid: &["if (a == 5) {}", "if (\"hello world\" != input) {}"],
+    }
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = EqeqeqVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(EqeqeqVisitor {
+      context,
+      smart: self.smart,
+      null: self.null,
+    }))
   }
 }
 
 struct EqeqeqVisitor {
   context: Arc<Context>,
+  smart: bool,
+  null: NullOption,
 }
 
 impl EqeqeqVisitor {
-  fn new(context: Arc<Context>) -> Self {
-    Self { context }
+  /// Span of the operator, or of the whole expression if it can't be found
+  /// between the operands.
+  fn operator_span(&self, bin_expr: &BinExpr, operator: &str) -> Span {
+    let between = Span::new(
+      bin_expr.left.span().hi(),
+      bin_expr.right.span().lo(),
+      Default::default(),
+    );
+    let offset = self
+      .context
+      .source_map
+      .span_to_snippet(between)
+      .ok()
+      .and_then(|text| text.find(operator));
+    match offset {
+      Some(offset) => {
+        let lo = between.lo() + BytePos(offset as u32);
+        Span::new(lo, lo + BytePos(operator.len() as u32), Default::default())
+      }
+      None => bin_expr.span,
+    }
+  }
+
+  /// Whether `==` or `!=` is allowed by the options.
+  fn allows_loose(&self, bin_expr: &BinExpr) -> bool {
+    let (left, right) = (&*bin_expr.left, &*bin_expr.right);
+    let against_null = is_null(left) || is_null(right);
+    if against_null && self.null != NullOption::Always {
+      return true;
+    }
+    self.smart
+      && (against_null
+        || is_typeof(left)
+        || is_typeof(right)
+        || (is_literal(left) && is_literal(right)))
+  }
+
+  /// Whether `===` or `!==` must be replaced by `==` or `!=`.
+  fn requires_loose(&self, bin_expr: &BinExpr) -> bool {
+    self.null == NullOption::Never
+      && (is_null(&bin_expr.left) || is_null(&bin_expr.right))
   }
 }
 
-impl Visit for EqeqeqVisitor {
-  noop_visit_type!();
-
-  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, parent: &dyn Node) {
-    if matches!(bin_expr.op, BinaryOp::EqEq | BinaryOp::NotEq) {
-      let message = if bin_expr.op == BinaryOp::EqEq {
-        "expected '===' and instead saw '=='."
-      } else {
-        "expected '!==' and instead saw '!='."
-      };
-      self
-        .context
-        .add_diagnostic(bin_expr.span, "eqeqeq", message)
+fn unparen(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(paren) => unparen(&paren.expr),
+    _ => expr,
+  }
+}
+
+fn is_null(expr: &Expr) -> bool {
+  matches!(unparen(expr), Expr::Lit(Lit::Null(_)))
+}
+
+fn is_typeof(expr: &Expr) -> bool {
+  match unparen(expr) {
+    Expr::Unary(unary) => unary.op == UnaryOp::TypeOf,
+    _ => false,
+  }
+}
+
+fn is_literal(expr: &Expr) -> bool {
+  matches!(unparen(expr), Expr::Lit(_))
+}
+
+impl Handler for EqeqeqVisitor {
+  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, _traversal: &mut Traversal) {
+    let operators = match bin_expr.op {
+      BinaryOp::EqEq if !self.allows_loose(bin_expr) => Some(("===", "==")),
+      BinaryOp::NotEq if !self.allows_loose(bin_expr) => Some(("!==", "!=")),
+      BinaryOp::EqEqEq if self.requires_loose(bin_expr) => Some(("==", "===")),
+      BinaryOp::NotEqEq if self.requires_loose(bin_expr) => Some(("!=", "!==")),
+      _ => None,
+    };
+    if let Some((expected, actual)) = operators {
+      self.context.add_diagnostic(
+        self.operator_span(bin_expr, actual),
+        "eqeqeq",
+        &format!("expected '{}' and instead saw '{}'.", expected, actual),
+      );
     }
-    swc_ecmascript::visit::visit_bin_expr(self, bin_expr, parent);
   }
 }
 
@@ -74,43 +224,92 @@ mod tests {
 
   #[test]
   fn eqeqeq_invalid() {
-    assert_lint_err::<Eqeqeq>("a == b", 0);
-    assert_lint_err::<Eqeqeq>("a != b", 0);
-    assert_lint_err::<Eqeqeq>("typeof a == 'number'", 0);
-    assert_lint_err::<Eqeqeq>("'string' != typeof a", 0);
-    assert_lint_err::<Eqeqeq>("true == true", 0);
-    assert_lint_err::<Eqeqeq>("2 == 3", 0);
-    assert_lint_err::<Eqeqeq>("'hello' != 'world'", 0);
-    assert_lint_err::<Eqeqeq>("a == null", 0);
-    assert_lint_err::<Eqeqeq>("null != a", 0);
-    assert_lint_err::<Eqeqeq>("true == null", 0);
-    assert_lint_err::<Eqeqeq>("true != null", 0);
-    assert_lint_err::<Eqeqeq>("null == null", 0);
-    assert_lint_err::<Eqeqeq>("null != null", 0);
+    assert_lint_err::<Eqeqeq>("a == b", 2);
+    assert_lint_err::<Eqeqeq>("a != b", 2);
+    assert_lint_err::<Eqeqeq>("typeof a == 'number'", 9);
+    assert_lint_err::<Eqeqeq>("'string' != typeof a", 9);
+    assert_lint_err::<Eqeqeq>("true == true", 5);
+    assert_lint_err::<Eqeqeq>("2 == 3", 2);
+    assert_lint_err::<Eqeqeq>("'hello' != 'world'", 8);
+    assert_lint_err::<Eqeqeq>("a == null", 2);
+    assert_lint_err::<Eqeqeq>("null != a", 5);
+    assert_lint_err::<Eqeqeq>("true == null", 5);
+    assert_lint_err::<Eqeqeq>("true != null", 5);
+    assert_lint_err::<Eqeqeq>("null == null", 5);
+    assert_lint_err::<Eqeqeq>("null != null", 5);
     assert_lint_err_on_line::<Eqeqeq>(
       r#"
 a
 ==
 b"#,
-      2,
+      3,
       0,
     );
-    assert_lint_err::<Eqeqeq>("(a) == b", 0);
-    assert_lint_err::<Eqeqeq>("(a) != b", 0);
-    assert_lint_err::<Eqeqeq>("a == (b)", 0);
-    assert_lint_err::<Eqeqeq>("a != (b)", 0);
-    assert_lint_err::<Eqeqeq>("(a) == (b)", 0);
-    assert_lint_err::<Eqeqeq>("(a) != (b)", 0);
-    assert_lint_err_n::<Eqeqeq>("(a == b) == (c)", vec![0, 1]);
-    assert_lint_err_n::<Eqeqeq>("(a != b) != (c)", vec![0, 1]);
-    assert_lint_err::<Eqeqeq>("(a == b) === (c)", 1);
-    assert_lint_err::<Eqeqeq>("(a == b) !== (c)", 1);
-    assert_lint_err::<Eqeqeq>("(a === b) == (c)", 0);
-    assert_lint_err::<Eqeqeq>("(a === b) != (c)", 0);
-    assert_lint_err::<Eqeqeq>("a == b;", 0);
-    assert_lint_err::<Eqeqeq>("a!=b;", 0);
-    assert_lint_err::<Eqeqeq>("(a + b) == c;", 0);
-    assert_lint_err::<Eqeqeq>("(a + b)  !=  c;", 0);
-    assert_lint_err::<Eqeqeq>("((1) )  ==  (2);", 0);
+    assert_lint_err::<Eqeqeq>("(a) == b", 4);
+    assert_lint_err::<Eqeqeq>("(a) != b", 4);
+    assert_lint_err::<Eqeqeq>("a == (b)", 2);
+    assert_lint_err::<Eqeqeq>("a != (b)", 2);
+    assert_lint_err::<Eqeqeq>("(a) == (b)", 4);
+    assert_lint_err::<Eqeqeq>("(a) != (b)", 4);
+    assert_lint_err_n::<Eqeqeq>("(a == b) == (c)", vec![3, 9]);
+    assert_lint_err_n::<Eqeqeq>("(a != b) != (c)", vec![3, 9]);
+    assert_lint_err::<Eqeqeq>("(a == b) === (c)", 3);
+    assert_lint_err::<Eqeqeq>("(a == b) !== (c)", 3);
+    assert_lint_err::<Eqeqeq>("(a === b) == (c)", 10);
+    assert_lint_err::<Eqeqeq>("(a === b) != (c)", 10);
+    assert_lint_err::<Eqeqeq>("a == b;", 2);
+    assert_lint_err::<Eqeqeq>("a!=b;", 1);
+    assert_lint_err::<Eqeqeq>("(a + b) == c;", 8);
+    assert_lint_err::<Eqeqeq>("(a + b)  !=  c;", 9);
+    assert_lint_err::<Eqeqeq>("((1) )  ==  (2);", 8);
+  }
+
+  #[test]
+  fn eqeqeq_operator_span() {
+    assert_lint_err_on_range::<Eqeqeq>(
+      "if (a /* == */ != b) {}",
+      (1, 15),
+      (1, 17),
+    );
+  }
+
+  #[test]
+  fn eqeqeq_null_ignore() {
+    let config = || serde_json::json!({ "null": "ignore" });
+    assert_lint_ok_with_config::<Eqeqeq>(
+      "if (a == null) {}\nif (null != b) {}\nif (a === null) {}",
+      config(),
+    );
+    assert_lint_err_with_config::<Eqeqeq>("if (a != b) {}", config(), 6);
+  }
+
+  #[test]
+  fn eqeqeq_null_never() {
+    let config = || serde_json::json!({ "null": "never" });
+    assert_lint_ok_with_config::<Eqeqeq>("a == null;\na === b;", config());
+    assert_lint_err_with_config::<Eqeqeq>("a === null", config(), 2);
+    assert_lint_err_with_config::<Eqeqeq>("null !== a", config(), 5);
+    assert_lint_err_with_config::<Eqeqeq>("a == b", config(), 2);
+  }
+
+  #[test]
+  fn eqeqeq_smart() {
+    let config = || serde_json::json!({ "mode": "smart" });
+    assert_lint_ok_with_config::<Eqeqeq>(
+      "typeof a == 'number';\n'a' != 'b';\na == null;\n(1) == 2;",
+      config(),
+    );
+    assert_lint_err_with_config::<Eqeqeq>("a == 'b'", config(), 2);
+    assert_lint_err_with_config::<Eqeqeq>("a != b", config(), 2);
+  }
+
+  #[test]
+  fn eqeqeq_invalid_config() {
+    assert!(Eqeqeq::new()
+      .new_with_config(serde_json::json!({ "mode": "never" }))
+      .is_err());
+    assert!(Eqeqeq::new()
+      .new_with_config(serde_json::json!({ "null": true }))
+      .is_err());
   }
 }
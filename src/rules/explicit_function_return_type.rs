@@ -1,9 +1,7 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
+use crate::handler::{walk, Handler, Traversal};
 
 use std::sync::Arc;
 
@@ -18,13 +16,15 @@ impl LintRule for ExplicitFunctionReturnType {
     "explicit-function-return-type"
   }
 
-  fn lint_module(
-    &self,
+  fn docs(&self) -> &'static str {
+    "Requires all functions to have an explicit return type."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = ExplicitFunctionReturnTypeVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(ExplicitFunctionReturnTypeVisitor::new(context)))
   }
 }
 
@@ -38,13 +38,11 @@ impl ExplicitFunctionReturnTypeVisitor {
   }
 }
 
-impl Visit for ExplicitFunctionReturnTypeVisitor {
-  noop_visit_type!();
-
+impl Handler for ExplicitFunctionReturnTypeVisitor {
   fn visit_function(
     &mut self,
     function: &swc_ecmascript::ast::Function,
-    _parent: &dyn Node,
+    traversal: &mut Traversal,
   ) {
     if function.return_type.is_none() {
       self.context.add_diagnostic(
@@ -53,8 +51,9 @@ impl Visit for ExplicitFunctionReturnTypeVisitor {
         "Missing return type on function",
       );
     }
-    for stmt in &function.body {
-      self.visit_block_stmt(stmt, _parent);
+    traversal.skip_children();
+    if let Some(body) = &function.body {
+      walk(self, body);
     }
   }
 }
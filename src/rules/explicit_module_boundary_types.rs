@@ -1,15 +1,13 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_common::Span;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 use swc_ecmascript::ast::{
-  ArrowExpr, Class, ClassMember, Decl, DefaultDecl, Expr, Function, Module,
-  ModuleDecl, Pat, TsKeywordTypeKind, TsType, TsTypeAnn, VarDecl,
+  ArrowExpr, Class, ClassMember, Decl, DefaultDecl, Expr, Function, ModuleDecl,
+  Pat, TsKeywordTypeKind, TsType, TsTypeAnn, VarDecl,
 };
 
 pub struct ExplicitModuleBoundaryTypes;
@@ -23,9 +21,15 @@ impl LintRule for ExplicitModuleBoundaryTypes {
     "explicit-module-boundary-types"
   }
 
-  fn lint_module(&self, context: Arc<Context>, module: &Module) {
-    let mut visitor = ExplicitModuleBoundaryTypesVisitor::new(context);
-    visitor.visit_module(module, module);
+  fn docs(&self) -> &'static str {
+    "Requires exported functions and class methods to have explicit argument and return types."
+  }
+
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(ExplicitModuleBoundaryTypesVisitor::new(context)))
   }
 }
 
@@ -115,14 +119,13 @@ impl ExplicitModuleBoundaryTypesVisitor {
   }
 }
 
-impl Visit for ExplicitModuleBoundaryTypesVisitor {
-  noop_visit_type!();
-
+impl Handler for ExplicitModuleBoundaryTypesVisitor {
   fn visit_module_decl(
     &mut self,
     module_decl: &ModuleDecl,
-    _parent: &dyn Node,
+    traversal: &mut Traversal,
   ) {
+    traversal.skip_children();
     match module_decl {
       ModuleDecl::ExportDecl(export) => match &export.decl {
         Decl::Class(decl) => self.check_class(&decl.class),
@@ -1,6 +1,7 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::Handler;
 use swc_ecmascript::ast::AssignExpr;
 use swc_ecmascript::ast::AssignOp;
 use swc_ecmascript::ast::BinaryOp;
@@ -11,10 +12,6 @@ use swc_ecmascript::ast::PatOrExpr;
 use swc_ecmascript::ast::UnaryOp;
 use swc_ecmascript::ast::UpdateExpr;
 use swc_ecmascript::ast::UpdateOp;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
-use swc_ecmascript::visit::VisitWith;
 
 use std::sync::Arc;
 
@@ -29,13 +26,19 @@ impl LintRule for ForDirection {
     "for-direction"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows `for` loops whose counter moves away from the stop condition."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = ForDirectionVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(ForDirectionVisitor::new(context)))
   }
 }
 
@@ -122,12 +125,8 @@ impl ForDirectionVisitor {
   }
 }
 
-impl Visit for ForDirectionVisitor {
-  noop_visit_type!();
-
-  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, _parent: &dyn Node) {
-    for_stmt.visit_children_with(self);
-
+impl Handler for ForDirectionVisitor {
+  fn exit_for_stmt(&mut self, for_stmt: &ForStmt) {
     if for_stmt.update.is_none() {
       return;
     }
@@ -1,14 +1,13 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
-use super::LintRule;
-use swc_ecmascript::ast::BlockStmt;
-use swc_ecmascript::ast::Class;
-use swc_ecmascript::ast::ClassMember;
-use swc_ecmascript::ast::Expr;
-use swc_ecmascript::ast::ExprOrSuper;
-use swc_ecmascript::ast::GetterProp;
-use swc_ecmascript::ast::MethodKind;
-use swc_ecmascript::ast::Stmt;
+use super::{LintRule, RuleConfigError};
+use crate::handler::{Handler, Traversal};
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  ArrowExpr, BlockStmt, BlockStmtOrExpr, CallExpr, ClassMethod, Expr,
+  ExprOrSuper, Function, GetterProp, MethodKind, ObjectLit, PrivateMethod,
+  Prop, PropName, PropOrSpread, ReturnStmt,
+};
 use swc_ecmascript::visit::noop_visit_type;
 use swc_ecmascript::visit::Node;
 use swc_ecmascript::visit::Visit;
@@ -16,137 +15,211 @@ use swc_ecmascript::visit::VisitWith;
 
 use std::sync::Arc;
 
-pub struct GetterReturn;
+pub struct GetterReturn {
+  /// Whether a bare `return;` is allowed.
+  allow_implicit: bool,
+}
 
 impl LintRule for GetterReturn {
   fn new() -> Box<Self> {
-    Box::new(GetterReturn)
+    Box::new(GetterReturn {
+      allow_implicit: false,
+    })
+  }
+
+  fn new_with_config(
+    &self,
+    config: serde_json::Value,
+  ) -> Result<Box<dyn LintRule>, RuleConfigError> {
+    let allow_implicit = match config.get("allowImplicit") {
+      None => false,
+      Some(serde_json::Value::Bool(allow_implicit)) => *allow_implicit,
+      Some(value) => {
+        return Err(RuleConfigError {
+          code: "getter-return".to_string(),
+          message: format!(
+            "expected allowImplicit to be a boolean, got {}",
+            value
+          ),
+        })
+      }
+    };
+    Ok(Box::new(GetterReturn { allow_implicit }))
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(serde_json::json!({
+      "type": "object",
+      "properties": {
+        "allowImplicit": { "type": "boolean" }
+      },
+      "additionalProperties": false
+    }))
   }
 
   fn code(&self) -> &'static str {
     "getter-return"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires getters to return a value.
+
+Getters in classes and object literals, and `get` functions in descriptors
+passed to `Object.defineProperty`, `Reflect.defineProperty` and
+`Object.defineProperties` must not be able to finish without returning.
+A bare `return;` is reported too, unless the `allowImplicit` option is set
+to `true`."#
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = GetterReturnVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(GetterReturnVisitor::new(
+      context,
+      self.allow_implicit,
+    )))
   }
 }
 
 struct GetterReturnVisitor {
   context: Arc<Context>,
+  allow_implicit: bool,
 }
 
 impl GetterReturnVisitor {
-  fn new(context: Arc<Context>) -> Self {
-    Self { context }
-  }
-
-  fn return_has_arg(
-    &self,
-    return_stmt: &swc_ecmascript::ast::ReturnStmt,
-  ) -> bool {
-    return_stmt.arg.is_some()
+  fn new(context: Arc<Context>, allow_implicit: bool) -> Self {
+    Self {
+      context,
+      allow_implicit,
+    }
   }
 
-  fn stmts_have_return(&self, stmts: &[Stmt]) -> bool {
-    for stmt in stmts {
-      if let Stmt::Return(return_stmt) = stmt {
-        return self.return_has_arg(return_stmt);
-      }
+  fn check_block_stmt(&self, block_stmt: &BlockStmt, span: Span) {
+    let returns_nothing = || {
+      let mut finder = BareReturnFinder { found: false };
+      block_stmt.visit_children_with(&mut finder);
+      finder.found
+    };
+    if self
+      .context
+      .control_flow
+      .is_end_reachable(&block_stmt.stmts)
+      || (!self.allow_implicit && returns_nothing())
+    {
+      self.context.add_diagnostic(
+        span,
+        "getter-return",
+        "Getter requires a return",
+      );
     }
-    false
   }
 
-  fn check_if_stmt(&self, if_stmt: &swc_ecmascript::ast::IfStmt) -> bool {
-    if if_stmt.alt.is_none() {
-      return false;
-    }
-    if let Stmt::Block(if_block) = &*if_stmt.cons {
-      if !self.stmts_have_return(&if_block.stmts) {
-        return false;
-      }
-      let mut alt_stmt = &if_stmt.alt;
-      loop {
-        if let Some(if_alt_block) = alt_stmt {
-          if let Stmt::If(else_if) = &**if_alt_block {
-            if else_if.alt.is_none() {
-              return false;
-            } else {
-              alt_stmt = &else_if.alt;
-            }
-            if let Stmt::Block(else_if_block) = &*else_if.cons {
-              if !self.stmts_have_return(&else_if_block.stmts) {
-                return false;
+  fn check_descriptor(&self, descriptor: &Expr) {
+    let obj_lit = match descriptor {
+      Expr::Object(obj_lit) => obj_lit,
+      _ => return,
+    };
+    for prop in &obj_lit.props {
+      let prop = match prop {
+        PropOrSpread::Prop(prop) => prop,
+        PropOrSpread::Spread(_) => continue,
+      };
+      match &**prop {
+        Prop::KeyValue(kv_prop) => {
+          if let Some(span) = get_key_span(&kv_prop.key) {
+            match &*kv_prop.value {
+              Expr::Fn(fn_expr) => {
+                if let Some(body) = &fn_expr.function.body {
+                  self.check_block_stmt(body, span);
+                }
               }
+              Expr::Arrow(ArrowExpr {
+                body: BlockStmtOrExpr::BlockStmt(body),
+                ..
+              }) => self.check_block_stmt(body, span),
+              _ => {}
             }
-          } else if let Stmt::Block(else_block) = &**if_alt_block {
-            if self.stmts_have_return(&else_block.stmts) {
-              break;
-            } else {
-              return false;
+          }
+        }
+        Prop::Method(method_prop) => {
+          if let Some(span) = get_key_span(&method_prop.key) {
+            if let Some(body) = &method_prop.function.body {
+              self.check_block_stmt(body, span);
             }
           }
         }
+        _ => {}
       }
     }
-    true
   }
+}
 
-  fn check_switch_stmt(
-    &self,
-    switch_stmt: &swc_ecmascript::ast::SwitchStmt,
-  ) -> bool {
-    for case in &switch_stmt.cases {
-      if !case.cons.is_empty() && !self.stmts_have_return(&case.cons) {
-        return false;
-      }
-    }
-    true
+/// Span of the key if it's `get`.
+fn get_key_span(key: &PropName) -> Option<Span> {
+  match key {
+    PropName::Ident(ident) if ident.sym == *"get" => Some(ident.span),
+    PropName::Str(str_) if str_.value == *"get" => Some(str_.span),
+    _ => None,
   }
+}
 
-  fn check_block_stmt(&self, block_stmt: &BlockStmt, span: swc_common::Span) {
-    if !block_stmt.stmts.iter().any(|s| match s {
-      Stmt::If(if_stmt) => self.check_if_stmt(if_stmt),
-      Stmt::Switch(switch_stmt) => self.check_switch_stmt(switch_stmt),
-      Stmt::Return(return_stmt) => self.return_has_arg(return_stmt),
-      _ => false,
-    }) {
-      self.context.add_diagnostic(
-        span,
-        "getter-return",
-        "Getter requires a return",
-      );
-    }
+fn unwrap_callee(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(paren) => unwrap_callee(&paren.expr),
+    Expr::OptChain(opt_chain) => unwrap_callee(&opt_chain.expr),
+    _ => expr,
   }
 }
 
-impl Visit for GetterReturnVisitor {
-  noop_visit_type!();
+/// Returns `(object, method)` names if the callee is like `Object.create`.
+fn callee_names(call_expr: &CallExpr) -> Option<(&str, &str)> {
+  let callee = match &call_expr.callee {
+    ExprOrSuper::Expr(callee) => unwrap_callee(callee),
+    ExprOrSuper::Super(_) => return None,
+  };
+  let member = match callee {
+    Expr::Member(member) if !member.computed => member,
+    _ => return None,
+  };
+  let obj = match &member.obj {
+    ExprOrSuper::Expr(obj) => match unwrap_callee(obj) {
+      Expr::Ident(ident) => &*ident.sym,
+      _ => return None,
+    },
+    ExprOrSuper::Super(_) => return None,
+  };
+  match &*member.prop {
+    Expr::Ident(ident) => Some((obj, &*ident.sym)),
+    _ => None,
+  }
+}
 
-  fn visit_class(&mut self, class: &Class, _parent: &dyn Node) {
-    class.visit_children_with(self);
-    for member in &class.body {
-      match member {
-        ClassMember::Method(class_method) => {
-          if class_method.kind == MethodKind::Getter {
-            if let Some(block_stmt) = &class_method.function.body {
-              self.check_block_stmt(block_stmt, class_method.span);
-            }
-          }
-        }
-        ClassMember::PrivateMethod(private_method) => {
-          if private_method.kind == MethodKind::Getter {
-            if let Some(block_stmt) = &private_method.function.body {
-              self.check_block_stmt(block_stmt, private_method.span);
-            }
-          }
-        }
-        _ => {}
+impl Handler for GetterReturnVisitor {
+  fn visit_class_method(
+    &mut self,
+    class_method: &ClassMethod,
+    _traversal: &mut Traversal,
+  ) {
+    if class_method.kind == MethodKind::Getter {
+      if let Some(block_stmt) = &class_method.function.body {
+        self.check_block_stmt(block_stmt, class_method.span);
+      }
+    }
+  }
+
+  fn visit_private_method(
+    &mut self,
+    private_method: &PrivateMethod,
+    _traversal: &mut Traversal,
+  ) {
+    if private_method.kind == MethodKind::Getter {
+      if let Some(block_stmt) = &private_method.function.body {
+        self.check_block_stmt(block_stmt, private_method.span);
       }
     }
   }
@@ -154,9 +227,8 @@ impl Visit for GetterReturnVisitor {
   fn visit_getter_prop(
     &mut self,
     getter_prop: &GetterProp,
-    _parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
-    getter_prop.visit_children_with(self);
     if let Some(block_stmt) = &getter_prop.body {
       self.check_block_stmt(block_stmt, getter_prop.span);
     }
@@ -164,66 +236,53 @@ impl Visit for GetterReturnVisitor {
 
   fn visit_call_expr(
     &mut self,
-    call_expr: &swc_ecmascript::ast::CallExpr,
-    _parent: &dyn Node,
+    call_expr: &CallExpr,
+    _traversal: &mut Traversal,
   ) {
-    call_expr.visit_children_with(self);
-    if call_expr.args.len() != 3 {
-      return;
-    }
-    if let ExprOrSuper::Expr(callee_expr) = &call_expr.callee {
-      if let Expr::Member(member) = &**callee_expr {
-        if let ExprOrSuper::Expr(member_obj) = &member.obj {
-          if let Expr::Ident(ident) = &**member_obj {
-            if ident.sym != *"Object" {
-              return;
-            }
-          }
-        }
-        if let Expr::Ident(ident) = &*member.prop {
-          if ident.sym != *"defineProperty" {
-            return;
-          }
+    match callee_names(call_expr) {
+      Some(("Object", "defineProperty"))
+      | Some(("Reflect", "defineProperty")) => {
+        if let Some(descriptor) = call_expr.args.get(2) {
+          self.check_descriptor(&descriptor.expr);
         }
       }
-    }
-    if let Expr::Object(obj_expr) = &*call_expr.args[2].expr {
-      for prop in obj_expr.props.iter() {
-        if let swc_ecmascript::ast::PropOrSpread::Prop(prop_expr) = prop {
-          if let swc_ecmascript::ast::Prop::KeyValue(kv_prop) = &**prop_expr {
-            if let swc_ecmascript::ast::PropName::Ident(ident) = &kv_prop.key {
-              if ident.sym != *"get" {
-                return;
-              }
-              if let Expr::Fn(fn_expr) = &*kv_prop.value {
-                if let Some(body) = &fn_expr.function.body {
-                  self.check_block_stmt(&body, ident.span);
+      Some(("Object", "defineProperties")) => {
+        if let Some(descriptors) = call_expr.args.get(1) {
+          if let Expr::Object(ObjectLit { props, .. }) = &*descriptors.expr {
+            for prop in props {
+              if let PropOrSpread::Prop(prop) = prop {
+                if let Prop::KeyValue(kv_prop) = &**prop {
+                  self.check_descriptor(&kv_prop.value);
                 }
-              } else if let Expr::Arrow(arrow_expr) = &*kv_prop.value {
-                if let swc_ecmascript::ast::BlockStmtOrExpr::BlockStmt(
-                  block_stmt,
-                ) = &arrow_expr.body
-                {
-                  self.check_block_stmt(&block_stmt, ident.span);
-                }
-              }
-            }
-          } else if let swc_ecmascript::ast::Prop::Method(method_prop) =
-            &**prop_expr
-          {
-            if let swc_ecmascript::ast::PropName::Ident(ident) =
-              &method_prop.key
-            {
-              if ident.sym != *"get" {
-                return;
-              }
-              if let Some(body) = &method_prop.function.body {
-                self.check_block_stmt(&body, ident.span);
               }
             }
           }
         }
       }
+      _ => {}
+    }
+  }
+}
+
+/// Finds a `return;` outside of nested functions.
+struct BareReturnFinder {
+  found: bool,
+}
+
+impl Visit for BareReturnFinder {
+  noop_visit_type!();
+
+  fn visit_function(&mut self, _function: &Function, _parent: &dyn Node) {}
+
+  fn visit_arrow_expr(&mut self, _arrow_expr: &ArrowExpr, _parent: &dyn Node) {}
+
+  fn visit_return_stmt(
+    &mut self,
+    return_stmt: &ReturnStmt,
+    _parent: &dyn Node,
+  ) {
+    if return_stmt.arg.is_none() {
+      self.found = true;
     }
   }
 }
@@ -422,4 +481,73 @@ Object.defineProperty(foo, 'bar', {
       vec![10, 27],
     );
   }
+
+  #[test]
+  fn getter_return_control_flow() {
+    assert_lint_ok_n::<GetterReturn>(vec![
+      "class Foo { get bar() { if (a) { return 1; } return 2; } }",
+      "class Foo { get bar() { if (a) return 1; else return 2; } }",
+      "class Foo { get bar() { switch (a) { case 1: return 1; default: return 2; } } }",
+      "class Foo { get bar() { try { return a(); } catch (e) { return 1; } } }",
+      "class Foo { get bar() { throw new Error(); } }",
+      "class Foo { get bar() { while (true) {} } }",
+      "class Foo { get bar() { for (const a of b) { return a; } return 1; } }",
+      "Reflect.defineProperty(foo, 'bar', { get() { return 1; } });",
+      "Object.defineProperty(foo, 'bar', { value: 1, get() { return 1; } });",
+      "foo(a, b, { get() {} });",
+    ]);
+    assert_lint_err::<GetterReturn>(
+      "class Foo { get bar() { if (a) { return 1; } else if (b) { return 2; } } }",
+      12,
+    );
+    assert_lint_err::<GetterReturn>(
+      "class Foo { get bar() { for (const a of b) { return a; } } }",
+      12,
+    );
+    assert_lint_err::<GetterReturn>(
+      "class Foo { get bar() { switch (a) { case 1: return 1; } } }",
+      12,
+    );
+    assert_lint_err::<GetterReturn>(
+      "class Foo { get bar() { const f = () => { return 1; }; } }",
+      12,
+    );
+    assert_lint_err::<GetterReturn>(
+      "class Foo { get bar() { if (a) { return; } return 1; } }",
+      12,
+    );
+    assert_lint_err::<GetterReturn>(
+      "Reflect.defineProperty(foo, 'bar', { get() {} });",
+      37,
+    );
+    assert_lint_err::<GetterReturn>(
+      "Object.defineProperty(foo, 'bar', { value: 1, get() {} });",
+      46,
+    );
+    assert_lint_err::<GetterReturn>(
+      "Object.defineProperties(foo, { bar: { get() {} } });",
+      38,
+    );
+  }
+
+  #[test]
+  fn getter_return_allow_implicit() {
+    let config = serde_json::json!({ "allowImplicit": true });
+    assert_lint_ok_with_config::<GetterReturn>(
+      "class Foo { get bar() { return; } }",
+      config.clone(),
+    );
+    assert_lint_ok_with_config::<GetterReturn>(
+      "const foo = { get bar() { if (a) { return; } return 1; } };",
+      config.clone(),
+    );
+    assert_lint_err_with_config::<GetterReturn>(
+      "class Foo { get bar() { if (a) { return; } } }",
+      config,
+      12,
+    );
+    assert!(GetterReturn::new()
+      .new_with_config(serde_json::json!({ "allowImplicit": 1 }))
+      .is_err());
+  }
 }
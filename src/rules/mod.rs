@@ -1,5 +1,10 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use crate::config::BUILTIN_CODES;
+use crate::diagnostic::Severity;
+use crate::handler::{walk, Handler};
 use crate::linter::Context;
+use std::error::Error;
+use std::fmt;
 use std::sync::Arc;
 
 pub mod adjacent_overload_signatures;
@@ -34,6 +39,7 @@ pub mod no_dupe_keys;
 pub mod no_duplicate_case;
 pub mod no_empty;
 pub mod no_empty_character_class;
+pub mod no_empty_function;
 pub mod no_empty_interface;
 pub mod no_empty_pattern;
 pub mod no_eval;
@@ -62,6 +68,7 @@ pub mod no_prototype_builtins;
 pub mod no_redeclare;
 pub mod no_regex_spaces;
 pub mod no_self_assign;
+pub mod no_self_compare;
 pub mod no_setter_return;
 pub mod no_shadow_restricted_names;
 pub mod no_sparse_arrays;
@@ -89,86 +96,91 @@ pub trait LintRule {
   fn new() -> Box<Self>
   where
     Self: Sized;
+  /// Creates a new instance of the rule from per-rule options. Fails for
+  /// rules that don't accept any options.
+  fn new_with_config(
+    &self,
+    _config: serde_json::Value,
+  ) -> Result<Box<dyn LintRule>, RuleConfigError> {
+    Err(RuleConfigError {
+      code: self.code().to_string(),
+      message: "rule takes no options".to_string(),
+    })
+  }
+  /// Callbacks run by the linter during the traversal of the module shared
+  /// by all rules, reporting diagnostics through `context`.
+  fn handler<'a>(
+    &'a self,
+    _context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    None
+  }
+  /// Lints `module`, reporting diagnostics through `context`. Both are
+  /// shared by all rules run on the file. Rules that don't provide a
+  /// `handler` walk the module themselves by overriding this.
   fn lint_module(
     &self,
     context: Arc<Context>,
     module: &swc_ecmascript::ast::Module,
-  );
+  ) {
+    if let Some(mut handler) = self.handler(context) {
+      walk(handler.as_mut(), module);
+    }
+  }
   fn code(&self) -> &'static str;
+  /// Severity of diagnostics reported by this rule.
+  fn severity(&self) -> Severity {
+    Severity::Error
+  }
+  /// Tags of the rule, e.g. `"recommended"`.
+  fn tags(&self) -> &[&'static str] {
+    &[]
+  }
   fn docs(&self) -> &'static str {
     ""
   }
+  /// Code samples shown in the generated documentation of the rule.
+  fn examples(&self) -> RuleExamples {
+    RuleExamples::default()
+  }
+  /// JSON schema of the options accepted by `new_with_config`, if any.
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    None
+  }
+}
+
+/// Code samples of a rule, see `LintRule::examples`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RuleExamples {
+  /// Samples the rule doesn't report.
+  pub valid: &'static [&'static str],
+  /// Samples the rule reports.
+  pub invalid: &'static [&'static str],
+}
+
+#[derive(Clone, Debug)]
+pub struct RuleConfigError {
+  pub code: String,
+  pub message: String,
+}
+
+impl Error for RuleConfigError {}
+
+impl fmt::Display for RuleConfigError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "Invalid config for rule \"{}\": {}",
+      self.code, self.message
+    )
+  }
 }
 
 pub fn get_recommended_rules() -> Vec<Box<dyn LintRule>> {
-  vec![
-    adjacent_overload_signatures::AdjacentOverloadSignatures::new(),
-    ban_ts_comment::BanTsComment::new(),
-    ban_types::BanTypes::new(),
-    ban_untagged_ignore::BanUntaggedIgnore::new(),
-    constructor_super::ConstructorSuper::new(),
-    for_direction::ForDirection::new(),
-    getter_return::GetterReturn::new(),
-    no_array_constructor::NoArrayConstructor::new(),
-    no_async_promise_executor::NoAsyncPromiseExecutor::new(),
-    no_case_declarations::NoCaseDeclarations::new(),
-    no_class_assign::NoClassAssign::new(),
-    no_compare_neg_zero::NoCompareNegZero::new(),
-    no_cond_assign::NoCondAssign::new(),
-    no_constant_condition::NoConstantCondition::new(),
-    no_control_regex::NoControlRegex::new(),
-    no_debugger::NoDebugger::new(),
-    no_delete_var::NoDeleteVar::new(),
-    no_dupe_args::NoDupeArgs::new(),
-    no_dupe_class_members::NoDupeClassMembers::new(),
-    no_dupe_else_if::NoDupeElseIf::new(),
-    no_dupe_keys::NoDupeKeys::new(),
-    no_duplicate_case::NoDuplicateCase::new(),
-    no_empty::NoEmpty::new(),
-    no_empty_character_class::NoEmptyCharacterClass::new(),
-    no_empty_interface::NoEmptyInterface::new(),
-    no_empty_pattern::NoEmptyPattern::new(),
-    no_ex_assign::NoExAssign::new(),
-    no_explicit_any::NoExplicitAny::new(),
-    no_extra_boolean_cast::NoExtraBooleanCast::new(),
-    no_extra_non_null_assertion::NoExtraNonNullAssertion::new(),
-    no_extra_semi::NoExtraSemi::new(),
-    no_fallthrough::NoFallthrough::new(),
-    no_func_assign::NoFuncAssign::new(),
-    no_global_assign::NoGlobalAssign::new(),
-    no_import_assign::NoImportAssign::new(),
-    no_inferrable_types::NoInferrableTypes::new(),
-    no_inner_declarations::NoInnerDeclarations::new(),
-    no_invalid_regexp::NoInvalidRegexp::new(),
-    no_irregular_whitespace::NoIrregularWhitespace::new(),
-    no_misused_new::NoMisusedNew::new(),
-    no_mixed_spaces_and_tabs::NoMixedSpacesAndTabs::new(),
-    no_namespace::NoNamespace::new(),
-    no_new_symbol::NoNewSymbol::new(),
-    no_obj_calls::NoObjCalls::new(),
-    no_octal::NoOctal::new(),
-    no_prototype_builtins::NoPrototypeBuiltins::new(),
-    no_redeclare::NoRedeclare::new(),
-    no_regex_spaces::NoRegexSpaces::new(),
-    no_self_assign::NoSelfAssign::new(),
-    no_setter_return::NoSetterReturn::new(),
-    no_shadow_restricted_names::NoShadowRestrictedNames::new(),
-    no_this_alias::NoThisAlias::new(),
-    no_this_before_super::NoThisBeforeSuper::new(),
-    no_undef::NoUndef::new(),
-    no_unreachable::NoUnreachable::new(),
-    no_unsafe_finally::NoUnsafeFinally::new(),
-    no_unsafe_negation::NoUnsafeNegation::new(),
-    no_unused_labels::NoUnusedLabels::new(),
-    no_with::NoWith::new(),
-    prefer_as_const::PreferAsConst::new(),
-    prefer_const::PreferConst::new(),
-    prefer_namespace_keyword::PreferNamespaceKeyword::new(),
-    require_yield::RequireYield::new(),
-    triple_slash_reference::TripleSlashReference::new(),
-    use_isnan::UseIsNaN::new(),
-    valid_typeof::ValidTypeof::new(),
-  ]
+  get_all_rules()
+    .into_iter()
+    .filter(|rule| rule.tags().contains(&"recommended"))
+    .collect()
 }
 
 pub fn get_all_rules() -> Vec<Box<dyn LintRule>> {
@@ -205,6 +217,7 @@ pub fn get_all_rules() -> Vec<Box<dyn LintRule>> {
     no_duplicate_case::NoDuplicateCase::new(),
     no_empty::NoEmpty::new(),
     no_empty_character_class::NoEmptyCharacterClass::new(),
+    no_empty_function::NoEmptyFunction::new(),
     no_empty_interface::NoEmptyInterface::new(),
     no_empty_pattern::NoEmptyPattern::new(),
     no_eval::NoEval::new(),
@@ -233,6 +246,7 @@ pub fn get_all_rules() -> Vec<Box<dyn LintRule>> {
     no_redeclare::NoRedeclare::new(),
     no_regex_spaces::NoRegexSpaces::new(),
     no_self_assign::NoSelfAssign::new(),
+    no_self_compare::NoSelfCompare::new(),
     no_setter_return::NoSetterReturn::new(),
     no_shadow_restricted_names::NoShadowRestrictedNames::new(),
     no_sparse_arrays::NoSparseArrays::new(),
@@ -258,9 +272,203 @@ pub fn get_all_rules() -> Vec<Box<dyn LintRule>> {
   ]
 }
 
+/// Creates the rule with given code, or returns `None` if there's no rule
+/// with such code.
+pub fn get_rule_by_code(code: &str) -> Option<Box<dyn LintRule>> {
+  get_all_rules().into_iter().find(|rule| rule.code() == code)
+}
+
+/// Camel case codes used by earlier releases, mapped to current codes.
+const DEPRECATED_CODES: &[(&str, &str)] = &[
+  ("adjacentOverloadSignatures", "adjacent-overload-signatures"),
+  ("banTsComment", "ban-ts-comment"),
+  ("banTsIgnore", "ban-ts-ignore"),
+  ("banTypes", "ban-types"),
+  ("banUntaggedIgnore", "ban-untagged-ignore"),
+  ("banUntaggedTodo", "ban-untagged-todo"),
+  ("constructorSuper", "constructor-super"),
+  ("defaultParamLast", "default-param-last"),
+  (
+    "explicitFunctionReturnType",
+    "explicit-function-return-type",
+  ),
+  (
+    "explicitModuleBoundaryTypes",
+    "explicit-module-boundary-types",
+  ),
+  ("forDirection", "for-direction"),
+  ("getterReturn", "getter-return"),
+  ("noArrayConstructor", "no-array-constructor"),
+  ("noAsyncPromiseExecutor", "no-async-promise-executor"),
+  ("noAwaitInLoop", "no-await-in-loop"),
+  ("noCaseDeclarations", "no-case-declarations"),
+  ("noClassAssign", "no-class-assign"),
+  ("noCompareNegZero", "no-compare-neg-zero"),
+  ("noCondAssign", "no-cond-assign"),
+  ("noConstAssign", "no-const-assign"),
+  ("noConstantCondition", "no-constant-condition"),
+  ("noControlRegex", "no-control-regex"),
+  ("noDebugger", "no-debugger"),
+  ("noDeleteVar", "no-delete-var"),
+  ("noDupeArgs", "no-dupe-args"),
+  ("noDupeClassMembers", "no-dupe-class-members"),
+  ("noDupeElseIf", "no-dupe-else-if"),
+  ("noDupeKeys", "no-dupe-keys"),
+  ("noDuplicateCase", "no-duplicate-case"),
+  ("noEmpty", "no-empty"),
+  ("noEmptyCharacterClass", "no-empty-character-class"),
+  ("noEmptyInterface", "no-empty-interface"),
+  ("noEmptyPattern", "no-empty-pattern"),
+  ("noEval", "no-eval"),
+  ("noExAssign", "no-ex-assign"),
+  ("noExplicitAny", "no-explicit-any"),
+  ("noExtraBooleanCast", "no-extra-boolean-cast"),
+  ("noExtraNonNullAssertion", "no-extra-non-null-assertion"),
+  ("noExtraSemi", "no-extra-semi"),
+  ("noFallthrough", "no-fallthrough"),
+  ("noFuncAssign", "no-func-assign"),
+  ("noGlobalAssign", "no-global-assign"),
+  ("noImportAssign", "no-import-assign"),
+  ("noInferrableTypes", "no-inferrable-types"),
+  ("noInnerDeclarations", "no-inner-declarations"),
+  ("noInvalidRegexp", "no-invalid-regexp"),
+  ("noIrregularWhitespace", "no-irregular-whitespace"),
+  ("noMisusedNew", "no-misused-new"),
+  ("noMixedSpacesAndTabs", "no-mixed-spaces-and-tabs"),
+  ("noNamespace", "no-namespace"),
+  ("noNewSymbol", "no-new-symbol"),
+  (
+    "noNonNullAssertedOptionalChain",
+    "no-non-null-asserted-optional-chain",
+  ),
+  ("noNonNullAssertion", "no-non-null-assertion"),
+  ("noObjCalls", "no-obj-calls"),
+  ("noOctal", "no-octal"),
+  ("noPrototypeBuiltins", "no-prototype-builtins"),
+  ("noRedeclare", "no-redeclare"),
+  ("noRegexSpaces", "no-regex-spaces"),
+  ("noSelfAssign", "no-self-assign"),
+  ("noSetterReturn", "no-setter-return"),
+  ("noShadowRestrictedNames", "no-shadow-restricted-names"),
+  ("noSparseArrays", "no-sparse-arrays"),
+  ("noThisAlias", "no-this-alias"),
+  ("noThisBeforeSuper", "no-this-before-super"),
+  ("noThrowLiteral", "no-throw-literal"),
+  ("noUndef", "no-undef"),
+  ("noUnreachable", "no-unreachable"),
+  ("noUnsafeFinally", "no-unsafe-finally"),
+  ("noUnsafeNegation", "no-unsafe-negation"),
+  ("noUnusedLabels", "no-unused-labels"),
+  ("noUnusedVars", "no-unused-vars"),
+  ("noVar", "no-var"),
+  ("noWith", "no-with"),
+  ("preferAsConst", "prefer-as-const"),
+  ("preferConst", "prefer-const"),
+  ("preferNamespaceKeyword", "prefer-namespace-keyword"),
+  ("requireYield", "require-yield"),
+  ("singleVarDeclarator", "single-var-declarator"),
+  ("tripleSlashReference", "triple-slash-reference"),
+  ("useIsNaN", "use-isnan"),
+  ("validTypeof", "valid-typeof"),
+];
+
+/// Returns the code of the rule previously known under the camelCase
+/// `code`, e.g. `"no-cond-assign"` for `"noCondAssign"`.
+pub fn resolve_deprecated_code(code: &str) -> Option<&'static str> {
+  DEPRECATED_CODES
+    .iter()
+    .find(|(old_code, _)| *old_code == code)
+    .map(|(_, new_code)| *new_code)
+}
+
+/// Creates the rule with given code, passing it per-rule options unless
+/// they're `null`. Returns `None` if there's no rule with such code.
+pub fn get_rule_with_config(
+  code: &str,
+  config: serde_json::Value,
+) -> Option<Result<Box<dyn LintRule>, RuleConfigError>> {
+  let rule = get_rule_by_code(code)?;
+  if config.is_null() {
+    return Some(Ok(rule));
+  }
+  Some(rule.new_with_config(config))
+}
+
+/// Error returned when registering a rule whose code is already taken.
+#[derive(Clone, Debug)]
+pub struct DuplicateRuleError {
+  pub code: String,
+}
+
+impl Error for DuplicateRuleError {}
+
+impl fmt::Display for DuplicateRuleError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "A rule with code \"{}\" is already registered",
+      self.code
+    )
+  }
+}
+
+/// A set of rules with unique codes, combining built-in rules with rules
+/// defined outside of this crate.
+#[derive(Default)]
+pub struct RuleRegistry {
+  rules: Vec<Box<dyn LintRule>>,
+}
+
+impl RuleRegistry {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Creates a registry containing all built-in rules.
+  pub fn with_builtin_rules() -> Self {
+    Self {
+      rules: get_all_rules(),
+    }
+  }
+
+  /// Adds a rule. Fails if its code is used by a registered rule or by a
+  /// diagnostic the linter reports itself, such as `ban-unused-ignore`.
+  pub fn register(
+    &mut self,
+    rule: Box<dyn LintRule>,
+  ) -> Result<(), DuplicateRuleError> {
+    let code = rule.code();
+    if self.get(code).is_some() || BUILTIN_CODES.contains(&code) {
+      return Err(DuplicateRuleError {
+        code: code.to_string(),
+      });
+    }
+    self.rules.push(rule);
+    Ok(())
+  }
+
+  pub fn get(&self, code: &str) -> Option<&dyn LintRule> {
+    self
+      .rules
+      .iter()
+      .find(|rule| rule.code() == code)
+      .map(|rule| rule.as_ref())
+  }
+
+  pub fn codes(&self) -> Vec<&'static str> {
+    self.rules.iter().map(|rule| rule.code()).collect()
+  }
+
+  pub fn into_rules(self) -> Vec<Box<dyn LintRule>> {
+    self.rules
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::test_util::lint_with_rule;
+  use std::collections::HashSet;
 
   #[test]
   fn recommended_rules_sorted_alphabetically() {
@@ -281,4 +489,163 @@ mod tests {
       assert_eq!(sorted.code(), unsorted.code());
     }
   }
+
+  #[test]
+  fn all_rules_have_unique_codes() {
+    let mut codes = HashSet::new();
+    for rule in get_all_rules() {
+      assert!(!rule.code().is_empty());
+      assert!(
+        rule
+          .code()
+          .chars()
+          .all(|c| c.is_ascii_lowercase() || c == '-'),
+        "{} is not kebab-case",
+        rule.code()
+      );
+      assert!(codes.insert(rule.code()), "duplicate code {}", rule.code());
+    }
+  }
+
+  #[test]
+  fn all_rules_have_docs() {
+    for rule in get_all_rules() {
+      assert!(!rule.docs().is_empty(), "{} has no docs", rule.code());
+    }
+  }
+
+  #[test]
+  fn examples_match_rule() {
+    for code in RuleRegistry::with_builtin_rules().codes() {
+      let examples = get_rule_by_code(code).unwrap().examples();
+      for source in examples.valid {
+        let rule = get_rule_by_code(code).unwrap();
+        assert!(
+          lint_with_rule(rule, source).is_empty(),
+          "{} reports valid example:\n{}",
+          code,
+          source
+        );
+      }
+      for source in examples.invalid {
+        let rule = get_rule_by_code(code).unwrap();
+        assert!(
+          !lint_with_rule(rule, source).is_empty(),
+          "{} doesn't report invalid example:\n{}",
+          code,
+          source
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn recommended_rules_are_tagged() {
+    let recommended_rules = get_recommended_rules();
+    assert!(!recommended_rules.is_empty());
+    for rule in recommended_rules {
+      assert!(rule.tags().contains(&"recommended"));
+    }
+  }
+
+  #[test]
+  fn rule_with_config() {
+    use crate::test_util::lint_with_rule;
+    use serde_json::json;
+
+    let source = "if ((a = b)) {}";
+    let except_parens =
+      get_rule_with_config("no-cond-assign", json!("except-parens"))
+        .unwrap()
+        .unwrap();
+    assert!(lint_with_rule(except_parens, source).is_empty());
+    let always = get_rule_with_config("no-cond-assign", json!("always"))
+      .unwrap()
+      .unwrap();
+    assert_eq!(lint_with_rule(always, source).len(), 1);
+
+    let err = get_rule_with_config("no-cond-assign", json!(42))
+      .unwrap()
+      .err()
+      .unwrap();
+    assert_eq!(err.code, "no-cond-assign");
+
+    assert!(get_rule_with_config("no-debugger", json!(null))
+      .unwrap()
+      .is_ok());
+    let err = get_rule_with_config("no-debugger", json!({ "a": 1 }))
+      .unwrap()
+      .err()
+      .unwrap();
+    assert_eq!(
+      err.to_string(),
+      "Invalid config for rule \"no-debugger\": rule takes no options"
+    );
+    assert!(get_rule_with_config("no-such-rule", json!(null)).is_none());
+  }
+
+  struct Custom(&'static str);
+
+  impl LintRule for Custom {
+    fn new() -> Box<Self> {
+      Box::new(Custom("custom"))
+    }
+
+    fn code(&self) -> &'static str {
+      self.0
+    }
+
+    fn lint_module(
+      &self,
+      _context: Arc<Context>,
+      _module: &swc_ecmascript::ast::Module,
+    ) {
+    }
+  }
+
+  #[test]
+  fn registry_rejects_duplicate_codes() {
+    let mut registry = RuleRegistry::with_builtin_rules();
+    let builtin_count = registry.codes().len();
+    registry.register(Custom::new()).unwrap();
+    assert_eq!(registry.codes().len(), builtin_count + 1);
+    assert_eq!(registry.get("custom").unwrap().code(), "custom");
+
+    for code in &["custom", "no-debugger", "ban-unused-ignore"] {
+      let err = registry.register(Box::new(Custom(code))).unwrap_err();
+      assert_eq!(err.code, *code);
+    }
+    assert_eq!(
+      registry
+        .register(Box::new(Custom("no-var")))
+        .unwrap_err()
+        .to_string(),
+      "A rule with code \"no-var\" is already registered"
+    );
+    assert_eq!(registry.into_rules().len(), builtin_count + 1);
+  }
+
+  #[test]
+  fn rule_by_code() {
+    assert_eq!(get_rule_by_code("no-var").unwrap().code(), "no-var");
+    assert!(get_rule_by_code("noVar").is_none());
+    assert!(get_rule_by_code("no-such-rule").is_none());
+  }
+
+  #[test]
+  fn deprecated_codes() {
+    assert_eq!(
+      resolve_deprecated_code("noCondAssign"),
+      Some("no-cond-assign")
+    );
+    assert_eq!(resolve_deprecated_code("useIsNaN"), Some("use-isnan"));
+    assert_eq!(resolve_deprecated_code("useIsnan"), None);
+    assert_eq!(resolve_deprecated_code("no-var"), None);
+    assert_eq!(resolve_deprecated_code("eqeqeq"), None);
+    assert_eq!(resolve_deprecated_code("noSuchRule"), None);
+
+    for (_, code) in DEPRECATED_CODES {
+      assert!(get_rule_by_code(code).is_some(), "unknown code {}", code);
+    }
+  }
 }
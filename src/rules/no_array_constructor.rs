@@ -1,12 +1,9 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::Handler;
 use swc_common::Span;
 use swc_ecmascript::ast::{CallExpr, Expr, ExprOrSpread, ExprOrSuper, NewExpr};
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
-use swc_ecmascript::visit::VisitWith;
 
 use std::sync::Arc;
 
@@ -21,13 +18,19 @@ impl LintRule for NoArrayConstructor {
     "no-array-constructor"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows the `Array` constructor, except for creating sparse arrays of a given size."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoArrayConstructorVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoArrayConstructorVisitor::new(context)))
   }
 }
 
@@ -51,11 +54,8 @@ impl NoArrayConstructorVisitor {
   }
 }
 
-impl Visit for NoArrayConstructorVisitor {
-  noop_visit_type!();
-
-  fn visit_new_expr(&mut self, new_expr: &NewExpr, _parent: &dyn Node) {
-    new_expr.visit_children_with(self);
+impl Handler for NoArrayConstructorVisitor {
+  fn exit_new_expr(&mut self, new_expr: &NewExpr) {
     if let Expr::Ident(ident) = &*new_expr.callee {
       let name = ident.sym.as_ref();
       if name != "Array" {
@@ -73,8 +73,7 @@ impl Visit for NoArrayConstructorVisitor {
     }
   }
 
-  fn visit_call_expr(&mut self, call_expr: &CallExpr, _parent: &dyn Node) {
-    call_expr.visit_children_with(self);
+  fn exit_call_expr(&mut self, call_expr: &CallExpr) {
     if let ExprOrSuper::Expr(expr) = &call_expr.callee {
       if let Expr::Ident(ident) = expr.as_ref() {
         let name = ident.sym.as_ref();
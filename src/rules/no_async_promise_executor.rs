@@ -1,14 +1,15 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::Handler;
 use swc_ecmascript::ast::{Expr, NewExpr, ParenExpr};
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
-use swc_ecmascript::visit::VisitWith;
+use swc_ecmascript::utils::ident::IdentLike;
 
 use std::sync::Arc;
 
+const HINT: &str =
+  "Remove `async` from the executor, or drop the `Promise` wrapper";
+
 pub struct NoAsyncPromiseExecutor;
 
 impl LintRule for NoAsyncPromiseExecutor {
@@ -20,13 +21,26 @@ impl LintRule for NoAsyncPromiseExecutor {
     "no-async-promise-executor"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows the use of async functions as `Promise` executors.
+
+Errors thrown by an async executor are lost instead of rejecting the
+promise, and awaiting inside the executor usually means the `Promise`
+wrapper isn't needed at all.
+
+Only the global `Promise` is checked, so executors passed to an alias like
+`const P = Promise; new P(async () => {})` are not reported."#
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoAsyncPromiseExecutorVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoAsyncPromiseExecutorVisitor::new(context)))
   }
 }
 
@@ -49,24 +63,23 @@ fn is_async_function(expr: &Expr) -> bool {
   }
 }
 
-impl Visit for NoAsyncPromiseExecutorVisitor {
-  noop_visit_type!();
-
-  fn visit_new_expr(&mut self, new_expr: &NewExpr, _parent: &dyn Node) {
-    new_expr.visit_children_with(self);
+impl Handler for NoAsyncPromiseExecutorVisitor {
+  fn exit_new_expr(&mut self, new_expr: &NewExpr) {
     if let Expr::Ident(ident) = &*new_expr.callee {
       let name = ident.sym.as_ref();
-      if name != "Promise" {
+      // A locally declared `Promise` is not the global one.
+      if name != "Promise" || self.context.scope.var(&ident.to_id()).is_some() {
         return;
       }
 
       if let Some(args) = &new_expr.args {
         if let Some(first_arg) = args.get(0) {
           if is_async_function(&*first_arg.expr) {
-            self.context.add_diagnostic(
+            self.context.add_diagnostic_with_hint(
               new_expr.span,
               "no-async-promise-executor",
               "Async promise executors are not allowed",
+              HINT,
             );
           }
         }
@@ -88,6 +101,10 @@ mod tests {
       "new Promise((resolve, reject) => {}, async function unrelated() {})",
       "new Foo(async (resolve, reject) => {})",
       "new class { foo() { new Promise(function(resolve, reject) {}); } }",
+      // Aliases of `Promise` are not tracked.
+      "const P = Promise;\nnew P(async (resolve, reject) => {});",
+      "class Promise {}\nnew Promise(async (resolve, reject) => {});",
+      "new Promise(async);",
     ]);
   }
 
@@ -109,6 +126,12 @@ mod tests {
       "new Promise(((((async () => {})))));",
       0,
     );
+    assert_lint_err_with_hint::<NoAsyncPromiseExecutor>(
+      "new Promise(async () => {});",
+      1,
+      0,
+      HINT,
+    );
     // nested
     assert_lint_err_on_line::<NoAsyncPromiseExecutor>(
       r#"
@@ -1,12 +1,13 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_common::Span;
 use swc_ecmascript::ast::{
   ArrowExpr, AwaitExpr, DoWhileStmt, ForInStmt, ForOfStmt, ForStmt, Function,
   WhileStmt,
 };
-use swc_ecmascript::visit::{noop_visit_type, Node, Visit};
+use swc_ecmascript::visit::{Node, Visit};
 
 use std::sync::Arc;
 
@@ -21,13 +22,15 @@ impl LintRule for NoAwaitInLoop {
     "no-await-in-loop"
   }
 
-  fn lint_module(
-    &self,
+  fn docs(&self) -> &'static str {
+    "Disallows the use of `await` inside loop bodies."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoAwaitInLoopVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoAwaitInLoopVisitor::new(context)))
   }
 }
 
@@ -49,51 +52,72 @@ impl NoAwaitInLoopVisitor {
   }
 }
 
-impl Visit for NoAwaitInLoopVisitor {
-  noop_visit_type!();
-
-  fn visit_function(&mut self, func: &Function, parent: &dyn Node) {
+impl Handler for NoAwaitInLoopVisitor {
+  fn visit_function(&mut self, func: &Function, traversal: &mut Traversal) {
+    traversal.skip_children();
     let mut func_visitor = FunctionVisitor::new(&self, func.is_async);
-    func_visitor.visit_function(func, parent);
+    func_visitor.visit_function(func, func);
   }
 
-  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, parent: &dyn Node) {
+  fn visit_arrow_expr(
+    &mut self,
+    arrow_expr: &ArrowExpr,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
     let mut func_visitor = FunctionVisitor::new(&self, arrow_expr.is_async);
-    func_visitor.visit_arrow_expr(arrow_expr, parent);
+    func_visitor.visit_arrow_expr(arrow_expr, arrow_expr);
   }
 
-  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, parent: &dyn Node) {
+  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, traversal: &mut Traversal) {
+    traversal.skip_children();
     let mut loop_visitor = LoopVisitor::new(&self);
-    loop_visitor.visit_for_stmt(for_stmt, parent);
+    loop_visitor.visit_for_stmt(for_stmt, for_stmt);
   }
 
-  fn visit_for_of_stmt(&mut self, for_of_stmt: &ForOfStmt, parent: &dyn Node) {
+  fn visit_for_of_stmt(
+    &mut self,
+    for_of_stmt: &ForOfStmt,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
     if for_of_stmt.await_token.is_some() {
       let mut func_visitor = FunctionVisitor::new(&self, true);
-      func_visitor.visit_for_of_stmt(for_of_stmt, parent);
+      func_visitor.visit_for_of_stmt(for_of_stmt, for_of_stmt);
     } else {
       let mut loop_visitor = LoopVisitor::new(&self);
-      loop_visitor.visit_for_of_stmt(for_of_stmt, parent);
+      loop_visitor.visit_for_of_stmt(for_of_stmt, for_of_stmt);
     }
   }
 
-  fn visit_for_in_stmt(&mut self, for_in_stmt: &ForInStmt, parent: &dyn Node) {
+  fn visit_for_in_stmt(
+    &mut self,
+    for_in_stmt: &ForInStmt,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
     let mut loop_visitor = LoopVisitor::new(&self);
-    loop_visitor.visit_for_in_stmt(for_in_stmt, parent);
+    loop_visitor.visit_for_in_stmt(for_in_stmt, for_in_stmt);
   }
 
-  fn visit_while_stmt(&mut self, while_stmt: &WhileStmt, parent: &dyn Node) {
+  fn visit_while_stmt(
+    &mut self,
+    while_stmt: &WhileStmt,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
     let mut loop_visitor = LoopVisitor::new(&self);
-    loop_visitor.visit_while_stmt(while_stmt, parent);
+    loop_visitor.visit_while_stmt(while_stmt, while_stmt);
   }
 
   fn visit_do_while_stmt(
     &mut self,
     do_while_stmt: &DoWhileStmt,
-    parent: &dyn Node,
+    traversal: &mut Traversal,
   ) {
+    traversal.skip_children();
     let mut loop_visitor = LoopVisitor::new(&self);
-    loop_visitor.visit_do_while_stmt(do_while_stmt, parent);
+    loop_visitor.visit_do_while_stmt(do_while_stmt, do_while_stmt);
   }
 }
 
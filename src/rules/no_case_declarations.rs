@@ -1,14 +1,12 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::Handler;
+use swc_common::Spanned;
 use swc_ecmascript::ast::Decl;
 use swc_ecmascript::ast::Stmt;
 use swc_ecmascript::ast::SwitchCase;
 use swc_ecmascript::ast::VarDeclKind;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
-use swc_ecmascript::visit::VisitWith;
 
 use std::sync::Arc;
 
@@ -23,13 +21,23 @@ impl LintRule for NoCaseDeclarations {
     "no-case-declarations"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows lexical declarations in `case` and `default` clauses.
+
+Declarations with `let`, `const`, `class` and `function` directly in a
+clause are visible in the whole `switch`, but only initialized when the
+clause runs. Wrap the clause in a block instead."#
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoCaseDeclarationsVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoCaseDeclarationsVisitor::new(context)))
   }
 }
 
@@ -43,16 +51,8 @@ impl NoCaseDeclarationsVisitor {
   }
 }
 
-impl Visit for NoCaseDeclarationsVisitor {
-  noop_visit_type!();
-
-  fn visit_switch_case(
-    &mut self,
-    switch_case: &SwitchCase,
-    _parent: &dyn Node,
-  ) {
-    switch_case.visit_children_with(self);
-
+impl Handler for NoCaseDeclarationsVisitor {
+  fn exit_switch_case(&mut self, switch_case: &SwitchCase) {
     for stmt in &switch_case.cons {
       let is_lexical_decl = match stmt {
         Stmt::Decl(decl) => match &decl {
@@ -65,10 +65,11 @@ impl Visit for NoCaseDeclarationsVisitor {
       };
 
       if is_lexical_decl {
-        self.context.add_diagnostic(
-          switch_case.span,
+        self.context.add_diagnostic_with_hint(
+          stmt.span(),
           "no-case-declarations",
           "Unexpected declaration in case",
+          "Wrap the case in a block `{ ... }` to scope the declaration to it",
         );
       }
     }
@@ -124,8 +125,8 @@ switch (foo) {
     break;
 }
     "#,
-      3,
-      2,
+      4,
+      4,
     );
     assert_lint_err_on_line::<NoCaseDeclarations>(
       r#"
@@ -135,8 +136,8 @@ switch (bar) {
     break;
 }
     "#,
-      3,
-      2,
+      4,
+      4,
     );
     assert_lint_err_on_line::<NoCaseDeclarations>(
       r#"
@@ -146,8 +147,8 @@ switch (fizz) {
     break;
 }
     "#,
-      3,
-      2,
+      4,
+      4,
     );
     assert_lint_err_on_line::<NoCaseDeclarations>(
       r#"
@@ -157,8 +158,8 @@ switch (buzz) {
     break;
 }
     "#,
-      3,
-      2,
+      4,
+      4,
     );
     assert_lint_err_on_line::<NoCaseDeclarations>(
       r#"
@@ -170,8 +171,8 @@ switch (fncase) {
     break;
 }
     "#,
-      3,
-      2,
+      4,
+      4,
     );
     assert_lint_err_on_line::<NoCaseDeclarations>(
       r#"
@@ -183,8 +184,8 @@ switch (fncase) {
     break;
 }
     "#,
-      3,
-      2,
+      4,
+      4,
     );
     assert_lint_err_on_line::<NoCaseDeclarations>(
       r#"
@@ -196,8 +197,8 @@ switch (classcase) {
     break;
 }
     "#,
-      3,
-      2,
+      4,
+      4,
     );
     assert_lint_err_on_line::<NoCaseDeclarations>(
       r#"
@@ -209,8 +210,8 @@ switch (classcase) {
     break;
 }
     "#,
-      3,
-      2,
+      4,
+      4,
     );
 
     // nested switch
@@ -226,8 +227,8 @@ switch (foo) {
     break;
 }
     "#,
-      5,
       6,
+      8,
     );
     assert_lint_err_on_line::<NoCaseDeclarations>(
       r#"
@@ -241,8 +242,8 @@ switch (foo) {
     break;
 }
     "#,
-      5,
       6,
+      8,
     );
     assert_lint_err_on_line::<NoCaseDeclarations>(
       r#"
@@ -256,8 +257,8 @@ switch (foo) {
     break;
 }
     "#,
-      5,
       6,
+      8,
     );
     assert_lint_err_on_line::<NoCaseDeclarations>(
       r#"
@@ -271,8 +272,36 @@ switch (foo) {
     break;
 }
     "#,
-      5,
       6,
+      8,
+    );
+  }
+
+  #[test]
+  fn no_case_declarations_var_and_blocks() {
+    assert_lint_ok_n::<NoCaseDeclarations>(vec![
+      "switch (a) { case 1: var b = 1; break; }",
+      "switch (a) { case 1: { let b = 1; } }",
+      "switch (a) { case 1: if (b) { const c = 1; } break; }",
+      "switch (a) { case 1: foo(() => { let b = 1; }); }",
+    ]);
+    assert_lint_err::<NoCaseDeclarations>(
+      "switch (a) { case 1: let b = 1; break; }",
+      21,
+    );
+    assert_lint_err_n::<NoCaseDeclarations>(
+      "switch (a) { case 1: let b = 1; const c = 2; default: class D {} }",
+      vec![21, 32, 54],
+    );
+  }
+
+  #[test]
+  fn no_case_declarations_hint() {
+    assert_lint_err_with_hint::<NoCaseDeclarations>(
+      "switch (a) { default: function b() {} }",
+      1,
+      22,
+      "Wrap the case in a block `{ ... }` to scope the declaration to it",
     );
   }
 }
@@ -1,12 +1,12 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::Handler;
 use crate::{scopes::BindingKind, swc_util::find_lhs_ids};
-use swc_ecmascript::ast::AssignExpr;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
-use swc_ecmascript::visit::VisitWith;
+use swc_common::Span;
+use swc_ecmascript::ast::{AssignExpr, Expr, UpdateExpr};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
 
 use std::sync::Arc;
 
@@ -21,13 +21,19 @@ impl LintRule for NoClassAssign {
     "no-class-assign"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows reassigning variables declared by class declarations."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoClassAssignVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoClassAssignVisitor::new(context)))
   }
 }
 
@@ -39,30 +45,34 @@ impl NoClassAssignVisitor {
   fn new(context: Arc<Context>) -> Self {
     Self { context }
   }
-}
 
-impl Visit for NoClassAssignVisitor {
-  noop_visit_type!();
-
-  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, _node: &dyn Node) {
-    assign_expr.visit_children_with(self);
-
-    let ids = find_lhs_ids(&assign_expr.left);
-    for id in ids {
-      let var = self.context.scope.var(&id);
-      if let Some(var) = var {
-        if let BindingKind::Class = var.kind() {
-          self.context.add_diagnostic(
-            assign_expr.span,
-            "no-class-assign",
-            "Reassigning class declaration is not allowed",
-          );
-        }
+  fn check(&self, id: &Id, span: Span) {
+    if let Some(var) = self.context.scope.var(id) {
+      if let BindingKind::Class = var.kind() {
+        self.context.add_diagnostic(
+          span,
+          "no-class-assign",
+          "Reassigning class declaration is not allowed",
+        );
       }
     }
   }
 }
 
+impl Handler for NoClassAssignVisitor {
+  fn exit_assign_expr(&mut self, assign_expr: &AssignExpr) {
+    for id in find_lhs_ids(&assign_expr.left) {
+      self.check(&id, assign_expr.span);
+    }
+  }
+
+  fn exit_update_expr(&mut self, update_expr: &UpdateExpr) {
+    if let Expr::Ident(ident) = &*update_expr.arg {
+      self.check(&ident.to_id(), update_expr.span);
+    }
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -114,6 +124,10 @@ A = class {
       r#"function x() {} x = 1;"#,
       r#"function foo(x) { x = 1; }"#,
       r#"try {} catch (x) { x = 1; }"#,
+      r#"let A = class {}; A = 0; A++;"#,
+      r#"var A = class B {}; [A] = [0];"#,
+      r#"class A {} A.b = 0; A.c++;"#,
+      r#"class A {} function foo() { let A = 0; A += 1; A++; }"#,
     ]);
   }
 
@@ -194,5 +208,10 @@ A = class {
       6,
       4,
     );
+    assert_lint_err::<NoClassAssign>("class A {} [A] = [0];", 11);
+    assert_lint_err::<NoClassAssign>("class A {} [, ...A] = [0];", 11);
+    assert_lint_err::<NoClassAssign>("class A {} A += 1;", 11);
+    assert_lint_err::<NoClassAssign>("class A {} A++;", 11);
+    assert_lint_err::<NoClassAssign>("class A { static foo() { --A; } }", 25);
   }
 }
@@ -1,12 +1,12 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::{Context, LintRule};
+use crate::handler::Handler;
 use swc_ecmascript::ast::BinaryOp::*;
-use swc_ecmascript::ast::Expr::{Lit, Unary};
+use swc_ecmascript::ast::Expr::{Lit, Paren, Unary};
 use swc_ecmascript::ast::Lit::Num;
 use swc_ecmascript::ast::UnaryExpr;
 use swc_ecmascript::ast::UnaryOp::Minus;
-use swc_ecmascript::ast::{BinExpr, BinaryOp, Expr, Module};
-use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+use swc_ecmascript::ast::{BinExpr, BinaryOp, Expr};
 
 use std::sync::Arc;
 
@@ -21,9 +21,19 @@ impl LintRule for NoCompareNegZero {
     "no-compare-neg-zero"
   }
 
-  fn lint_module(&self, context: Arc<Context>, module: &Module) {
-    let mut visitor = NoCompareNegZeroVisitor::new(context);
-    visitor.visit_module(module, module);
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows comparing against `-0`."
+  }
+
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoCompareNegZeroVisitor::new(context)))
   }
 }
 
@@ -37,21 +47,18 @@ impl NoCompareNegZeroVisitor {
   }
 }
 
-impl Visit for NoCompareNegZeroVisitor {
-  noop_visit_type!();
-
-  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, _parent: &dyn Node) {
-    bin_expr.visit_children_with(self);
-
+impl Handler for NoCompareNegZeroVisitor {
+  fn exit_bin_expr(&mut self, bin_expr: &BinExpr) {
     if !bin_expr.op.is_comparator() {
       return;
     }
 
     if bin_expr.left.is_neg_zero() || bin_expr.right.is_neg_zero() {
-      self.context.add_diagnostic(
+      self.context.add_diagnostic_with_hint(
         bin_expr.span,
         "no-compare-neg-zero",
         "Do not compare against -0",
+        "Use `Object.is(x, -0)` to check whether a value is -0",
       );
     }
   }
@@ -78,6 +85,7 @@ impl NegZero for Expr {
   fn is_neg_zero(&self) -> bool {
     match self {
       Unary(unary) => unary.is_neg_zero(),
+      Paren(paren) => paren.expr.is_neg_zero(),
       _ => false,
     }
   }
@@ -85,7 +93,11 @@ impl NegZero for Expr {
 
 impl NegZero for UnaryExpr {
   fn is_neg_zero(&self) -> bool {
-    if let (Minus, Lit(Num(number))) = (self.op, &*self.arg) {
+    let mut arg = &*self.arg;
+    while let Paren(paren) = arg {
+      arg = &*paren.expr;
+    }
+    if let (Minus, Lit(Num(number))) = (self.op, arg) {
       return number.value == 0.0;
     }
     false
@@ -133,6 +145,9 @@ mod tests {
       r#"x !== 0"#,
       r#"0 !== x"#,
       r#"{} == { foo: x === 0 }"#,
+      r#"x === -0n"#,
+      r#"x === -(1 - 1)"#,
+      r#"x + -0"#,
     ]);
   }
 
@@ -158,7 +173,27 @@ mod tests {
     assert_lint_err::<NoCompareNegZero>("if (-0.0 == x) { }", 4);
     assert_lint_err::<NoCompareNegZero>("if (x === -0.0) { }", 4);
     assert_lint_err::<NoCompareNegZero>("if (-0.0 === x) { }", 4);
+    assert_lint_err::<NoCompareNegZero>("if (x === (-0)) { }", 4);
+    assert_lint_err::<NoCompareNegZero>("if (x === -(0)) { }", 4);
+    assert_lint_err::<NoCompareNegZero>("if ((-0) < x) { }", 4);
+    assert_lint_err::<NoCompareNegZero>("if (x === -0x0) { }", 4);
     // nested
     assert_lint_err::<NoCompareNegZero>("{} == { foo: x === -0 }", 13);
+    assert_lint_err!(
+      NoCompareNegZero,
+      "function foo(x) {\n  return bar(x >= -0);\n}",
+      2,
+      13
+    );
+  }
+
+  #[test]
+  fn no_compare_neg_zero_hint() {
+    assert_lint_err_with_hint::<NoCompareNegZero>(
+      "x === -0",
+      1,
+      0,
+      "Use `Object.is(x, -0)` to check whether a value is -0",
+    );
   }
 }
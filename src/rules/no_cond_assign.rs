@@ -1,27 +1,70 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
-use super::{Context, LintRule};
-use swc_common::Span;
-use swc_ecmascript::ast::Expr;
-use swc_ecmascript::ast::Expr::{Assign, Bin, Paren};
-use swc_ecmascript::ast::Module;
-use swc_ecmascript::visit::{noop_visit_type, Node, Visit};
+use super::{Context, LintRule, RuleConfigError};
+use crate::handler::{Handler, Traversal};
+use swc_common::{Span, Spanned, SyntaxContext};
+use swc_ecmascript::ast::Expr::{Assign, Bin, Cond, Paren, Unary};
+use swc_ecmascript::ast::{AssignExpr, AssignOp, BinaryOp, Expr};
 
 use std::sync::Arc;
 
-pub struct NoCondAssign;
+pub struct NoCondAssign {
+  mode: Mode,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Mode {
+  /// Allows assignments in test conditions only if they are enclosed in
+  /// parentheses.
+  ExceptParens,
+  /// Disallows all assignments in test conditions.
+  Always,
+}
 
 impl LintRule for NoCondAssign {
   fn new() -> Box<Self> {
-    Box::new(NoCondAssign)
+    Box::new(NoCondAssign {
+      mode: Mode::ExceptParens,
+    })
+  }
+
+  fn new_with_config(
+    &self,
+    config: serde_json::Value,
+  ) -> Result<Box<dyn LintRule>, RuleConfigError> {
+    let mode = match config.as_str() {
+      None if config.is_null() => Mode::ExceptParens,
+      Some("except-parens") => Mode::ExceptParens,
+      Some("always") => Mode::Always,
+      _ => {
+        return Err(RuleConfigError {
+          code: "no-cond-assign".to_string(),
+          message: format!(
+            "expected \"except-parens\" or \"always\", got {}",
+            config
+          ),
+        })
+      }
+    };
+    Ok(Box::new(NoCondAssign { mode }))
   }
 
   fn code(&self) -> &'static str {
     "no-cond-assign"
   }
 
-  fn lint_module(&self, context: Arc<Context>, module: &Module) {
-    let mut visitor = NoCondAssignVisitor::new(context);
-    visitor.visit_module(module, module);
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(serde_json::json!({ "enum": ["except-parens", "always"] }))
+  }
+
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoCondAssignVisitor::new(context, self.mode)))
   }
 
   fn docs(&self) -> &'static str {
@@ -29,6 +72,8 @@ impl LintRule for NoCondAssign {
 
 Use of the assignment operator within a conditional statement is often the result of mistyping the equality operator, `==`. If an assignment within a conditional statement is required then this rule allows it by wrapping the assignment in parentheses.
 
+The rule accepts `"except-parens"` (default) or `"always"` as an option. In `"always"` mode assignments are reported even if they are wrapped in parentheses.
+
 ### Valid:
 ```typescript
 var x;
@@ -63,65 +108,116 @@ function setHeight(someNode) {
 
 struct NoCondAssignVisitor {
   context: Arc<Context>,
+  mode: Mode,
 }
 
 impl NoCondAssignVisitor {
-  fn new(context: Arc<Context>) -> Self {
-    Self { context }
+  fn new(context: Arc<Context>, mode: Mode) -> Self {
+    Self { context, mode }
   }
 
-  fn add_diagnostic(&self, span: Span) {
-    self.context.add_diagnostic(
-      span,
+  /// `allow_parens` tells if wrapping the assignment in parentheses
+  /// would silence the diagnostic.
+  fn add_diagnostic(&self, assign: &AssignExpr, allow_parens: bool) {
+    let mut suggestions = vec![];
+    if assign.op == AssignOp::Assign {
+      let op_span = Span::new(
+        assign.left.span().hi(),
+        assign.right.span().lo(),
+        SyntaxContext::empty(),
+      );
+      suggestions.push(self.context.create_suggestion(
+        "Use '===' to compare values",
+        op_span,
+        " === ",
+      ));
+    }
+    if allow_parens {
+      if let Ok(snippet) = self.context.source_map.span_to_snippet(assign.span)
+      {
+        suggestions.push(self.context.create_suggestion(
+          "Wrap the assignment in parentheses",
+          assign.span,
+          &format!("({})", snippet),
+        ));
+      }
+    }
+
+    let mut diagnostic = self.context.create_default_diagnostic(
+      assign.span,
       "no-cond-assign",
       "Expected a conditional expression and instead saw an assignment",
     );
+    diagnostic.hint =
+      Some("Did you mean to use '===' instead of '='?".to_string());
+    diagnostic.suggestions = suggestions;
+    self.context.push_diagnostic(diagnostic);
   }
 
   fn check_condition(&self, condition: &Expr) {
-    match condition {
+    self.check_expr(condition, self.mode == Mode::ExceptParens);
+  }
+
+  /// Walks sub-expressions of a test looking for assignments.
+  /// `allow_parens` is true as long as only `||` operators were traversed,
+  /// which allows parenthesized assignments like `a || (a = b)`.
+  fn check_expr(&self, expr: &Expr, allow_parens: bool) {
+    match expr {
       Assign(assign) => {
-        self.add_diagnostic(assign.span);
+        self.add_diagnostic(assign, allow_parens);
       }
       Bin(bin) => {
-        if bin.op == swc_ecmascript::ast::BinaryOp::LogicalOr {
-          self.check_condition(&bin.left);
-          self.check_condition(&bin.right);
+        let allow_parens = allow_parens && bin.op == BinaryOp::LogicalOr;
+        self.check_expr(&bin.left, allow_parens);
+        self.check_expr(&bin.right, allow_parens);
+      }
+      Paren(paren) => {
+        if let Assign(_) = &*paren.expr {
+          if allow_parens {
+            return;
+          }
         }
+        self.check_expr(&paren.expr, false);
+      }
+      Unary(unary) => {
+        self.check_expr(&unary.arg, false);
+      }
+      Cond(cond) => {
+        self.check_expr(&cond.test, false);
+        self.check_expr(&cond.cons, false);
+        self.check_expr(&cond.alt, false);
       }
       _ => {}
     }
   }
 }
 
-impl Visit for NoCondAssignVisitor {
-  noop_visit_type!();
-
+impl Handler for NoCondAssignVisitor {
   fn visit_if_stmt(
     &mut self,
     if_stmt: &swc_ecmascript::ast::IfStmt,
-    _parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     self.check_condition(&if_stmt.test);
   }
   fn visit_while_stmt(
     &mut self,
     while_stmt: &swc_ecmascript::ast::WhileStmt,
-    _parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     self.check_condition(&while_stmt.test);
   }
   fn visit_do_while_stmt(
     &mut self,
     do_while_stmt: &swc_ecmascript::ast::DoWhileStmt,
-    _parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     self.check_condition(&do_while_stmt.test);
   }
   fn visit_for_stmt(
     &mut self,
     for_stmt: &swc_ecmascript::ast::ForStmt,
-    _parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     if let Some(for_test) = &for_stmt.test {
       self.check_condition(&for_test);
@@ -130,10 +226,11 @@ impl Visit for NoCondAssignVisitor {
   fn visit_cond_expr(
     &mut self,
     cond_expr: &swc_ecmascript::ast::CondExpr,
-    _parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
-    if let Paren(paren) = &*cond_expr.test {
-      self.check_condition(&paren.expr);
+    match &*cond_expr.test {
+      Paren(paren) => self.check_condition(&paren.expr),
+      test => self.check_condition(test),
     }
   }
 }
@@ -145,84 +242,232 @@ mod tests {
 
   #[test]
   fn it_passes_using_equality_operator() {
-    assert_lint_ok::<NoCondAssign>("if (x === 0) { };");
+    assert_lint_ok!(NoCondAssign, "if (x === 0) { };");
   }
 
   #[test]
   fn it_passes_with_bracketed_assignment() {
-    assert_lint_ok::<NoCondAssign>("if ((x = y)) { }");
+    assert_lint_ok!(NoCondAssign, "if ((x = y)) { }");
   }
 
   #[test]
   fn it_fails_using_assignment_in_if_stmt() {
-    assert_lint_err::<NoCondAssign>("if (x = 0) { }", 4);
+    assert_lint_err!(NoCondAssign, "if (x = 0) { }", 1, 4);
   }
 
   #[test]
   fn it_fails_using_assignment_in_while_stmt() {
-    assert_lint_err::<NoCondAssign>("while (x = 0) { }", 7);
+    assert_lint_err!(NoCondAssign, "while (x = 0) { }", 1, 7);
   }
 
   #[test]
   fn it_fails_using_assignment_in_do_while_stmt() {
-    assert_lint_err::<NoCondAssign>("do { } while (x = 0);", 14);
+    assert_lint_err!(NoCondAssign, "do { } while (x = 0);", 1, 14);
   }
 
   #[test]
   fn it_fails_using_assignment_in_for_stmt() {
-    assert_lint_err::<NoCondAssign>("for (let i = 0; i = 10; i++) { }", 16);
+    assert_lint_err!(NoCondAssign, "for (let i = 0; i = 10; i++) { }", 1, 16);
   }
 
   #[test]
   fn no_cond_assign_valid() {
-    assert_lint_ok::<NoCondAssign>("const x = 0; if (x == 0) { const b = 1; }");
-    assert_lint_ok::<NoCondAssign>("const x = 5; while (x < 5) { x = x + 1; }");
-    assert_lint_ok::<NoCondAssign>("while ((a = b));");
-    assert_lint_ok::<NoCondAssign>("do {} while ((a = b));");
-    assert_lint_ok::<NoCondAssign>("for (;(a = b););");
-    assert_lint_ok::<NoCondAssign>("for (;;) {}");
-    assert_lint_ok::<NoCondAssign>(
+    assert_lint_ok!(
+      NoCondAssign,
+      "const x = 0; if (x == 0) { const b = 1; }",
+      "const x = 5; while (x < 5) { x = x + 1; }",
+      "while ((a = b));",
+      "do {} while ((a = b));",
+      "for (;(a = b););",
+      "for (;;) {}",
       "if (someNode || (someNode = parentNode)) { }",
-    );
-    assert_lint_ok::<NoCondAssign>(
       "while (someNode || (someNode = parentNode)) { }",
-    );
-    assert_lint_ok::<NoCondAssign>(
       "do { } while (someNode || (someNode = parentNode));",
-    );
-    assert_lint_ok::<NoCondAssign>(
       "for (;someNode || (someNode = parentNode););",
-    );
-    assert_lint_ok::<NoCondAssign>(
       "if ((function(node) { return node = parentNode; })(someNode)) { }",
-    );
-    assert_lint_ok::<NoCondAssign>(
       "if ((node => node = parentNode)(someNode)) { }",
-    );
-    assert_lint_ok::<NoCondAssign>(
       "if (function(node) { return node = parentNode; }) { }",
+      "const x; const b = (x === 0) ? 1 : 0;",
+      "switch (foo) { case a = b: bar(); }",
     );
-    assert_lint_ok::<NoCondAssign>("const x; const b = (x === 0) ? 1 : 0;");
-    assert_lint_ok::<NoCondAssign>("switch (foo) { case a = b: bar(); }");
   }
 
   #[test]
   fn no_cond_assign_invalid() {
-    assert_lint_err::<NoCondAssign>("const x; if (x = 0) { const b = 1; }", 13);
-    assert_lint_err::<NoCondAssign>(
+    assert_lint_err!(
+      NoCondAssign,
+      "const x; if (x = 0) { const b = 1; }",
+      1,
+      13
+    );
+    assert_lint_err!(
+      NoCondAssign,
       "const x; while (x = 0) { const b = 1; }",
-      16,
+      1,
+      16
     );
-    assert_lint_err::<NoCondAssign>(
+    assert_lint_err!(
+      NoCondAssign,
       "const x = 0, y; do { y = x; } while (x = x + 1);",
-      37,
+      1,
+      37
     );
-    assert_lint_err::<NoCondAssign>("let x; for(; x+=1 ;){};", 13);
-    assert_lint_err::<NoCondAssign>("let x; if ((x) = (0));", 11);
-    assert_lint_err::<NoCondAssign>("let x; let b = (x = 0) ? 1 : 0;", 16);
-    assert_lint_err::<NoCondAssign>(
+    assert_lint_err!(NoCondAssign, "let x; for(; x+=1 ;){};", 1, 13);
+    assert_lint_err!(NoCondAssign, "let x; if ((x) = (0));", 1, 11);
+    assert_lint_err!(NoCondAssign, "let x; let b = (x = 0) ? 1 : 0;", 1, 16);
+    assert_lint_err!(
+      NoCondAssign,
       "(((123.45)).abcd = 54321) ? foo : bar;",
       1,
+      1
+    );
+  }
+
+  #[test]
+  fn it_fails_using_assignment_in_logical_expr() {
+    assert_lint_err!(NoCondAssign, "if (x === 1 && (y = 2)) {}", 1, 16);
+    assert_lint_err!(NoCondAssign, "if ((y = 2) && x === 1) {}", 1, 5);
+    assert_lint_err!(NoCondAssign, "while ((a = b) && c) {}", 1, 8);
+    assert_lint_err!(NoCondAssign, "if (a ?? (b = c)) {}", 1, 10);
+    assert_lint_err!(NoCondAssign, "if (!(a = b)) {}", 1, 6);
+    assert_lint_err!(NoCondAssign, "if (a ? (b = c) : d) {}", 1, 9);
+    assert_lint_err!(
+      NoCondAssign,
+      "if ((a = 1) && (b = 2)) {}",
+      [(1, 5), (1, 16)]
+    );
+  }
+
+  #[test]
+  fn it_fails_using_deeply_nested_assignment() {
+    assert_lint_err!(
+      NoCondAssign,
+      "if (a && (b || (c === (d = 1)))) {}",
+      1,
+      23
+    );
+  }
+
+  #[test]
+  fn it_passes_using_comparisons_in_logical_expr() {
+    assert_lint_ok!(NoCondAssign, "if (a == 1 && (b == 2 || !(c == 3))) {}");
+    assert_lint_ok!(NoCondAssign, "while (a == b && (c == d ? e == f : g)) {}");
+  }
+
+  #[test]
+  fn it_uses_config_mode() {
+    let always = serde_json::json!("always");
+    let except_parens = serde_json::json!("except-parens");
+
+    let fixtures = vec![
+      ("if ((x = y)) { }", 5),
+      ("while ((a = b));", 8),
+      ("do {} while ((a = b));", 14),
+      ("for (;(a = b););", 7),
+      ("if (someNode || (someNode = parentNode)) { }", 17),
+      ("const b = ((x = 0)) ? 1 : 0;", 12),
+    ];
+
+    for (source, col) in fixtures {
+      assert_lint_ok_with_config::<NoCondAssign>(source, except_parens.clone());
+      assert_lint_err_with_config::<NoCondAssign>(source, always.clone(), col);
+    }
+
+    for source in &["if (x = 0) { }", "do {} while (a = b);"] {
+      assert_lint_err_with_config::<NoCondAssign>(
+        source,
+        except_parens.clone(),
+        source.find('=').unwrap() - 2,
+      );
+      assert_lint_err_with_config::<NoCondAssign>(
+        source,
+        always.clone(),
+        source.find('=').unwrap() - 2,
+      );
+    }
+  }
+
+  #[test]
+  fn it_rejects_invalid_config() {
+    assert!(NoCondAssign::new()
+      .new_with_config(serde_json::json!("sometimes"))
+      .is_err());
+    assert!(NoCondAssign::new()
+      .new_with_config(serde_json::json!(null))
+      .is_ok());
+  }
+
+  #[test]
+  fn it_fails_using_assignment_in_ternary() {
+    assert_lint_err!(NoCondAssign, "const v = (x = 0) ? a : b;", 1, 11);
+    assert_lint_err!(NoCondAssign, "foo(bar, (x = 0) ? a : b);", 1, 10);
+    assert_lint_err!(NoCondAssign, "const v = a && (x = 0) ? b : c;", 1, 16);
+    assert_lint_err!(
+      NoCondAssign,
+      "const v = c ? ((x = 0) ? a : b) : d;",
+      1,
+      16
+    );
+    assert_lint_ok!(NoCondAssign, "const v = ((x = 0)) ? a : b;");
+    assert_lint_ok!(NoCondAssign, "foo(((x = 0)) ? a : b);");
+  }
+
+  #[test]
+  fn it_fails_using_assignment_in_nested_condition() {
+    assert_lint_err!(NoCondAssign, "if (a) { if (b = c) {} }", 1, 13);
+    assert_lint_err!(NoCondAssign, "while (a) { do {} while (b = c); }", 1, 25);
+    assert_lint_err!(
+      NoCondAssign,
+      "while (arr.some(i => { if (x = i) { return true; } })) {}",
+      1,
+      27
+    );
+    assert_lint_err!(
+      NoCondAssign,
+      "for (;;) { const f = function() { while (x = y) {} }; }",
+      1,
+      41
+    );
+    assert_lint_err!(NoCondAssign, "const f = () => (x = y) ? a : b;", 1, 17);
+  }
+
+  #[test]
+  fn suggestions() {
+    use crate::diagnostic::apply_suggestion;
+
+    let source = "if (x = 0) {}";
+    let diagnostics = lint_with_rule(NoCondAssign::new(), source);
+    assert_eq!(diagnostics.len(), 1);
+    let diagnostic = &diagnostics[0];
+    assert!(diagnostic.fix.is_none());
+    assert_eq!(diagnostic.suggestions.len(), 2);
+    assert_eq!(
+      apply_suggestion(source, diagnostic, 0).unwrap(),
+      "if (x === 0) {}"
+    );
+    assert_eq!(
+      apply_suggestion(source, diagnostic, 1).unwrap(),
+      "if ((x = 0)) {}"
+    );
+    assert!(apply_suggestion(source, diagnostic, 2).is_none());
+
+    let source = "if (x += 1) {}";
+    let diagnostics = lint_with_rule(
+      NoCondAssign::new()
+        .new_with_config(serde_json::json!("always"))
+        .unwrap(),
+      source,
+    );
+    assert!(diagnostics[0].suggestions.is_empty());
+  }
+
+  #[test]
+  fn hint() {
+    assert_lint_err_with_hint::<NoCondAssign>(
+      "if (x = 0) {}",
+      1,
+      4,
+      "Did you mean to use '===' instead of '='?",
     );
   }
 }
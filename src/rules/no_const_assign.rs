@@ -1,16 +1,17 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use crate::scopes::BindingKind;
-use swc_common::Span;
+use swc_common::{Span, Spanned};
 use swc_ecmascript::ast::AssignExpr;
 use swc_ecmascript::ast::Expr;
 use swc_ecmascript::ast::ObjectPatProp;
 use swc_ecmascript::ast::Pat;
 use swc_ecmascript::ast::PatOrExpr;
+use swc_ecmascript::ast::{ForInStmt, ForOfStmt, VarDeclOrPat};
 use swc_ecmascript::ast::{Ident, UpdateExpr};
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::{utils::ident::IdentLike, visit::Visit};
+use swc_ecmascript::utils::ident::IdentLike;
 
 use std::sync::Arc;
 
@@ -25,13 +26,15 @@ impl LintRule for NoConstAssign {
     "no-const-assign"
   }
 
-  fn lint_module(
-    &self,
+  fn docs(&self) -> &'static str {
+    "Disallows reassigning variables declared with `const`."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoConstAssignVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoConstAssignVisitor::new(context)))
   }
 }
 
@@ -58,6 +61,14 @@ impl NoConstAssignVisitor {
       Pat::Object(object) => {
         self.check_obj_pat(object, span);
       }
+      Pat::Rest(rest) => {
+        self.check_pat(&rest.arg, span);
+      }
+      Pat::Expr(expr) => {
+        if let Expr::Ident(ident) = &**expr {
+          self.check_scope_for_const(span, ident);
+        }
+      }
       _ => {}
     }
   }
@@ -73,6 +84,8 @@ impl NoConstAssignVisitor {
           self.check_scope_for_const(assign_prop.key.span, &assign_prop.key);
         } else if let ObjectPatProp::KeyValue(kv_prop) = prop {
           self.check_pat(&kv_prop.value, span);
+        } else if let ObjectPatProp::Rest(rest) = prop {
+          self.check_pat(&rest.arg, span);
         }
       }
     }
@@ -106,8 +119,16 @@ impl NoConstAssignVisitor {
   }
 }
 
-impl Visit for NoConstAssignVisitor {
-  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, _node: &dyn Node) {
+impl Handler for NoConstAssignVisitor {
+  fn visit_types(&self) -> bool {
+    true
+  }
+
+  fn visit_assign_expr(
+    &mut self,
+    assign_expr: &AssignExpr,
+    _traversal: &mut Traversal,
+  ) {
     match &assign_expr.left {
       PatOrExpr::Expr(pat_expr) => {
         if let Expr::Ident(ident) = &**pat_expr {
@@ -118,11 +139,35 @@ impl Visit for NoConstAssignVisitor {
     };
   }
 
-  fn visit_update_expr(&mut self, update_expr: &UpdateExpr, _node: &dyn Node) {
+  fn visit_update_expr(
+    &mut self,
+    update_expr: &UpdateExpr,
+    _traversal: &mut Traversal,
+  ) {
     if let Expr::Ident(ident) = &*update_expr.arg {
       self.check_scope_for_const(update_expr.span, &ident);
     }
   }
+
+  fn visit_for_in_stmt(
+    &mut self,
+    for_in_stmt: &ForInStmt,
+    _traversal: &mut Traversal,
+  ) {
+    if let VarDeclOrPat::Pat(pat) = &for_in_stmt.left {
+      self.check_pat(pat, pat.span());
+    }
+  }
+
+  fn visit_for_of_stmt(
+    &mut self,
+    for_of_stmt: &ForOfStmt,
+    _traversal: &mut Traversal,
+  ) {
+    if let VarDeclOrPat::Pat(pat) = &for_of_stmt.left {
+      self.check_pat(pat, pat.span());
+    }
+  }
 }
 
 #[cfg(test)]
@@ -140,6 +185,10 @@ mod tests {
       for (const x in [1,2,3]) { foo(x); }
       for (const x of [1,2,3]) { foo(x); }
       const x = {key: 0}; x.key = 1;
+      const x = 0; { let x; [x] = [1]; x++; }
+      const x = 0; for (let x of [1]) { x = 2; }
+      const x = 0; function foo() { let x; for (x of [1]) {} }
+      const x = 0; for (const y in x) { foo(y); }
       if (true) {const a = 1} else { a = 2};
       // ignores non constant.
       var x = 0; x = 1;
@@ -297,6 +346,15 @@ declare module "foo" {
       4,
       2,
     );
+    assert_lint_err::<NoConstAssign>("const x = 0; x++;", 13);
+    assert_lint_err::<NoConstAssign>("const x = 0; [x] = [1];", 13);
+    assert_lint_err::<NoConstAssign>("const x = 0; [, ...x] = [1];", 13);
+    assert_lint_err::<NoConstAssign>("const x = 0; ({...x} = {});", 14);
+    assert_lint_err::<NoConstAssign>("const x = 0; for (x of [1]) {}", 18);
+    assert_lint_err::<NoConstAssign>("const x = 0; for (x in {}) {}", 18);
+    assert_lint_err::<NoConstAssign>("const x = 0; for ([x] of [[1]]) {}", 18);
+    assert_lint_err::<NoConstAssign>("let y; const x = 0; y = (x = 1);", 25);
+    assert_lint_err::<NoConstAssign>("const x = 0; foo(() => x++);", 23);
     assert_lint_err_n::<NoConstAssign>(
       "const x = 0  ; x = 1; x = 2;",
       vec![15, 22],
@@ -1,13 +1,12 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use std::sync::Arc;
 use swc_common::Span;
 use swc_common::Spanned;
 use swc_ecmascript::ast::Expr;
 use swc_ecmascript::ast::Lit;
-use swc_ecmascript::ast::Module;
-use swc_ecmascript::visit::{noop_visit_type, Node, Visit};
 
 pub struct NoConstantCondition;
 
@@ -20,9 +19,19 @@ impl LintRule for NoConstantCondition {
     "no-constant-condition"
   }
 
-  fn lint_module(&self, context: Arc<Context>, module: &Module) {
-    let mut visitor = NoConstantConditionVisitor::new(context);
-    visitor.visit_module(module, module);
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows constant expressions in conditions."
+  }
+
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoConstantConditionVisitor::new(context)))
   }
 }
 
@@ -165,22 +174,22 @@ impl NoConstantConditionVisitor {
   }
 }
 
-impl Visit for NoConstantConditionVisitor {
-  noop_visit_type!();
-
+impl Handler for NoConstantConditionVisitor {
   fn visit_cond_expr(
     &mut self,
     cond_expr: &swc_ecmascript::ast::CondExpr,
-    _parent: &dyn Node,
+    traversal: &mut Traversal,
   ) {
+    traversal.skip_children();
     self.report(&cond_expr.test)
   }
 
   fn visit_if_stmt(
     &mut self,
     if_stmt: &swc_ecmascript::ast::IfStmt,
-    _parent: &dyn Node,
+    traversal: &mut Traversal,
   ) {
+    traversal.skip_children();
     self.report(&if_stmt.test)
   }
 
@@ -1,14 +1,12 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use crate::swc_util::extract_regex;
 use std::iter::Peekable;
 use std::str::Chars;
 use swc_common::Span;
 use swc_ecmascript::ast::{CallExpr, Expr, ExprOrSuper, NewExpr, Regex};
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -23,13 +21,19 @@ impl LintRule for NoControlRegex {
     "no-control-regex"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows control characters in regular expressions."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoControlRegexVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoControlRegexVisitor::new(context)))
   }
 }
 
@@ -111,15 +115,12 @@ fn read_hex_until_brace(iter: &mut Peekable<Chars>) -> Option<u64> {
   u64::from_str_radix(s.as_str(), 16).ok()
 }
 
-impl Visit for NoControlRegexVisitor {
-  noop_visit_type!();
-
-  fn visit_regex(&mut self, regex: &Regex, parent: &dyn Node) {
+impl Handler for NoControlRegexVisitor {
+  fn visit_regex(&mut self, regex: &Regex, _traversal: &mut Traversal) {
     self.check_regex(regex.exp.to_string().as_str(), regex.span);
-    swc_ecmascript::visit::visit_regex(self, regex, parent);
   }
 
-  fn visit_new_expr(&mut self, new_expr: &NewExpr, parent: &dyn Node) {
+  fn visit_new_expr(&mut self, new_expr: &NewExpr, _traversal: &mut Traversal) {
     if let Expr::Ident(ident) = &*new_expr.callee {
       if let Some(args) = &new_expr.args {
         if let Some(regex) = extract_regex(&self.context.scope, ident, args) {
@@ -127,10 +128,13 @@ impl Visit for NoControlRegexVisitor {
         }
       }
     }
-    swc_ecmascript::visit::visit_new_expr(self, new_expr, parent);
   }
 
-  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+  fn visit_call_expr(
+    &mut self,
+    call_expr: &CallExpr,
+    _traversal: &mut Traversal,
+  ) {
     if let ExprOrSuper::Expr(expr) = &call_expr.callee {
       if let Expr::Ident(ident) = expr.as_ref() {
         if let Some(regex) =
@@ -140,7 +144,6 @@ impl Visit for NoControlRegexVisitor {
         }
       }
     }
-    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
   }
 }
 
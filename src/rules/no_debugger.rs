@@ -1,10 +1,8 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
-use super::LintRule;
+use super::{LintRule, RuleExamples};
+use crate::handler::{Handler, Traversal};
 use swc_ecmascript::ast::DebuggerStmt;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -19,13 +17,28 @@ impl LintRule for NoDebugger {
     "no-debugger"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows the use of the `debugger` statement."
+  }
+
+  fn examples(&self) -> RuleExamples {
+    RuleExamples {
+      valid: &["function isTruthy(x) {\n  return Boolean(x);\n}"],
+      invalid: &[
+        "function isTruthy(x) {\n  debugger;\n  return Boolean(x);\n}",
+      ],
+    }
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoDebuggerVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoDebuggerVisitor::new(context)))
   }
 }
 struct NoDebuggerVisitor {
@@ -38,18 +51,17 @@ impl NoDebuggerVisitor {
   }
 }
 
-impl Visit for NoDebuggerVisitor {
-  noop_visit_type!();
-
+impl Handler for NoDebuggerVisitor {
   fn visit_debugger_stmt(
     &mut self,
     debugger_stmt: &DebuggerStmt,
-    _parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
-    self.context.add_diagnostic(
+    self.context.add_diagnostic_with_hint(
       debugger_stmt.span,
       "no-debugger",
       "`debugger` statement is not allowed",
+      "Remove the `debugger` statement",
     );
   }
 }
@@ -66,4 +78,44 @@ mod tests {
       47,
     )
   }
+
+  #[test]
+  fn no_debugger_valid() {
+    assert_lint_ok! {
+      NoDebugger,
+      "function debugger_() { return 1; }",
+      "const debuggerEnabled = true;",
+      "const a = { debugger: 1 }; a.debugger;",
+      "// debugger;\nconst a = 1;",
+      "const s = \"debugger;\";",
+    };
+  }
+
+  #[test]
+  fn no_debugger_invalid() {
+    assert_lint_err!(NoDebugger, "debugger;", 1, 0);
+    assert_lint_err!(NoDebugger, "function f() {\n  debugger;\n}", 2, 2);
+    assert_lint_err!(NoDebugger, "const f = () => {\n  debugger;\n};", 2, 2);
+    assert_lint_err!(
+      NoDebugger,
+      "switch (a) {\n  case 1:\n    debugger;\n  default:\n    debugger;\n}",
+      [(3, 4), (5, 4)]
+    );
+    assert_lint_err!(
+      NoDebugger,
+      "if (a) {\n  while (b) {\n    for (;;) {\n      debugger;\n    }\n  }\n}",
+      4,
+      6
+    );
+  }
+
+  #[test]
+  fn hint() {
+    assert_lint_err_with_hint::<NoDebugger>(
+      "debugger;",
+      1,
+      0,
+      "Remove the `debugger` statement",
+    );
+  }
 }
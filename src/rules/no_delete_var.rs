@@ -1,12 +1,10 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_ecmascript::ast::Expr;
 use swc_ecmascript::ast::UnaryExpr;
 use swc_ecmascript::ast::UnaryOp;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -21,13 +19,19 @@ impl LintRule for NoDeleteVar {
     "no-delete-var"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows the use of the `delete` operator on variables."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoDeleteVarVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoDeleteVarVisitor::new(context)))
   }
 }
 
@@ -41,15 +45,13 @@ impl NoDeleteVarVisitor {
   }
 }
 
-impl Visit for NoDeleteVarVisitor {
-  noop_visit_type!();
-
-  fn visit_unary_expr(&mut self, unary_expr: &UnaryExpr, _parent: &dyn Node) {
-    if unary_expr.op != UnaryOp::Delete {
-      return;
-    }
-
-    if let Expr::Ident(_) = *unary_expr.arg {
+impl Handler for NoDeleteVarVisitor {
+  fn visit_unary_expr(
+    &mut self,
+    unary_expr: &UnaryExpr,
+    _traversal: &mut Traversal,
+  ) {
+    if unary_expr.op == UnaryOp::Delete && is_ident(&unary_expr.arg) {
       self.context.add_diagnostic(
         unary_expr.span,
         "no-delete-var",
@@ -59,6 +61,15 @@ impl Visit for NoDeleteVarVisitor {
   }
 }
 
+/// Whether `expr` is an identifier, possibly wrapped in parentheses.
+fn is_ident(expr: &Expr) -> bool {
+  match expr {
+    Expr::Ident(_) => true,
+    Expr::Paren(paren) => is_ident(&paren.expr),
+    _ => false,
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -71,4 +82,29 @@ mod tests {
       25,
     );
   }
+
+  #[test]
+  fn no_delete_var_valid() {
+    assert_lint_ok! {
+      NoDeleteVar,
+      "delete obj.prop;",
+      "delete obj[key];",
+      "delete (obj).prop;",
+      "delete obj?.prop;",
+      "delete foo();",
+    };
+  }
+
+  #[test]
+  fn no_delete_var_invalid() {
+    assert_lint_err::<NoDeleteVar>("delete (someVar);", 0);
+    assert_lint_err::<NoDeleteVar>("delete ((someVar));", 0);
+    assert_lint_err::<NoDeleteVar>("const deleted = !delete someVar;", 17);
+    assert_lint_err!(
+      NoDeleteVar,
+      "function foo() {\n  if (delete bar) {}\n}",
+      2,
+      6
+    );
+  }
 }
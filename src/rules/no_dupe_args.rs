@@ -1,15 +1,13 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use std::collections::HashSet;
-use swc_common::Span;
+use swc_atoms::JsWord;
 use swc_ecmascript::ast::ArrowExpr;
 use swc_ecmascript::ast::Function;
-use swc_ecmascript::ast::Param;
-use swc_ecmascript::ast::Pat;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
+use swc_ecmascript::ast::Ident;
+use swc_ecmascript::utils::find_ids;
 
 use std::sync::Arc;
 
@@ -24,13 +22,19 @@ impl LintRule for NoDupeArgs {
     "no-dupe-args"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows duplicate parameter names in function definitions."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoDupeArgsVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoDupeArgsVisitor::new(context)))
   }
 }
 
@@ -43,43 +47,38 @@ impl NoDupeArgsVisitor {
     Self { context }
   }
 
-  fn check_pats(&self, span: Span, pats: &[Pat]) {
-    let mut seen: HashSet<String> = HashSet::new();
-
-    for pat in pats {
-      match &pat {
-        Pat::Ident(ident) => {
-          if !seen.insert(ident.sym.to_string()) {
-            self.context.add_diagnostic(
-              span,
-              "no-dupe-args",
-              "Duplicate arguments not allowed",
-            );
-          }
-        }
-        _ => continue,
+  /// Reports every parameter name bound more than once, including names
+  /// bound by destructuring patterns, at its repeated occurrence.
+  fn check_idents(&self, idents: Vec<Ident>) {
+    let mut seen: HashSet<JsWord> = HashSet::new();
+
+    for ident in idents {
+      if !seen.insert(ident.sym.clone()) {
+        self.context.add_diagnostic(
+          ident.span,
+          "no-dupe-args",
+          "Duplicate arguments not allowed",
+        );
       }
     }
   }
-
-  fn check_params(&self, span: Span, params: &[Param]) {
-    let pats = params
-      .iter()
-      .map(|param| param.pat.clone())
-      .collect::<Vec<Pat>>();
-    self.check_pats(span, &pats);
-  }
 }
 
-impl Visit for NoDupeArgsVisitor {
-  noop_visit_type!();
-
-  fn visit_function(&mut self, function: &Function, _parent: &dyn Node) {
-    self.check_params(function.span, &function.params);
+impl Handler for NoDupeArgsVisitor {
+  fn visit_function(
+    &mut self,
+    function: &Function,
+    _traversal: &mut Traversal,
+  ) {
+    self.check_idents(find_ids(&function.params));
   }
 
-  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, _parent: &dyn Node) {
-    self.check_pats(arrow_expr.span, &arrow_expr.params);
+  fn visit_arrow_expr(
+    &mut self,
+    arrow_expr: &ArrowExpr,
+    _traversal: &mut Traversal,
+  ) {
+    self.check_idents(find_ids(&arrow_expr.params));
   }
 }
 
@@ -89,8 +88,32 @@ mod tests {
   use crate::test_util::*;
 
   #[test]
-  fn no_dupe_args_test() {
-    assert_lint_err::<NoDupeArgs>("function dupeArgs1(a, b, a) { }", 0);
-    assert_lint_err::<NoDupeArgs>("const dupeArgs2 = (a, b, a) => { }", 18);
+  fn no_dupe_args_valid() {
+    assert_lint_ok! {
+      NoDupeArgs,
+      "function f(a, b, c) {}",
+      "const f = function (a, { b, c: [d] }, ...e) {};",
+      "function f(a) { return function (a) {}; }",
+      "function f({ a: b }, a) {}",
+    };
+  }
+
+  #[test]
+  fn no_dupe_args_invalid() {
+    assert_lint_err::<NoDupeArgs>("function dupeArgs1(a, b, a) { }", 25);
+    assert_lint_err::<NoDupeArgs>("const dupeArgs2 = (a, b, a) => { }", 25);
+    assert_lint_err::<NoDupeArgs>("function f(a, { a }) {}", 16);
+    assert_lint_err::<NoDupeArgs>("function f(a, [b, { c: a }]) {}", 23);
+    assert_lint_err::<NoDupeArgs>("function f(a, ...a) {}", 17);
+    assert_lint_err_n::<NoDupeArgs>(
+      "const f = function (a, a, a) {};",
+      vec![23, 26],
+    );
+    assert_lint_err!(
+      NoDupeArgs,
+      "function f() {\n  function g(a, a) {}\n}",
+      2,
+      16
+    );
   }
 }
@@ -1,16 +1,14 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
-use std::cmp::Ordering;
-use std::collections::BTreeMap;
-use swc_common::Span;
+use crate::handler::{Handler, Traversal};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use swc_common::{Span, Spanned};
 use swc_ecmascript::ast::{
-  BigInt, Bool, Class, ClassMethod, ComputedPropName, Expr, Ident, Lit,
-  MethodKind, Null, Number, PropName, Str, Tpl,
+  BigInt, Bool, Class, ClassMember, Expr, Ident, Lit, MethodKind, Null, Number,
+  PropName, Str, Tpl,
 };
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -25,181 +23,170 @@ impl LintRule for NoDupeClassMembers {
     "no-dupe-class-members"
   }
 
-  fn lint_module(
-    &self,
-    context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoDupeClassMembersVisitor::new(context);
-    visitor.visit_module(module, module);
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
   }
-}
 
-struct NoDupeClassMembersVisitor {
-  context: Arc<Context>,
-}
+  fn docs(&self) -> &'static str {
+    r#"Disallows duplicate names of class members.
 
-impl NoDupeClassMembersVisitor {
-  fn new(context: Arc<Context>) -> Self {
-    Self { context }
+A later method or field with the same name overrides the earlier one. A
+getter and a setter may share a name, and static members don't conflict
+with instance members. Computed names are only checked if they're
+literals."#
   }
 
-  fn add_diagnostic(&self, span: Span, name: &str) {
-    self.context.add_diagnostic(
-      span,
-      "no-dupe-class-members",
-      &format!("Duplicate name '{}'", name),
-    );
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoDupeClassMembersVisitor::new(context)))
   }
 }
 
-impl Visit for NoDupeClassMembersVisitor {
-  noop_visit_type!();
+#[derive(Clone, Copy)]
+enum MemberKind {
+  /// Method or field
+  Init,
+  Get,
+  Set,
+}
 
-  fn visit_class(&mut self, class: &Class, parent: &dyn Node) {
-    let mut visitor = ClassVisitor::new(self);
-    visitor.visit_class(class, parent);
-    visitor.aggregate_dupes();
+impl From<MethodKind> for MemberKind {
+  fn from(kind: MethodKind) -> Self {
+    match kind {
+      MethodKind::Method => MemberKind::Init,
+      MethodKind::Getter => MemberKind::Get,
+      MethodKind::Setter => MemberKind::Set,
+    }
   }
 }
 
-struct ClassVisitor<'a> {
-  root_visitor: &'a NoDupeClassMembersVisitor,
-  appeared_methods: BTreeMap<MethodToCheck, Vec<(Span, String)>>,
-}
+/// Kinds of the members seen so far for a name.
+struct NameState {
+  first: Span,
+  init: bool,
+  get: bool,
+  set: bool,
+}
+
+impl NameState {
+  fn new(first: Span, kind: MemberKind) -> Self {
+    let mut state = Self {
+      first,
+      init: false,
+      get: false,
+      set: false,
+    };
+    state.add(kind);
+    state
+  }
 
-impl<'a> ClassVisitor<'a> {
-  fn new(root_visitor: &'a NoDupeClassMembersVisitor) -> Self {
-    Self {
-      root_visitor,
-      appeared_methods: BTreeMap::new(),
+  /// Records a member of `kind`, returning whether it redefines the name.
+  /// Only a getter and a setter may share a name.
+  fn add(&mut self, kind: MemberKind) -> bool {
+    let is_duplicate = match kind {
+      MemberKind::Init => true,
+      MemberKind::Get => self.init || self.get,
+      MemberKind::Set => self.init || self.set,
+    };
+    match kind {
+      MemberKind::Init => self.init = true,
+      MemberKind::Get => self.get = true,
+      MemberKind::Set => self.set = true,
     }
+    is_duplicate
   }
+}
 
-  fn aggregate_dupes(&self) {
-    self
-      .appeared_methods
-      .values()
-      .filter(|m| m.len() >= 2)
-      .flatten()
-      .for_each(|(span, name)| {
-        self.root_visitor.add_diagnostic(*span, name);
-      });
+/// Returns the normalized name, whether it's static and the kind of the
+/// member, or `None` if it can't be checked.
+fn member_key(member: &ClassMember) -> Option<((String, bool), MemberKind)> {
+  match member {
+    // Overload signatures have no body.
+    ClassMember::Method(method) if method.function.body.is_some() => {
+      let name = normalize_prop_name(&method.key)?;
+      Some(((name, method.is_static), method.kind.into()))
+    }
+    ClassMember::ClassProp(prop) => {
+      let name = if prop.computed {
+        normalize_computed_name(&prop.key)?
+      } else {
+        match &*prop.key {
+          Expr::Ident(Ident { sym, .. }) => sym.to_string(),
+          expr => normalize_computed_name(expr)?,
+        }
+      };
+      Some(((name, prop.is_static), MemberKind::Init))
+    }
+    _ => None,
   }
 }
 
-impl<'a> Visit for ClassVisitor<'a> {
-  noop_visit_type!();
+struct NoDupeClassMembersVisitor {
+  context: Arc<Context>,
+}
 
-  fn visit_class(&mut self, class: &Class, parent: &dyn Node) {
-    let mut visitor = ClassVisitor::new(self.root_visitor);
-    swc_ecmascript::visit::visit_class(&mut visitor, class, parent);
-    visitor.aggregate_dupes();
+impl NoDupeClassMembersVisitor {
+  fn new(context: Arc<Context>) -> Self {
+    Self { context }
   }
+}
 
-  fn visit_class_method(
-    &mut self,
-    class_method: &ClassMethod,
-    parent: &dyn Node,
-  ) {
-    if class_method.function.body.is_some() {
-      if let Some(m) = MethodToCheck::new(
-        &class_method.key,
-        class_method.kind,
-        class_method.is_static,
-      ) {
-        let name = m.normalized_name.clone();
-        self
-          .appeared_methods
-          .entry(m)
-          .or_insert_with(Vec::new)
-          .push((class_method.span, name));
+impl Handler for NoDupeClassMembersVisitor {
+  fn visit_class(&mut self, class: &Class, _traversal: &mut Traversal) {
+    let mut names: HashMap<(String, bool), NameState> = HashMap::new();
+
+    for member in &class.body {
+      let (key, kind) = match member_key(member) {
+        Some(key) => key,
+        None => continue,
+      };
+      let span = member.span();
+      match names.entry(key) {
+        Entry::Occupied(mut entry) => {
+          if entry.get_mut().add(kind) {
+            self.context.add_diagnostic_with_related(
+              span,
+              "no-dupe-class-members",
+              &format!("Duplicate name '{}'", entry.key().0),
+              vec![(entry.get().first, "first defined here")],
+            );
+          }
+        }
+        Entry::Vacant(entry) => {
+          entry.insert(NameState::new(span, kind));
+        }
       }
     }
-    swc_ecmascript::visit::visit_class_method(self, class_method, parent);
   }
 }
 
 fn normalize_prop_name(name: &PropName) -> Option<String> {
-  let normalized = match *name {
-    PropName::Ident(Ident { ref sym, .. }) => sym.to_string(),
-    PropName::Str(Str { ref value, .. }) => value.to_string(),
-    PropName::Num(Number { ref value, .. }) => value.to_string(),
-    PropName::Computed(ComputedPropName { ref expr, .. }) => match &**expr {
-      Expr::Lit(Lit::Str(Str { ref value, .. })) => value.to_string(),
-      Expr::Lit(Lit::Bool(Bool { ref value, .. })) => value.to_string(),
-      Expr::Lit(Lit::Null(Null { .. })) => "null".to_string(),
-      Expr::Lit(Lit::Num(Number { ref value, .. })) => value.to_string(),
-      Expr::Lit(Lit::BigInt(BigInt { ref value, .. })) => value.to_string(),
-      Expr::Tpl(Tpl {
-        ref quasis,
-        ref exprs,
-        ..
-      }) if exprs.is_empty() => {
-        quasis.iter().next().map(|q| q.raw.value.to_string())?
-      }
-      _ => return None,
-    },
+  let normalized = match name {
+    PropName::Ident(Ident { sym, .. }) => sym.to_string(),
+    PropName::Str(Str { value, .. }) => value.to_string(),
+    PropName::Num(Number { value, .. }) => value.to_string(),
+    PropName::Computed(computed) => normalize_computed_name(&computed.expr)?,
   };
 
   Some(normalized)
 }
 
-struct MethodToCheck {
-  normalized_name: String,
-  kind: MethodKind,
-  is_static: bool,
-}
-
-impl MethodToCheck {
-  fn new(name: &PropName, kind: MethodKind, is_static: bool) -> Option<Self> {
-    let normalized_name = normalize_prop_name(name)?;
-    Some(Self {
-      normalized_name,
-      kind,
-      is_static,
-    })
-  }
-}
-
-impl PartialEq for MethodToCheck {
-  fn eq(&self, other: &Self) -> bool {
-    if self.normalized_name != other.normalized_name {
-      return false;
-    }
-
-    if self.is_static != other.is_static {
-      return false;
+fn normalize_computed_name(expr: &Expr) -> Option<String> {
+  let normalized = match expr {
+    Expr::Lit(Lit::Str(Str { value, .. })) => value.to_string(),
+    Expr::Lit(Lit::Bool(Bool { value, .. })) => value.to_string(),
+    Expr::Lit(Lit::Null(Null { .. })) => "null".to_string(),
+    Expr::Lit(Lit::Num(Number { value, .. })) => value.to_string(),
+    Expr::Lit(Lit::BigInt(BigInt { value, .. })) => value.to_string(),
+    Expr::Tpl(Tpl { quasis, exprs, .. }) if exprs.is_empty() => {
+      quasis.iter().next().map(|q| q.raw.value.to_string())?
     }
+    _ => return None,
+  };
 
-    match (self.kind, other.kind) {
-      (MethodKind::Getter, MethodKind::Setter)
-      | (MethodKind::Setter, MethodKind::Getter) => false,
-      _ => true,
-    }
-  }
-}
-
-impl Eq for MethodToCheck {}
-
-impl PartialOrd for MethodToCheck {
-  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-    Some(self.cmp(other))
-  }
-}
-
-impl Ord for MethodToCheck {
-  fn cmp(&self, other: &Self) -> Ordering {
-    self
-      .normalized_name
-      .cmp(&other.normalized_name)
-      .then(self.is_static.cmp(&other.is_static))
-      .then_with(|| match (self.kind, other.kind) {
-        (MethodKind::Getter, MethodKind::Setter) => Ordering::Less,
-        (MethodKind::Setter, MethodKind::Getter) => Ordering::Greater,
-        _ => Ordering::Equal,
-      })
-  }
+  Some(normalized)
 }
 
 #[cfg(test)]
@@ -478,7 +465,7 @@ class Foo {
   bar() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -488,7 +475,7 @@ class Foo {
   bar() {}
 };
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -498,7 +485,7 @@ class Foo {
   'bar'() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -508,7 +495,7 @@ class Foo {
   1e1() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -518,7 +505,7 @@ class Foo {
   ['bar']() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -528,7 +515,7 @@ class Foo {
   static bar() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -538,7 +525,7 @@ class Foo {
   set ['bar'](val: number) {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -548,7 +535,7 @@ class Foo {
   ['']() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -558,7 +545,7 @@ class Foo {
   [`bar`]() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -568,7 +555,7 @@ class Foo {
   static get ['bar']() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -578,7 +565,7 @@ class Foo {
   [`bar`]() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -588,7 +575,7 @@ class Foo {
   'bar'() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -598,7 +585,7 @@ class Foo {
   static [`bar`]() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -608,7 +595,7 @@ class Foo {
   ['constructor']() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -618,7 +605,7 @@ class Foo {
   static ['constructor']() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -628,7 +615,7 @@ class Foo {
   [123]() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -638,7 +625,7 @@ class Foo {
   16() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -648,7 +635,7 @@ class Foo {
   [1e2]() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -658,7 +645,7 @@ class Foo {
   [`123`]() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -668,7 +655,7 @@ class Foo {
   static [0o101]() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -678,7 +665,7 @@ class Foo {
   123() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -688,7 +675,7 @@ class Foo {
   'null'() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -698,7 +685,7 @@ class Foo {
   get bar() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -709,7 +696,7 @@ class Foo {
   get bar() {}
 }
       "#,
-      vec![(3, 2), (4, 2), (5, 2)],
+      vec![(4, 2), (5, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -719,7 +706,7 @@ class Foo {
   static bar() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -729,7 +716,7 @@ class Foo {
   bar() {}
 }
       "#,
-      vec![(3, 2), (4, 2)],
+      vec![(4, 2)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -743,7 +730,7 @@ class Foo {
   }
 }
       "#,
-      vec![(5, 6), (6, 6)],
+      vec![(6, 6)],
     );
 
     assert_lint_err_on_line_n::<NoDupeClassMembers>(
@@ -755,7 +742,51 @@ class Foo {
   set bar(value: number) {}
 }
       "#,
-      vec![(5, 2), (6, 2)],
+      vec![(6, 2)],
+    );
+  }
+
+  #[test]
+  fn no_dupe_class_members_fields() {
+    assert_lint_ok_n::<NoDupeClassMembers>(vec![
+      "class Foo { bar = 1; baz = 2; }",
+      "class Foo { static bar = 1; bar = 2; }",
+      "class Foo { static bar() {} bar = 2; }",
+      "class Foo { [bar] = 1; [bar] = 2; }",
+      "class Foo { get bar() {} set bar(v) {} static get bar() {} }",
+    ]);
+    assert_lint_err::<NoDupeClassMembers>(
+      "class Foo { bar() {} bar = 1; }",
+      21,
+    );
+    assert_lint_err::<NoDupeClassMembers>(
+      "class Foo { bar = 1; bar() {} }",
+      21,
+    );
+    assert_lint_err::<NoDupeClassMembers>(
+      "class Foo { bar = 1; ['bar'] = 2; }",
+      21,
+    );
+    assert_lint_err::<NoDupeClassMembers>(
+      "class Foo { static 'bar' = 1; static bar = 2; }",
+      30,
+    );
+    assert_lint_err::<NoDupeClassMembers>(
+      "class Foo { get bar() {} get bar() {} }",
+      25,
     );
   }
+
+  #[test]
+  fn no_dupe_class_members_related() {
+    let diagnostics = lint_with_rule(
+      NoDupeClassMembers::new(),
+      "class Foo {\n  bar() {}\n  bar() {}\n}",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 3);
+    assert_eq!(diagnostics[0].related.len(), 1);
+    assert_eq!(diagnostics[0].related[0].range.start.line, 2);
+    assert_eq!(diagnostics[0].related[0].message, "first defined here");
+  }
 }
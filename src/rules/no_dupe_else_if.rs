@@ -1,12 +1,10 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::{Context, LintRule};
+use crate::handler::{Handler, Traversal};
 use crate::swc_util::DropSpan;
 use std::collections::HashSet;
 use swc_common::{Span, Spanned};
-use swc_ecmascript::ast::{
-  BinExpr, BinaryOp, Expr, IfStmt, Module, ParenExpr, Stmt,
-};
-use swc_ecmascript::visit::{noop_visit_type, Node, Visit};
+use swc_ecmascript::ast::{BinExpr, BinaryOp, Expr, IfStmt, ParenExpr, Stmt};
 
 use std::sync::Arc;
 
@@ -21,9 +19,19 @@ impl LintRule for NoDupeElseIf {
     "no-dupe-else-if"
   }
 
-  fn lint_module(&self, context: Arc<Context>, module: &Module) {
-    let mut visitor = NoDupeElseIfVisitor::new(context);
-    visitor.visit_module(module, module);
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows duplicate conditions in `if`-`else if` chains."
+  }
+
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoDupeElseIfVisitor::new(context)))
   }
 }
 
@@ -43,10 +51,8 @@ impl NoDupeElseIfVisitor {
   }
 }
 
-impl Visit for NoDupeElseIfVisitor {
-  noop_visit_type!();
-
-  fn visit_if_stmt(&mut self, if_stmt: &IfStmt, parent: &dyn Node) {
+impl Handler for NoDupeElseIfVisitor {
+  fn visit_if_stmt(&mut self, if_stmt: &IfStmt, _traversal: &mut Traversal) {
     let span = if_stmt.test.span();
 
     // This check is necessary to avoid outputting the same errors multiple times.
@@ -105,8 +111,6 @@ impl Visit for NoDupeElseIfVisitor {
         }
       }
     }
-
-    swc_ecmascript::visit::visit_if_stmt(self, if_stmt, parent);
   }
 }
 
@@ -1,10 +1,12 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use crate::swc_util::Key;
-use std::collections::{BTreeSet, HashSet};
-use swc_ecmascript::ast::{Module, ObjectLit};
-use swc_ecmascript::visit::{noop_visit_type, Node, Visit};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use swc_common::{Span, Spanned};
+use swc_ecmascript::ast::{ObjectLit, Prop, PropName, PropOrSpread};
 
 use std::sync::Arc;
 
@@ -19,9 +21,23 @@ impl LintRule for NoDupeKeys {
     "no-dupe-keys"
   }
 
-  fn lint_module(&self, context: Arc<Context>, module: &Module) {
-    let mut visitor = NoDupeKeysVisitor::new(context);
-    visitor.visit_module(module, module);
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows duplicate keys in object literals.
+
+Keys are compared by their string value, so `1`, `0x1` and `"1"` are the
+same key. A getter and a setter may share a key, and computed keys are
+ignored."#
+  }
+
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoDupeKeysVisitor::new(context)))
   }
 }
 
@@ -35,30 +51,100 @@ impl NoDupeKeysVisitor {
   }
 }
 
-impl Visit for NoDupeKeysVisitor {
-  noop_visit_type!();
+#[derive(Clone, Copy)]
+enum PropKind {
+  Init,
+  Get,
+  Set,
+}
+
+/// Kinds of the properties seen so far for a key.
+struct KeyState {
+  first: Span,
+  init: bool,
+  get: bool,
+  set: bool,
+}
+
+impl KeyState {
+  fn new(first: Span, kind: PropKind) -> Self {
+    let mut state = Self {
+      first,
+      init: false,
+      get: false,
+      set: false,
+    };
+    state.add(kind);
+    state
+  }
+
+  /// Records a property of `kind`, returning whether it redefines the key.
+  /// Only a getter and a setter may define the same key.
+  fn add(&mut self, kind: PropKind) -> bool {
+    let is_duplicate = match kind {
+      PropKind::Init => true,
+      PropKind::Get => self.init || self.get,
+      PropKind::Set => self.init || self.set,
+    };
+    match kind {
+      PropKind::Init => self.init = true,
+      PropKind::Get => self.get = true,
+      PropKind::Set => self.set = true,
+    }
+    is_duplicate
+  }
+}
+
+fn prop_key(prop: &PropOrSpread) -> Option<(String, PropKind)> {
+  let prop = match prop {
+    PropOrSpread::Prop(prop) => prop,
+    PropOrSpread::Spread(_) => return None,
+  };
+  let (key, kind) = match &**prop {
+    Prop::Shorthand(ident) => {
+      return Some((ident.sym.to_string(), PropKind::Init))
+    }
+    Prop::KeyValue(key_value) => (&key_value.key, PropKind::Init),
+    Prop::Method(method) => (&method.key, PropKind::Init),
+    Prop::Getter(getter) => (&getter.key, PropKind::Get),
+    Prop::Setter(setter) => (&setter.key, PropKind::Set),
+    Prop::Assign(_) => return None,
+  };
+  match key {
+    PropName::Computed(_) => None,
+    _ => key.get_key().map(|key| (key, kind)),
+  }
+}
 
-  fn visit_object_lit(&mut self, obj_lit: &ObjectLit, _parent: &dyn Node) {
-    let mut keys: HashSet<String> = HashSet::new();
-    let mut duplicates: BTreeSet<String> = BTreeSet::new();
+impl Handler for NoDupeKeysVisitor {
+  fn visit_object_lit(
+    &mut self,
+    obj_lit: &ObjectLit,
+    _traversal: &mut Traversal,
+  ) {
+    let mut keys: HashMap<String, KeyState> = HashMap::new();
 
     for prop in &obj_lit.props {
-      if let Some(key) = prop.get_key() {
-        if keys.contains(&key) {
-          duplicates.insert(key);
-        } else {
-          keys.insert(key);
+      let (key, kind) = match prop_key(prop) {
+        Some(key) => key,
+        None => continue,
+      };
+      match keys.entry(key) {
+        Entry::Occupied(mut entry) => {
+          if entry.get_mut().add(kind) {
+            self.context.add_diagnostic_with_related(
+              prop.span(),
+              "no-dupe-keys",
+              format!("Duplicate key '{}'", entry.key()).as_str(),
+              vec![(entry.get().first, "first defined here")],
+            );
+          }
+        }
+        Entry::Vacant(entry) => {
+          entry.insert(KeyState::new(prop.span(), kind));
         }
       }
     }
-
-    for key in duplicates {
-      self.context.add_diagnostic(
-        obj_lit.span,
-        "no-dupe-keys",
-        format!("Duplicate key '{}'", key).as_str(),
-      );
-    }
   }
 }
 
@@ -79,11 +165,23 @@ mod tests {
     );
   }
 
+  #[test]
+  fn it_passes_for_getter_and_setter_pairs() {
+    assert_lint_ok! {
+      NoDupeKeys,
+      "var foo = { get bar() { return 1; }, set bar(v) {} };",
+      "var foo = { set bar(v) {}, get bar() { return 1; } };",
+      "var foo = { [bar]: 1, [bar]: 2 };",
+      "var foo = { ['bar']: 1, bar: 2 };",
+      "var foo = { ...bar, ...bar };",
+    };
+  }
+
   #[test]
   fn it_fails_when_there_are_duplicate_keys() {
     assert_lint_err::<NoDupeKeys>(
       r#"var foo = { bar: "baz", bar: "qux" };"#,
-      10,
+      24,
     );
   }
 
@@ -91,7 +189,7 @@ mod tests {
   fn it_fails_when_there_are_multiple_duplicate_keys() {
     assert_lint_err_n::<NoDupeKeys>(
       r#"var foo = { bar: "baz", bar: "qux", quux: "boom", quux: "bang" };"#,
-      vec![10, 10],
+      vec![24, 50],
     );
   }
 
@@ -99,28 +197,77 @@ mod tests {
   fn it_fails_when_there_are_duplicate_string_keys() {
     assert_lint_err::<NoDupeKeys>(
       r#"var foo = { bar: "baz", "bar": "qux" };"#,
-      10,
+      24,
     );
   }
 
   #[test]
   fn it_fails_when_there_are_duplicate_numeric_keys() {
-    assert_lint_err::<NoDupeKeys>(r#"var foo = { 1: "baz", 0x1: "qux" };"#, 10);
+    assert_lint_err::<NoDupeKeys>(r#"var foo = { 1: "baz", 0x1: "qux" };"#, 22);
+    assert_lint_err::<NoDupeKeys>(r#"var foo = { 1: "baz", "1": "qux" };"#, 22);
+    assert_lint_err::<NoDupeKeys>(r#"var foo = { 1.0: "a", 1: "b" };"#, 22);
   }
 
   #[test]
   fn it_fails_when_there_are_duplicate_getter_keys() {
     assert_lint_err::<NoDupeKeys>(
       r#"var foo = { bar: "baz", get bar() {} };"#,
-      10,
+      24,
+    );
+    assert_lint_err::<NoDupeKeys>(
+      r#"var foo = { get bar() {}, get bar() {} };"#,
+      26,
     );
   }
 
   #[test]
   fn it_fails_when_there_are_duplicate_setter_keys() {
     assert_lint_err::<NoDupeKeys>(
-      r#"var foo = { bar: "baz", set bar() {} };"#,
-      10,
+      r#"var foo = { bar: "baz", set bar(v) {} };"#,
+      24,
+    );
+    assert_lint_err::<NoDupeKeys>(
+      r#"var foo = { get bar() {}, set bar(v) {}, bar: 1 };"#,
+      41,
+    );
+  }
+
+  #[test]
+  fn it_checks_nested_objects_independently() {
+    assert_lint_err::<NoDupeKeys>(
+      r#"var foo = { bar: 1, baz: { bar: 2, bar: 3 } };"#,
+      35,
+    );
+    assert_lint_err!(
+      NoDupeKeys,
+      "var foo = {\n  a: { b: 1, b: 2 },\n  a: 3,\n};",
+      [(2, 13), (3, 2)]
+    );
+  }
+
+  #[test]
+  fn it_points_at_first_and_duplicate_keys() {
+    let diagnostics = lint_with_rule(
+      NoDupeKeys::new(),
+      "var foo = {\n  bar: 1,\n  baz: 2,\n  bar: 3,\n  'bar': 4,\n};",
+    );
+    let locations = diagnostics
+      .iter()
+      .map(|d| {
+        let related = &d.related[0];
+        (
+          (d.range.start.line, d.range.start.col),
+          (related.range.start.line, related.range.start.col),
+          related.message.as_str(),
+        )
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(
+      locations,
+      vec![
+        ((4, 2), (2, 2), "first defined here"),
+        ((5, 2), (2, 2), "first defined here"),
+      ]
     );
   }
 }
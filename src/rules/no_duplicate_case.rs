@@ -1,11 +1,10 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
-use std::collections::HashSet;
+use crate::handler::{Handler, Traversal};
 use swc_common::Spanned;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
+use swc_ecmascript::ast::Expr;
+use swc_ecmascript::utils::drop_span;
 
 use std::sync::Arc;
 
@@ -20,13 +19,19 @@ impl LintRule for NoDuplicateCase {
     "no-duplicate-case"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows duplicate test expressions in `case` clauses."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoDuplicateCaseVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoDuplicateCaseVisitor::new(context)))
   }
 }
 
@@ -40,27 +45,27 @@ impl NoDuplicateCaseVisitor {
   }
 }
 
-impl Visit for NoDuplicateCaseVisitor {
-  noop_visit_type!();
-
+impl Handler for NoDuplicateCaseVisitor {
   fn visit_switch_stmt(
     &mut self,
     switch_stmt: &swc_ecmascript::ast::SwitchStmt,
-    _parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
-    // Works like in ESLint - by comparing text repr of case statement
-    let mut seen: HashSet<String> = HashSet::new();
+    // Like ESLint, which compares the tokens of the tests, formatting and
+    // comments are ignored by comparing the syntax trees without spans.
+    let mut seen: Vec<Expr> = vec![];
 
     for case in &switch_stmt.cases {
       if let Some(test) = &case.test {
-        let span = test.span();
-        let test_txt = self.context.source_map.span_to_snippet(span).unwrap();
-        if !seen.insert(test_txt) {
+        let test_expr = drop_span((**test).clone());
+        if seen.contains(&test_expr) {
           self.context.add_diagnostic(
-            span,
+            test.span(),
             "no-duplicate-case",
             "Duplicate values in `case` are not allowed",
           );
+        } else {
+          seen.push(test_expr);
         }
       }
     }
@@ -92,4 +97,49 @@ switch (someText) {
       9,
     );
   }
+
+  #[test]
+  fn no_duplicate_case_valid() {
+    assert_lint_ok! {
+      NoDuplicateCase,
+      "switch (a) { case 1: break; case \"1\": break; }",
+      "switch (a) { case foo.bar: break; case foo.baz: break; }",
+      "switch (a) { case `a`: break; case \"a\": break; case `b`: break; }",
+      "switch (a) { case \"a b\": break; case \"ab\": break; }",
+      "switch (a) { case typeof b: break; case typeofb: break; }",
+      "switch (a) { case 1: switch (b) { case 1: break; } }",
+      "switch (a) { case / a/.source: break; case /a/.source: break; }",
+      "switch (a) { case a++ + b: break; case a + ++b: break; }",
+    };
+  }
+
+  #[test]
+  fn no_duplicate_case_invalid() {
+    assert_lint_err::<NoDuplicateCase>(
+      "switch (a) { case 1: break; case 1: break; }",
+      33,
+    );
+    assert_lint_err::<NoDuplicateCase>(
+      "switch (a) { case foo.bar: break; case foo . bar: break; }",
+      39,
+    );
+    assert_lint_err::<NoDuplicateCase>(
+      "switch (a) { case `a${b}`: break; case `a${b}`: break; }",
+      39,
+    );
+    assert_lint_err_n::<NoDuplicateCase>(
+      "switch (a) { case f(1, 2): case f(1,2): case f( 1, 2 ): break; }",
+      vec![32, 45],
+    );
+    assert_lint_err::<NoDuplicateCase>(
+      "switch (x) { case a /* x */ + b: break; case a+b: break; }",
+      45,
+    );
+    assert_lint_err!(
+      NoDuplicateCase,
+      "switch (a) {\n  case 1:\n    switch (b) { case 2: case 2: }\n}",
+      3,
+      30
+    );
+  }
 }
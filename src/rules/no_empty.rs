@@ -1,27 +1,70 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
-use super::{Context, LintRule};
+use super::{Context, LintRule, RuleConfigError};
+use crate::handler::{walk, Handler, Traversal};
 use swc_ecmascript::ast::{
-  ArrowExpr, BlockStmt, BlockStmtOrExpr, Constructor, Function, Module,
+  ArrowExpr, BlockStmt, BlockStmtOrExpr, CatchClause, Constructor, Function,
   SwitchStmt,
 };
-use swc_ecmascript::visit::{noop_visit_type, Node, Visit};
 
 use std::sync::Arc;
 
-pub struct NoEmpty;
+pub struct NoEmpty {
+  allow_empty_catch: bool,
+}
 
 impl LintRule for NoEmpty {
   fn new() -> Box<Self> {
-    Box::new(NoEmpty)
+    Box::new(NoEmpty {
+      allow_empty_catch: false,
+    })
+  }
+
+  fn new_with_config(
+    &self,
+    config: serde_json::Value,
+  ) -> Result<Box<dyn LintRule>, RuleConfigError> {
+    let allow_empty_catch = match config.get("allowEmptyCatch") {
+      None => false,
+      Some(serde_json::Value::Bool(allow)) => *allow,
+      Some(value) => {
+        return Err(RuleConfigError {
+          code: "no-empty".to_string(),
+          message: format!(
+            "expected allowEmptyCatch to be a boolean, got {}",
+            value
+          ),
+        })
+      }
+    };
+    Ok(Box::new(NoEmpty { allow_empty_catch }))
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(serde_json::json!({
+      "type": "object",
+      "properties": {
+        "allowEmptyCatch": { "type": "boolean" }
+      },
+      "additionalProperties": false
+    }))
   }
 
   fn code(&self) -> &'static str {
     "no-empty"
   }
 
-  fn lint_module(&self, context: Arc<Context>, module: &Module) {
-    let mut visitor = NoEmptyVisitor::new(context);
-    visitor.visit_module(module, module);
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoEmptyVisitor::new(
+      context,
+      self.allow_empty_catch,
+    )))
   }
 
   fn docs(&self) -> &'static str {
@@ -29,6 +72,8 @@ impl LintRule for NoEmpty {
 
 Empty block statements are legal but often represent that something was missed and can make code less readable. This rule ignores block statements that only contain comments. This rule also ignores empty constructors and function bodies (including arrow functions), which are covered by the `no-empty-function` rule.
 
+The rule accepts `{ "allowEmptyCatch": true }` as an option to allow empty `catch` clauses.
+
 ### Valid:
 ```typescript
 if (foo) {
@@ -82,64 +127,91 @@ try {
 
 struct NoEmptyVisitor {
   context: Arc<Context>,
+  allow_empty_catch: bool,
 }
 
 impl NoEmptyVisitor {
-  fn new(context: Arc<Context>) -> Self {
-    Self { context }
+  fn new(context: Arc<Context>, allow_empty_catch: bool) -> Self {
+    Self {
+      context,
+      allow_empty_catch,
+    }
   }
 }
 
-impl Visit for NoEmptyVisitor {
-  noop_visit_type!();
-
-  fn visit_function(&mut self, function: &Function, _parent: &dyn Node) {
+impl Handler for NoEmptyVisitor {
+  fn visit_function(&mut self, function: &Function, traversal: &mut Traversal) {
     // Empty functions shouldn't be caught by this rule.
     // Because function's body is a block statement, we're gonna
     // manually visit each member; otherwise rule would produce errors
     // for empty function body.
+    traversal.skip_children();
     if let Some(body) = &function.body {
       for stmt in &body.stmts {
-        swc_ecmascript::visit::visit_stmt(self, stmt, body);
+        walk(self, stmt);
       }
     }
   }
 
-  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, _parent: &dyn Node) {
+  fn visit_arrow_expr(
+    &mut self,
+    arrow_expr: &ArrowExpr,
+    traversal: &mut Traversal,
+  ) {
     // Similar to the above, empty arrow expressions shouldn't be caught.
+    traversal.skip_children();
     if let BlockStmtOrExpr::BlockStmt(block_stmt) = &arrow_expr.body {
       for stmt in &block_stmt.stmts {
-        swc_ecmascript::visit::visit_stmt(self, stmt, block_stmt);
+        walk(self, stmt);
       }
     }
   }
 
-  fn visit_constructor(&mut self, cons: &Constructor, _parent: &dyn Node) {
+  fn visit_constructor(
+    &mut self,
+    cons: &Constructor,
+    traversal: &mut Traversal,
+  ) {
     // Similar to the above, empty constructors shouldn't be caught.
+    traversal.skip_children();
     if let Some(body) = &cons.body {
       for stmt in &body.stmts {
-        swc_ecmascript::visit::visit_stmt(self, stmt, body);
+        walk(self, stmt);
       }
     }
   }
 
-  fn visit_block_stmt(&mut self, block_stmt: &BlockStmt, _parent: &dyn Node) {
-    if block_stmt.stmts.is_empty() {
-      if !block_stmt.contains_comments(&self.context) {
-        self.context.add_diagnostic(
-          block_stmt.span,
-          "no-empty",
-          "Empty block statement",
-        );
-      }
-    } else {
-      for stmt in &block_stmt.stmts {
-        self.visit_stmt(stmt, _parent);
-      }
+  fn visit_block_stmt(
+    &mut self,
+    block_stmt: &BlockStmt,
+    _traversal: &mut Traversal,
+  ) {
+    if block_stmt.stmts.is_empty()
+      && !block_stmt.contains_comments(&self.context)
+    {
+      self.context.add_diagnostic(
+        block_stmt.span,
+        "no-empty",
+        "Empty block statement",
+      );
+    }
+  }
+
+  fn visit_catch_clause(
+    &mut self,
+    catch_clause: &CatchClause,
+    traversal: &mut Traversal,
+  ) {
+    if self.allow_empty_catch && catch_clause.body.stmts.is_empty() {
+      traversal.skip_children();
     }
   }
 
-  fn visit_switch_stmt(&mut self, switch: &SwitchStmt, _parent: &dyn Node) {
+  fn visit_switch_stmt(
+    &mut self,
+    switch: &SwitchStmt,
+    _traversal: &mut Traversal,
+  ) {
     if switch.cases.is_empty() {
       self.context.add_diagnostic(
         switch.span,
@@ -157,9 +229,8 @@ trait ContainsComments {
 impl ContainsComments for BlockStmt {
   fn contains_comments(&self, context: &Context) -> bool {
     context
-      .leading_comments
-      .values()
-      .flatten()
+      .all_comments()
+      .iter()
       .any(|comment| self.span.contains(comment.span))
   }
 }
@@ -301,4 +372,38 @@ if (foo) { }
   fn it_fails_for_a_nested_empty_switch() {
     assert_lint_err::<NoEmpty>("if (foo) { switch (foo) { } }", 11);
   }
+
+  #[test]
+  fn it_fails_for_an_empty_block_in_a_switch_case() {
+    assert_lint_err::<NoEmpty>("switch (foo) { case 1: if (bar) {} }", 32);
+  }
+
+  #[test]
+  fn it_fails_for_an_empty_standalone_block() {
+    assert_lint_err::<NoEmpty>("{}", 0);
+    assert_lint_err_n::<NoEmpty>("if (foo) {} else {}", vec![9, 17]);
+  }
+
+  #[test]
+  fn allow_empty_catch() {
+    let config = serde_json::json!({ "allowEmptyCatch": true });
+    assert_lint_ok_with_config::<NoEmpty>(
+      "try { foo(); } catch (err) {}",
+      config.clone(),
+    );
+    assert_lint_err_with_config::<NoEmpty>(
+      "try { foo(); } catch (err) { if (err) {} }",
+      config.clone(),
+      38,
+    );
+    assert_lint_err_with_config::<NoEmpty>("try {} catch (err) {}", config, 4);
+    assert_lint_err::<NoEmpty>("try { foo(); } catch (err) {}", 27);
+
+    assert!(NoEmpty::new()
+      .new_with_config(serde_json::json!(null))
+      .is_ok());
+    assert!(NoEmpty::new()
+      .new_with_config(serde_json::json!({ "allowEmptyCatch": 1 }))
+      .is_err());
+  }
 }
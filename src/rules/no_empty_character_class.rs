@@ -1,10 +1,8 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_ecmascript::ast::Regex;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -19,13 +17,19 @@ impl LintRule for NoEmptyCharacterClass {
     "no-empty-character-class"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows empty character classes in regular expressions."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoEmptyCharacterClassVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoEmptyCharacterClassVisitor::new(context)))
   }
 }
 
@@ -39,10 +43,8 @@ impl NoEmptyCharacterClassVisitor {
   }
 }
 
-impl Visit for NoEmptyCharacterClassVisitor {
-  noop_visit_type!();
-
-  fn visit_regex(&mut self, regex: &Regex, _parent: &dyn Node) {
+impl Handler for NoEmptyCharacterClassVisitor {
+  fn visit_regex(&mut self, regex: &Regex, _traversal: &mut Traversal) {
     let raw_regex = self
       .context
       .source_map
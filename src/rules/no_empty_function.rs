@@ -0,0 +1,407 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, RuleConfigError, RuleExamples};
+use crate::handler::{Handler, Traversal};
+use swc_ecmascript::ast::{
+  ArrowExpr, BlockStmt, BlockStmtOrExpr, ClassMethod, Constructor, FnDecl,
+  FnExpr, Function, GetterProp, MethodKind, MethodProp, ParamOrTsParamProp,
+  PrivateMethod, SetterProp,
+};
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum FunctionKind {
+  Functions,
+  ArrowFunctions,
+  GeneratorFunctions,
+  AsyncFunctions,
+  Methods,
+  GeneratorMethods,
+  AsyncMethods,
+  Getters,
+  Setters,
+  Constructors,
+}
+
+impl FunctionKind {
+  const ALL: &'static [FunctionKind] = &[
+    FunctionKind::Functions,
+    FunctionKind::ArrowFunctions,
+    FunctionKind::GeneratorFunctions,
+    FunctionKind::AsyncFunctions,
+    FunctionKind::Methods,
+    FunctionKind::GeneratorMethods,
+    FunctionKind::AsyncMethods,
+    FunctionKind::Getters,
+    FunctionKind::Setters,
+    FunctionKind::Constructors,
+  ];
+
+  /// Name of the kind in the `allow` option.
+  fn option_name(self) -> &'static str {
+    match self {
+      FunctionKind::Functions => "functions",
+      FunctionKind::ArrowFunctions => "arrowFunctions",
+      FunctionKind::GeneratorFunctions => "generatorFunctions",
+      FunctionKind::AsyncFunctions => "asyncFunctions",
+      FunctionKind::Methods => "methods",
+      FunctionKind::GeneratorMethods => "generatorMethods",
+      FunctionKind::AsyncMethods => "asyncMethods",
+      FunctionKind::Getters => "getters",
+      FunctionKind::Setters => "setters",
+      FunctionKind::Constructors => "constructors",
+    }
+  }
+
+  fn description(self) -> &'static str {
+    match self {
+      FunctionKind::Functions => "function",
+      FunctionKind::ArrowFunctions => "arrow function",
+      FunctionKind::GeneratorFunctions => "generator function",
+      FunctionKind::AsyncFunctions => "async function",
+      FunctionKind::Methods => "method",
+      FunctionKind::GeneratorMethods => "generator method",
+      FunctionKind::AsyncMethods => "async method",
+      FunctionKind::Getters => "getter",
+      FunctionKind::Setters => "setter",
+      FunctionKind::Constructors => "constructor",
+    }
+  }
+
+  fn of_function(function: &Function) -> Self {
+    if function.is_generator {
+      FunctionKind::GeneratorFunctions
+    } else if function.is_async {
+      FunctionKind::AsyncFunctions
+    } else {
+      FunctionKind::Functions
+    }
+  }
+
+  fn of_method(function: &Function, kind: MethodKind) -> Self {
+    match kind {
+      MethodKind::Getter => FunctionKind::Getters,
+      MethodKind::Setter => FunctionKind::Setters,
+      MethodKind::Method if function.is_generator => {
+        FunctionKind::GeneratorMethods
+      }
+      MethodKind::Method if function.is_async => FunctionKind::AsyncMethods,
+      MethodKind::Method => FunctionKind::Methods,
+    }
+  }
+}
+
+pub struct NoEmptyFunction {
+  allow: HashSet<FunctionKind>,
+}
+
+impl LintRule for NoEmptyFunction {
+  fn new() -> Box<Self> {
+    Box::new(NoEmptyFunction {
+      allow: HashSet::new(),
+    })
+  }
+
+  fn new_with_config(
+    &self,
+    config: serde_json::Value,
+  ) -> Result<Box<dyn LintRule>, RuleConfigError> {
+    let error = |message: String| RuleConfigError {
+      code: "no-empty-function".to_string(),
+      message,
+    };
+
+    let names = match config.get("allow") {
+      None => return Ok(NoEmptyFunction::new()),
+      Some(serde_json::Value::Array(names)) => names,
+      Some(value) => {
+        return Err(error(format!(
+          "expected allow to be an array, got {}",
+          value
+        )))
+      }
+    };
+    let mut allow = HashSet::new();
+    for name in names {
+      let kind = FunctionKind::ALL
+        .iter()
+        .find(|kind| name.as_str() == Some(kind.option_name()));
+      match kind {
+        Some(kind) => allow.insert(*kind),
+        None => {
+          let message = format!("unknown function kind {} in allow", name);
+          return Err(error(message));
+        }
+      };
+    }
+    Ok(Box::new(NoEmptyFunction { allow }))
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    let kinds = FunctionKind::ALL
+      .iter()
+      .map(|kind| kind.option_name())
+      .collect::<Vec<_>>();
+    Some(serde_json::json!({
+      "type": "object",
+      "properties": {
+        "allow": {
+          "type": "array",
+          "items": { "enum": kinds },
+          "uniqueItems": true
+        }
+      },
+      "additionalProperties": false
+    }))
+  }
+
+  fn code(&self) -> &'static str {
+    "no-empty-function"
+  }
+
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoEmptyFunctionVisitor {
+      context,
+      allow: &self.allow,
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows functions with an empty body.
+
+Functions whose body contains a comment are considered intentionally empty
+and are not reported. Neither are constructors with TypeScript parameter
+properties, like `constructor(private x: number) {}`.
+
+The `allow` option takes a list of function kinds to allow: `functions`,
+`arrowFunctions`, `generatorFunctions`, `asyncFunctions`, `methods`,
+`generatorMethods`, `asyncMethods`, `getters`, `setters` and
+`constructors`."#
+  }
+
+  fn examples(&self) -> RuleExamples {
+    RuleExamples {
+      valid: &["function noop() {\n  // intentionally empty\n}"],
+      invalid: &["function noop() {}", "const noop = () => {};"],
+    }
+  }
+}
+
+struct NoEmptyFunctionVisitor<'a> {
+  context: Arc<Context>,
+  allow: &'a HashSet<FunctionKind>,
+}
+
+impl<'a> NoEmptyFunctionVisitor<'a> {
+  fn check_body(&self, body: Option<&BlockStmt>, kind: FunctionKind) {
+    let body = match body {
+      Some(body) => body,
+      // Overloads and ambient declarations have no body.
+      None => return,
+    };
+    if !body.stmts.is_empty() || self.allow.contains(&kind) {
+      return;
+    }
+    let has_comments = self
+      .context
+      .all_comments()
+      .iter()
+      .any(|comment| body.span.contains(comment.span));
+    if !has_comments {
+      self.context.add_diagnostic_with_hint(
+        body.span,
+        "no-empty-function",
+        &format!("Unexpected empty {}", kind.description()),
+        "Add code or a comment explaining why the function is empty",
+      );
+    }
+  }
+}
+
+impl<'a> Handler for NoEmptyFunctionVisitor<'a> {
+  fn visit_fn_decl(&mut self, fn_decl: &FnDecl, _traversal: &mut Traversal) {
+    let function = &fn_decl.function;
+    let kind = FunctionKind::of_function(function);
+    self.check_body(function.body.as_ref(), kind);
+  }
+
+  fn visit_fn_expr(&mut self, fn_expr: &FnExpr, _traversal: &mut Traversal) {
+    let function = &fn_expr.function;
+    let kind = FunctionKind::of_function(function);
+    self.check_body(function.body.as_ref(), kind);
+  }
+
+  fn visit_arrow_expr(
+    &mut self,
+    arrow_expr: &ArrowExpr,
+    _traversal: &mut Traversal,
+  ) {
+    if let BlockStmtOrExpr::BlockStmt(body) = &arrow_expr.body {
+      self.check_body(Some(body), FunctionKind::ArrowFunctions);
+    }
+  }
+
+  fn visit_class_method(
+    &mut self,
+    method: &ClassMethod,
+    _traversal: &mut Traversal,
+  ) {
+    let function = &method.function;
+    let kind = FunctionKind::of_method(function, method.kind);
+    self.check_body(function.body.as_ref(), kind);
+  }
+
+  fn visit_private_method(
+    &mut self,
+    method: &PrivateMethod,
+    _traversal: &mut Traversal,
+  ) {
+    let function = &method.function;
+    let kind = FunctionKind::of_method(function, method.kind);
+    self.check_body(function.body.as_ref(), kind);
+  }
+
+  fn visit_method_prop(
+    &mut self,
+    method: &MethodProp,
+    _traversal: &mut Traversal,
+  ) {
+    let function = &method.function;
+    let kind = FunctionKind::of_method(function, MethodKind::Method);
+    self.check_body(function.body.as_ref(), kind);
+  }
+
+  fn visit_getter_prop(
+    &mut self,
+    getter: &GetterProp,
+    _traversal: &mut Traversal,
+  ) {
+    self.check_body(getter.body.as_ref(), FunctionKind::Getters);
+  }
+
+  fn visit_setter_prop(
+    &mut self,
+    setter: &SetterProp,
+    _traversal: &mut Traversal,
+  ) {
+    self.check_body(setter.body.as_ref(), FunctionKind::Setters);
+  }
+
+  fn visit_constructor(
+    &mut self,
+    cons: &Constructor,
+    _traversal: &mut Traversal,
+  ) {
+    // Parameter properties make the constructor assign fields.
+    let has_param_props = cons
+      .params
+      .iter()
+      .any(|param| matches!(param, ParamOrTsParamProp::TsParamProp(_)));
+    if !has_param_props {
+      self.check_body(cons.body.as_ref(), FunctionKind::Constructors);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_empty_function_valid() {
+    assert_lint_ok! {
+      NoEmptyFunction,
+      "function foo() { bar(); }",
+      "function foo() {\n  // empty\n}",
+      "const foo = () => {\n  /* empty */\n};",
+      "const foo = () => bar;",
+      "class Foo {\n  constructor(private x: number) {}\n}",
+      "class Foo {\n  constructor(public readonly x = 1) {}\n}",
+      "class Foo {\n  bar(): void;\n  bar(x?: number) { baz(x); }\n}",
+      "declare function foo(): void;",
+      "abstract class Foo {\n  abstract bar(): void;\n}",
+    };
+  }
+
+  #[test]
+  fn no_empty_function_invalid() {
+    assert_lint_err::<NoEmptyFunction>("function foo() {}", 15);
+    assert_lint_err::<NoEmptyFunction>("const foo = function () {};", 24);
+    assert_lint_err::<NoEmptyFunction>("function* foo() {}", 16);
+    assert_lint_err::<NoEmptyFunction>("async function foo() {}", 21);
+    assert_lint_err::<NoEmptyFunction>("const foo = () => {};", 18);
+    assert_lint_err::<NoEmptyFunction>("const foo = { bar() {} };", 20);
+    assert_lint_err::<NoEmptyFunction>("const foo = { get bar() {} };", 24);
+    assert_lint_err::<NoEmptyFunction>("const foo = { set bar(v) {} };", 25);
+    assert_lint_err!(
+      NoEmptyFunction,
+      r#"
+class Foo {
+  constructor() {}
+  bar() {}
+  async baz() {}
+  get qux() {}
+  set qux(v) {}
+  #quux() {}
+}
+"#,
+      [(3, 16), (4, 8), (5, 14), (6, 12), (7, 13), (8, 10)]
+    );
+    assert_lint_err!(
+      NoEmptyFunction,
+      "function foo() {\n  return () => {};\n}",
+      2,
+      15
+    );
+  }
+
+  #[test]
+  fn no_empty_function_message() {
+    assert_lint_err_with_hint::<NoEmptyFunction>(
+      "class Foo { constructor() {} }",
+      1,
+      26,
+      "Add code or a comment explaining why the function is empty",
+    );
+    let diagnostics =
+      lint_with_rule(NoEmptyFunction::new(), "const foo = { get bar() {} };");
+    assert_eq!(diagnostics[0].message, "Unexpected empty getter");
+  }
+
+  #[test]
+  fn no_empty_function_allow() {
+    let config = json!({ "allow": ["arrowFunctions", "constructors"] });
+    assert_lint_ok_with_config::<NoEmptyFunction>(
+      "const foo = () => {};\nclass Foo { constructor() {} }",
+      config.clone(),
+    );
+    assert_lint_err_with_config::<NoEmptyFunction>(
+      "function foo() {}",
+      config.clone(),
+      15,
+    );
+    assert_lint_err_with_config::<NoEmptyFunction>(
+      "class Foo { bar() {} }",
+      config,
+      18,
+    );
+  }
+
+  #[test]
+  fn no_empty_function_invalid_config() {
+    let err = NoEmptyFunction::new()
+      .new_with_config(json!({ "allow": ["lambdas"] }))
+      .err()
+      .unwrap();
+    assert_eq!(err.message, "unknown function kind \"lambdas\" in allow");
+    assert!(NoEmptyFunction::new()
+      .new_with_config(json!({ "allow": "methods" }))
+      .is_err());
+  }
+}
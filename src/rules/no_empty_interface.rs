@@ -1,9 +1,8 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_ecmascript::ast::TsInterfaceDecl;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -18,13 +17,19 @@ impl LintRule for NoEmptyInterface {
     "no-empty-interface"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows the declaration of empty interfaces."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoEmptyInterfaceVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoEmptyInterfaceVisitor::new(context)))
   }
 }
 
@@ -38,11 +43,15 @@ impl NoEmptyInterfaceVisitor {
   }
 }
 
-impl Visit for NoEmptyInterfaceVisitor {
+impl Handler for NoEmptyInterfaceVisitor {
+  fn visit_types(&self) -> bool {
+    true
+  }
+
   fn visit_ts_interface_decl(
     &mut self,
     interface_decl: &TsInterfaceDecl,
-    _parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     if interface_decl.extends.len() <= 1 && interface_decl.body.body.is_empty()
     {
@@ -1,10 +1,8 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
-use swc_ecmascript::ast::{ArrayPat, ObjectPat, ObjectPatProp};
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
+use crate::handler::{walk, Handler, Traversal};
+use swc_ecmascript::ast::{ArrayPat, AssignPat, ObjectPat, Pat};
 
 use std::sync::Arc;
 
@@ -19,13 +17,22 @@ impl LintRule for NoEmptyPattern {
     "no-empty-pattern"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows empty destructuring patterns.
+
+Empty patterns with a default value, like `function foo({} = {}) {}`, are
+allowed."#
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoEmptyPatternVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoEmptyPatternVisitor::new(context)))
   }
 }
 
@@ -39,56 +46,50 @@ impl NoEmptyPatternVisitor {
   }
 }
 
-impl Visit for NoEmptyPatternVisitor {
-  noop_visit_type!();
-
-  fn visit_object_pat_prop(
+impl Handler for NoEmptyPatternVisitor {
+  fn visit_assign_pat(
     &mut self,
-    obj_pat_prop: &ObjectPatProp,
-    _parent: &dyn Node,
+    assign_pat: &AssignPat,
+    traversal: &mut Traversal,
   ) {
-    if let ObjectPatProp::KeyValue(kv_prop) = obj_pat_prop {
-      if let swc_ecmascript::ast::Pat::Object(obj_pat) = &*kv_prop.value {
-        self.visit_object_pat(obj_pat, _parent);
-      } else if let swc_ecmascript::ast::Pat::Array(arr_pat) = &*kv_prop.value {
-        self.visit_array_pat(arr_pat, _parent);
-      }
+    // Like ESLint, `{} = {}` is allowed as it's a common way of making
+    // a parameter optional.
+    let is_empty = match &*assign_pat.left {
+      Pat::Object(obj_pat) => obj_pat.props.is_empty(),
+      Pat::Array(arr_pat) => arr_pat.elems.is_empty(),
+      _ => false,
+    };
+    if is_empty {
+      traversal.skip_children();
+      walk(self, &*assign_pat.right);
     }
   }
 
-  fn visit_object_pat(&mut self, obj_pat: &ObjectPat, _parent: &dyn Node) {
-    if obj_pat.props.is_empty() {
-      if obj_pat.type_ann.is_none() {
-        self.context.add_diagnostic(
-          obj_pat.span,
-          "no-empty-pattern",
-          "empty patterns are not allowed",
-        )
-      }
-    } else {
-      for prop in &obj_pat.props {
-        self.visit_object_pat_prop(prop, _parent)
-      }
+  fn visit_object_pat(
+    &mut self,
+    obj_pat: &ObjectPat,
+    _traversal: &mut Traversal,
+  ) {
+    if obj_pat.props.is_empty() && obj_pat.type_ann.is_none() {
+      self.context.add_diagnostic(
+        obj_pat.span,
+        "no-empty-pattern",
+        "empty patterns are not allowed",
+      )
     }
   }
 
-  fn visit_array_pat(&mut self, arr_pat: &ArrayPat, _parent: &dyn Node) {
+  fn visit_array_pat(
+    &mut self,
+    arr_pat: &ArrayPat,
+    _traversal: &mut Traversal,
+  ) {
     if arr_pat.elems.is_empty() {
       self.context.add_diagnostic(
         arr_pat.span,
         "no-empty-pattern",
         "empty patterns are not allowed",
       )
-    } else {
-      for elem in &arr_pat.elems {
-        if let Some(element) = elem {
-          if let swc_ecmascript::ast::Pat::Object(obj_pat) = element {
-            self.visit_object_pat(&obj_pat, _parent);
-          } else if let swc_ecmascript::ast::Pat::Array(arr_pat) = element {
-            self.visit_array_pat(&arr_pat, _parent);
-          }
-        }
-      }
     }
   }
 }
@@ -108,6 +109,11 @@ mod tests {
       "function foo({a = []}) {}",
       "var [a] = foo",
       "async function startFileServerAsLibrary({}: FileServerCfg = {}): Promise<void>",
+      "function foo({} = {}) {}",
+      "function foo([] = []) {}",
+      "const foo = ({} = {}) => {};",
+      "const {a: {} = {}} = foo;",
+      "const [{} = {}] = foo;",
     ]);
   }
 
@@ -122,5 +128,17 @@ mod tests {
     assert_lint_err::<NoEmptyPattern>("function foo([]) {}", 13);
     assert_lint_err::<NoEmptyPattern>("function foo({a: {}}) {}", 17);
     assert_lint_err::<NoEmptyPattern>("function foo({a: []}) {}", 17);
+    assert_lint_err::<NoEmptyPattern>("const foo = ({}) => {};", 13);
+    assert_lint_err::<NoEmptyPattern>("const {a: {b: {}}} = foo", 14);
+    assert_lint_err::<NoEmptyPattern>("const [a, {}] = foo", 10);
+    assert_lint_err::<NoEmptyPattern>("const {a: [{}]} = foo", 11);
+    assert_lint_err::<NoEmptyPattern>("function foo({a: {}} = {}) {}", 17);
+    assert_lint_err::<NoEmptyPattern>("function foo({} = ({}) => {}) {}", 19);
+    assert_lint_err!(
+      NoEmptyPattern,
+      "function foo() {\n  for (const {} of bar) {}\n}",
+      2,
+      13
+    );
   }
 }
@@ -1,15 +1,20 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
-use swc_ecmascript::ast::CallExpr;
-use swc_ecmascript::ast::Expr;
-use swc_ecmascript::ast::ExprOrSuper;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
+use crate::handler::{walk, Handler, Traversal};
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  CallExpr, Expr, ExprOrSuper, Ident, Lit, MemberExpr,
+};
+use swc_ecmascript::utils::ident::IdentLike;
 
 use std::sync::Arc;
 
+const MESSAGE: &str = "eval can be harmful";
+
+/// Globals through which `eval` can be accessed as a property.
+const GLOBAL_OBJECTS: &[&str] = &["globalThis", "self", "window"];
+
 pub struct NoEval;
 
 impl LintRule for NoEval {
@@ -21,13 +26,19 @@ impl LintRule for NoEval {
     "no-eval"
   }
 
-  fn lint_module(
-    &self,
+  fn docs(&self) -> &'static str {
+    r#"Disallows the use of `eval`.
+
+Calls to `eval`, `globalThis.eval`, `window.eval` and `self.eval` are
+reported, as well as references to `eval` that could be called indirectly.
+Bindings named `eval` declared in the file are not reported."#
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoEvalVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoEvalVisitor::new(context)))
   }
 }
 
@@ -41,32 +52,108 @@ impl NoEvalVisitor {
   }
 }
 
-impl Visit for NoEvalVisitor {
-  noop_visit_type!();
-
-  fn visit_call_expr(&mut self, call_expr: &CallExpr, _parent: &dyn Node) {
-    if let ExprOrSuper::Expr(expr) = &call_expr.callee {
-      if let Expr::Ident(ident) = expr.as_ref() {
-        let name = ident.sym.as_ref();
-        if name == "eval" {
-          self.context.add_diagnostic(
-            call_expr.span,
-            "no-eval",
-            "`eval` call is not allowed",
-          );
+impl NoEvalVisitor {
+  /// True if `ident` is `name` and not shadowed by a declared variable.
+  fn is_global(&self, ident: &Ident, name: &str) -> bool {
+    ident.sym.as_ref() == name
+      && self.context.scope.var(&ident.to_id()).is_none()
+  }
+
+  /// True if `expr` is the global `eval` function, either directly or as
+  /// a property of a global object like `globalThis.eval`.
+  fn is_eval(&self, expr: &Expr) -> bool {
+    match expr {
+      Expr::Ident(ident) => self.is_global(ident, "eval"),
+      Expr::Member(member) => {
+        let is_global_object = match &member.obj {
+          ExprOrSuper::Expr(obj) => match obj.as_ref() {
+            Expr::Ident(obj) => {
+              GLOBAL_OBJECTS.iter().any(|name| self.is_global(obj, name))
+            }
+            _ => false,
+          },
+          ExprOrSuper::Super(_) => false,
+        };
+        let is_eval_prop = match member.prop.as_ref() {
+          Expr::Ident(prop) if !member.computed => prop.sym == *"eval",
+          Expr::Lit(Lit::Str(prop)) if member.computed => prop.value == *"eval",
+          _ => false,
+        };
+        is_global_object && is_eval_prop
+      }
+      _ => false,
+    }
+  }
+}
+
+impl Handler for NoEvalVisitor {
+  fn visit_call_expr(
+    &mut self,
+    call_expr: &CallExpr,
+    traversal: &mut Traversal,
+  ) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      if self.is_eval(callee) {
+        self
+          .context
+          .add_diagnostic(call_expr.span, "no-eval", MESSAGE);
+        traversal.skip_children();
+        for arg in &call_expr.args {
+          walk(self, arg);
         }
       }
     }
   }
+
+  /// Reports `eval` referenced without being called, e.g. when it's
+  /// passed as a callback, as it can still be called indirectly.
+  fn visit_expr(&mut self, expr: &Expr, traversal: &mut Traversal) {
+    if self.is_eval(expr) {
+      self.context.add_diagnostic(expr.span(), "no-eval", MESSAGE);
+      traversal.skip_children();
+    }
+  }
+
+  fn visit_member_expr(
+    &mut self,
+    member: &MemberExpr,
+    traversal: &mut Traversal,
+  ) {
+    // Properties like `obj.eval` are not references to `eval`.
+    if !member.computed {
+      traversal.skip_children();
+      walk(self, &member.obj);
+    }
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::test_util::*;
 
   #[test]
-  fn no_eval_test() {
-    assert_lint_err::<NoEval>(r#"eval("123");"#, 0)
+  fn no_eval_valid() {
+    assert_lint_ok! {
+      NoEval,
+      "function eval(code) { return code; }\neval(\"1\");",
+      "function f(eval) { eval(\"1\"); }",
+      "const obj = { eval() {} };\nobj.eval(\"1\");",
+      "foo.eval(\"1\");",
+      "const window = { eval: (s) => s };\nwindow.eval(\"1\");",
+      "const evaluate = (s) => s;\nevaluate(\"1\");",
+    };
+  }
+
+  #[test]
+  fn no_eval_invalid() {
+    assert_lint_err!(NoEval, r#"eval("123");"#, 1, 0);
+    assert_lint_err!(NoEval, r#"globalThis.eval("123");"#, 1, 0);
+    assert_lint_err!(NoEval, r#"window["eval"]("123");"#, 1, 0);
+    assert_lint_err!(NoEval, r#"self.eval("123");"#, 1, 0);
+    assert_lint_err!(NoEval, "function f() {\n  eval(code);\n}", 2, 2);
+    assert_lint_err!(NoEval, "[\"1\"].map(eval);", 1, 10);
+    assert_lint_err!(NoEval, "const e = eval;\ne(\"1\");", 1, 10);
+    assert_lint_err!(NoEval, "const e = globalThis.eval;", 1, 10);
+    assert_lint_err!(NoEval, r#"eval(eval("1"));"#, [(1, 0), (1, 5)]);
   }
 }
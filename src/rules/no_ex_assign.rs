@@ -1,12 +1,13 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use crate::{scopes::BindingKind, swc_util::find_lhs_ids};
 use std::sync::Arc;
-use swc_ecmascript::ast::AssignExpr;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
+use swc_common::Span;
+use swc_ecmascript::ast::{AssignExpr, Expr, UpdateExpr};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
 
 pub struct NoExAssign;
 
@@ -19,13 +20,19 @@ impl LintRule for NoExAssign {
     "no-ex-assign"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows reassigning the exception parameter of a `catch` clause."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoExAssignVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoExAssignVisitor::new(context)))
   }
 }
 
@@ -37,26 +44,38 @@ impl NoExAssignVisitor {
   fn new(context: Arc<Context>) -> Self {
     Self { context }
   }
-}
 
-impl Visit for NoExAssignVisitor {
-  noop_visit_type!();
-
-  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, _node: &dyn Node) {
-    let ids = find_lhs_ids(&assign_expr.left);
+  fn check(&self, id: &Id, span: Span) {
+    if let Some(var) = self.context.scope.var(id) {
+      if let BindingKind::CatchClause = var.kind() {
+        self.context.add_diagnostic(
+          span,
+          "no-ex-assign",
+          "Reassigning exception parameter is not allowed",
+        );
+      }
+    }
+  }
+}
 
-    for id in ids {
-      let var = self.context.scope.var(&id);
+impl Handler for NoExAssignVisitor {
+  fn visit_assign_expr(
+    &mut self,
+    assign_expr: &AssignExpr,
+    _traversal: &mut Traversal,
+  ) {
+    for id in find_lhs_ids(&assign_expr.left) {
+      self.check(&id, assign_expr.span);
+    }
+  }
 
-      if let Some(var) = var {
-        if let BindingKind::CatchClause = var.kind() {
-          self.context.add_diagnostic(
-            assign_expr.span,
-            "no-ex-assign",
-            "Reassigning exception parameter is not allowed",
-          );
-        }
-      }
+  fn visit_update_expr(
+    &mut self,
+    update_expr: &UpdateExpr,
+    _traversal: &mut Traversal,
+  ) {
+    if let Expr::Ident(ident) = &*update_expr.arg {
+      self.check(&ident.to_id(), update_expr.span);
     }
   }
 }
@@ -64,8 +83,7 @@ impl Visit for NoExAssignVisitor {
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::test_util::assert_lint_err_on_line_n;
-  use crate::test_util::assert_lint_ok;
+  use crate::test_util::*;
 
   #[test]
   fn no_ex_assign_valid() {
@@ -77,6 +95,15 @@ try {} catch (ex) { return 1; }
 function foo() { try { } catch (e) { return false; } }
       "#,
     );
+    assert_lint_ok! {
+      NoExAssign,
+      "try {} catch ({ message }) { e.message = 1; }",
+      "try {} catch (e) { e.message = 'foo'; e.stack++; }",
+      "try {} catch (e) { { let e = 1; e = 2; } }",
+      "try {} catch (e) { function foo(e) { e = 1; } }",
+      "try {} catch (e) { const foo = (e) => { e += 1; }; }",
+      "try {} catch (e) { for (let e of []) { e = 1; } }",
+    };
   }
 
   #[test]
@@ -92,4 +119,27 @@ try {} catch ({message}) { message = 1; }
       vec![(2, 19), (3, 20), (4, 20), (5, 21), (6, 27)],
     );
   }
+
+  #[test]
+  fn no_ex_assign_compound_and_nested() {
+    assert_lint_err::<NoExAssign>("try {} catch (e) { e += 1; }", 19);
+    assert_lint_err::<NoExAssign>("try {} catch (e) { e ||= 1; }", 19);
+    assert_lint_err::<NoExAssign>("try {} catch (e) { e++; }", 19);
+    assert_lint_err::<NoExAssign>("try {} catch (e) { --e; }", 19);
+    assert_lint_err::<NoExAssign>("try {} catch ([e]) { e = 1; }", 21);
+    assert_lint_err::<NoExAssign>(
+      "try {} catch (e) { if (a) { foo(e = 1); } }",
+      32,
+    );
+    assert_lint_err_n::<NoExAssign>(
+      "try {} catch (e) { a = e = 1; }",
+      vec![23],
+    );
+    assert_lint_err!(
+      NoExAssign,
+      "try {} catch (e) {\n  setTimeout(() => {\n    e = 1;\n  });\n}",
+      3,
+      4
+    );
+  }
 }
@@ -1,9 +1,8 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_ecmascript::ast::TsKeywordType;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -18,13 +17,19 @@ impl LintRule for NoExplicitAny {
     "no-explicit-any"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows the use of the `any` type."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoExplicitAnyVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoExplicitAnyVisitor::new(context)))
   }
 }
 
@@ -38,11 +43,15 @@ impl NoExplicitAnyVisitor {
   }
 }
 
-impl Visit for NoExplicitAnyVisitor {
+impl Handler for NoExplicitAnyVisitor {
+  fn visit_types(&self) -> bool {
+    true
+  }
+
   fn visit_ts_keyword_type(
     &mut self,
     ts_keyword_type: &TsKeywordType,
-    _parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     use swc_ecmascript::ast::TsKeywordTypeKind::*;
 
@@ -1,14 +1,12 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_common::Span;
 use swc_ecmascript::ast::{
   CallExpr, CondExpr, DoWhileStmt, Expr, ExprOrSpread, ExprOrSuper, ForStmt,
   Ident, IfStmt, NewExpr, ParenExpr, UnaryExpr, UnaryOp, WhileStmt,
 };
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -23,13 +21,19 @@ impl LintRule for NoExtraBooleanCast {
     "no-extra-boolean-cast"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows unnecessary boolean casts."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoExtraBooleanCastVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoExtraBooleanCastVisitor::new(context)))
   }
 }
 
@@ -114,50 +118,54 @@ impl NoExtraBooleanCastVisitor {
   }
 }
 
-impl Visit for NoExtraBooleanCastVisitor {
-  noop_visit_type!();
-
-  fn visit_cond_expr(&mut self, cond_expr: &CondExpr, parent: &dyn Node) {
+impl Handler for NoExtraBooleanCastVisitor {
+  fn visit_cond_expr(
+    &mut self,
+    cond_expr: &CondExpr,
+    _traversal: &mut Traversal,
+  ) {
     self.check_condition(&*cond_expr.test);
-    swc_ecmascript::visit::visit_cond_expr(self, cond_expr, parent);
   }
 
-  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, parent: &dyn Node) {
+  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, _traversal: &mut Traversal) {
     if let Some(ref test_expr) = for_stmt.test {
       self.check_condition(&**test_expr);
     }
-    swc_ecmascript::visit::visit_for_stmt(self, for_stmt, parent);
   }
 
-  fn visit_if_stmt(&mut self, if_stmt: &IfStmt, parent: &dyn Node) {
+  fn visit_if_stmt(&mut self, if_stmt: &IfStmt, _traversal: &mut Traversal) {
     self.check_condition(&*if_stmt.test);
-    swc_ecmascript::visit::visit_if_stmt(self, if_stmt, parent);
   }
 
-  fn visit_while_stmt(&mut self, while_stmt: &WhileStmt, parent: &dyn Node) {
+  fn visit_while_stmt(
+    &mut self,
+    while_stmt: &WhileStmt,
+    _traversal: &mut Traversal,
+  ) {
     self.check_condition(&*while_stmt.test);
-    swc_ecmascript::visit::visit_while_stmt(self, while_stmt, parent);
   }
 
   fn visit_do_while_stmt(
     &mut self,
     do_while_stmt: &DoWhileStmt,
-    parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     self.check_condition(&*do_while_stmt.test);
-    swc_ecmascript::visit::visit_do_while_stmt(self, do_while_stmt, parent);
   }
 
-  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+  fn visit_call_expr(
+    &mut self,
+    call_expr: &CallExpr,
+    _traversal: &mut Traversal,
+  ) {
     if expr_or_super_callee_is_boolean(&call_expr.callee) {
       if let Some(ExprOrSpread { expr, .. }) = call_expr.args.get(0) {
         self.check_condition(&*expr);
       }
     }
-    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
   }
 
-  fn visit_new_expr(&mut self, new_expr: &NewExpr, parent: &dyn Node) {
+  fn visit_new_expr(&mut self, new_expr: &NewExpr, _traversal: &mut Traversal) {
     if expr_callee_is_boolean(&new_expr.callee) {
       if let Some(ExprOrSpread { expr, .. }) =
         new_expr.args.as_ref().and_then(|a| a.get(0))
@@ -165,12 +173,14 @@ impl Visit for NoExtraBooleanCastVisitor {
         self.check_condition(&*expr);
       }
     }
-    swc_ecmascript::visit::visit_new_expr(self, new_expr, parent);
   }
 
-  fn visit_unary_expr(&mut self, unary_expr: &UnaryExpr, parent: &dyn Node) {
+  fn visit_unary_expr(
+    &mut self,
+    unary_expr: &UnaryExpr,
+    _traversal: &mut Traversal,
+  ) {
     self.check_unary_expr(unary_expr);
-    swc_ecmascript::visit::visit_unary_expr(self, unary_expr, parent);
   }
 }
 
@@ -1,13 +1,12 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_common::Span;
 use swc_ecmascript::ast::Expr;
 use swc_ecmascript::ast::ExprOrSuper;
 use swc_ecmascript::ast::OptChainExpr;
 use swc_ecmascript::ast::TsNonNullExpr;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -22,13 +21,19 @@ impl LintRule for NoExtraNonNullAssertion {
     "no-extra-non-null-assertion"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows unnecessary non-null assertions."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoExtraNonNullAssertionVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoExtraNonNullAssertionVisitor::new(context)))
   }
 }
 
@@ -60,27 +65,26 @@ impl NoExtraNonNullAssertionVisitor {
   }
 }
 
-impl Visit for NoExtraNonNullAssertionVisitor {
+impl Handler for NoExtraNonNullAssertionVisitor {
+  fn visit_types(&self) -> bool {
+    true
+  }
+
   fn visit_ts_non_null_expr(
     &mut self,
     ts_non_null_expr: &TsNonNullExpr,
-    parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     self.check_expr_for_nested_non_null_assert(
       ts_non_null_expr.span,
       &*ts_non_null_expr.expr,
     );
-    swc_ecmascript::visit::visit_ts_non_null_expr(
-      self,
-      ts_non_null_expr,
-      parent,
-    );
   }
 
   fn visit_opt_chain_expr(
     &mut self,
     opt_chain_expr: &OptChainExpr,
-    parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     let maybe_expr_or_super = match &*opt_chain_expr.expr {
       Expr::Member(member_expr) => Some(&member_expr.obj),
@@ -93,8 +97,6 @@ impl Visit for NoExtraNonNullAssertionVisitor {
         self.check_expr_for_nested_non_null_assert(opt_chain_expr.span, expr);
       }
     }
-
-    swc_ecmascript::visit::visit_opt_chain_expr(self, opt_chain_expr, parent);
   }
 }
 
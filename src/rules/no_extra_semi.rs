@@ -1,13 +1,11 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{walk, Handler, Traversal};
 use swc_ecmascript::ast::{
   DoWhileStmt, EmptyStmt, ForInStmt, ForOfStmt, ForStmt, IfStmt, LabeledStmt,
   Stmt, WhileStmt, WithStmt,
 };
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -22,13 +20,19 @@ impl LintRule for NoExtraSemi {
     "no-extra-semi"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows unnecessary semicolons."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoExtraSemiVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoExtraSemiVisitor::new(context)))
   }
 }
 
@@ -42,99 +46,106 @@ impl NoExtraSemiVisitor {
   }
 }
 
-impl Visit for NoExtraSemiVisitor {
-  noop_visit_type!();
-
-  fn visit_empty_stmt(&mut self, empty_stmt: &EmptyStmt, _parent: &dyn Node) {
-    self.context.add_diagnostic(
+impl Handler for NoExtraSemiVisitor {
+  fn visit_empty_stmt(
+    &mut self,
+    empty_stmt: &EmptyStmt,
+    _traversal: &mut Traversal,
+  ) {
+    self.context.add_diagnostic_with_fix(
       empty_stmt.span,
       "no-extra-semi",
       "Unnecessary semicolon.",
+      empty_stmt.span,
+      "",
     );
   }
 
-  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, parent: &dyn Node) {
+  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, traversal: &mut Traversal) {
     if matches!(&*for_stmt.body, Stmt::Empty(_)) {
+      traversal.skip_children();
       if let Some(ref init) = for_stmt.init {
-        swc_ecmascript::visit::visit_var_decl_or_expr(self, init, parent);
+        walk(self, init);
       }
       if let Some(ref test) = for_stmt.test {
-        swc_ecmascript::visit::visit_expr(self, test, parent);
+        walk(self, test);
       }
       if let Some(ref update) = for_stmt.update {
-        swc_ecmascript::visit::visit_expr(self, update, parent);
+        walk(self, update);
       }
-    } else {
-      swc_ecmascript::visit::visit_for_stmt(self, for_stmt, parent);
     }
   }
 
-  fn visit_while_stmt(&mut self, while_stmt: &WhileStmt, parent: &dyn Node) {
+  fn visit_while_stmt(
+    &mut self,
+    while_stmt: &WhileStmt,
+    traversal: &mut Traversal,
+  ) {
     if matches!(&*while_stmt.body, Stmt::Empty(_)) {
-      swc_ecmascript::visit::visit_expr(self, &*while_stmt.test, parent);
-    } else {
-      swc_ecmascript::visit::visit_while_stmt(self, while_stmt, parent);
+      traversal.skip_children();
+      walk(self, &*while_stmt.test);
     }
   }
 
   fn visit_do_while_stmt(
     &mut self,
     do_while_stmt: &DoWhileStmt,
-    parent: &dyn Node,
+    traversal: &mut Traversal,
   ) {
     if matches!(&*do_while_stmt.body, Stmt::Empty(_)) {
-      swc_ecmascript::visit::visit_expr(self, &*do_while_stmt.test, parent);
-    } else {
-      swc_ecmascript::visit::visit_do_while_stmt(self, do_while_stmt, parent);
+      traversal.skip_children();
+      walk(self, &*do_while_stmt.test);
     }
   }
 
-  fn visit_with_stmt(&mut self, with_stmt: &WithStmt, parent: &dyn Node) {
+  fn visit_with_stmt(
+    &mut self,
+    with_stmt: &WithStmt,
+    traversal: &mut Traversal,
+  ) {
     if matches!(&*with_stmt.body, Stmt::Empty(_)) {
-      swc_ecmascript::visit::visit_expr(self, &*with_stmt.obj, parent);
-    } else {
-      swc_ecmascript::visit::visit_with_stmt(self, with_stmt, parent);
+      traversal.skip_children();
+      walk(self, &*with_stmt.obj);
     }
   }
 
-  fn visit_for_of_stmt(&mut self, for_of_stmt: &ForOfStmt, parent: &dyn Node) {
+  fn visit_for_of_stmt(
+    &mut self,
+    for_of_stmt: &ForOfStmt,
+    traversal: &mut Traversal,
+  ) {
     if matches!(&*for_of_stmt.body, Stmt::Empty(_)) {
-      swc_ecmascript::visit::visit_var_decl_or_pat(
-        self,
-        &for_of_stmt.left,
-        parent,
-      );
-      swc_ecmascript::visit::visit_expr(self, &*for_of_stmt.right, parent);
-    } else {
-      swc_ecmascript::visit::visit_for_of_stmt(self, for_of_stmt, parent);
+      traversal.skip_children();
+      walk(self, &for_of_stmt.left);
+      walk(self, &*for_of_stmt.right);
     }
   }
 
-  fn visit_for_in_stmt(&mut self, for_in_stmt: &ForInStmt, parent: &dyn Node) {
+  fn visit_for_in_stmt(
+    &mut self,
+    for_in_stmt: &ForInStmt,
+    traversal: &mut Traversal,
+  ) {
     if matches!(&*for_in_stmt.body, Stmt::Empty(_)) {
-      swc_ecmascript::visit::visit_var_decl_or_pat(
-        self,
-        &for_in_stmt.left,
-        parent,
-      );
-      swc_ecmascript::visit::visit_expr(self, &*for_in_stmt.right, parent);
-    } else {
-      swc_ecmascript::visit::visit_for_in_stmt(self, for_in_stmt, parent);
+      traversal.skip_children();
+      walk(self, &for_in_stmt.left);
+      walk(self, &*for_in_stmt.right);
     }
   }
 
-  fn visit_if_stmt(&mut self, if_stmt: &IfStmt, parent: &dyn Node) {
-    swc_ecmascript::visit::visit_expr(self, &*if_stmt.test, parent);
+  fn visit_if_stmt(&mut self, if_stmt: &IfStmt, traversal: &mut Traversal) {
+    traversal.skip_children();
+    walk(self, &*if_stmt.test);
     match &*if_stmt.cons {
       Stmt::Empty(_) => {}
       cons => {
-        swc_ecmascript::visit::visit_stmt(self, cons, parent);
+        walk(self, cons);
       }
     }
     match if_stmt.alt.as_deref() {
       None | Some(Stmt::Empty(_)) => {}
       Some(alt) => {
-        swc_ecmascript::visit::visit_stmt(self, alt, parent);
+        walk(self, alt);
       }
     }
   }
@@ -142,13 +153,14 @@ impl Visit for NoExtraSemiVisitor {
   fn visit_labeled_stmt(
     &mut self,
     labeled_stmt: &LabeledStmt,
-    parent: &dyn Node,
+    traversal: &mut Traversal,
   ) {
-    swc_ecmascript::visit::visit_ident(self, &labeled_stmt.label, parent);
+    traversal.skip_children();
+    walk(self, &labeled_stmt.label);
     match &*labeled_stmt.body {
       Stmt::Empty(_) => {}
       body => {
-        swc_ecmascript::visit::visit_stmt(self, body, parent);
+        walk(self, body);
       }
     }
   }
@@ -302,4 +314,17 @@ class A {
       14,
     );
   }
+
+  #[test]
+  fn no_extra_semi_fix() {
+    use crate::diagnostic::apply_fixes;
+
+    let source = "var x = 5;;\nfunction foo(){};\nclass A { ; }";
+    let diagnostics = lint_with_rule(NoExtraSemi::new(), source);
+    assert_eq!(diagnostics.len(), 3);
+    assert_eq!(
+      apply_fixes(source, &diagnostics),
+      "var x = 5;\nfunction foo(){}\nclass A {  }"
+    );
+  }
 }
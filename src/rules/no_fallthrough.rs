@@ -1,126 +1,201 @@
-use super::LintRule;
+use super::{LintRule, RuleConfigError, RuleExamples};
+use crate::handler::Handler;
 use crate::linter::Context;
-use swc_common::{comments::Comment, Spanned, DUMMY_SP};
-use swc_ecmascript::{
-  ast::*,
-  visit::{noop_visit_type, Node, Visit, VisitWith},
-};
+use regex::Regex;
+use swc_common::{comments::Comment, Spanned};
+use swc_ecmascript::ast::*;
 
 use std::sync::Arc;
 
-pub struct NoFallthrough;
+lazy_static! {
+  static ref DEFAULT_COMMENT_PATTERN: Regex =
+    Regex::new(r"(?i)falls?\s?through").unwrap();
+}
+
+pub struct NoFallthrough {
+  comment_pattern: Regex,
+  /// - None: Empty case is allowed only if the next case directly follows it
+  /// - Some(true): Empty case is always allowed
+  /// - Some(false): Empty case is never allowed
+  allow_empty_case: Option<bool>,
+}
 
 impl LintRule for NoFallthrough {
   fn new() -> Box<Self> {
-    Box::new(NoFallthrough)
+    Box::new(NoFallthrough {
+      comment_pattern: DEFAULT_COMMENT_PATTERN.clone(),
+      allow_empty_case: None,
+    })
+  }
+
+  fn new_with_config(
+    &self,
+    config: serde_json::Value,
+  ) -> Result<Box<dyn LintRule>, RuleConfigError> {
+    let error = |message: String| RuleConfigError {
+      code: "no-fallthrough".to_string(),
+      message,
+    };
+
+    let comment_pattern = match config.get("commentPattern") {
+      None => DEFAULT_COMMENT_PATTERN.clone(),
+      Some(serde_json::Value::String(pattern)) => Regex::new(pattern)
+        .map_err(|err| error(format!("invalid commentPattern: {}", err)))?,
+      Some(value) => {
+        return Err(error(format!(
+          "expected commentPattern to be a string, got {}",
+          value
+        )))
+      }
+    };
+
+    let allow_empty_case = match config.get("allowEmptyCase") {
+      None => None,
+      Some(serde_json::Value::Bool(allow)) => Some(*allow),
+      Some(value) => {
+        return Err(error(format!(
+          "expected allowEmptyCase to be a boolean, got {}",
+          value
+        )))
+      }
+    };
+
+    Ok(Box::new(NoFallthrough {
+      comment_pattern,
+      allow_empty_case,
+    }))
   }
 
   fn code(&self) -> &'static str {
     "no-fallthrough"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows fallthrough of `case` statements."
+  }
+
+  fn examples(&self) -> RuleExamples {
+    RuleExamples {
+      valid: &[
+        "switch (a) {\n  case 1:\n    b();\n    break;\n  case 2:\n    c();\n}",
+        "switch (a) {\n  case 1:\n    b();\n  // falls through\n  \
+         case 2:\n    c();\n}",
+      ],
+      invalid: &["switch (a) {\n  case 1:\n    b();\n  case 2:\n    c();\n}"],
+    }
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(serde_json::json!({
+      "type": "object",
+      "properties": {
+        "commentPattern": { "type": "string" },
+        "allowEmptyCase": { "type": "boolean" }
+      },
+      "additionalProperties": false
+    }))
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoFallthroughVisitor { context };
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoFallthroughVisitor {
+      context,
+      comment_pattern: &self.comment_pattern,
+      allow_empty_case: self.allow_empty_case,
+    }))
   }
 }
 
-struct NoFallthroughVisitor {
+struct NoFallthroughVisitor<'a> {
   context: Arc<Context>,
+  comment_pattern: &'a Regex,
+  allow_empty_case: Option<bool>,
 }
 
-impl Visit for NoFallthroughVisitor {
-  noop_visit_type!();
-
-  fn visit_switch_cases(&mut self, cases: &[SwitchCase], parent: &dyn Node) {
-    let mut should_emit_err = false;
-    let mut prev_span = DUMMY_SP;
-
-    'cases: for (case_idx, case) in cases.iter().enumerate() {
-      case.visit_with(parent, self);
+impl<'a> NoFallthroughVisitor<'a> {
+  /// Returns true if execution can't get from `case` into `next`.
+  fn is_fallthrough_allowed(
+    &self,
+    case: &SwitchCase,
+    next: &SwitchCase,
+  ) -> bool {
+    // Handle return / throw / break / continue
+    if !self.context.control_flow.is_end_reachable(&case.cons) {
+      return true;
+    }
 
-      if should_emit_err {
-        let mut emit = true;
-        if let Some(comments) = self.context.leading_comments.get(&case.span.lo)
-        {
-          if allow_fall_through(&comments) {
-            emit = false;
-          }
-        }
-        if emit {
-          self.context.add_diagnostic(
-            prev_span,
-            "no-fallthrough",
-            "Fallthrough is not allowed",
-          );
-        }
+    // User comment beats everything
+    if let Some(last) = case.cons.last() {
+      if self.allow_fall_through(self.context.trailing_comments(last.span().hi))
+      {
+        return true;
       }
-      should_emit_err = true;
-      let mut stops_exec = false;
-
-      // Handle return / throw / break / continue
-      for (idx, stmt) in case.cons.iter().enumerate() {
-        let last = idx + 1 == case.cons.len();
-        let metadata = self.context.control_flow.meta(stmt.span().lo);
-        stops_exec |= metadata.map(|v| v.stops_execution()).unwrap_or(false);
-        if stops_exec {
-          should_emit_err = false;
-        }
+    }
+    if self.allow_fall_through(self.context.leading_comments(next.span.lo)) {
+      return true;
+    }
 
-        if last {
-          if let Some(comments) =
-            self.context.trailing_comments.get(&stmt.span().hi)
-          {
-            if allow_fall_through(&comments) {
-              should_emit_err = false;
-              // User comment beats everything
-              prev_span = case.span;
-              continue 'cases;
-            }
-          }
+    let empty = case.cons.is_empty()
+      || match &case.cons[0] {
+        Stmt::Block(b) => b.stmts.is_empty(),
+        _ => false,
+      };
+
+    // By default empty case is a case shared with the next one, unless they
+    // are separated by blank lines or comments.
+    if empty {
+      return match self.allow_empty_case {
+        Some(allow) => allow,
+        None => {
+          let source_map = &self.context.source_map;
+          let case_end = source_map.lookup_char_pos(case.span.hi).line;
+          let next_start = source_map.lookup_char_pos(next.span.lo).line;
+          next_start <= case_end + 1
         }
-      }
-
-      let empty = case.cons.is_empty()
-        || match &case.cons[0] {
-          Stmt::Block(b) => b.stmts.is_empty(),
-          _ => false,
-        };
+      };
+    }
 
-      if case_idx + 1 < cases.len() {
-        // A case is not allowed to fall through to default handler
-        if cases[case_idx + 1].test.is_none() {
-          if empty {
-            should_emit_err = true;
-          }
-        } else {
-          // Fallthrough
-          if empty {
-            should_emit_err = false;
-          }
-        }
-      }
+    false
+  }
 
-      prev_span = case.span;
-    }
+  fn allow_fall_through(&self, comments: &[Comment]) -> bool {
+    comments
+      .iter()
+      .any(|comment| self.comment_pattern.is_match(&comment.text))
   }
 }
 
-fn allow_fall_through(comments: &[Comment]) -> bool {
-  for comment in comments {
-    let l = comment.text.to_ascii_lowercase();
-    if l.contains("fallthrough")
-      || l.contains("falls through")
-      || l.contains("fall through")
-    {
-      return true;
+impl<'a> Handler for NoFallthroughVisitor<'a> {
+  fn exit_switch_cases(&mut self, cases: &[SwitchCase]) {
+    for pair in cases.windows(2) {
+      let (case, next) = (&pair[0], &pair[1]);
+      if !self.is_fallthrough_allowed(case, next) {
+        let label = match &next.test {
+          Some(test) => {
+            let test = self
+              .context
+              .source_map
+              .span_to_snippet(test.span())
+              .unwrap_or_default();
+            format!("case {}", test)
+          }
+          None => "default".to_string(),
+        };
+        self.context.add_diagnostic_with_hint(
+          next.span,
+          "no-fallthrough",
+          &format!("Expected a 'break' statement before '{}'", label),
+          "Add a 'break' statement, or a '// fallthrough' comment if falling through is intended",
+        );
+      }
     }
   }
-  false
 }
 
 #[cfg(test)]
@@ -276,19 +351,21 @@ mod tests {
 
   #[test]
   fn err_1() {
-    assert_lint_err::<NoFallthrough>(
+    assert_lint_err_on_line::<NoFallthrough>(
       "switch(foo) { case 0: a();\ncase 1: b() }",
-      14,
+      2,
+      0,
     );
 
-    assert_lint_err::<NoFallthrough>(
+    assert_lint_err_on_line::<NoFallthrough>(
       "switch(foo) { case 0: a();\ndefault: b() }",
-      14,
+      2,
+      0,
     );
 
     assert_lint_err::<NoFallthrough>(
       "switch(foo) { case 0: a(); default: b() }",
-      14,
+      27,
     );
   }
 
@@ -296,17 +373,17 @@ mod tests {
   fn err_2() {
     assert_lint_err::<NoFallthrough>(
       "switch(foo) { case 0: if (a) { break; } default: b() }",
-      14,
+      40,
     );
 
     assert_lint_err::<NoFallthrough>(
       "switch(foo) { case 0: try { throw 0; } catch (err) {} default: b() }",
-      14,
+      54,
     );
 
     assert_lint_err::<NoFallthrough>(
       "switch(foo) { case 0: while (a) { break; } default: b() }",
-      14,
+      43,
     );
   }
 
@@ -321,14 +398,16 @@ mod tests {
 
   #[test]
   fn err_3() {
-    assert_lint_err::<NoFallthrough>(
+    assert_lint_err_on_line::<NoFallthrough>(
       "switch(foo) { case 0:\n\n default: b() }",
-      14,
+      3,
+      1,
     );
 
-    assert_lint_err::<NoFallthrough>(
+    assert_lint_err_on_line::<NoFallthrough>(
       "switch(foo) { case 0:\n // comment\n default: b() }",
-      14,
+      3,
+      1,
     );
   }
 
@@ -336,7 +415,180 @@ mod tests {
   fn err_4() {
     assert_lint_err::<NoFallthrough>(
       "switch(foo) { case 0: a(); /* falling through */ default: b() }",
-      14,
+      49,
+    );
+  }
+
+  #[test]
+  fn all_cases_are_checked() {
+    assert_lint_err::<NoFallthrough>(
+      "switch(foo) { case 0: a(); break; case 1: b(); case 2: c(); break; }",
+      47,
+    );
+
+    assert_lint_err::<NoFallthrough>(
+      "switch(foo) { case 0: a(); break; case 1: b(); break; case 2: c(); default: d(); }",
+      67,
+    );
+
+    assert_lint_err_n::<NoFallthrough>(
+      "switch(foo) { case 0: a(); case 1: b(); break; case 2: c(); case 3: d(); }",
+      vec![27, 60],
+    );
+  }
+
+  #[test]
+  fn shared_cases() {
+    assert_lint_ok::<NoFallthrough>(
+      "switch(foo) { case 0: a(); break; case 1: case 2: case 3: b(); break; default: c(); }",
+    );
+
+    assert_lint_ok::<NoFallthrough>(
+      "switch(foo) { case 0: a(); break; case 1:\n case 2:\n default: b(); }",
+    );
+
+    assert_lint_err::<NoFallthrough>(
+      "switch(foo) { case 0: case 1: a(); case 2: b(); }",
+      35,
+    );
+  }
+
+  #[test]
+  fn nested_terminators() {
+    assert_lint_ok::<NoFallthrough>(
+      "switch(foo) { case 0: { a(); break; } case 1: { b(); } }",
+    );
+
+    assert_lint_ok::<NoFallthrough>(
+      "function f() { switch(foo) { case 0: if (a) { return 1; } else if (b) { throw e; } else { break; } case 1: b(); } }",
+    );
+
+    assert_lint_err::<NoFallthrough>(
+      "switch(foo) { case 0: { a(); } case 1: b(); }",
+      31,
+    );
+
+    assert_lint_err::<NoFallthrough>(
+      "switch(foo) { case 0: if (a) { break; } else if (b) { c(); } else { break; } case 1: b(); }",
+      77,
+    );
+  }
+
+  #[test]
+  fn fallthrough_comments() {
+    assert_lint_ok::<NoFallthrough>(
+      "switch(foo) { case 0: a(); // falls through\n case 1: b(); }",
+    );
+
+    assert_lint_ok::<NoFallthrough>(
+      "switch(foo) { case 0: a();\n // fallsthrough\n case 1: b(); }",
+    );
+
+    assert_lint_err_on_line::<NoFallthrough>(
+      "switch(foo) { case 0: a(); // fals through\n case 1: b(); }",
+      2,
+      1,
+    );
+
+    assert_lint_err::<NoFallthrough>(
+      "switch(foo) { case 0: { a(); /* falls through */ } case 1: b(); }",
+      51,
+    );
+  }
+
+  #[test]
+  fn custom_comment_pattern() {
+    let config =
+      serde_json::json!({ "commentPattern": "break[\\s\\w]*omitted" });
+
+    assert_lint_ok_with_config::<NoFallthrough>(
+      "switch(foo) { case 0: a(); /* break omitted */ case 1: b(); }",
+      config.clone(),
+    );
+
+    assert_lint_ok_with_config::<NoFallthrough>(
+      "switch(foo) { case 0: a(); /* break intentionally omitted */ case 1: b(); }",
+      config.clone(),
+    );
+
+    assert_lint_err_with_config::<NoFallthrough>(
+      "switch(foo) { case 0: a(); /* falls through */ case 1: b(); }",
+      config,
+      47,
+    );
+
+    assert!(NoFallthrough::new()
+      .new_with_config(serde_json::json!({ "commentPattern": "(" }))
+      .is_err());
+  }
+
+  #[test]
+  fn allow_empty_case() {
+    let allow = serde_json::json!({ "allowEmptyCase": true });
+    let disallow = serde_json::json!({ "allowEmptyCase": false });
+
+    assert_lint_ok_with_config::<NoFallthrough>(
+      "switch(foo) { case 0:\n\n case 1: a(); }",
+      allow.clone(),
+    );
+
+    assert_lint_ok_with_config::<NoFallthrough>(
+      "switch(foo) { case 0:\n // comment\n default: a(); }",
+      allow,
+    );
+
+    assert_lint_err_on_line::<NoFallthrough>(
+      "switch(foo) { case 0:\n\n case 1: a(); }",
+      3,
+      1,
+    );
+
+    assert_lint_err_with_config::<NoFallthrough>(
+      "switch(foo) { case 0: case 1: a(); }",
+      disallow.clone(),
+      22,
+    );
+
+    assert_lint_err_with_config::<NoFallthrough>(
+      "switch(foo) { case 0: a(); break; case 1: default: b(); }",
+      disallow.clone(),
+      42,
+    );
+
+    assert_lint_ok_with_config::<NoFallthrough>(
+      "switch(foo) { case 0: a(); break; case 1: b(); }",
+      disallow,
+    );
+
+    assert!(NoFallthrough::new()
+      .new_with_config(serde_json::json!({ "allowEmptyCase": "yes" }))
+      .is_err());
+  }
+
+  #[test]
+  fn message_names_case() {
+    let diagnostics = lint_with_rule(
+      NoFallthrough::new(),
+      "switch(foo) { case 0: a(); case bar.baz: b(); default: c(); }",
+    );
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(
+      diagnostics[0].message,
+      "Expected a 'break' statement before 'case bar.baz'"
+    );
+    assert_eq!(
+      diagnostics[1].message,
+      "Expected a 'break' statement before 'default'"
+    );
+  }
+
+  #[test]
+  fn hint() {
+    assert_lint_err_with_hint::<NoFallthrough>(
+      "switch (a) { case 1: foo(); case 2: bar(); }",
+      1,
+      28,
+      "Add a 'break' statement, or a '// fallthrough' comment if falling through is intended",
     );
   }
 }
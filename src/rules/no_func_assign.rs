@@ -1,11 +1,12 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use crate::{scopes::BindingKind, swc_util::find_lhs_ids};
-use swc_ecmascript::ast::AssignExpr;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
+use swc_common::Span;
+use swc_ecmascript::ast::{AssignExpr, Expr, UpdateExpr};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
 
 use std::sync::Arc;
 
@@ -20,13 +21,19 @@ impl LintRule for NoFuncAssign {
     "no-func-assign"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows reassigning variables declared by function declarations."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoFuncAssignVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoFuncAssignVisitor::new(context)))
   }
 }
 
@@ -38,33 +45,46 @@ impl NoFuncAssignVisitor {
   fn new(context: Arc<Context>) -> Self {
     Self { context }
   }
-}
 
-impl Visit for NoFuncAssignVisitor {
-  noop_visit_type!();
-
-  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, _node: &dyn Node) {
-    let ids = find_lhs_ids(&assign_expr.left);
-
-    for id in ids {
-      let var = self.context.scope.var(&id);
-      if let Some(var) = var {
-        if let BindingKind::Function = var.kind() {
-          self.context.add_diagnostic(
-            assign_expr.span,
-            "no-func-assign",
-            "Reassigning function declaration is not allowed",
-          );
-        }
+  fn check(&self, id: &Id, span: Span) {
+    if let Some(var) = self.context.scope.var(id) {
+      if let BindingKind::Function = var.kind() {
+        self.context.add_diagnostic(
+          span,
+          "no-func-assign",
+          "Reassigning function declaration is not allowed",
+        );
       }
     }
   }
 }
 
+impl Handler for NoFuncAssignVisitor {
+  fn visit_assign_expr(
+    &mut self,
+    assign_expr: &AssignExpr,
+    _traversal: &mut Traversal,
+  ) {
+    for id in find_lhs_ids(&assign_expr.left) {
+      self.check(&id, assign_expr.span);
+    }
+  }
+
+  fn visit_update_expr(
+    &mut self,
+    update_expr: &UpdateExpr,
+    _traversal: &mut Traversal,
+  ) {
+    if let Expr::Ident(ident) = &*update_expr.arg {
+      self.check(&ident.to_id(), update_expr.span);
+    }
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::test_util::assert_lint_err_on_line;
+  use crate::test_util::*;
 
   #[test]
   fn no_func_assign() {
@@ -85,4 +105,37 @@ asdf = "foobar";
       0,
     );
   }
+
+  #[test]
+  fn no_func_assign_valid() {
+    assert_lint_ok! {
+      NoFuncAssign,
+      "var foo = function() {}; foo = bar;",
+      "let foo = () => {}; foo = bar;",
+      "function foo() { var foo = bar; foo = baz; }",
+      "function foo(foo) { foo = bar; }",
+      "function foo() { { let foo = 1; foo++; } }",
+      "foo = bar; var foo = function() {};",
+      "function foo() {} foo.bar = 1;",
+      "class Foo {} function bar() { let Foo = 1; Foo = 2; }",
+    };
+  }
+
+  #[test]
+  fn no_func_assign_invalid() {
+    assert_lint_err::<NoFuncAssign>("function foo() {} foo = bar;", 18);
+    assert_lint_err::<NoFuncAssign>("foo = bar; function foo() {}", 0);
+    assert_lint_err::<NoFuncAssign>("function foo() { foo = bar; }", 17);
+    assert_lint_err::<NoFuncAssign>("function foo() {} foo += 1;", 18);
+    assert_lint_err::<NoFuncAssign>("function foo() {} foo++;", 18);
+    assert_lint_err::<NoFuncAssign>("function foo() {} [foo] = bar;", 18);
+    assert_lint_err::<NoFuncAssign>(
+      "function foo() {} ({ x: foo = 0 } = bar);",
+      19,
+    );
+    assert_lint_err::<NoFuncAssign>(
+      "function foo() {} bar(() => { foo = 1; });",
+      30,
+    );
+  }
 }
@@ -1,5 +1,6 @@
 use super::LintRule;
-use crate::{globals::GLOBALS, linter::Context, swc_util::find_lhs_ids};
+use crate::handler::{Handler, Traversal};
+use crate::{linter::Context, swc_util::find_lhs_ids};
 use std::{collections::HashSet, sync::Arc};
 use swc_common::Span;
 use swc_ecmascript::{
@@ -22,18 +23,19 @@ impl LintRule for NoGlobalAssign {
     "no-global-assign"
   }
 
-  fn lint_module(
-    &self,
-    context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut collector = Collector {
-      bindings: Default::default(),
-    };
-    module.visit_with(module, &mut collector);
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows assignment to native objects and read-only global variables."
+  }
 
-    let mut visitor = NoGlobalAssignVisitor::new(context, collector.bindings);
-    module.visit_with(module, &mut visitor);
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoGlobalAssignVisitor::new(context)))
   }
 }
 
@@ -98,8 +100,11 @@ struct NoGlobalAssignVisitor {
 }
 
 impl NoGlobalAssignVisitor {
-  fn new(context: Arc<Context>, bindings: HashSet<Id>) -> Self {
-    Self { context, bindings }
+  fn new(context: Arc<Context>) -> Self {
+    Self {
+      context,
+      bindings: Default::default(),
+    }
   }
 
   fn check(&self, span: Span, id: Id) {
@@ -113,7 +118,7 @@ impl NoGlobalAssignVisitor {
     }
 
     // We only care about globals.
-    if !GLOBALS.contains(&&*id.0) {
+    if !self.context.scope.is_global(&id.0) {
       return;
     }
 
@@ -125,10 +130,17 @@ impl NoGlobalAssignVisitor {
   }
 }
 
-impl Visit for NoGlobalAssignVisitor {
-  noop_visit_type!();
+impl Handler for NoGlobalAssignVisitor {
+  fn visit_module(&mut self, module: &Module, _traversal: &mut Traversal) {
+    let mut collector = Collector {
+      bindings: Default::default(),
+    };
+    module.visit_with(module, &mut collector);
+    self.bindings = collector.bindings;
+  }
 
-  fn visit_assign_expr(&mut self, e: &AssignExpr, _: &dyn Node) {
+  fn visit_assign_expr(&mut self, e: &AssignExpr, traversal: &mut Traversal) {
+    traversal.skip_children();
     let idents: Vec<Ident> = find_lhs_ids(&e.left);
 
     for ident in idents {
@@ -136,11 +148,10 @@ impl Visit for NoGlobalAssignVisitor {
     }
   }
 
-  fn visit_update_expr(&mut self, e: &UpdateExpr, _: &dyn Node) {
+  fn visit_update_expr(&mut self, e: &UpdateExpr, traversal: &mut Traversal) {
     if let Expr::Ident(i) = &*e.arg {
+      traversal.skip_children();
       self.check(e.span, i.to_id());
-    } else {
-      e.visit_children_with(self);
     }
   }
 }
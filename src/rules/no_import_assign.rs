@@ -1,12 +1,13 @@
 use super::LintRule;
+use crate::handler::{walk, Handler, Traversal};
 use crate::linter::Context;
+use crate::scopes::BindingKind;
 use std::{collections::HashSet, sync::Arc};
 use swc_atoms::js_word;
 use swc_common::Span;
 use swc_common::Spanned;
 use swc_ecmascript::{
   ast::*,
-  utils::find_ids,
   utils::ident::IdentLike,
   utils::Id,
   visit::Node,
@@ -24,53 +25,31 @@ impl LintRule for NoImportAssign {
     "no-import-assign"
   }
 
-  fn lint_module(
-    &self,
-    context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut collector = Collector {
-      imports: Default::default(),
-      ns_imports: Default::default(),
-      other_bindings: Default::default(),
-    };
-    module.visit_with(module, &mut collector);
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
 
-    let mut visitor = NoImportAssignVisitor::new(
-      context,
-      collector.imports,
-      collector.ns_imports,
-      collector.other_bindings,
-    );
-    module.visit_with(module, &mut visitor);
+  fn docs(&self) -> &'static str {
+    "Disallows assignment to imported bindings."
+  }
+
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoImportAssignVisitor::new(context)))
   }
 }
 
+/// Collects namespace imports, as the scope analysis doesn't tell them apart
+/// from other imports.
 struct Collector {
-  imports: HashSet<Id>,
   ns_imports: HashSet<Id>,
-  other_bindings: HashSet<Id>,
 }
 
 impl Visit for Collector {
   noop_visit_type!();
 
-  fn visit_import_named_specifier(
-    &mut self,
-    i: &ImportNamedSpecifier,
-    _: &dyn Node,
-  ) {
-    self.imports.insert(i.local.to_id());
-  }
-
-  fn visit_import_default_specifier(
-    &mut self,
-    i: &ImportDefaultSpecifier,
-    _: &dyn Node,
-  ) {
-    self.imports.insert(i.local.to_id());
-  }
-
   fn visit_import_star_as_specifier(
     &mut self,
     i: &ImportStarAsSpecifier,
@@ -79,65 +58,30 @@ impl Visit for Collector {
     self.ns_imports.insert(i.local.to_id());
   }
 
-  // Other top level bindings
-
-  fn visit_fn_decl(&mut self, n: &FnDecl, _: &dyn Node) {
-    self.other_bindings.insert(n.ident.to_id());
-  }
-
-  fn visit_class_decl(&mut self, n: &ClassDecl, _: &dyn Node) {
-    self.other_bindings.insert(n.ident.to_id());
-  }
-
-  fn visit_var_declarator(&mut self, n: &VarDeclarator, _: &dyn Node) {
-    let ids: Vec<Id> = find_ids(&n.name);
-
-    for id in ids {
-      self.other_bindings.insert(id);
-    }
-  }
-
   fn visit_expr(&mut self, _: &Expr, _: &dyn Node) {}
 }
 
 struct NoImportAssignVisitor {
   context: Arc<Context>,
-  /// This hashset only contains top level bindings, so using HashSet<JsWord>
-  /// also can be an option.
-  imports: HashSet<Id>,
   ns_imports: HashSet<Id>,
-  /// Top level bindings other than import.
-  other_bindings: HashSet<Id>,
 }
 
 impl NoImportAssignVisitor {
-  fn new(
-    context: Arc<Context>,
-    imports: HashSet<Id>,
-    ns_imports: HashSet<Id>,
-    other_bindings: HashSet<Id>,
-  ) -> Self {
+  fn new(context: Arc<Context>) -> Self {
     Self {
       context,
-      imports,
-      ns_imports,
-      other_bindings,
+      ns_imports: Default::default(),
     }
   }
 
   fn check(&self, span: Span, i: &Ident, is_assign_to_prop: bool) {
-    // All imports are top-level and as a result,
-    // if an identifier is not top-level, we are not assigning to import
-    if i.span.ctxt != self.context.top_level_ctxt {
-      return;
-    }
-
-    // We only care about imports
-    if self.other_bindings.contains(&i.to_id()) {
-      return;
+    let id = i.to_id();
+    match self.context.scope.var(&id) {
+      Some(var) if var.kind() == BindingKind::Import => {}
+      _ => return,
     }
 
-    if self.ns_imports.contains(&i.to_id()) {
+    if self.ns_imports.contains(&id) {
       self.context.add_diagnostic(
         span,
         "no-import-assign",
@@ -146,7 +90,7 @@ impl NoImportAssignVisitor {
       return;
     }
 
-    if !is_assign_to_prop && self.imports.contains(&i.to_id()) {
+    if !is_assign_to_prop {
       self.context.add_diagnostic(
         span,
         "no-import-assign",
@@ -173,16 +117,14 @@ impl NoImportAssignVisitor {
       }
       Expr::OptChain(e) => self.check_expr(span, &e.expr),
       Expr::Paren(e) => self.check_expr(span, &e.expr),
-      _ => e.visit_children_with(self),
+      _ => walk(self, e),
     }
   }
 
   fn is_modifier(&self, obj: &Expr, prop: &Expr) -> bool {
+    // `Object` or `Reflect` may be shadowed.
     if let Expr::Ident(obj) = obj {
-      if self.context.top_level_ctxt != obj.span.ctxt {
-        return false;
-      }
-      if self.other_bindings.contains(&obj.to_id()) {
+      if self.context.scope.var(&obj.to_id()).is_some() {
         return false;
       }
     }
@@ -258,25 +200,32 @@ impl NoImportAssignVisitor {
   }
 }
 
-impl Visit for NoImportAssignVisitor {
-  noop_visit_type!();
+impl Handler for NoImportAssignVisitor {
+  fn visit_module(&mut self, module: &Module, _traversal: &mut Traversal) {
+    let mut collector = Collector {
+      ns_imports: Default::default(),
+    };
+    module.visit_with(module, &mut collector);
+    self.ns_imports = collector.ns_imports;
+  }
 
-  fn visit_pat(&mut self, n: &Pat, _: &dyn Node) {
+  fn visit_pat(&mut self, n: &Pat, traversal: &mut Traversal) {
     match n {
       Pat::Ident(i) => {
+        traversal.skip_children();
         self.check(i.span, &i, false);
       }
       Pat::Expr(e) => {
+        traversal.skip_children();
         self.check_expr(n.span(), e);
       }
-      _ => {
-        n.visit_children_with(self);
-      }
+      _ => {}
     }
   }
 
-  fn visit_rest_pat(&mut self, n: &RestPat, _: &dyn Node) {
+  fn visit_rest_pat(&mut self, n: &RestPat, traversal: &mut Traversal) {
     if let Pat::Expr(e) = &*n.arg {
+      traversal.skip_children();
       match &**e {
         Expr::Ident(i) => {
           self.check(i.span, i, true);
@@ -285,44 +234,38 @@ impl Visit for NoImportAssignVisitor {
           self.check_expr(e.span(), e);
         }
       }
-    } else {
-      n.visit_children_with(self)
     }
   }
 
-  fn visit_assign_expr(&mut self, n: &AssignExpr, _: &dyn Node) {
-    match &n.left {
-      PatOrExpr::Expr(e) => {
-        self.check_expr(n.span, e);
-      }
-      PatOrExpr::Pat(p) => {
-        p.visit_with(n, self);
-      }
-    };
-    n.right.visit_with(n, self);
+  fn visit_assign_expr(&mut self, n: &AssignExpr, traversal: &mut Traversal) {
+    if let PatOrExpr::Expr(e) = &n.left {
+      traversal.skip_children();
+      self.check_expr(n.span, e);
+      walk(self, &*n.right);
+    }
   }
 
-  fn visit_assign_pat_prop(&mut self, n: &AssignPatProp, _: &dyn Node) {
+  fn visit_assign_pat_prop(
+    &mut self,
+    n: &AssignPatProp,
+    _traversal: &mut Traversal,
+  ) {
     self.check(n.key.span, &n.key, false);
-
-    n.value.visit_children_with(self);
   }
 
-  fn visit_update_expr(&mut self, n: &UpdateExpr, _: &dyn Node) {
+  fn visit_update_expr(&mut self, n: &UpdateExpr, traversal: &mut Traversal) {
+    traversal.skip_children();
     self.check_expr(n.span, &n.arg);
   }
 
-  fn visit_unary_expr(&mut self, n: &UnaryExpr, _: &dyn Node) {
+  fn visit_unary_expr(&mut self, n: &UnaryExpr, traversal: &mut Traversal) {
     if let UnaryOp::Delete = n.op {
+      traversal.skip_children();
       self.check_expr(n.span, &n.arg);
-    } else {
-      n.arg.visit_with(n, self);
     }
   }
 
-  fn visit_call_expr(&mut self, n: &CallExpr, _: &dyn Node) {
-    n.visit_children_with(self);
-
+  fn exit_call_expr(&mut self, n: &CallExpr) {
     if let ExprOrSuper::Expr(callee) = &n.callee {
       if let Some(arg) = n.args.first() {
         if self.modifies_first(&callee) {
@@ -948,4 +891,27 @@ mod tests {
       28,
     );
   }
+
+  #[test]
+  fn scoped_bindings() {
+    assert_lint_ok_n::<NoImportAssign>(vec![
+      "import mod from 'mod'; function foo(mod) { mod = 0; }",
+      "import mod from 'mod'; const foo = (mod) => { mod++; };",
+      "import * as mod from 'mod'; function foo() { let mod; mod.x = 0; }",
+      "import * as mod from 'mod'; function foo(Object) { Object.assign(mod); }",
+      "import mod from 'mod'; try {} catch (mod) { mod = 0; }",
+    ]);
+    assert_lint_err::<NoImportAssign>(
+      "import mod from 'mod'; function foo() { mod = 0; }",
+      40,
+    );
+    assert_lint_err::<NoImportAssign>(
+      "import * as mod from 'mod'; const foo = () => { mod.x = 0; };",
+      48,
+    );
+    assert_lint_err::<NoImportAssign>(
+      "import * as mod from 'mod'; { let x; Object.assign(mod, x); }",
+      37,
+    );
+  }
 }
@@ -1,12 +1,11 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use std::sync::Arc;
 use swc_ecmascript::ast::{
   Expr, ExprOrSuper, Lit, TsKeywordType, TsType, TsTypeRef, VarDecl,
 };
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 pub struct NoInferrableTypes;
 
@@ -19,13 +18,19 @@ impl LintRule for NoInferrableTypes {
     "no-inferrable-types"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows explicit type annotations for variables and parameters initialized to a primitive literal."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoInferrableTypesVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoInferrableTypesVisitor::new(context)))
   }
 }
 
@@ -296,12 +301,17 @@ impl NoInferrableTypesVisitor {
   }
 }
 
-impl Visit for NoInferrableTypesVisitor {
+impl Handler for NoInferrableTypesVisitor {
+  fn visit_types(&self) -> bool {
+    true
+  }
+
   fn visit_function(
     &mut self,
     function: &swc_ecmascript::ast::Function,
-    _parent: &dyn Node,
+    traversal: &mut Traversal,
   ) {
+    traversal.skip_children();
     for param in &function.params {
       if let swc_ecmascript::ast::Pat::Assign(assign_pat) = &param.pat {
         if let swc_ecmascript::ast::Pat::Ident(ident) = &*assign_pat.left {
@@ -316,8 +326,9 @@ impl Visit for NoInferrableTypesVisitor {
   fn visit_arrow_expr(
     &mut self,
     arr_expr: &swc_ecmascript::ast::ArrowExpr,
-    _parent: &dyn Node,
+    traversal: &mut Traversal,
   ) {
+    traversal.skip_children();
     for param in &arr_expr.params {
       if let swc_ecmascript::ast::Pat::Assign(assign_pat) = &param {
         if let swc_ecmascript::ast::Pat::Ident(ident) = &*assign_pat.left {
@@ -336,8 +347,9 @@ impl Visit for NoInferrableTypesVisitor {
   fn visit_class_prop(
     &mut self,
     prop: &swc_ecmascript::ast::ClassProp,
-    _parent: &dyn Node,
+    traversal: &mut Traversal,
   ) {
+    traversal.skip_children();
     if prop.readonly || prop.is_optional {
       return;
     }
@@ -350,15 +362,16 @@ impl Visit for NoInferrableTypesVisitor {
     }
   }
 
-  fn visit_var_decl(&mut self, var_decl: &VarDecl, _parent: &dyn Node) {
+  fn visit_var_decl(&mut self, var_decl: &VarDecl, traversal: &mut Traversal) {
+    traversal.skip_children();
     if let Some(init) = &var_decl.decls[0].init {
       if let Expr::Fn(fn_expr) = &**init {
         if !fn_expr.function.params.is_empty() {
-          self.visit_function(&fn_expr.function, _parent);
+          self.visit_function(&fn_expr.function, traversal);
         }
       } else if let Expr::Arrow(arr_expr) = &**init {
         if !arr_expr.params.is_empty() {
-          self.visit_arrow_expr(&arr_expr, _parent);
+          self.visit_arrow_expr(arr_expr, traversal);
         }
       }
       if let swc_ecmascript::ast::Pat::Ident(ident) = &var_decl.decls[0].name {
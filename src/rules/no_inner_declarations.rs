@@ -1,13 +1,12 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_common::Span;
 use swc_common::Spanned;
 use swc_ecmascript::ast;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 pub struct NoInnerDeclarations;
 
@@ -20,186 +19,150 @@ impl LintRule for NoInnerDeclarations {
     "no-inner-declarations"
   }
 
-  fn lint_module(&self, context: Arc<Context>, module: &ast::Module) {
-    let mut valid_visitor = ValidDeclsVisitor::new();
-    valid_visitor.visit_module(module, module);
-    let mut valid_decls = valid_visitor.valid_decls;
-    valid_decls.dedup();
-    let mut visitor = NoInnerDeclarationsVisitor::new(context, valid_decls);
-    visitor.visit_module(module, module);
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows variable or function declarations in nested blocks."
+  }
+
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoInnerDeclarationsVisitor::new(context)))
   }
 }
 
-struct ValidDeclsVisitor {
-  pub valid_decls: Vec<Span>,
+struct NoInnerDeclarationsVisitor {
+  context: Arc<Context>,
+  /// Declarations at the root of the module or of a function, which are
+  /// collected when entering their parent.
+  valid_decls: HashSet<Span>,
+  function_depth: usize,
 }
 
-impl ValidDeclsVisitor {
-  fn new() -> Self {
+impl NoInnerDeclarationsVisitor {
+  fn new(context: Arc<Context>) -> Self {
     Self {
-      valid_decls: vec![],
+      context,
+      valid_decls: HashSet::new(),
+      function_depth: 0,
     }
   }
-}
 
-impl ValidDeclsVisitor {
-  fn check_decl(&mut self, decl: &ast::Decl) {
+  fn add_valid_decl(&mut self, decl: &ast::Decl) {
     match decl {
       ast::Decl::Fn(fn_decl) => {
-        self.valid_decls.push(fn_decl.span());
+        self.valid_decls.insert(fn_decl.span());
       }
       ast::Decl::Var(var_decl) => {
         if var_decl.kind == ast::VarDeclKind::Var {
-          self.valid_decls.push(var_decl.span());
+          self.valid_decls.insert(var_decl.span());
         }
       }
       _ => {}
     }
   }
-}
 
-impl Visit for ValidDeclsVisitor {
-  noop_visit_type!();
+  fn add_valid_body_decls(&mut self, body: Option<&ast::BlockStmt>) {
+    if let Some(block) = body {
+      for stmt in &block.stmts {
+        if let ast::Stmt::Decl(decl) = stmt {
+          self.add_valid_decl(decl);
+        }
+      }
+    }
+  }
 
-  fn visit_module_item(&mut self, item: &ast::ModuleItem, parent: &dyn Node) {
+  fn add_diagnostic(&mut self, span: Span, kind: &str) {
+    let root = if self.function_depth > 0 {
+      "function"
+    } else {
+      "module"
+    };
+
+    self.context.add_diagnostic(
+      span,
+      "no-inner-declarations",
+      &format!("Move {} declaration to {} root", kind, root),
+    );
+  }
+}
+
+impl Handler for NoInnerDeclarationsVisitor {
+  fn visit_module_item(
+    &mut self,
+    item: &ast::ModuleItem,
+    _traversal: &mut Traversal,
+  ) {
     match item {
       ast::ModuleItem::ModuleDecl(module_decl) => match module_decl {
         ast::ModuleDecl::ExportDecl(decl_export) => {
-          self.check_decl(&decl_export.decl)
+          self.add_valid_decl(&decl_export.decl)
         }
         ast::ModuleDecl::ExportDefaultDecl(default_export) => {
           if let ast::DefaultDecl::Fn(fn_expr) = &default_export.decl {
-            self.valid_decls.push(fn_expr.span());
+            self.valid_decls.insert(fn_expr.span());
           }
         }
         _ => {}
       },
       ast::ModuleItem::Stmt(module_stmt) => {
         if let ast::Stmt::Decl(decl) = module_stmt {
-          self.check_decl(decl)
-        }
-      }
-    }
-
-    swc_ecmascript::visit::visit_module_item(self, item, parent);
-  }
-
-  fn visit_function(&mut self, function: &ast::Function, parent: &dyn Node) {
-    if let Some(block) = &function.body {
-      for stmt in &block.stmts {
-        if let ast::Stmt::Decl(decl) = stmt {
-          self.check_decl(decl);
-        }
-      }
-    }
-    swc_ecmascript::visit::visit_function(self, function, parent);
-  }
-
-  fn visit_fn_decl(&mut self, fn_decl: &ast::FnDecl, parent: &dyn Node) {
-    if let Some(block) = &fn_decl.function.body {
-      for stmt in &block.stmts {
-        if let ast::Stmt::Decl(decl) = stmt {
-          self.check_decl(decl);
-        }
-      }
-    }
-    swc_ecmascript::visit::visit_fn_decl(self, fn_decl, parent);
-  }
-
-  fn visit_fn_expr(&mut self, fn_expr: &ast::FnExpr, parent: &dyn Node) {
-    if let Some(block) = &fn_expr.function.body {
-      for stmt in &block.stmts {
-        if let ast::Stmt::Decl(decl) = stmt {
-          self.check_decl(decl);
+          self.add_valid_decl(decl)
         }
       }
     }
-    swc_ecmascript::visit::visit_fn_expr(self, fn_expr, parent);
   }
 
   fn visit_arrow_expr(
     &mut self,
     arrow_expr: &ast::ArrowExpr,
-    parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     if let ast::BlockStmtOrExpr::BlockStmt(block) = &arrow_expr.body {
-      for stmt in &block.stmts {
-        if let ast::Stmt::Decl(decl) = stmt {
-          self.check_decl(decl);
-        }
-      }
+      self.add_valid_body_decls(Some(block));
     }
-    swc_ecmascript::visit::visit_arrow_expr(self, arrow_expr, parent);
+    self.function_depth += 1;
   }
-}
-
-struct NoInnerDeclarationsVisitor {
-  context: Arc<Context>,
-  valid_decls: Vec<Span>,
-  in_function: bool,
-}
 
-impl NoInnerDeclarationsVisitor {
-  fn new(context: Arc<Context>, valid_decls: Vec<Span>) -> Self {
-    Self {
-      context,
-      valid_decls,
-      in_function: false,
-    }
+  fn exit_arrow_expr(&mut self, _arrow_expr: &ast::ArrowExpr) {
+    self.function_depth -= 1;
   }
-}
 
-impl NoInnerDeclarationsVisitor {
-  fn add_diagnostic(&mut self, span: Span, kind: &str) {
-    let root = if self.in_function {
-      "function"
-    } else {
-      "module"
-    };
-
-    self.context.add_diagnostic(
-      span,
-      "no-inner-declarations",
-      &format!("Move {} declaration to {} root", kind, root),
-    );
-  }
-}
-
-impl Visit for NoInnerDeclarationsVisitor {
-  noop_visit_type!();
-
-  fn visit_arrow_expr(&mut self, fn_: &ast::ArrowExpr, parent: &dyn Node) {
-    let old = self.in_function;
-    self.in_function = true;
-    swc_ecmascript::visit::visit_arrow_expr(self, fn_, parent);
-    self.in_function = old;
+  fn visit_function(
+    &mut self,
+    function: &ast::Function,
+    _traversal: &mut Traversal,
+  ) {
+    self.add_valid_body_decls(function.body.as_ref());
+    self.function_depth += 1;
   }
 
-  fn visit_function(&mut self, fn_: &ast::Function, parent: &dyn Node) {
-    let old = self.in_function;
-    self.in_function = true;
-    swc_ecmascript::visit::visit_function(self, fn_, parent);
-    self.in_function = old;
+  fn exit_function(&mut self, _function: &ast::Function) {
+    self.function_depth -= 1;
   }
 
-  fn visit_fn_decl(&mut self, decl: &ast::FnDecl, parent: &dyn Node) {
+  fn visit_fn_decl(&mut self, decl: &ast::FnDecl, _traversal: &mut Traversal) {
     let span = decl.span();
 
     if !self.valid_decls.contains(&span) {
       self.add_diagnostic(span, "function");
     }
-
-    swc_ecmascript::visit::visit_fn_decl(self, decl, parent);
   }
 
-  fn visit_var_decl(&mut self, decl: &ast::VarDecl, parent: &dyn Node) {
+  fn visit_var_decl(
+    &mut self,
+    decl: &ast::VarDecl,
+    _traversal: &mut Traversal,
+  ) {
     let span = decl.span();
 
     if decl.kind == ast::VarDeclKind::Var && !self.valid_decls.contains(&span) {
       self.add_diagnostic(span, "variable");
     }
-
-    swc_ecmascript::visit::visit_var_decl(self, decl, parent);
   }
 }
 
@@ -1,13 +1,11 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use crate::js_regex::*;
 use swc_common::Span;
 use swc_ecmascript::ast::Expr;
 use swc_ecmascript::ast::ExprOrSpread;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -22,13 +20,19 @@ impl LintRule for NoInvalidRegexp {
     "no-invalid-regexp"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows invalid regular expression strings in `RegExp` constructors."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoInvalidRegexpVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoInvalidRegexpVisitor::new(context)))
   }
 }
 
@@ -101,13 +105,11 @@ impl NoInvalidRegexpVisitor {
   }
 }
 
-impl Visit for NoInvalidRegexpVisitor {
-  noop_visit_type!();
-
+impl Handler for NoInvalidRegexpVisitor {
   fn visit_regex(
     &mut self,
     regex: &swc_ecmascript::ast::Regex,
-    _parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     self.check_regex(&regex.exp, &regex.flags, regex.span);
   }
@@ -115,8 +117,9 @@ impl Visit for NoInvalidRegexpVisitor {
   fn visit_call_expr(
     &mut self,
     call_expr: &swc_ecmascript::ast::CallExpr,
-    _paren: &dyn Node,
+    traversal: &mut Traversal,
   ) {
+    traversal.skip_children();
     if let swc_ecmascript::ast::ExprOrSuper::Expr(expr) = &call_expr.callee {
       self.handle_call_or_new_expr(&*expr, &call_expr.args, call_expr.span);
     }
@@ -125,8 +128,9 @@ impl Visit for NoInvalidRegexpVisitor {
   fn visit_new_expr(
     &mut self,
     new_expr: &swc_ecmascript::ast::NewExpr,
-    _parent: &dyn Node,
+    traversal: &mut Traversal,
   ) {
+    traversal.skip_children();
     if new_expr.args.is_some() {
       self.handle_call_or_new_expr(
         &*new_expr.callee,
@@ -1,11 +1,10 @@
 use super::{Context, LintRule};
+use crate::handler::{Handler, Traversal};
 use regex::{Matches, Regex};
 use std::sync::Arc;
 use swc_common::{hygiene::SyntaxContext, BytePos, Span};
 use swc_ecmascript::ast::Module;
 use swc_ecmascript::ast::Str;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 pub struct NoIrregularWhitespace;
 
@@ -40,13 +39,50 @@ impl LintRule for NoIrregularWhitespace {
     "no-irregular-whitespace"
   }
 
-  fn lint_module(&self, context: Arc<Context>, module: &Module) {
-    let mut visitor = NoIrregularWhitespaceVisitor::default();
-    visitor.visit_module(module, module);
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows irregular whitespace characters."
+  }
+
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoIrregularWhitespaceVisitor::new(context)))
+  }
+}
+
+struct NoIrregularWhitespaceVisitor {
+  context: Arc<Context>,
+  ranges: Vec<Span>,
+}
+
+impl NoIrregularWhitespaceVisitor {
+  fn new(context: Arc<Context>) -> Self {
+    Self {
+      context,
+      ranges: vec![],
+    }
+  }
+}
+
+impl Handler for NoIrregularWhitespaceVisitor {
+  fn visit_types(&self) -> bool {
+    true
+  }
+
+  fn visit_str(&mut self, string_literal: &Str, _traversal: &mut Traversal) {
+    self.ranges.push(string_literal.span);
+  }
 
-    let excluded_ranges = visitor.ranges.iter();
+  fn exit_module(&mut self, module: &Module) {
+    let excluded_ranges = self.ranges.iter();
 
-    let file_and_lines = context.source_map.span_to_lines(module.span).unwrap();
+    let file_and_lines =
+      self.context.source_map.span_to_lines(module.span).unwrap();
     let file = file_and_lines.file;
 
     for line_index in 0..file.count_lines() {
@@ -65,7 +101,7 @@ impl LintRule for NoIrregularWhitespace {
             let is_excluded =
               excluded_ranges.clone().any(|range| range.contains(span));
             if !is_excluded {
-              context.add_diagnostic(
+              self.context.add_diagnostic(
                 span,
                 "no-irregular-whitespace",
                 "Irregular whitespace not allowed.",
@@ -78,22 +114,6 @@ impl LintRule for NoIrregularWhitespace {
   }
 }
 
-struct NoIrregularWhitespaceVisitor {
-  ranges: Vec<Span>,
-}
-
-impl NoIrregularWhitespaceVisitor {
-  fn default() -> Self {
-    Self { ranges: vec![] }
-  }
-}
-
-impl Visit for NoIrregularWhitespaceVisitor {
-  fn visit_str(&mut self, string_literal: &Str, _parent: &dyn Node) {
-    self.ranges.push(string_literal.span);
-  }
-}
-
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -1,13 +1,12 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_ecmascript::ast::{
-  ClassDecl, ClassMember, Expr, Ident, Module, PropName, TsEntityName,
-  TsInterfaceDecl, TsType, TsTypeAliasDecl, TsTypeAnn,
+  ClassDecl, ClassMember, Expr, Ident, PropName, TsEntityName, TsInterfaceDecl,
+  TsType, TsTypeAliasDecl, TsTypeAnn,
   TsTypeElement::{TsConstructSignatureDecl, TsMethodSignature},
 };
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -18,14 +17,24 @@ impl LintRule for NoMisusedNew {
     Box::new(NoMisusedNew)
   }
 
-  fn lint_module(&self, context: Arc<Context>, module: &Module) {
-    let mut visitor = NoMisusedNewVisitor::new(context);
-    visitor.visit_module(module, module);
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoMisusedNewVisitor::new(context)))
   }
 
   fn code(&self) -> &'static str {
     "no-misused-new"
   }
+
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows defining `new` in interfaces and `constructor` in type aliases or classes with return types."
+  }
 }
 
 struct NoMisusedNewVisitor {
@@ -52,12 +61,17 @@ impl NoMisusedNewVisitor {
   }
 }
 
-impl Visit for NoMisusedNewVisitor {
+impl Handler for NoMisusedNewVisitor {
+  fn visit_types(&self) -> bool {
+    true
+  }
+
   fn visit_ts_type_alias_decl(
     &mut self,
     t: &TsTypeAliasDecl,
-    _parent: &dyn Node,
+    traversal: &mut Traversal,
   ) {
+    traversal.skip_children();
     if let TsType::TsTypeLit(lit) = &*t.type_ann {
       for member in &lit.members {
         if let TsMethodSignature(signature) = &member {
@@ -78,7 +92,7 @@ impl Visit for NoMisusedNewVisitor {
   fn visit_ts_interface_decl(
     &mut self,
     n: &TsInterfaceDecl,
-    parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     for member in &n.body.body {
       match &member {
@@ -109,11 +123,9 @@ impl Visit for NoMisusedNewVisitor {
         _ => {}
       }
     }
-
-    swc_ecmascript::visit::visit_ts_interface_decl(self, n, parent);
   }
 
-  fn visit_class_decl(&mut self, expr: &ClassDecl, parent: &dyn Node) {
+  fn visit_class_decl(&mut self, expr: &ClassDecl, _traversal: &mut Traversal) {
     for member in &expr.class.body {
       if let ClassMember::Method(method) = member {
         let method_name = match &method.key {
@@ -141,8 +153,6 @@ impl Visit for NoMisusedNewVisitor {
         }
       }
     }
-
-    swc_ecmascript::visit::visit_class_decl(self, expr, parent);
   }
 }
 
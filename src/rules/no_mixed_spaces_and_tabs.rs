@@ -1,6 +1,7 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use regex::Regex;
 use std::sync::Arc;
 use swc_common::BytePos;
@@ -8,9 +9,8 @@ use swc_common::Span;
 use swc_common::Spanned;
 use swc_common::SyntaxContext;
 use swc_ecmascript::ast::Lit;
+use swc_ecmascript::ast::Module;
 use swc_ecmascript::ast::Tpl;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 lazy_static! {
   static ref RE: Regex = Regex::new("^([\t ]*(\t | \t))").unwrap();
@@ -27,45 +27,70 @@ impl LintRule for NoMixedSpacesAndTabs {
     "no-mixed-spaces-and-tabs"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows mixed spaces and tabs for indentation."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoMixedSpacesAndTabsVisitor::default();
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoMixedSpacesAndTabsVisitor::new(context)))
+  }
+}
+
+struct NoMixedSpacesAndTabsVisitor {
+  context: Arc<Context>,
+  ranges: Vec<Span>,
+}
+
+impl NoMixedSpacesAndTabsVisitor {
+  fn new(context: Arc<Context>) -> Self {
+    Self {
+      context,
+      ranges: vec![],
+    }
+  }
+}
+
+impl Handler for NoMixedSpacesAndTabsVisitor {
+  fn visit_types(&self) -> bool {
+    true
+  }
+
+  fn visit_lit(&mut self, lit: &Lit, traversal: &mut Traversal) {
+    traversal.skip_children();
+    self.ranges.push(lit.span());
+  }
+
+  fn visit_tpl(&mut self, tpl: &Tpl, traversal: &mut Traversal) {
+    traversal.skip_children();
+    self.ranges.push(tpl.span);
+  }
 
-    let file_and_lines = context.source_map.span_to_lines(module.span).unwrap();
+  fn exit_module(&mut self, module: &Module) {
+    let file_and_lines =
+      self.context.source_map.span_to_lines(module.span).unwrap();
     let file = file_and_lines.file;
 
-    let mut excluded_ranges = visitor.ranges;
-
-    context.leading_comments.values().for_each(|comments| {
-      for comment in comments {
-        let lines = context
-          .source_map
-          .span_to_lines(comment.span)
-          .unwrap()
-          .lines;
-        for line in lines.iter().skip(1) {
-          let (lo, hi) = file.line_bounds(line.line_index as usize);
-          excluded_ranges.push(Span::new(lo, hi, SyntaxContext::empty()));
-        }
+    let mut excluded_ranges = std::mem::take(&mut self.ranges);
+
+    for comment in self.context.all_comments() {
+      let lines = self
+        .context
+        .source_map
+        .span_to_lines(comment.span)
+        .unwrap()
+        .lines;
+      for line in lines.iter().skip(1) {
+        let (lo, hi) = file.line_bounds(line.line_index);
+        excluded_ranges.push(Span::new(lo, hi, SyntaxContext::empty()));
       }
-    });
-    context.trailing_comments.values().for_each(|comments| {
-      for comment in comments {
-        let lines = context
-          .source_map
-          .span_to_lines(comment.span)
-          .unwrap()
-          .lines;
-        for line in lines.iter().skip(1) {
-          let (lo, hi) = file.line_bounds(line.line_index as usize);
-          excluded_ranges.push(Span::new(lo, hi, SyntaxContext::empty()));
-        }
-      }
-    });
+    }
 
     let excluded_ranges = excluded_ranges.iter();
     for line_index in 0..file.count_lines() {
@@ -82,7 +107,7 @@ impl LintRule for NoMixedSpacesAndTabs {
         let is_excluded =
           excluded_ranges.clone().any(|range| range.contains(span));
         if !is_excluded {
-          context.add_diagnostic(
+          self.context.add_diagnostic(
             span,
             "no-mixed-spaces-and-tabs",
             "Mixed spaces and tabs are not allowed.",
@@ -93,26 +118,6 @@ impl LintRule for NoMixedSpacesAndTabs {
   }
 }
 
-struct NoMixedSpacesAndTabsVisitor {
-  ranges: Vec<Span>,
-}
-
-impl NoMixedSpacesAndTabsVisitor {
-  fn default() -> Self {
-    Self { ranges: vec![] }
-  }
-}
-
-impl Visit for NoMixedSpacesAndTabsVisitor {
-  fn visit_lit(&mut self, lit: &Lit, _parent: &dyn Node) {
-    self.ranges.push(lit.span());
-  }
-
-  fn visit_tpl(&mut self, tpl: &Tpl, _parent: &dyn Node) {
-    self.ranges.push(tpl.span);
-  }
-}
-
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -1,9 +1,8 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_ecmascript::ast::{TsModuleDecl, TsModuleName};
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -18,13 +17,19 @@ impl LintRule for NoNamespace {
     "no-namespace"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows the use of `namespace` and `module` declarations."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoNamespaceVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoNamespaceVisitor::new(context)))
   }
 }
 
@@ -38,11 +43,15 @@ impl NoNamespaceVisitor {
   }
 }
 
-impl Visit for NoNamespaceVisitor {
+impl Handler for NoNamespaceVisitor {
+  fn visit_types(&self) -> bool {
+    true
+  }
+
   fn visit_ts_module_decl(
     &mut self,
     mod_decl: &TsModuleDecl,
-    parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     if !mod_decl.global {
       if let TsModuleName::Ident(_) = mod_decl.id {
@@ -53,9 +62,6 @@ impl Visit for NoNamespaceVisitor {
         );
       }
     }
-    for stmt in &mod_decl.body {
-      self.visit_ts_namespace_body(stmt, parent);
-    }
   }
 }
 
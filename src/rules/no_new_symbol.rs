@@ -1,10 +1,10 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
+use swc_common::{Span, Spanned};
 use swc_ecmascript::ast::{Expr, NewExpr};
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
+use swc_ecmascript::utils::ident::IdentLike;
 
 use std::sync::Arc;
 
@@ -19,13 +19,19 @@ impl LintRule for NoNewSymbol {
     "no-new-symbol"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows using `new` with the `Symbol` function."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoNewSymbolVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoNewSymbolVisitor::new(context)))
   }
 }
 
@@ -39,17 +45,29 @@ impl NoNewSymbolVisitor {
   }
 }
 
-impl Visit for NoNewSymbolVisitor {
-  noop_visit_type!();
-
-  fn visit_new_expr(&mut self, new_expr: &NewExpr, _parent: &dyn Node) {
+impl Handler for NoNewSymbolVisitor {
+  fn visit_new_expr(&mut self, new_expr: &NewExpr, _traversal: &mut Traversal) {
     if let Expr::Ident(ident) = &*new_expr.callee {
-      if ident.sym == *"Symbol" {
-        self.context.add_diagnostic(
+      // A locally declared `Symbol` may be a class.
+      if ident.sym == *"Symbol"
+        && self.context.scope.var(&ident.to_id()).is_none()
+      {
+        let mut diagnostic = self.context.create_default_diagnostic(
           new_expr.span,
           "no-new-symbol",
           "`Symbol` cannot be called as a constructor.",
         );
+        diagnostic.hint = Some("Call `Symbol()` without `new`".to_string());
+        let new_keyword = Span::new(
+          new_expr.span.lo(),
+          new_expr.callee.span().lo(),
+          Default::default(),
+        );
+        diagnostic.suggestions =
+          vec![self
+            .context
+            .create_suggestion("Remove `new`", new_keyword, "")];
+        self.context.push_diagnostic(diagnostic);
       }
     }
   }
@@ -63,6 +81,45 @@ mod tests {
   #[test]
   fn test_new_symbol() {
     assert_lint_err::<NoNewSymbol>("new Symbol()", 0);
+    assert_lint_err::<NoNewSymbol>("new Symbol(\"desc\")", 0);
+    assert_lint_err!(
+      NoNewSymbol,
+      "const a = [new Symbol(new Symbol())];",
+      [(1, 11), (1, 22)]
+    );
+    assert_lint_err!(
+      NoNewSymbol,
+      "function foo() {\n  return new Symbol();\n}",
+      2,
+      9
+    );
+  }
+
+  #[test]
+  fn test_new_symbol_hint_and_suggestion() {
+    use crate::diagnostic::apply_suggestion;
+
+    let source = "const sym = new Symbol(\"desc\");";
+    assert_lint_err_with_hint::<NoNewSymbol>(
+      source,
+      1,
+      12,
+      "Call `Symbol()` without `new`",
+    );
+    let diagnostics = lint_with_rule(NoNewSymbol::new(), source);
+    assert_eq!(
+      apply_suggestion(source, &diagnostics[0], 0).unwrap(),
+      "const sym = Symbol(\"desc\");"
+    );
+  }
+
+  #[test]
+  fn test_new_local_symbol_class() {
+    assert_lint_ok! {
+      NoNewSymbol,
+      "class Symbol {}\nnew Symbol();",
+      "function foo(Symbol) {\n  return new Symbol();\n}",
+    };
   }
 
   #[test]
@@ -1,9 +1,8 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_ecmascript::ast::{Expr, ExprOrSuper};
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 use swc_common::Span;
@@ -19,13 +18,17 @@ impl LintRule for NoNonNullAssertedOptionalChain {
     "no-non-null-asserted-optional-chain"
   }
 
-  fn lint_module(
-    &self,
+  fn docs(&self) -> &'static str {
+    "Disallows non-null assertions after an optional chain expression."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoNonNullAssertedOptionalChainVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoNonNullAssertedOptionalChainVisitor::new(
+      context,
+    )))
   }
 }
 
@@ -53,12 +56,17 @@ impl NoNonNullAssertedOptionalChainVisitor {
   }
 }
 
-impl Visit for NoNonNullAssertedOptionalChainVisitor {
+impl Handler for NoNonNullAssertedOptionalChainVisitor {
+  fn visit_types(&self) -> bool {
+    true
+  }
+
   fn visit_ts_non_null_expr(
     &mut self,
     ts_non_null_expr: &swc_ecmascript::ast::TsNonNullExpr,
-    _parent: &dyn Node,
+    traversal: &mut Traversal,
   ) {
+    traversal.skip_children();
     match &*ts_non_null_expr.expr {
       Expr::Member(member_expr) => {
         if let ExprOrSuper::Expr(expr) = &member_expr.obj {
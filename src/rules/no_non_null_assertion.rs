@@ -1,8 +1,7 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
+use crate::handler::{Handler, Traversal};
 
 use std::sync::Arc;
 
@@ -16,13 +15,15 @@ impl LintRule for NoNonNullAssertion {
     "no-non-null-assertion"
   }
 
-  fn lint_module(
-    &self,
+  fn docs(&self) -> &'static str {
+    "Disallows non-null assertions using the `!` postfix operator."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoNonNullAssertionVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoNonNullAssertionVisitor::new(context)))
   }
 }
 
@@ -36,12 +37,17 @@ impl NoNonNullAssertionVisitor {
   }
 }
 
-impl Visit for NoNonNullAssertionVisitor {
+impl Handler for NoNonNullAssertionVisitor {
+  fn visit_types(&self) -> bool {
+    true
+  }
+
   fn visit_ts_non_null_expr(
     &mut self,
     non_null_expr: &swc_ecmascript::ast::TsNonNullExpr,
-    _parent: &dyn Node,
+    traversal: &mut Traversal,
   ) {
+    traversal.skip_children();
     self.context.add_diagnostic(
       non_null_expr.span,
       "no-non-null-assertion",
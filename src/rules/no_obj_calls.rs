@@ -1,14 +1,15 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_common::Span;
 use swc_ecmascript::ast::CallExpr;
 use swc_ecmascript::ast::Expr;
 use swc_ecmascript::ast::ExprOrSuper;
+use swc_ecmascript::ast::Ident;
+use swc_ecmascript::ast::Lit;
 use swc_ecmascript::ast::NewExpr;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
+use swc_ecmascript::utils::ident::IdentLike;
 
 use std::sync::Arc;
 
@@ -23,16 +24,32 @@ impl LintRule for NoObjCalls {
     "no-obj-calls"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows calling global objects such as `Math` or `JSON` as functions.
+
+`Atomics`, `Intl`, `JSON`, `Math` and `Reflect` are namespaces which throw
+when called or constructed, also when accessed like `globalThis.Math`."#
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoObjCallsVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoObjCallsVisitor::new(context)))
   }
 }
 
+/// Global objects which aren't functions.
+const NON_CALLABLE_GLOBALS: [&str; 5] =
+  ["Atomics", "Intl", "JSON", "Math", "Reflect"];
+
+/// Names of the global object.
+const GLOBAL_OBJECTS: [&str; 3] = ["globalThis", "self", "window"];
+
 struct NoObjCallsVisitor {
   context: Arc<Context>,
 }
@@ -42,36 +59,67 @@ impl NoObjCallsVisitor {
     Self { context }
   }
 
-  fn check_callee(&self, callee_name: impl AsRef<str>, span: Span) {
-    let callee_name = callee_name.as_ref();
-    match callee_name {
-      "Math" | "JSON" | "Reflect" | "Atomics" => {
-        self.context.add_diagnostic(
-          span,
-          "no-obj-calls",
-          format!("`{}` call as function is not allowed", callee_name).as_ref(),
-        );
+  fn is_global(&self, ident: &Ident) -> bool {
+    self.context.scope.var(&ident.to_id()).is_none()
+  }
+
+  /// Returns the name of the global object if the callee is one of
+  /// `NON_CALLABLE_GLOBALS`, either directly or like `globalThis.Math`.
+  fn non_callable_name<'e>(&self, callee: &'e Expr) -> Option<&'e str> {
+    let name = match callee {
+      Expr::Paren(paren) => return self.non_callable_name(&paren.expr),
+      Expr::Ident(ident) if self.is_global(ident) => &*ident.sym,
+      Expr::Member(member) => {
+        let is_global_object = match &member.obj {
+          ExprOrSuper::Expr(obj) => matches!(
+            &**obj,
+            Expr::Ident(ident)
+              if GLOBAL_OBJECTS.contains(&&*ident.sym) && self.is_global(ident)
+          ),
+          ExprOrSuper::Super(_) => false,
+        };
+        if !is_global_object {
+          return None;
+        }
+        match &*member.prop {
+          Expr::Ident(ident) if !member.computed => &*ident.sym,
+          Expr::Lit(Lit::Str(str_)) if member.computed => &*str_.value,
+          _ => return None,
+        }
       }
-      _ => {}
+      _ => return None,
+    };
+    if NON_CALLABLE_GLOBALS.contains(&name) {
+      Some(name)
+    } else {
+      None
     }
   }
-}
 
-impl Visit for NoObjCallsVisitor {
-  noop_visit_type!();
+  fn check_callee(&self, callee: &Expr, span: Span) {
+    if let Some(callee_name) = self.non_callable_name(callee) {
+      self.context.add_diagnostic(
+        span,
+        "no-obj-calls",
+        format!("`{}` call as function is not allowed", callee_name).as_ref(),
+      );
+    }
+  }
+}
 
-  fn visit_call_expr(&mut self, call_expr: &CallExpr, _parent: &dyn Node) {
+impl Handler for NoObjCallsVisitor {
+  fn visit_call_expr(
+    &mut self,
+    call_expr: &CallExpr,
+    _traversal: &mut Traversal,
+  ) {
     if let ExprOrSuper::Expr(expr) = &call_expr.callee {
-      if let Expr::Ident(ident) = expr.as_ref() {
-        self.check_callee(&ident.sym, call_expr.span);
-      }
+      self.check_callee(expr, call_expr.span);
     }
   }
 
-  fn visit_new_expr(&mut self, new_expr: &NewExpr, _parent: &dyn Node) {
-    if let Expr::Ident(ident) = &*new_expr.callee {
-      self.check_callee(&ident.sym, new_expr.span);
-    }
+  fn visit_new_expr(&mut self, new_expr: &NewExpr, _traversal: &mut Traversal) {
+    self.check_callee(&new_expr.callee, new_expr.span);
   }
 }
 
@@ -139,4 +187,39 @@ mod tests {
   fn test_atomic_load_ok() {
     assert_lint_ok::<NoObjCalls>("Atomics.load(foo, 0);");
   }
+
+  #[test]
+  fn test_intl() {
+    assert_lint_err::<NoObjCalls>("Intl();", 0);
+    assert_lint_err::<NoObjCalls>("new Intl();", 0);
+    assert_lint_ok::<NoObjCalls>("new Intl.DateTimeFormat();");
+  }
+
+  #[test]
+  fn test_global_object_members() {
+    assert_lint_err::<NoObjCalls>("globalThis.JSON();", 0);
+    assert_lint_err::<NoObjCalls>("new globalThis.Math();", 0);
+    assert_lint_err::<NoObjCalls>("window.Reflect();", 0);
+    assert_lint_err::<NoObjCalls>("self['Atomics']();", 0);
+    assert_lint_err::<NoObjCalls>("(Math)();", 0);
+    assert_lint_ok::<NoObjCalls>("globalThis.JSON.parse('{}');");
+    assert_lint_ok::<NoObjCalls>("foo.Math();");
+    assert_lint_ok::<NoObjCalls>("globalThis[Math]();");
+  }
+
+  #[test]
+  fn test_nested() {
+    assert_lint_err::<NoObjCalls>("foo(Math());", 4);
+    assert_lint_err::<NoObjCalls>("function foo() { return JSON(); }", 24);
+  }
+
+  #[test]
+  fn test_shadowed() {
+    assert_lint_ok::<NoObjCalls>("function Math() {} Math();");
+    assert_lint_ok::<NoObjCalls>("const JSON = () => {}; JSON();");
+    assert_lint_ok::<NoObjCalls>("function foo(Reflect) { new Reflect(); }");
+    assert_lint_ok::<NoObjCalls>(
+      "const globalThis = { Math() {} }; globalThis.Math();",
+    );
+  }
 }
@@ -1,9 +1,8 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_ecmascript::ast::Number;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -18,13 +17,23 @@ impl LintRule for NoOctal {
     "no-octal"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows legacy octal literals like `071`.
+
+They're forbidden in strict mode and easily mistaken for decimals. Use the
+`0o71` notation instead. Like in ESLint, decimals with a leading zero like
+`08` are also reported."#
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoOctalVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoOctalVisitor::new(context)))
   }
 }
 
@@ -38,8 +47,12 @@ impl NoOctalVisitor {
   }
 }
 
-impl Visit for NoOctalVisitor {
-  fn visit_number(&mut self, literal_num: &Number, _parent: &dyn Node) {
+impl Handler for NoOctalVisitor {
+  fn visit_types(&self) -> bool {
+    true
+  }
+
+  fn visit_number(&mut self, literal_num: &Number, _traversal: &mut Traversal) {
     lazy_static! {
       static ref OCTAL: regex::Regex = regex::Regex::new(r"^0[0-9]").unwrap();
     }
@@ -68,6 +81,28 @@ mod tests {
   #[test]
   fn test_literal_octal() {
     assert_lint_err::<NoOctal>("07", 0);
+    assert_lint_err::<NoOctal>("var num = 071;", 10);
+    assert_lint_err::<NoOctal>("var num = 00;", 10);
+    assert_lint_err::<NoOctal>("var num = -071;", 11);
+  }
+
+  #[test]
+  fn test_decimal_with_leading_zero() {
+    // Like ESLint, `08` and `09` are reported even though they're parsed
+    // as decimals, as they're just as confusing. They're only valid in
+    // scripts.
+    let diagnostics =
+      lint_script_with_rule(NoOctal::new(), "var a = 08;\nvar b = 09;");
+    let positions = diagnostics
+      .iter()
+      .map(|d| (d.range.start.line, d.range.start.col))
+      .collect::<Vec<_>>();
+    assert_eq!(positions, vec![(1, 8), (2, 8)]);
+  }
+
+  #[test]
+  fn test_nested_octal() {
+    assert_lint_err!(NoOctal, "function foo() {\n  return [1, 017];\n}", 2, 13);
   }
 
   #[test]
@@ -77,6 +112,9 @@ mod tests {
 
   #[test]
   fn test_octals_valid() {
-    assert_lint_ok_n::<NoOctal>(vec!["7", "\"07\"", "0x08", "-0.01"]);
+    assert_lint_ok_n::<NoOctal>(vec![
+      "7", "\"07\"", "0x08", "-0.01", "0", "0.5", "0o71", "0O71", "0b101",
+      "0e1", "0n",
+    ]);
   }
 }
@@ -1,16 +1,15 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use std::sync::Arc;
 use swc_ecmascript::ast::CallExpr;
 use swc_ecmascript::ast::Expr;
 use swc_ecmascript::ast::ExprOrSuper;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
+use swc_ecmascript::ast::Lit;
 
 const BANNED_PROPERTIES: &[&str] =
-  &["hasOwnProperty", "isPrototypeOf", "propertyIsEnumberable"];
+  &["hasOwnProperty", "isPrototypeOf", "propertyIsEnumerable"];
 
 pub struct NoPrototypeBuiltins;
 
@@ -23,13 +22,19 @@ impl LintRule for NoPrototypeBuiltins {
     "no-prototype-builtins"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows calling `Object.prototype` methods directly on objects."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoPrototypeBuiltinsVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoPrototypeBuiltinsVisitor::new(context)))
   }
 }
 
@@ -43,34 +48,35 @@ impl NoPrototypeBuiltinsVisitor {
   }
 }
 
-impl Visit for NoPrototypeBuiltinsVisitor {
-  noop_visit_type!();
-
-  fn visit_call_expr(&mut self, call_expr: &CallExpr, _parent: &dyn Node) {
-    let member_expr = match &call_expr.callee {
-      ExprOrSuper::Expr(boxed_expr) => match &**boxed_expr {
-        Expr::Member(member_expr) => {
-          if member_expr.computed {
-            return;
+impl Handler for NoPrototypeBuiltinsVisitor {
+  fn visit_call_expr(
+    &mut self,
+    call_expr: &CallExpr,
+    _traversal: &mut Traversal,
+  ) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      if let Expr::Member(member_expr) = &**callee {
+        let prop_name = match &*member_expr.prop {
+          Expr::Ident(ident) if !member_expr.computed => Some(&ident.sym),
+          Expr::Lit(Lit::Str(str)) if member_expr.computed => Some(&str.value),
+          _ => None,
+        };
+        if let Some(prop_name) = prop_name {
+          if BANNED_PROPERTIES.contains(&prop_name.as_ref()) {
+            self.context.add_diagnostic_with_hint(
+              call_expr.span,
+              "no-prototype-builtins",
+              &format!(
+                "Access to Object.prototype.{} is not allowed from target object",
+                prop_name
+              ),
+              &format!(
+                "Use `Object.prototype.{}.call(obj, ...)` instead",
+                prop_name
+              ),
+            );
           }
-          member_expr
         }
-        _ => return,
-      },
-      ExprOrSuper::Super(_) => return,
-    };
-
-    if let Expr::Ident(ident) = &*member_expr.prop {
-      let prop_name = ident.sym.as_ref();
-      if BANNED_PROPERTIES.contains(&prop_name) {
-        self.context.add_diagnostic(
-          call_expr.span,
-          "no-prototype-builtins",
-          &format!(
-            "Access to Object.prototype.{} is not allowed from target object",
-            prop_name
-          ),
-        );
       }
     }
   }
@@ -87,19 +93,22 @@ mod tests {
       r#"
   Object.prototype.hasOwnProperty.call(foo, "bar");
   Object.prototype.isPrototypeOf.call(foo, "bar");
-  Object.prototype.propertyIsEnumberable.call(foo, "bar");
+  Object.prototype.propertyIsEnumerable.call(foo, "bar");
   Object.prototype.hasOwnProperty.apply(foo, ["bar"]);
   Object.prototype.isPrototypeOf.apply(foo, ["bar"]);
-  Object.prototype.propertyIsEnumberable.apply(foo, ["bar"]);
+  Object.prototype.propertyIsEnumerable.apply(foo, ["bar"]);
   hasOwnProperty(foo, "bar");
   isPrototypeOf(foo, "bar");
-  propertyIsEnumberable(foo, "bar");
+  propertyIsEnumerable(foo, "bar");
   ({}.hasOwnProperty.call(foo, "bar"));
   ({}.isPrototypeOf.call(foo, "bar"));
-  ({}.propertyIsEnumberable.call(foo, "bar"));
+  ({}.propertyIsEnumerable.call(foo, "bar"));
   ({}.hasOwnProperty.apply(foo, ["bar"]));
   ({}.isPrototypeOf.apply(foo, ["bar"]));
-  ({}.propertyIsEnumberable.apply(foo, ["bar"]));
+  ({}.propertyIsEnumerable.apply(foo, ["bar"]));
+  foo[hasOwnProperty]("bar");
+  foo[`hasOwnProperty`]("bar");
+  foo.hasOwnPropertyOf("bar");
       "#,
     );
   }
@@ -109,12 +118,38 @@ mod tests {
     assert_lint_err::<NoPrototypeBuiltins>(r#"foo.hasOwnProperty("bar");"#, 0);
     assert_lint_err::<NoPrototypeBuiltins>(r#"foo.isPrototypeOf("bar");"#, 0);
     assert_lint_err::<NoPrototypeBuiltins>(
-      r#"foo.propertyIsEnumberable("bar");"#,
+      r#"foo.propertyIsEnumerable("bar");"#,
       0,
     );
     assert_lint_err::<NoPrototypeBuiltins>(
       r#"foo.bar.baz.hasOwnProperty("bar");"#,
       0,
     );
+    assert_lint_err::<NoPrototypeBuiltins>(
+      r#"foo["hasOwnProperty"]("bar");"#,
+      0,
+    );
+    assert_lint_err::<NoPrototypeBuiltins>(r#"foo['isPrototypeOf'](bar);"#, 0);
+    assert_lint_err!(
+      NoPrototypeBuiltins,
+      r#"if (foo.hasOwnProperty(bar.hasOwnProperty("baz"))) {}"#,
+      [(1, 4), (1, 23)]
+    );
+    assert_lint_err!(
+      NoPrototypeBuiltins,
+      "function foo(obj) {\n  return obj.propertyIsEnumerable(key);\n}",
+      2,
+      9
+    );
+  }
+
+  #[test]
+  fn no_prototype_builtins_hint() {
+    assert_lint_err_with_hint::<NoPrototypeBuiltins>(
+      r#"foo.hasOwnProperty("bar");"#,
+      1,
+      0,
+      "Use `Object.prototype.hasOwnProperty.call(obj, ...)` instead",
+    );
   }
 }
@@ -1,10 +1,9 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
-use swc_ecmascript::visit::noop_visit_type;
+use crate::handler::{walk, Handler, Traversal};
 use swc_ecmascript::{
-  ast::*, utils::find_ids, utils::ident::IdentLike, utils::Id, visit::Node,
-  visit::Visit, visit::VisitWith,
+  ast::*, utils::find_ids, utils::ident::IdentLike, utils::Id,
 };
 
 use std::collections::HashSet;
@@ -21,12 +20,22 @@ impl LintRule for NoRedeclare {
     "no-redeclare"
   }
 
-  fn lint_module(&self, context: Arc<Context>, module: &Module) {
-    let mut visitor = NoRedeclareVisitor {
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows redeclaring variables in the same scope."
+  }
+
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoRedeclareVisitor {
       context,
       bindings: Default::default(),
-    };
-    module.visit_with(module, &mut visitor);
+    }))
   }
 }
 
@@ -50,20 +59,22 @@ impl NoRedeclareVisitor {
   }
 }
 
-impl Visit for NoRedeclareVisitor {
-  noop_visit_type!();
-
-  fn visit_fn_decl(&mut self, f: &FnDecl, _: &dyn Node) {
+impl Handler for NoRedeclareVisitor {
+  fn visit_fn_decl(&mut self, f: &FnDecl, traversal: &mut Traversal) {
     if f.function.body.is_none() {
+      traversal.skip_children();
       return;
     }
 
     self.declare(&f.ident);
-
-    f.visit_children_with(self);
   }
 
-  fn visit_var_declarator(&mut self, v: &VarDeclarator, _: &dyn Node) {
+  fn visit_var_declarator(
+    &mut self,
+    v: &VarDeclarator,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
     let ids: Vec<Ident> = find_ids(&v.name);
 
     for id in ids {
@@ -71,7 +82,8 @@ impl Visit for NoRedeclareVisitor {
     }
   }
 
-  fn visit_param(&mut self, p: &Param, _: &dyn Node) {
+  fn visit_param(&mut self, p: &Param, traversal: &mut Traversal) {
+    traversal.skip_children();
     let ids: Vec<Ident> = find_ids(&p.pat);
 
     for id in ids {
@@ -79,12 +91,13 @@ impl Visit for NoRedeclareVisitor {
     }
   }
 
-  fn visit_class_prop(&mut self, p: &ClassProp, _: &dyn Node) {
+  fn visit_class_prop(&mut self, p: &ClassProp, traversal: &mut Traversal) {
+    traversal.skip_children();
     if p.computed {
-      p.key.visit_with(p, self);
+      walk(self, &*p.key);
     }
 
-    p.value.visit_with(p, self);
+    walk(self, &p.value);
   }
 }
 
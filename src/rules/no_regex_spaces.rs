@@ -1,12 +1,10 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use crate::swc_util::extract_regex;
 use swc_common::Span;
 use swc_ecmascript::ast::{CallExpr, Expr, ExprOrSuper, NewExpr, Regex};
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -21,13 +19,22 @@ impl LintRule for NoRegexSpaces {
     "no-regex-spaces"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows multiple consecutive spaces in regular expressions.
+
+Runs of spaces are hard to count. Use a quantifier like `/foo {3}bar/`
+instead. Spaces in character classes like `[  ]` are allowed."#
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoRegexSpacesVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoRegexSpacesVisitor::new(context)))
   }
 }
 
@@ -40,70 +47,114 @@ impl NoRegexSpacesVisitor {
     Self { context }
   }
 
-  fn check_regex(&self, regex: &str, span: Span) {
-    lazy_static! {
-      static ref DOUBLE_SPACE: regex::Regex =
-        regex::Regex::new(r"(?u) {2}").unwrap();
-      static ref BRACKETS: regex::Regex =
-        regex::Regex::new(r"\[.*?[^\\]\]").unwrap();
-      static ref SPACES: regex::Regex =
-        regex::Regex::new(r#"(?u)( {2,})(?: [+*{?]|[^+*{?]|$)"#).unwrap();
-    }
-    if !DOUBLE_SPACE.is_match(regex) {
-      return;
-    }
+  /// Reports the pattern if it has a run of spaces. `flags` is only given
+  /// for regex literals, which get a suggestion replacing the runs with
+  /// quantifiers.
+  fn check_regex(&self, pattern: &str, span: Span, flags: Option<&str>) {
+    let runs = find_space_runs(pattern);
+    let first_len = match runs.first() {
+      Some((_, len)) => *len,
+      None => return,
+    };
+    let mut diagnostic = self.context.create_default_diagnostic(
+      span,
+      "no-regex-spaces",
+      "more than one consecutive spaces in RegExp is not allowed",
+    );
+    diagnostic.hint = Some(format!(
+      "Use ` {{{}}}` instead of {} consecutive spaces",
+      first_len, first_len
+    ));
 
-    let mut character_classes = vec![];
-    for mtch in BRACKETS.find_iter(regex) {
-      character_classes.push((mtch.start(), mtch.end()));
+    if let Some(flags) = flags {
+      let mut fixed = String::with_capacity(pattern.len());
+      let mut last = 0;
+      for (start, len) in runs {
+        fixed.push_str(&pattern[last..start]);
+        fixed.push_str(&format!(" {{{}}}", len));
+        last = start + len;
+      }
+      fixed.push_str(&pattern[last..]);
+      diagnostic.suggestions = vec![self.context.create_suggestion(
+        "Replace the spaces with a quantifier",
+        span,
+        &format!("/{}/{}", fixed, flags),
+      )];
     }
+    self.context.push_diagnostic(diagnostic);
+  }
+}
 
-    for mtch in SPACES.find_iter(regex) {
-      let not_in_classes = &character_classes
-        .iter()
-        .all(|ref v| mtch.start() < v.0 || v.1 <= mtch.start());
-      if *not_in_classes {
-        self.context.add_diagnostic(
-          span,
-          "no-regex-spaces",
-          "more than one consecutive spaces in RegExp is not allowed",
-        );
-        return;
+/// Finds runs of two or more spaces outside of character classes, as byte
+/// offset and length. A space followed by a quantifier isn't part of a run,
+/// as the quantifier only applies to it.
+fn find_space_runs(pattern: &str) -> Vec<(usize, usize)> {
+  let bytes = pattern.as_bytes();
+  let mut runs = vec![];
+  let mut in_class = false;
+  let mut i = 0;
+  while i < bytes.len() {
+    match bytes[i] {
+      // Escaped spaces still count, as they appear consecutive in source.
+      b'\\' if bytes.get(i + 1) != Some(&b' ') => {
+        i += 2;
+        continue;
       }
+      b'[' if !in_class => in_class = true,
+      b']' if in_class => in_class = false,
+      b' ' if !in_class => {
+        let start = i;
+        while bytes.get(i) == Some(&b' ') {
+          i += 1;
+        }
+        let mut len = i - start;
+        if matches!(
+          bytes.get(i),
+          Some(b'+') | Some(b'*') | Some(b'?') | Some(b'{')
+        ) {
+          len -= 1;
+        }
+        if len >= 2 {
+          runs.push((start, len));
+        }
+        continue;
+      }
+      _ => {}
     }
+    i += 1;
   }
+  runs
 }
 
-impl Visit for NoRegexSpacesVisitor {
-  noop_visit_type!();
-
-  fn visit_regex(&mut self, regex: &Regex, parent: &dyn Node) {
-    self.check_regex(regex.exp.to_string().as_str(), regex.span);
-    swc_ecmascript::visit::visit_regex(self, regex, parent);
+impl Handler for NoRegexSpacesVisitor {
+  fn visit_regex(&mut self, regex: &Regex, _traversal: &mut Traversal) {
+    self.check_regex(&regex.exp, regex.span, Some(&regex.flags));
   }
 
-  fn visit_new_expr(&mut self, new_expr: &NewExpr, parent: &dyn Node) {
+  fn visit_new_expr(&mut self, new_expr: &NewExpr, _traversal: &mut Traversal) {
     if let Expr::Ident(ident) = &*new_expr.callee {
       if let Some(args) = &new_expr.args {
         if let Some(regex) = extract_regex(&self.context.scope, ident, args) {
-          self.check_regex(regex.as_str(), new_expr.span);
+          self.check_regex(regex.as_str(), new_expr.span, None);
         }
       }
     }
-    swc_ecmascript::visit::visit_new_expr(self, new_expr, parent);
   }
 
-  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+  fn visit_call_expr(
+    &mut self,
+    call_expr: &CallExpr,
+    _traversal: &mut Traversal,
+  ) {
     if let ExprOrSuper::Expr(expr) = &call_expr.callee {
       if let Expr::Ident(ident) = expr.as_ref() {
         if let Some(regex) =
           extract_regex(&self.context.scope, ident, &call_expr.args)
         {
-          self.check_regex(regex.as_str(), call_expr.span);
+          self.check_regex(regex.as_str(), call_expr.span, None);
         }
       }
     }
-    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
   }
 }
 
@@ -154,6 +205,9 @@ mod tests {
       "var foo = new RegExp(' [  ] ');",
       "var foo = RegExp(' [  ] [  ] ');",
       "var foo = new RegExp(' \\[   \\] ');",
+      r"var foo = /[\]  ]/;",
+      "var foo = /[ ]{2}/;",
+      "var foo = new RegExp('[ ]{2}');",
       // TODO(@disizali) invalid regexes must handled on separated rule called `no-invalid-regexp`.
       // "var foo = new RegExp('[  ');",
       // "var foo = new RegExp('{  ', 'u');",
@@ -196,4 +250,32 @@ mod tests {
       10,
     );
   }
+
+  #[test]
+  fn no_regex_spaces_suggestion() {
+    let cases = [
+      ("let foo = /foo   bar/;", "let foo = /foo {3}bar/;"),
+      ("let foo = /a  b   c/gu;", "let foo = /a {2}b {3}c/gu;"),
+      ("let foo = /bar    ?baz/;", "let foo = /bar {3} ?baz/;"),
+      ("let foo = /[  ]  x/;", "let foo = /[  ] {2}x/;"),
+    ];
+    for (source, expected) in cases.iter() {
+      let diagnostics = lint_with_rule(NoRegexSpaces::new(), source);
+      assert_eq!(diagnostics.len(), 1, "{}", source);
+      assert_eq!(
+        crate::diagnostic::apply_suggestion(source, &diagnostics[0], 0)
+          .as_deref(),
+        Some(*expected)
+      );
+    }
+
+    let diagnostics =
+      lint_with_rule(NoRegexSpaces::new(), "new RegExp('foo   bar');");
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].suggestions.is_empty());
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Use ` {3}` instead of 3 consecutive spaces")
+    );
+  }
 }
@@ -1,6 +1,7 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
-use super::LintRule;
+use super::{LintRule, RuleConfigError};
+use crate::handler::{Handler, Traversal};
 use crate::swc_util::Key;
 use std::sync::Arc;
 use swc_common::Span;
@@ -16,38 +17,75 @@ use swc_ecmascript::ast::Pat;
 use swc_ecmascript::ast::PatOrExpr;
 use swc_ecmascript::ast::Prop;
 use swc_ecmascript::ast::PropOrSpread;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
-pub struct NoSelfAssign;
+pub struct NoSelfAssign {
+  /// Whether to check member expressions like `a.b = a.b`.
+  props: bool,
+}
 
 impl LintRule for NoSelfAssign {
   fn new() -> Box<Self> {
-    Box::new(NoSelfAssign)
+    Box::new(NoSelfAssign { props: true })
+  }
+
+  fn new_with_config(
+    &self,
+    config: serde_json::Value,
+  ) -> Result<Box<dyn LintRule>, RuleConfigError> {
+    let props = match config.get("props") {
+      None => true,
+      Some(serde_json::Value::Bool(props)) => *props,
+      Some(value) => {
+        return Err(RuleConfigError {
+          code: "no-self-assign".to_string(),
+          message: format!("expected props to be a boolean, got {}", value),
+        })
+      }
+    };
+    Ok(Box::new(NoSelfAssign { props }))
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(serde_json::json!({
+      "type": "object",
+      "properties": {
+        "props": { "type": "boolean" }
+      },
+      "additionalProperties": false
+    }))
   }
 
   fn code(&self) -> &'static str {
     "no-self-assign"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows assignments where both sides are exactly the same.
+
+Member expressions like `a.b = a.b` are checked too, unless the `props`
+option is set to `false`."#
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoSelfAssignVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoSelfAssignVisitor::new(context, self.props)))
   }
 }
 
 struct NoSelfAssignVisitor {
   context: Arc<Context>,
+  props: bool,
 }
 
 impl NoSelfAssignVisitor {
-  fn new(context: Arc<Context>) -> Self {
-    Self { context }
+  fn new(context: Arc<Context>, props: bool) -> Self {
+    Self { context, props }
   }
 
   fn add_diagnostic(&mut self, span: Span, name: &str) {
@@ -144,7 +182,7 @@ impl NoSelfAssignVisitor {
       (Expr::Ident(l_ident), Expr::Ident(r_ident)) => {
         self.check_same_ident(l_ident, r_ident);
       }
-      (Expr::Member(l_member), Expr::Member(r_member)) => {
+      (Expr::Member(l_member), Expr::Member(r_member)) if self.props => {
         self.check_same_member(l_member, r_member);
       }
       _ => {}
@@ -264,13 +302,11 @@ impl NoSelfAssignVisitor {
   }
 }
 
-impl Visit for NoSelfAssignVisitor {
-  noop_visit_type!();
-
+impl Handler for NoSelfAssignVisitor {
   fn visit_assign_expr(
     &mut self,
     assign_expr: &AssignExpr,
-    _parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     if assign_expr.op == AssignOp::Assign {
       match &assign_expr.left {
@@ -327,6 +363,9 @@ mod tests {
       "this.x = this.y",
       "this.x = options.x",
       "this.name = this.constructor.name",
+      "a[b()] = a[b()]",
+      "a[i++] = a[i++]",
+      "a.b = a?.b",
     ]);
   }
 
@@ -351,7 +390,7 @@ mod tests {
     assert_lint_err::<NoSelfAssign>("({'a': b} = {[`a`]: b})", 20);
     assert_lint_err::<NoSelfAssign>("({1: b} = {[1]: b})", 16);
     assert_lint_err_n::<NoSelfAssign>("({a, b} = {a, b})", vec![11, 14]);
-    assert_lint_err_n::<NoSelfAssign>("({a, b} = {b, a})", vec![14, 11]);
+    assert_lint_err_n::<NoSelfAssign>("({a, b} = {b, a})", vec![11, 14]);
     assert_lint_err::<NoSelfAssign>("({a, b} = {c, a})", 14);
     assert_lint_err_n::<NoSelfAssign>(
       "({a: {b}, c: [d]} = {a: {b}, c: [d]})",
@@ -369,5 +408,26 @@ mod tests {
     );
     assert_lint_err::<NoSelfAssign>("this.x = this.x", 9);
     assert_lint_err::<NoSelfAssign>("a['/(?<zero>0)/'] = a[/(?<zero>0)/]", 20);
+    assert_lint_err::<NoSelfAssign>("a = b = b", 8);
+    assert_lint_err!(
+      NoSelfAssign,
+      "function foo(a) {\n  if (a) {\n    a.b.c = a.b.c;\n  }\n}",
+      3,
+      12
+    );
+  }
+
+  #[test]
+  fn no_self_assign_props() {
+    let config = || serde_json::json!({ "props": false });
+    assert_lint_ok_with_config::<NoSelfAssign>(
+      "a.b = a.b;\na[b] = a[b];\n[a.b] = [a.b];\nthis.x = this.x;",
+      config(),
+    );
+    assert_lint_err_with_config::<NoSelfAssign>("a = a", config(), 4);
+    assert_lint_err_with_config::<NoSelfAssign>("[a] = [a]", config(), 7);
+    assert!(NoSelfAssign::new()
+      .new_with_config(serde_json::json!({ "props": 1 }))
+      .is_err());
   }
 }
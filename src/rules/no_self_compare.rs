@@ -0,0 +1,164 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, RuleExamples};
+use crate::handler::{Handler, Traversal};
+use crate::swc_util::DropSpan;
+use swc_ecmascript::ast::{BinExpr, BinaryOp, Expr, ExprOrSuper};
+
+use std::sync::Arc;
+
+pub struct NoSelfCompare;
+
+impl LintRule for NoSelfCompare {
+  fn new() -> Box<Self> {
+    Box::new(NoSelfCompare)
+  }
+
+  fn code(&self) -> &'static str {
+    "no-self-compare"
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows comparisons where both sides are exactly the same.
+
+Comparing a value to itself is only useful to check whether it's `NaN`,
+which `Number.isNaN` does more clearly. Only identifiers, literals, `this`
+and member expressions on them are compared, as other expressions like
+calls may evaluate to different values."#
+  }
+
+  fn examples(&self) -> RuleExamples {
+    RuleExamples {
+      valid: &["if (Number.isNaN(x)) {}", "if (a.b === a.c) {}"],
+      invalid: &["if (x !== x) {}", "if (a.b.c > a.b.c) {}"],
+    }
+  }
+
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoSelfCompareVisitor { context }))
+  }
+}
+
+struct NoSelfCompareVisitor {
+  context: Arc<Context>,
+}
+
+fn unparen(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(paren) => unparen(&paren.expr),
+    _ => expr,
+  }
+}
+
+/// Whether evaluating `expr` twice always gives the same value, ignoring
+/// getters.
+fn is_pure(expr: &Expr) -> bool {
+  match unparen(expr) {
+    Expr::Ident(_) | Expr::Lit(_) | Expr::This(_) => true,
+    Expr::Member(member) => {
+      let is_pure_obj = match &member.obj {
+        ExprOrSuper::Expr(obj) => is_pure(obj),
+        ExprOrSuper::Super(_) => true,
+      };
+      is_pure_obj && (!member.computed || is_pure(&member.prop))
+    }
+    _ => false,
+  }
+}
+
+fn strip_parens(expr: &Expr) -> Expr {
+  let mut expr = unparen(expr).clone();
+  if let Expr::Member(member) = &mut expr {
+    if let ExprOrSuper::Expr(obj) = &mut member.obj {
+      **obj = strip_parens(obj);
+    }
+    if member.computed {
+      *member.prop = strip_parens(&member.prop);
+    }
+  }
+  expr
+}
+
+impl Handler for NoSelfCompareVisitor {
+  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, _traversal: &mut Traversal) {
+    let is_comparison = matches!(
+      bin_expr.op,
+      BinaryOp::EqEq
+        | BinaryOp::NotEq
+        | BinaryOp::EqEqEq
+        | BinaryOp::NotEqEq
+        | BinaryOp::Lt
+        | BinaryOp::LtEq
+        | BinaryOp::Gt
+        | BinaryOp::GtEq
+    );
+    if is_comparison
+      && is_pure(&bin_expr.left)
+      && is_pure(&bin_expr.right)
+      && strip_parens(&bin_expr.left).drop_span()
+        == strip_parens(&bin_expr.right).drop_span()
+    {
+      self.context.add_diagnostic_with_hint(
+        bin_expr.span,
+        "no-self-compare",
+        "Comparing to itself is potentially pointless",
+        "Use `Number.isNaN(value)` to check whether a value is `NaN`",
+      );
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_self_compare_valid() {
+    assert_lint_ok! {
+      NoSelfCompare,
+      "if (x === y) {}",
+      "if (a.b === a.c) {}",
+      "if (a.b.c > a.b.d) {}",
+      "if (a[0] === a[1]) {}",
+      "if (a['b'] === a.b) {}",
+      "if (foo() === foo()) {}",
+      "if (a[i++] === a[i++]) {}",
+      "x = x + x;",
+      "x += x;",
+    };
+  }
+
+  #[test]
+  fn no_self_compare_invalid() {
+    assert_lint_err::<NoSelfCompare>("if (x === x) {}", 4);
+    assert_lint_err::<NoSelfCompare>("if (x !== x) {}", 4);
+    assert_lint_err::<NoSelfCompare>("if (x == x) {}", 4);
+    assert_lint_err::<NoSelfCompare>("if (x < x) {}", 4);
+    assert_lint_err::<NoSelfCompare>("if (x >= x) {}", 4);
+    assert_lint_err::<NoSelfCompare>("if (a.b.c > a.b.c) {}", 4);
+    assert_lint_err::<NoSelfCompare>("if (a[b].c === a[b].c) {}", 4);
+    assert_lint_err::<NoSelfCompare>("if (this.x === this . x) {}", 4);
+    assert_lint_err::<NoSelfCompare>("if ((x) === x) {}", 4);
+    assert_lint_err::<NoSelfCompare>("if ((a).b === a.b) {}", 4);
+    assert_lint_err::<NoSelfCompare>("if ('a' === 'a') {}", 4);
+    assert_lint_err!(
+      NoSelfCompare,
+      "function isNaN(x) {\n  return x !== x;\n}",
+      2,
+      9
+    );
+  }
+
+  #[test]
+  fn no_self_compare_hint() {
+    assert_lint_err_with_hint::<NoSelfCompare>(
+      "x === x",
+      1,
+      0,
+      "Use `Number.isNaN(value)` to check whether a value is `NaN`",
+    );
+  }
+}
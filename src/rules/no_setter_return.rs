@@ -1,16 +1,15 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
-use swc_ecmascript::ast::BlockStmt;
-use swc_ecmascript::ast::Class;
-use swc_ecmascript::ast::ClassMember;
-use swc_ecmascript::ast::MethodKind;
-use swc_ecmascript::ast::SetterProp;
-use swc_ecmascript::ast::Stmt;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
+use crate::handler::{Handler, Traversal};
+use swc_common::{Span, Spanned};
+use swc_ecmascript::ast::{
+  ArrowExpr, BlockStmtOrExpr, CallExpr, ClassMethod, Constructor, Expr,
+  ExprOrSuper, Function, GetterProp, MethodKind, ObjectLit, PrivateMethod,
+  Prop, PropName, PropOrSpread, ReturnStmt, SetterProp,
+};
 
+use std::collections::HashSet;
 use std::sync::Arc;
 
 pub struct NoSetterReturn;
@@ -24,72 +23,221 @@ impl LintRule for NoSetterReturn {
     "no-setter-return"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows returning a value from setters.
+
+The value returned from a setter is ignored. This applies to setters in
+classes and object literals, and to `set` functions in descriptors passed
+to `Object.defineProperty` and `Reflect.defineProperty`. A bare `return;`
+is allowed to exit early."#
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoSetterReturnVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoSetterReturnVisitor::new(context)))
   }
 }
 
 struct NoSetterReturnVisitor {
   context: Arc<Context>,
+  /// Spans of the functions that are known to be setters from their
+  /// parent, collected before the functions are visited.
+  setters: HashSet<Span>,
+  /// Whether each function being visited is a setter, innermost last.
+  functions: Vec<bool>,
 }
 
 impl NoSetterReturnVisitor {
   fn new(context: Arc<Context>) -> Self {
-    Self { context }
-  }
-
-  fn check_block_stmt(&self, block_stmt: &BlockStmt) {
-    for stmt in &block_stmt.stmts {
-      if let Stmt::Return(return_stmt) = stmt {
-        if return_stmt.arg.is_some() {
-          self.context.add_diagnostic(
-            return_stmt.span,
-            "no-setter-return",
-            "Setter cannot return a value",
-          );
-        }
-      }
+    Self {
+      context,
+      setters: HashSet::new(),
+      functions: vec![],
     }
   }
-}
 
-impl Visit for NoSetterReturnVisitor {
-  noop_visit_type!();
+  fn in_setter(&self) -> bool {
+    self.functions.last() == Some(&true)
+  }
+
+  fn report(&self, span: Span) {
+    self.context.add_diagnostic(
+      span,
+      "no-setter-return",
+      "Setter cannot return a value",
+    );
+  }
 
-  fn visit_class(&mut self, class: &Class, _parent: &dyn Node) {
-    for member in &class.body {
-      match member {
-        ClassMember::Method(class_method) => {
-          if class_method.kind == MethodKind::Setter {
-            if let Some(block_stmt) = &class_method.function.body {
-              self.check_block_stmt(block_stmt);
+  /// Marks the `set` function of a property descriptor as a setter.
+  fn add_descriptor(&mut self, obj_lit: &ObjectLit) {
+    for prop in &obj_lit.props {
+      if let PropOrSpread::Prop(prop) = prop {
+        match &**prop {
+          Prop::KeyValue(kv_prop) if is_set_key(&kv_prop.key) => {
+            match &*kv_prop.value {
+              Expr::Fn(fn_expr) => {
+                self.setters.insert(fn_expr.function.span);
+              }
+              Expr::Arrow(arrow_expr) => {
+                self.setters.insert(arrow_expr.span);
+              }
+              _ => {}
             }
           }
-        }
-        ClassMember::PrivateMethod(private_method) => {
-          if private_method.kind == MethodKind::Setter {
-            if let Some(block_stmt) = &private_method.function.body {
-              self.check_block_stmt(block_stmt);
-            }
+          Prop::Method(method_prop) if is_set_key(&method_prop.key) => {
+            self.setters.insert(method_prop.function.span);
           }
+          _ => {}
         }
-        _ => {}
       }
     }
   }
+}
+
+fn is_set_key(key: &PropName) -> bool {
+  match key {
+    PropName::Ident(ident) => ident.sym == *"set",
+    PropName::Str(str_) => str_.value == *"set",
+    _ => false,
+  }
+}
+
+/// Whether the call is `Object.defineProperty` or `Reflect.defineProperty`.
+fn is_define_property(call_expr: &CallExpr) -> bool {
+  let callee = match &call_expr.callee {
+    ExprOrSuper::Expr(callee) => callee,
+    ExprOrSuper::Super(_) => return false,
+  };
+  let member = match &**callee {
+    Expr::Member(member) if !member.computed => member,
+    _ => return false,
+  };
+  let is_receiver = match &member.obj {
+    ExprOrSuper::Expr(obj) => match &**obj {
+      Expr::Ident(ident) => ident.sym == *"Object" || ident.sym == *"Reflect",
+      _ => false,
+    },
+    ExprOrSuper::Super(_) => false,
+  };
+  is_receiver
+    && matches!(&*member.prop, Expr::Ident(ident) if ident.sym == *"defineProperty")
+}
+
+impl Handler for NoSetterReturnVisitor {
+  fn visit_function(
+    &mut self,
+    function: &Function,
+    _traversal: &mut Traversal,
+  ) {
+    let is_setter = self.setters.contains(&function.span);
+    self.functions.push(is_setter);
+  }
+
+  fn exit_function(&mut self, _function: &Function) {
+    self.functions.pop();
+  }
+
+  fn visit_arrow_expr(
+    &mut self,
+    arrow_expr: &ArrowExpr,
+    _traversal: &mut Traversal,
+  ) {
+    let is_setter = self.setters.contains(&arrow_expr.span);
+    if is_setter {
+      if let BlockStmtOrExpr::Expr(expr) = &arrow_expr.body {
+        self.report(expr.span());
+      }
+    }
+    self.functions.push(is_setter);
+  }
+
+  fn exit_arrow_expr(&mut self, _arrow_expr: &ArrowExpr) {
+    self.functions.pop();
+  }
+
+  fn visit_class_method(
+    &mut self,
+    class_method: &ClassMethod,
+    _traversal: &mut Traversal,
+  ) {
+    if class_method.kind == MethodKind::Setter {
+      self.setters.insert(class_method.function.span);
+    }
+  }
+
+  fn visit_private_method(
+    &mut self,
+    private_method: &PrivateMethod,
+    _traversal: &mut Traversal,
+  ) {
+    if private_method.kind == MethodKind::Setter {
+      self.setters.insert(private_method.function.span);
+    }
+  }
 
   fn visit_setter_prop(
     &mut self,
-    setter_prop: &SetterProp,
-    _parent: &dyn Node,
+    _setter_prop: &SetterProp,
+    _traversal: &mut Traversal,
+  ) {
+    self.functions.push(true);
+  }
+
+  fn exit_setter_prop(&mut self, _setter_prop: &SetterProp) {
+    self.functions.pop();
+  }
+
+  fn visit_getter_prop(
+    &mut self,
+    _getter_prop: &GetterProp,
+    _traversal: &mut Traversal,
+  ) {
+    self.functions.push(false);
+  }
+
+  fn exit_getter_prop(&mut self, _getter_prop: &GetterProp) {
+    self.functions.pop();
+  }
+
+  fn visit_constructor(
+    &mut self,
+    _constructor: &Constructor,
+    _traversal: &mut Traversal,
+  ) {
+    self.functions.push(false);
+  }
+
+  fn exit_constructor(&mut self, _constructor: &Constructor) {
+    self.functions.pop();
+  }
+
+  fn visit_call_expr(
+    &mut self,
+    call_expr: &CallExpr,
+    _traversal: &mut Traversal,
+  ) {
+    if is_define_property(call_expr) {
+      if let Some(arg) = call_expr.args.get(2) {
+        if let Expr::Object(obj_lit) = &*arg.expr {
+          self.add_descriptor(obj_lit);
+        }
+      }
+    }
+  }
+
+  fn visit_return_stmt(
+    &mut self,
+    return_stmt: &ReturnStmt,
+    _traversal: &mut Traversal,
   ) {
-    if let Some(block_stmt) = &setter_prop.body {
-      self.check_block_stmt(block_stmt);
+    if self.in_setter() && return_stmt.arg.is_some() {
+      self.report(return_stmt.span);
     }
   }
 }
@@ -119,4 +267,68 @@ class b {
       vec![(4, 4), (7, 4)],
     );
   }
+
+  #[test]
+  fn setter_return_valid() {
+    assert_lint_ok! {
+      NoSetterReturn,
+      "const a = { set foo(v) { this.v = v; } };",
+      "const a = { set foo(v) { if (!v) { return; } this.v = v; } };",
+      "class A { set foo(v) { return; } }",
+      "class A { get foo() { return 1; } }",
+      "class A { foo() { return 1; } }",
+      "const a = { set foo(v) { function f() { return 1; } } };",
+      "const a = { set foo(v) { [1].map((x) => { return x; }); } };",
+      "const a = { set foo(v) { [1].map((x) => x); } };",
+      "class A { set foo(v) { const o = { bar() { return 1; } }; } }",
+      "class A { set a(v) { const o = { get b() { return 1; } }; } }",
+      "class A { set a(v) { class C { constructor() { return {}; } } } }",
+      "Object.defineProperty(o, 'foo', { set(v) { return; } });",
+      "Object.defineProperty(o, 'foo', { get() { return 1; } });",
+      "Object.defineProperty(o, 'foo', { value: function() { return 1; } });",
+      "foo.defineProperty(o, 'foo', { set(v) { return 1; } });",
+      "Object.keys(o, 'foo', { set(v) { return 1; } });",
+      "function set(v) { return 1; }",
+    };
+  }
+
+  #[test]
+  fn setter_return_invalid() {
+    assert_lint_err::<NoSetterReturn>(
+      "const a = { set foo(v) { if (v) { return 1; } } };",
+      34,
+    );
+    assert_lint_err::<NoSetterReturn>(
+      "class A { set foo(v) { return 1; } }",
+      23,
+    );
+    assert_lint_err::<NoSetterReturn>(
+      "class A { static set foo(v) { return 1; } }",
+      30,
+    );
+    assert_lint_err::<NoSetterReturn>(
+      "Object.defineProperty(o, 'foo', { set(v) { return 1; } });",
+      43,
+    );
+    assert_lint_err::<NoSetterReturn>(
+      "Object.defineProperty(o, 'foo', { set: function(v) { return 1; } });",
+      53,
+    );
+    assert_lint_err::<NoSetterReturn>(
+      "Reflect.defineProperty(o, 'foo', { set: (v) => { return 1; } });",
+      49,
+    );
+    assert_lint_err::<NoSetterReturn>(
+      "Object.defineProperty(o, 'foo', { set: (v) => v });",
+      46,
+    );
+    assert_lint_err::<NoSetterReturn>(
+      "const a = { set foo(v) { function f() {} return f; } };",
+      41,
+    );
+    assert_lint_err::<NoSetterReturn>(
+      "function f() { return { set foo(v) { return 1; } }; }",
+      37,
+    );
+  }
 }
@@ -1,14 +1,12 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_ecmascript::ast::{
-  ArrowExpr, AssignExpr, CatchClause, Expr, FnDecl, FnExpr, Ident, Module,
+  ArrowExpr, AssignExpr, CatchClause, Expr, FnDecl, FnExpr, Ident,
   ObjectPatProp, Pat, PatOrExpr, VarDecl,
 };
-use swc_ecmascript::{
-  utils::ident::IdentLike,
-  visit::{noop_visit_type, Node, Visit},
-};
+use swc_ecmascript::utils::ident::IdentLike;
 
 use std::sync::Arc;
 
@@ -19,14 +17,24 @@ impl LintRule for NoShadowRestrictedNames {
     Box::new(NoShadowRestrictedNames)
   }
 
-  fn lint_module(&self, context: Arc<Context>, module: &Module) {
-    let mut visitor = NoShadowRestrictedNamesVisitor::new(context);
-    visitor.visit_module(module, module);
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoShadowRestrictedNamesVisitor::new(context)))
   }
 
   fn code(&self) -> &'static str {
     "no-shadow-restricted-names"
   }
+
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows shadowing of restricted names such as `undefined` or `NaN`."
+  }
 }
 
 struct NoShadowRestrictedNamesVisitor {
@@ -106,10 +114,8 @@ impl NoShadowRestrictedNamesVisitor {
   }
 }
 
-impl Visit for NoShadowRestrictedNamesVisitor {
-  noop_visit_type!();
-
-  fn visit_var_decl(&mut self, node: &VarDecl, parent: &dyn Node) {
+impl Handler for NoShadowRestrictedNamesVisitor {
+  fn visit_var_decl(&mut self, node: &VarDecl, _traversal: &mut Traversal) {
     for decl in &node.decls {
       if let Pat::Ident(ident) = &decl.name {
         // `undefined` variable declaration without init is have same meaning
@@ -120,21 +126,17 @@ impl Visit for NoShadowRestrictedNamesVisitor {
 
       self.check_pat(&decl.name, false);
     }
-
-    swc_ecmascript::visit::visit_var_decl(self, node, parent);
   }
 
-  fn visit_fn_decl(&mut self, node: &FnDecl, parent: &dyn Node) {
+  fn visit_fn_decl(&mut self, node: &FnDecl, _traversal: &mut Traversal) {
     self.check_shadowing(&node.ident);
 
     for param in &node.function.params {
       self.check_pat(&param.pat, false);
     }
-
-    swc_ecmascript::visit::visit_fn_decl(self, node, parent);
   }
 
-  fn visit_fn_expr(&mut self, node: &FnExpr, parent: &dyn Node) {
+  fn visit_fn_expr(&mut self, node: &FnExpr, _traversal: &mut Traversal) {
     if node.ident.is_some() {
       self.check_shadowing(node.ident.as_ref().unwrap())
     }
@@ -142,27 +144,30 @@ impl Visit for NoShadowRestrictedNamesVisitor {
     for param in &node.function.params {
       self.check_pat(&param.pat, false);
     }
-
-    swc_ecmascript::visit::visit_fn_expr(self, node, parent);
   }
 
-  fn visit_arrow_expr(&mut self, node: &ArrowExpr, parent: &dyn Node) {
+  fn visit_arrow_expr(&mut self, node: &ArrowExpr, _traversal: &mut Traversal) {
     for param in &node.params {
       self.check_pat(&param, false);
     }
-
-    swc_ecmascript::visit::visit_arrow_expr(self, node, parent);
   }
 
-  fn visit_catch_clause(&mut self, node: &CatchClause, parent: &dyn Node) {
+  fn visit_catch_clause(
+    &mut self,
+    node: &CatchClause,
+    _traversal: &mut Traversal,
+  ) {
     if node.param.is_some() {
       self.check_pat(node.param.as_ref().unwrap(), false);
     }
-
-    swc_ecmascript::visit::visit_catch_clause(self, node, parent);
   }
 
-  fn visit_assign_expr(&mut self, node: &AssignExpr, _parent: &dyn Node) {
+  fn visit_assign_expr(
+    &mut self,
+    node: &AssignExpr,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
     if let PatOrExpr::Pat(pat) = &node.left {
       self.check_pat(pat, true);
     }
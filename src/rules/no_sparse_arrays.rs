@@ -1,9 +1,7 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
+use crate::handler::{Handler, Traversal};
 
 use std::sync::Arc;
 
@@ -18,13 +16,15 @@ impl LintRule for NoSparseArrays {
     "no-sparse-arrays"
   }
 
-  fn lint_module(
-    &self,
+  fn docs(&self) -> &'static str {
+    "Disallows sparse array literals."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoSparseArraysVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoSparseArraysVisitor::new(context)))
   }
 }
 
@@ -38,13 +38,11 @@ impl NoSparseArraysVisitor {
   }
 }
 
-impl Visit for NoSparseArraysVisitor {
-  noop_visit_type!();
-
+impl Handler for NoSparseArraysVisitor {
   fn visit_array_lit(
     &mut self,
     array_lit: &swc_ecmascript::ast::ArrayLit,
-    _parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     if array_lit.elems.iter().any(|e| e.is_none()) {
       self.context.add_diagnostic(
@@ -66,4 +64,24 @@ mod tests {
     assert_lint_ok::<NoSparseArrays>("const sparseArray1 = [1,null,3];");
     assert_lint_err::<NoSparseArrays>("const sparseArray = [1,,3];", 20);
   }
+
+  #[test]
+  fn no_sparse_arrays_valid() {
+    assert_lint_ok! {
+      NoSparseArrays,
+      "const a = [];",
+      "const a = [1, 2,];",
+      "const a = [[1], [2],];",
+      "const [, b] = foo;",
+    };
+  }
+
+  #[test]
+  fn no_sparse_arrays_invalid() {
+    assert_lint_err::<NoSparseArrays>("const a = [, 1];", 10);
+    assert_lint_err::<NoSparseArrays>("const a = [1, , , 2];", 10);
+    assert_lint_err::<NoSparseArrays>("const a = [1, 2,,];", 10);
+    assert_lint_err::<NoSparseArrays>("const a = [1, [2, , 3]];", 14);
+    assert_lint_err_n::<NoSparseArrays>("const a = [, [, 1]];", vec![10, 13]);
+  }
 }
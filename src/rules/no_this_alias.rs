@@ -1,10 +1,8 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{walk, Handler, Traversal};
 use swc_ecmascript::ast::{ArrowExpr, Expr, Function, Pat, VarDecl};
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -19,13 +17,19 @@ impl LintRule for NoThisAlias {
     "no-this-alias"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows aliasing `this` to a variable."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoThisAliasVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoThisAliasVisitor::new(context)))
   }
 }
 
@@ -39,14 +43,13 @@ impl NoThisAliasVisitor {
   }
 }
 
-impl Visit for NoThisAliasVisitor {
-  noop_visit_type!();
-
-  fn visit_var_decl(&mut self, var_decl: &VarDecl, _parent: &dyn Node) {
+impl Handler for NoThisAliasVisitor {
+  fn visit_var_decl(&mut self, var_decl: &VarDecl, traversal: &mut Traversal) {
+    traversal.skip_children();
     for decl in &var_decl.decls {
       if let Some(init) = &decl.init {
         if let Expr::Arrow(arrow) = &**init {
-          self.visit_arrow_expr(&arrow, _parent);
+          walk(self, arrow);
         } else if let Expr::This(_) = &**init {
           if let Pat::Ident(_ident) = &decl.name {
             self.context.add_diagnostic(
@@ -60,23 +63,30 @@ impl Visit for NoThisAliasVisitor {
     }
   }
 
-  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, _parent: &dyn Node) {
-    self.visit_block_stmt_or_expr(&arrow_expr.body, _parent);
+  fn visit_arrow_expr(
+    &mut self,
+    arrow_expr: &ArrowExpr,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
+    walk(self, &arrow_expr.body);
   }
 
   fn visit_expr_stmt(
     &mut self,
     expr: &swc_ecmascript::ast::ExprStmt,
-    _parent: &dyn Node,
+    traversal: &mut Traversal,
   ) {
+    traversal.skip_children();
     if let Expr::Arrow(arrow) = &*expr.expr {
-      self.visit_arrow_expr(arrow, _parent);
+      walk(self, arrow);
     }
   }
 
-  fn visit_function(&mut self, function: &Function, _parent: &dyn Node) {
+  fn visit_function(&mut self, function: &Function, traversal: &mut Traversal) {
+    traversal.skip_children();
     if let Some(stmt) = &function.body {
-      self.visit_block_stmt(stmt, _parent);
+      walk(self, stmt);
     }
   }
 }
@@ -1,8 +1,11 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_ecmascript::ast::{
-  CallExpr, Class, Constructor, ExprOrSuper, Super, ThisExpr,
+  ArrowExpr, BinExpr, BinaryOp, CallExpr, Class, CondExpr, Constructor,
+  DoWhileStmt, ExprOrSuper, ForInStmt, ForOfStmt, ForStmt, Function, IfStmt,
+  ReturnStmt, Super, SwitchStmt, ThisExpr, ThrowStmt, TryStmt, WhileStmt,
 };
 use swc_ecmascript::visit::{noop_visit_type, Node, Visit};
 
@@ -19,13 +22,23 @@ impl LintRule for NoThisBeforeSuper {
     "no-this-before-super"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows using `this` or `super` before calling `super()` in constructors.
+
+`super()` must be called on every path before them, e.g. calling it in
+only one branch of an `if` isn't enough. `this` in nested functions isn't
+checked, as they have their own `this`, but arrow functions are."#
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoThisBeforeSuperVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoThisBeforeSuperVisitor::new(context)))
   }
 }
 
@@ -39,13 +52,12 @@ impl NoThisBeforeSuperVisitor {
   }
 }
 
-impl Visit for NoThisBeforeSuperVisitor {
-  noop_visit_type!();
-
-  fn visit_class(&mut self, class: &Class, parent: &dyn Node) {
+impl Handler for NoThisBeforeSuperVisitor {
+  fn visit_class(&mut self, class: &Class, traversal: &mut Traversal) {
+    traversal.skip_children();
     let mut class_visitor =
       ClassVisitor::new(&self.context, class.super_class.is_some());
-    swc_ecmascript::visit::visit_class(&mut class_visitor, class, parent);
+    swc_ecmascript::visit::visit_class(&mut class_visitor, class, class);
   }
 }
 
@@ -82,8 +94,10 @@ impl<'a> Visit for ClassVisitor<'a> {
   }
 }
 
+/// Walks a derived class constructor in execution order.
 struct ConstructorVisitor<'a> {
   context: &'a Context,
+  /// Whether `super()` has been called on every path to the current node.
   super_called: bool,
 }
 
@@ -94,6 +108,42 @@ impl<'a> ConstructorVisitor<'a> {
       super_called: false,
     }
   }
+
+  /// Visits code that may not run, like a loop body, discarding the calls
+  /// to `super()` in it.
+  fn visit_maybe<F>(&mut self, f: F)
+  where
+    F: FnOnce(&mut Self),
+  {
+    let super_called = self.super_called;
+    f(self);
+    self.super_called = super_called;
+  }
+
+  /// Visits both branches from the current state, returning whether
+  /// `super()` is called in each.
+  fn visit_branches<F, G>(&mut self, cons: F, alt: G) -> (bool, bool)
+  where
+    F: FnOnce(&mut Self),
+    G: FnOnce(&mut Self),
+  {
+    let super_called = self.super_called;
+    cons(self);
+    let cons_called = self.super_called;
+    self.super_called = super_called;
+    alt(self);
+    (cons_called, self.super_called)
+  }
+
+  fn report(&self, span: swc_common::Span) {
+    if !self.super_called {
+      self.context.add_diagnostic(
+        span,
+        "no-this-before-super",
+        "'this' / 'super' are not allowed before 'super()'.",
+      );
+    }
+  }
 }
 
 impl<'a> Visit for ConstructorVisitor<'a> {
@@ -103,36 +153,172 @@ impl<'a> Visit for ConstructorVisitor<'a> {
     swc_ecmascript::visit::visit_class(&mut class_visitor, class, parent);
   }
 
+  fn visit_function(&mut self, function: &Function, parent: &dyn Node) {
+    // Functions have their own `this`, but may contain classes.
+    let mut class_visitor = ClassVisitor::new(self.context, false);
+    swc_ecmascript::visit::visit_function(&mut class_visitor, function, parent);
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, parent: &dyn Node) {
+    self.visit_maybe(|v| {
+      swc_ecmascript::visit::visit_arrow_expr(v, arrow_expr, parent)
+    });
+  }
+
   fn visit_call_expr(&mut self, call_expr: &CallExpr, _parent: &dyn Node) {
+    if let ExprOrSuper::Expr(expr) = &call_expr.callee {
+      self.visit_expr(expr, call_expr);
+    }
+
     for arg in &call_expr.args {
       self.visit_expr(&*arg.expr, call_expr);
     }
 
-    match call_expr.callee {
-      ExprOrSuper::Super(_) => self.super_called = true,
-      ExprOrSuper::Expr(ref expr) => self.visit_expr(&**expr, call_expr),
+    if let ExprOrSuper::Super(_) = call_expr.callee {
+      self.super_called = true;
     }
   }
 
   fn visit_this_expr(&mut self, this_expr: &ThisExpr, _parent: &dyn Node) {
-    if !self.super_called {
-      self.context.add_diagnostic(
-        this_expr.span,
-        "no-this-before-super",
-        "'this' / 'super' are not allowed before 'super()'.",
-      );
-    }
+    self.report(this_expr.span);
   }
 
   fn visit_super(&mut self, sup: &Super, _parent: &dyn Node) {
-    if !self.super_called {
-      self.context.add_diagnostic(
-        sup.span,
-        "no-this-before-super",
-        "'this' / 'super' are not allowed before 'super()'.",
-      );
+    self.report(sup.span);
+  }
+
+  fn visit_if_stmt(&mut self, if_stmt: &IfStmt, _parent: &dyn Node) {
+    self.visit_expr(&if_stmt.test, if_stmt);
+    let (cons_called, alt_called) = self.visit_branches(
+      |v| v.visit_stmt(&if_stmt.cons, if_stmt),
+      |v| {
+        if let Some(alt) = &if_stmt.alt {
+          v.visit_stmt(alt, if_stmt);
+        }
+      },
+    );
+    self.super_called = cons_called && alt_called;
+  }
+
+  fn visit_cond_expr(&mut self, cond_expr: &CondExpr, _parent: &dyn Node) {
+    self.visit_expr(&cond_expr.test, cond_expr);
+    let (cons_called, alt_called) = self.visit_branches(
+      |v| v.visit_expr(&cond_expr.cons, cond_expr),
+      |v| v.visit_expr(&cond_expr.alt, cond_expr),
+    );
+    self.super_called = cons_called && alt_called;
+  }
+
+  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, _parent: &dyn Node) {
+    self.visit_expr(&bin_expr.left, bin_expr);
+    match bin_expr.op {
+      BinaryOp::LogicalAnd
+      | BinaryOp::LogicalOr
+      | BinaryOp::NullishCoalescing => {
+        self.visit_maybe(|v| v.visit_expr(&bin_expr.right, bin_expr));
+      }
+      _ => self.visit_expr(&bin_expr.right, bin_expr),
     }
   }
+
+  fn visit_switch_stmt(
+    &mut self,
+    switch_stmt: &SwitchStmt,
+    _parent: &dyn Node,
+  ) {
+    self.visit_expr(&switch_stmt.discriminant, switch_stmt);
+    let super_called = self.super_called;
+    let mut all_called = true;
+    for case in &switch_stmt.cases {
+      self.super_called = super_called;
+      if let Some(test) = &case.test {
+        self.visit_expr(test, case);
+      }
+      for stmt in &case.cons {
+        self.visit_stmt(stmt, case);
+      }
+      // Empty cases fall through to the next one.
+      if !case.cons.is_empty() {
+        all_called &= self.super_called;
+      }
+    }
+    let has_default = switch_stmt.cases.iter().any(|case| case.test.is_none());
+    self.super_called = super_called || (has_default && all_called);
+  }
+
+  fn visit_try_stmt(&mut self, try_stmt: &TryStmt, _parent: &dyn Node) {
+    let super_called = self.super_called;
+    self.visit_block_stmt(&try_stmt.block, try_stmt);
+    let mut completed_called = self.super_called;
+    if let Some(handler) = &try_stmt.handler {
+      // The block may throw before calling `super()`.
+      self.super_called = super_called;
+      self.visit_catch_clause(handler, try_stmt);
+      completed_called &= self.super_called;
+    }
+    if let Some(finalizer) = &try_stmt.finalizer {
+      self.super_called = super_called;
+      self.visit_block_stmt(finalizer, try_stmt);
+      completed_called |= self.super_called;
+    }
+    self.super_called = completed_called;
+  }
+
+  fn visit_while_stmt(&mut self, while_stmt: &WhileStmt, _parent: &dyn Node) {
+    self.visit_expr(&while_stmt.test, while_stmt);
+    self.visit_maybe(|v| v.visit_stmt(&while_stmt.body, while_stmt));
+  }
+
+  fn visit_do_while_stmt(
+    &mut self,
+    do_while_stmt: &DoWhileStmt,
+    _parent: &dyn Node,
+  ) {
+    self.visit_stmt(&do_while_stmt.body, do_while_stmt);
+    self.visit_expr(&do_while_stmt.test, do_while_stmt);
+  }
+
+  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, _parent: &dyn Node) {
+    if let Some(init) = &for_stmt.init {
+      self.visit_var_decl_or_expr(init, for_stmt);
+    }
+    if let Some(test) = &for_stmt.test {
+      self.visit_expr(test, for_stmt);
+    }
+    self.visit_maybe(|v| {
+      v.visit_stmt(&for_stmt.body, for_stmt);
+      if let Some(update) = &for_stmt.update {
+        v.visit_expr(update, for_stmt);
+      }
+    });
+  }
+
+  fn visit_for_in_stmt(&mut self, for_in_stmt: &ForInStmt, _parent: &dyn Node) {
+    self.visit_expr(&for_in_stmt.right, for_in_stmt);
+    self.visit_maybe(|v| {
+      v.visit_var_decl_or_pat(&for_in_stmt.left, for_in_stmt);
+      v.visit_stmt(&for_in_stmt.body, for_in_stmt);
+    });
+  }
+
+  fn visit_for_of_stmt(&mut self, for_of_stmt: &ForOfStmt, _parent: &dyn Node) {
+    self.visit_expr(&for_of_stmt.right, for_of_stmt);
+    self.visit_maybe(|v| {
+      v.visit_var_decl_or_pat(&for_of_stmt.left, for_of_stmt);
+      v.visit_stmt(&for_of_stmt.body, for_of_stmt);
+    });
+  }
+
+  fn visit_return_stmt(&mut self, return_stmt: &ReturnStmt, parent: &dyn Node) {
+    swc_ecmascript::visit::visit_return_stmt(self, return_stmt, parent);
+    // Code after it is unreachable, so this path doesn't matter anymore.
+    self.super_called = true;
+  }
+
+  fn visit_throw_stmt(&mut self, throw_stmt: &ThrowStmt, parent: &dyn Node) {
+    swc_ecmascript::visit::visit_throw_stmt(self, throw_stmt, parent);
+    self.super_called = true;
+  }
 }
 
 #[cfg(test)]
@@ -419,4 +605,59 @@ class A extends B {
       8,
     );
   }
+
+  #[test]
+  fn no_this_before_super_branches() {
+    assert_lint_ok_n::<NoThisBeforeSuper>(vec![
+      "class A extends B { constructor() { if (a) { super(); } else { super(1); } this.a = 0; } }",
+      "class A extends B { constructor() { a ? super() : super(1); this.a = 0; } }",
+      "class A extends B { constructor() { if (a) { super(); } else { throw e; } this.a = 0; } }",
+      "class A extends B { constructor() { if (!a) { return; } super(); this.a = 0; } }",
+      "class A extends B { constructor() { switch (a) { case 1: super(1); break; default: super(); } this.a = 0; } }",
+      "class A extends B { constructor() { try { super(); } finally { } this.a = 0; } }",
+      "class A extends B { constructor() { do { super(); } while (a); this.a = 0; } }",
+      "class A extends B { constructor() { function foo() { this.a = 0; } super(); } }",
+      "class A extends B { constructor() { const foo = function() { return this; }; super(); } }",
+    ]);
+  }
+
+  #[test]
+  fn no_this_before_super_branches_invalid() {
+    assert_lint_err::<NoThisBeforeSuper>(
+      "class A extends B { constructor() { if (a) { super(); } this.a = 0; } }",
+      56,
+    );
+    assert_lint_err::<NoThisBeforeSuper>(
+      "class A extends B { constructor() { if (a) { super(); } else { foo(); } this.a = 0; } }",
+      72,
+    );
+    assert_lint_err::<NoThisBeforeSuper>(
+      "class A extends B { constructor() { a && super(); this.a = 0; } }",
+      50,
+    );
+    assert_lint_err::<NoThisBeforeSuper>(
+      "class A extends B { constructor() { while (a) { super(); } this.a = 0; } }",
+      59,
+    );
+    assert_lint_err::<NoThisBeforeSuper>(
+      "class A extends B { constructor() { for (const a of b) { super(); } this.a = 0; } }",
+      68,
+    );
+    assert_lint_err::<NoThisBeforeSuper>(
+      "class A extends B { constructor() { switch (a) { case 1: super(); } this.a = 0; } }",
+      68,
+    );
+    assert_lint_err::<NoThisBeforeSuper>(
+      "class A extends B { constructor() { try { super(); } catch (e) {} this.a = 0; } }",
+      66,
+    );
+    assert_lint_err::<NoThisBeforeSuper>(
+      "class A extends B { constructor() { const foo = () => this; super(); } }",
+      54,
+    );
+    assert_lint_err::<NoThisBeforeSuper>(
+      "class A extends B { constructor() { foo(() => super()); this.a = 0; } }",
+      56,
+    );
+  }
 }
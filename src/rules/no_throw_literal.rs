@@ -1,10 +1,8 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_ecmascript::ast::{Expr, ThrowStmt};
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -19,13 +17,15 @@ impl LintRule for NoThrowLiteral {
     "no-throw-literal"
   }
 
-  fn lint_module(
-    &self,
+  fn docs(&self) -> &'static str {
+    "Disallows throwing literals as exceptions."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoThrowLiteralVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoThrowLiteralVisitor::new(context)))
   }
 }
 
@@ -39,16 +39,24 @@ impl NoThrowLiteralVisitor {
   }
 }
 
-impl Visit for NoThrowLiteralVisitor {
-  noop_visit_type!();
-
-  fn visit_throw_stmt(&mut self, throw_stmt: &ThrowStmt, _parent: &dyn Node) {
-    match &*throw_stmt.arg {
-      Expr::Lit(_) => self.context.add_diagnostic(
-        throw_stmt.span,
-        "no-throw-literal",
-        "expected an error object to be thrown",
-      ),
+impl Handler for NoThrowLiteralVisitor {
+  fn visit_throw_stmt(
+    &mut self,
+    throw_stmt: &ThrowStmt,
+    _traversal: &mut Traversal,
+  ) {
+    let mut arg = &*throw_stmt.arg;
+    while let Expr::Paren(paren) = arg {
+      arg = &*paren.expr;
+    }
+    match arg {
+      Expr::Lit(_) | Expr::Tpl(_) | Expr::Object(_) | Expr::Array(_) => {
+        self.context.add_diagnostic(
+          throw_stmt.span,
+          "no-throw-literal",
+          "expected an error object to be thrown",
+        )
+      }
       Expr::Ident(ident) if ident.sym == *"undefined" => {
         self.context.add_diagnostic(
           throw_stmt.span,
@@ -72,15 +80,45 @@ mod tests {
     assert_lint_err::<NoThrowLiteral>("throw true", 0);
     assert_lint_err::<NoThrowLiteral>("throw 1096", 0);
     assert_lint_err::<NoThrowLiteral>("throw null", 0);
+    assert_lint_err::<NoThrowLiteral>("throw ('kumiko')", 0);
+    assert_lint_err::<NoThrowLiteral>("throw /kumiko/", 0);
+  }
+
+  #[test]
+  fn check_template_and_object_literals() {
+    assert_lint_err::<NoThrowLiteral>("throw `kumiko`", 0);
+    assert_lint_err::<NoThrowLiteral>("throw `error: ${message}`", 0);
+    assert_lint_err::<NoThrowLiteral>("throw { message: 'kumiko' }", 0);
+    assert_lint_err::<NoThrowLiteral>("throw [new Error('kumiko')]", 0);
+    assert_lint_err!(
+      NoThrowLiteral,
+      "function foo() {\n  if (bar) {\n    throw 'kumiko';\n  }\n}",
+      3,
+      4
+    );
   }
 
   #[test]
   fn check_undefined() {
     assert_lint_err::<NoThrowLiteral>("throw undefined", 0);
+    let diagnostics = lint_with_rule(NoThrowLiteral::new(), "throw undefined");
+    assert_eq!(diagnostics[0].message, "do not throw undefined");
+    let diagnostics = lint_with_rule(NoThrowLiteral::new(), "throw 1");
+    assert_eq!(
+      diagnostics[0].message,
+      "expected an error object to be thrown"
+    );
   }
 
   #[test]
   fn check_variable() {
-    assert_lint_ok::<NoThrowLiteral>("throw e");
+    assert_lint_ok! {
+      NoThrowLiteral,
+      "throw e",
+      "throw new Error('kumiko')",
+      "throw createError()",
+      "throw err.cause",
+      "try {} catch (e) { throw e; }",
+    };
   }
 }
@@ -1,14 +1,14 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
-use crate::globals::GLOBALS;
+use crate::handler::{walk, Handler, Traversal};
 use swc_atoms::js_word;
 use swc_common::SyntaxContext;
 use swc_ecmascript::{
   ast::*,
   utils::ident::IdentLike,
   visit::Node,
-  visit::{noop_visit_type, Visit, VisitWith},
+  visit::{Visit, VisitWith},
 };
 use swc_ecmascript::{utils::find_ids, utils::Id};
 
@@ -26,15 +26,19 @@ impl LintRule for NoUndef {
     "no-undef"
   }
 
-  fn lint_module(&self, context: Arc<Context>, module: &Module) {
-    let mut collector = BindingCollector {
-      top_level_ctxt: context.top_level_ctxt,
-      declared: Default::default(),
-    };
-    module.visit_with(module, &mut collector);
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
 
-    let mut visitor = NoUndefVisitor::new(context, collector.declared);
-    module.visit_with(module, &mut visitor);
+  fn docs(&self) -> &'static str {
+    "Disallows the use of undeclared variables."
+  }
+
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoUndefVisitor::new(context)))
   }
 }
 
@@ -146,8 +150,11 @@ struct NoUndefVisitor {
 }
 
 impl NoUndefVisitor {
-  fn new(context: Arc<Context>, declared: HashSet<Id>) -> Self {
-    Self { context, declared }
+  fn new(context: Arc<Context>) -> Self {
+    Self {
+      context,
+      declared: Default::default(),
+    }
   }
 
   fn check(&self, ident: &Ident) {
@@ -171,7 +178,7 @@ impl NoUndefVisitor {
     }
 
     // Globals
-    if GLOBALS.contains(&&*ident.sym) {
+    if self.context.scope.is_global(&ident.sym) {
       return;
     }
 
@@ -183,67 +190,70 @@ impl NoUndefVisitor {
   }
 }
 
-impl Visit for NoUndefVisitor {
-  noop_visit_type!();
+impl Handler for NoUndefVisitor {
+  fn visit_module(&mut self, module: &Module, _traversal: &mut Traversal) {
+    // Bindings may be referenced before they are declared.
+    let mut collector = BindingCollector {
+      top_level_ctxt: self.context.top_level_ctxt,
+      declared: Default::default(),
+    };
+    module.visit_with(module, &mut collector);
+    self.declared = collector.declared;
+  }
 
-  fn visit_member_expr(&mut self, e: &MemberExpr, _: &dyn Node) {
-    e.obj.visit_with(e, self);
-    if e.computed {
-      e.prop.visit_with(e, self);
+  fn visit_member_expr(&mut self, e: &MemberExpr, traversal: &mut Traversal) {
+    if !e.computed {
+      traversal.skip_children();
+      walk(self, &e.obj);
     }
   }
 
-  fn visit_unary_expr(&mut self, e: &UnaryExpr, _: &dyn Node) {
+  fn visit_unary_expr(&mut self, e: &UnaryExpr, traversal: &mut Traversal) {
     if e.op == UnaryOp::TypeOf {
-      return;
+      traversal.skip_children();
     }
-
-    e.visit_children_with(self);
   }
 
-  fn visit_expr(&mut self, e: &Expr, _: &dyn Node) {
-    e.visit_children_with(self);
-
+  fn visit_expr(&mut self, e: &Expr, _traversal: &mut Traversal) {
     if let Expr::Ident(ident) = e {
       self.check(ident)
     }
   }
 
-  fn visit_class_prop(&mut self, p: &ClassProp, _: &dyn Node) {
-    p.value.visit_with(p, self)
+  fn visit_class_prop(&mut self, p: &ClassProp, traversal: &mut Traversal) {
+    traversal.skip_children();
+    walk(self, &p.value);
   }
 
-  fn visit_prop(&mut self, p: &Prop, _: &dyn Node) {
-    p.visit_children_with(self);
-
+  fn visit_prop(&mut self, p: &Prop, _traversal: &mut Traversal) {
     if let Prop::Shorthand(i) = &p {
       self.check(i);
     }
   }
 
-  fn visit_pat(&mut self, p: &Pat, _: &dyn Node) {
+  fn visit_pat(&mut self, p: &Pat, traversal: &mut Traversal) {
     if let Pat::Ident(i) = p {
+      traversal.skip_children();
       self.check(i);
-    } else {
-      p.visit_children_with(self);
     }
   }
 
-  fn visit_assign_pat_prop(&mut self, p: &AssignPatProp, _: &dyn Node) {
+  fn visit_assign_pat_prop(
+    &mut self,
+    p: &AssignPatProp,
+    _traversal: &mut Traversal,
+  ) {
     self.check(&p.key);
-    p.value.visit_with(p, self);
   }
 
-  fn visit_call_expr(&mut self, e: &CallExpr, _: &dyn Node) {
+  fn visit_call_expr(&mut self, e: &CallExpr, traversal: &mut Traversal) {
     if let ExprOrSuper::Expr(callee) = &e.callee {
       if let Expr::Ident(i) = &**callee {
         if i.sym == js_word!("import") {
-          return;
+          traversal.skip_children();
         }
       }
     }
-
-    e.visit_children_with(self)
   }
 }
 
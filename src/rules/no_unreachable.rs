@@ -1,11 +1,11 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
-use swc_common::Spanned;
-use swc_ecmascript::ast::{Decl, Stmt, VarDecl, VarDeclKind};
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
-use swc_ecmascript::visit::VisitWith;
+use crate::handler::{walk, Handler, Traversal};
+use swc_common::{Span, Spanned};
+use swc_ecmascript::ast::{
+  Decl, ExportDecl, ModuleDecl, ModuleItem, Stmt, VarDecl, VarDeclKind,
+};
 
 use std::sync::Arc;
 
@@ -20,13 +20,19 @@ impl LintRule for NoUnreachable {
     "no-unreachable"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows unreachable code after `return`, `throw`, `break` and `continue` statements."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoUnreachableVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoUnreachableVisitor::new(context)))
   }
 }
 
@@ -38,38 +44,133 @@ impl NoUnreachableVisitor {
   fn new(context: Arc<Context>) -> Self {
     Self { context }
   }
-}
 
-impl Visit for NoUnreachableVisitor {
-  fn visit_stmt(&mut self, stmt: &Stmt, _: &dyn Node) {
-    stmt.visit_children_with(self);
-
-    match stmt {
-      // Don't print unused error for block statements
-      Stmt::Block(_) => return,
-      // Hoisted, so reachable.
-      Stmt::Decl(Decl::Fn(..)) => return,
-      Stmt::Decl(Decl::Var(VarDecl {
-        kind: VarDeclKind::Var,
-        decls,
-        ..
-      }))
-        if decls.iter().all(|decl| decl.init.is_none()) =>
-      {
-        return;
+  fn report_region(&self, region: &mut Option<Span>) {
+    if let Some(span) = region.take() {
+      self.context.add_diagnostic_with_hint(
+        span,
+        "no-unreachable",
+        "This statement is unreachable",
+        "Remove the code or move it before the statement ending execution",
+      )
+    }
+  }
+
+  /// Reports consecutive unreachable statements as a single region.
+  /// Hoisted declarations split regions as they are reachable.
+  fn check_stmts<'a>(&mut self, items: impl Iterator<Item = Item<'a>>) {
+    let mut region: Option<Span> = None;
+
+    for item in items {
+      match item {
+        Item::Stmt(Stmt::Empty(_)) => continue,
+        // Don't print unused error for block statements
+        Item::Stmt(stmt @ Stmt::Block(_)) => {
+          self.report_region(&mut region);
+          walk(self, stmt);
+          continue;
+        }
+        _ => {}
+      }
+      match item.decl() {
+        // Hoisted, so reachable.
+        Some(Decl::Fn(..)) => {
+          self.report_region(&mut region);
+          item.walk(self);
+          continue;
+        }
+        Some(Decl::Var(VarDecl {
+          kind: VarDeclKind::Var,
+          decls,
+          ..
+        }))
+          if decls.iter().all(|decl| decl.init.is_none()) =>
+        {
+          self.report_region(&mut region);
+          continue;
+        }
+        _ => {}
+      }
+
+      if self.context.control_flow.is_unreachable(&item) {
+        region = Some(match region {
+          Some(span) => span.to(item.span()),
+          None => item.span(),
+        });
+      } else {
+        self.report_region(&mut region);
+        item.walk(self);
       }
-      _ => {}
     }
 
-    if let Some(meta) = self.context.control_flow.meta(stmt.span().lo) {
-      if meta.unreachable {
-        self.context.add_diagnostic(
-          stmt.span(),
-          "no-unreachable",
-          "This statement is unreachable",
-        )
+    self.report_region(&mut region);
+  }
+}
+
+/// Statement, or `export` declaration at the top level of a module, which
+/// is checked like the statement declaring the same.
+#[derive(Clone, Copy)]
+enum Item<'a> {
+  Stmt(&'a Stmt),
+  ExportDecl(&'a ExportDecl),
+}
+
+impl<'a> Item<'a> {
+  fn decl(self) -> Option<&'a Decl> {
+    match self {
+      Item::Stmt(Stmt::Decl(decl)) => Some(decl),
+      Item::Stmt(_) => None,
+      Item::ExportDecl(export_decl) => Some(&export_decl.decl),
+    }
+  }
+
+  fn walk(self, handler: &mut dyn Handler) {
+    match self {
+      Item::Stmt(stmt) => walk(handler, stmt),
+      Item::ExportDecl(export_decl) => walk(handler, export_decl),
+    }
+  }
+}
+
+impl Spanned for Item<'_> {
+  fn span(&self) -> Span {
+    match self {
+      Item::Stmt(stmt) => stmt.span(),
+      Item::ExportDecl(export_decl) => export_decl.span,
+    }
+  }
+}
+
+impl Handler for NoUnreachableVisitor {
+  fn visit_types(&self) -> bool {
+    true
+  }
+
+  fn visit_module_items(
+    &mut self,
+    items: &[ModuleItem],
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
+    let mut stmts = vec![];
+    for item in items {
+      match item {
+        ModuleItem::Stmt(stmt) => stmts.push(Item::Stmt(stmt)),
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => {
+          stmts.push(Item::ExportDecl(export_decl))
+        }
+        ModuleItem::ModuleDecl(decl) => {
+          self.check_stmts(stmts.drain(..));
+          walk(self, decl);
+        }
       }
     }
+    self.check_stmts(stmts.into_iter());
+  }
+
+  fn visit_stmts(&mut self, stmts: &[Stmt], traversal: &mut Traversal) {
+    traversal.skip_children();
+    self.check_stmts(stmts.iter().map(Item::Stmt));
   }
 }
 
@@ -384,7 +485,7 @@ function normalize(type: string): string | undefined {
       // comment
       c();
   }",
-      vec![(3, 6), (4, 6), (6, 6)],
+      vec![(3, 6)],
     );
 
     assert_lint_err_on_line_n::<NoUnreachable>(
@@ -398,7 +499,7 @@ function normalize(type: string): string | undefined {
           d();
       }
   }",
-      vec![(4, 10), (5, 10), (8, 10)],
+      vec![(4, 10), (8, 10)],
     );
   }
 
@@ -416,7 +517,7 @@ function normalize(type: string): string | undefined {
       }
       e();
   }",
-      vec![(4, 10), (5, 10), (8, 10), (10, 6)],
+      vec![(4, 10), (8, 10), (10, 6)],
     );
 
     assert_lint_err_on_line::<NoUnreachable>(
@@ -634,4 +735,311 @@ console.log("unreachable???");
       "#,
     );
   }
+
+  #[test]
+  fn if_else_both_terminate() {
+    assert_lint_err::<NoUnreachable>(
+      r#"function f() { if (a) { return 1; } else { return 2; } console.log("dead"); }"#,
+      55,
+    );
+
+    assert_lint_err::<NoUnreachable>(
+      "function f() { if (a) { throw e1; } else { throw e2; } dead(); }",
+      55,
+    );
+
+    assert_lint_ok::<NoUnreachable>(
+      r#"function f() { if (a) { return 1; } console.log("live"); }"#,
+    );
+  }
+
+  #[test]
+  fn nested_if_else() {
+    assert_lint_ok::<NoUnreachable>(
+      "function f() { if (a) { if (b) { return 1; } } else { return 2; } live(); }",
+    );
+
+    assert_lint_err::<NoUnreachable>(
+      "function f() { if (a) { if (b) { return 1; } else { throw 2; } } else { return 2; } dead(); }",
+      84,
+    );
+
+    assert_lint_err::<NoUnreachable>(
+      "function f() { if (a) throw 1; else if (b) throw 2; else return; dead(); }",
+      65,
+    );
+  }
+
+  #[test]
+  fn infinite_loop() {
+    assert_lint_err::<NoUnreachable>(
+      "function f() { while(true){} dead(); }",
+      29,
+    );
+
+    assert_lint_err::<NoUnreachable>(
+      "function f() { while(1){ work(); } dead(); }",
+      35,
+    );
+
+    assert_lint_err::<NoUnreachable>("function f() { for(;;){} dead(); }", 25);
+
+    assert_lint_ok::<NoUnreachable>(
+      "function f() { while(true){ if(x) break; } live(); }",
+    );
+  }
+
+  #[test]
+  fn infinite_loop_with_nested_break() {
+    assert_lint_err::<NoUnreachable>(
+      "function f() { while(true){ function g() { while (a) { break; } } } dead(); }",
+      68,
+    );
+
+    assert_lint_err::<NoUnreachable>(
+      "function f() { for(;;){ const g = () => { for (;;) { break; } }; } dead(); }",
+      67,
+    );
+
+    assert_lint_err::<NoUnreachable>(
+      "function f() { while(true){ switch (a) { case 1: break; } } dead(); }",
+      60,
+    );
+
+    assert_lint_err::<NoUnreachable>(
+      "function f() { while(true){ while (b) { break; } } dead(); }",
+      51,
+    );
+
+    assert_lint_ok::<NoUnreachable>(
+      "function f() { A: while(true){ while (b) { break A; } } live(); }",
+    );
+
+    assert_lint_ok::<NoUnreachable>(
+      "function f() { A: while(true){ switch (b) { case 1: break A; } } live(); }",
+    );
+  }
+
+  #[test]
+  fn merged_regions() {
+    assert_lint_err_on_line_n::<NoUnreachable>(
+      "function foo() {
+      return;
+      a();
+      b();
+      function bar() {}
+      c();
+      d();
+  }",
+      vec![(3, 6), (6, 6)],
+    );
+
+    assert_lint_err_on_line_n::<NoUnreachable>(
+      "function foo() {
+      throw err;
+      a();
+      var x;
+      b();
+  }",
+      vec![(3, 6), (5, 6)],
+    );
+
+    assert_lint_err_on_line::<NoUnreachable>(
+      "function foo() {
+      return;
+      if (a) {
+        b();
+      }
+      c();
+  }",
+      3,
+      6,
+    );
+  }
+
+  #[test]
+  fn region_range() {
+    assert_lint_err_on_range::<NoUnreachable>(
+      "function foo() { return; a(); b(); }",
+      (1, 25),
+      (1, 34),
+    );
+
+    assert_lint_err_on_range::<NoUnreachable>(
+      "function foo() {
+      return;
+      a();
+      if (b) {
+        c();
+      }
+  }",
+      (3, 6),
+      (6, 7),
+    );
+  }
+
+  #[test]
+  fn switch_case_body() {
+    assert_lint_err::<NoUnreachable>(
+      r#"function f() { switch (a) { case 1: return; console.log("dead"); } }"#,
+      44,
+    );
+
+    assert_lint_err::<NoUnreachable>(
+      "function f() { switch (a) { default: throw e; dead(); } }",
+      46,
+    );
+
+    assert_lint_err::<NoUnreachable>(
+      "switch (a) { case 1: break; dead(); }",
+      28,
+    );
+  }
+
+  #[test]
+  fn module_top_level() {
+    assert_lint_err::<NoUnreachable>("throw x; foo();", 9);
+
+    assert_lint_err_on_line::<NoUnreachable>(
+      "import { a } from './a.ts';
+throw a;
+export const b = 1;
+foo();",
+      3,
+      0,
+    );
+
+    // Hoisted, so only the call is unreachable.
+    assert_lint_err_on_line::<NoUnreachable>(
+      "throw a;
+export function f() {}
+foo();",
+      3,
+      0,
+    );
+  }
+
+  #[test]
+  fn try_finally() {
+    assert_lint_err::<NoUnreachable>(
+      "function f() { try { throw new Error(); } finally { cleanup(); } dead(); }",
+      65,
+    );
+
+    assert_lint_err::<NoUnreachable>(
+      "function f() { try { a(); } finally { return; } dead(); }",
+      48,
+    );
+
+    assert_lint_err::<NoUnreachable>(
+      "function f() { try { a(); } catch (e) { b(); } finally { return; } dead(); }",
+      67,
+    );
+
+    assert_lint_err::<NoUnreachable>(
+      "function f() { try { return a(); } catch (e) { throw e; } dead(); }",
+      58,
+    );
+
+    assert_lint_ok::<NoUnreachable>(
+      "function f() { try { a(); } finally { b(); } live(); }",
+    );
+  }
+
+  #[test]
+  fn try_catch_swallows_throw() {
+    assert_lint_ok::<NoUnreachable>(
+      "function f() { try { throw new Error(); } catch (e) { log(e); } live(); }",
+    );
+
+    assert_lint_ok::<NoUnreachable>(
+      "function f() { try { throw a; } catch (e) { c(); } live(); }",
+    );
+
+    assert_lint_ok::<NoUnreachable>(
+      "function f() { try { a(); } catch (e) { return; } live(); }",
+    );
+  }
+
+  #[test]
+  fn nested_try() {
+    assert_lint_err::<NoUnreachable>(
+      "function f() { try { try { throw a; } finally { b(); } } finally { c(); } dead(); }",
+      74,
+    );
+
+    assert_lint_ok::<NoUnreachable>(
+      "function f() { try { try { throw a; } finally { b(); } } catch (e) { c(); } live(); }",
+    );
+  }
+
+  #[test]
+  fn labeled_loops() {
+    assert_lint_err::<NoUnreachable>(
+      "outer: while (a) { while (b) { break outer; dead(); } live(); }",
+      44,
+    );
+
+    assert_lint_err::<NoUnreachable>(
+      "outer: while (a) { while (b) { continue outer; dead(); } live(); }",
+      47,
+    );
+
+    assert_lint_ok::<NoUnreachable>(
+      "outer: while (a) { while (b) { break outer; } live(); }",
+    );
+
+    assert_lint_ok::<NoUnreachable>(
+      "outer: while (a) { while (b) { if (c) break outer; } live(); }",
+    );
+
+    assert_lint_ok::<NoUnreachable>(
+      "function f() { A: for (;;) { for (;;) { break A; } } live(); }",
+    );
+  }
+
+  #[test]
+  fn labeled_blocks() {
+    assert_lint_ok::<NoUnreachable>("function f() { A: { break A; } live(); }");
+
+    assert_lint_ok::<NoUnreachable>(
+      "function f() { A: { if (a) break A; return; } live(); }",
+    );
+
+    assert_lint_err::<NoUnreachable>(
+      "function f() { A: { return; } dead(); }",
+      30,
+    );
+
+    assert_lint_err::<NoUnreachable>(
+      "function f() { A: { B: { break A; } dead(); } live(); }",
+      36,
+    );
+
+    assert_lint_ok::<NoUnreachable>(
+      "function f() { A: { B: { break B; } live(); } live(); }",
+    );
+  }
+
+  #[test]
+  fn labeled_switch() {
+    assert_lint_ok::<NoUnreachable>(
+      "function f() { A: switch (a) { case 1: break A; default: return; } live(); }",
+    );
+
+    assert_lint_err::<NoUnreachable>(
+      "function f() { A: switch (a) { case 1: throw e; default: return; } dead(); }",
+      67,
+    );
+  }
+
+  #[test]
+  fn hint() {
+    assert_lint_err_with_hint::<NoUnreachable>(
+      "function foo() { return; a(); }",
+      1,
+      25,
+      "Remove the code or move it before the statement ending execution",
+    );
+  }
 }
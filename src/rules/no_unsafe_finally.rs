@@ -1,8 +1,13 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::{Context, LintRule};
-use swc_ecmascript::ast::Module;
-use swc_ecmascript::ast::Stmt::{Break, Continue, Return, Throw};
-use swc_ecmascript::ast::TryStmt;
+use crate::handler::{Handler, Traversal};
+use swc_atoms::JsWord;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  ArrowExpr, BreakStmt, Class, ContinueStmt, DoWhileStmt, ForInStmt, ForOfStmt,
+  ForStmt, Function, GetterProp, LabeledStmt, ReturnStmt, SetterProp,
+  SwitchStmt, ThrowStmt, TryStmt, WhileStmt,
+};
 use swc_ecmascript::visit::{self, noop_visit_type, Node, Visit};
 
 use std::sync::Arc;
@@ -18,9 +23,24 @@ impl LintRule for NoUnsafeFinally {
     "no-unsafe-finally"
   }
 
-  fn lint_module(&self, context: Arc<Context>, module: &Module) {
-    let mut visitor = NoUnsafeFinallyVisitor::new(context);
-    visitor.visit_module(module, module);
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows control flow statements in `finally` blocks.
+
+`return`, `throw`, `break` and `continue` in a `finally` block override
+the completion of the `try` and `catch` blocks, swallowing exceptions.
+They're allowed in nested functions, and `break` and `continue` are allowed
+if they target a loop, `switch` or label inside the `finally` block."#
+  }
+
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoUnsafeFinallyVisitor::new(context)))
   }
 }
 
@@ -34,32 +54,140 @@ impl NoUnsafeFinallyVisitor {
   }
 }
 
-impl Visit for NoUnsafeFinallyVisitor {
-  noop_visit_type!();
-
-  fn visit_try_stmt(&mut self, try_stmt: &TryStmt, parent: &dyn Node) {
+impl Handler for NoUnsafeFinallyVisitor {
+  fn visit_try_stmt(&mut self, try_stmt: &TryStmt, _traversal: &mut Traversal) {
     if let Some(finally_block) = &try_stmt.finalizer {
-      // Convenience function for providing different diagnostic message
-      // depending on statement type
-      let add_diagnostic = |stmt_type: &str| {
+      let mut finder = UnsafeStmtFinder::default();
+      for stmt in &finally_block.stmts {
+        finder.visit_stmt(stmt, finally_block);
+      }
+      for (span, stmt_type) in finder.found {
         self.context.add_diagnostic(
-          finally_block.span,
+          span,
           "no-unsafe-finally",
           format!("Unsafe usage of {}Statement", stmt_type).as_str(),
         );
-      };
-
-      for stmt in &finally_block.stmts {
-        match stmt {
-          Break(_) => add_diagnostic("Break"),
-          Continue(_) => add_diagnostic("Continue"),
-          Return(_) => add_diagnostic("Return"),
-          Throw(_) => add_diagnostic("Throw"),
-          _ => {}
-        }
       }
     }
-    visit::visit_try_stmt(self, try_stmt, parent);
+  }
+}
+
+/// Finds statements which leave a `finally` block.
+#[derive(Default)]
+struct UnsafeStmtFinder {
+  /// Spans and types of the statements found
+  found: Vec<(Span, &'static str)>,
+  /// Number of enclosing loops inside the `finally` block
+  loop_depth: usize,
+  /// Number of enclosing switches inside the `finally` block
+  switch_depth: usize,
+  /// Labels declared inside the `finally` block
+  labels: Vec<JsWord>,
+}
+
+impl UnsafeStmtFinder {
+  fn visit_loop<F>(&mut self, f: F)
+  where
+    F: FnOnce(&mut Self),
+  {
+    self.loop_depth += 1;
+    f(self);
+    self.loop_depth -= 1;
+  }
+}
+
+impl Visit for UnsafeStmtFinder {
+  noop_visit_type!();
+
+  // Nested functions have their own control flow.
+  fn visit_function(&mut self, _function: &Function, _parent: &dyn Node) {}
+
+  fn visit_arrow_expr(&mut self, _arrow_expr: &ArrowExpr, _parent: &dyn Node) {}
+
+  fn visit_class(&mut self, _class: &Class, _parent: &dyn Node) {}
+
+  fn visit_getter_prop(&mut self, _getter_prop: &GetterProp, _: &dyn Node) {}
+
+  fn visit_setter_prop(&mut self, _setter_prop: &SetterProp, _: &dyn Node) {}
+
+  fn visit_try_stmt(&mut self, try_stmt: &TryStmt, _parent: &dyn Node) {
+    // Nested `finally` blocks are checked on their own.
+    self.visit_block_stmt(&try_stmt.block, try_stmt);
+    if let Some(handler) = &try_stmt.handler {
+      self.visit_catch_clause(handler, try_stmt);
+    }
+  }
+
+  fn visit_return_stmt(&mut self, return_stmt: &ReturnStmt, _: &dyn Node) {
+    self.found.push((return_stmt.span, "Return"));
+  }
+
+  fn visit_throw_stmt(&mut self, throw_stmt: &ThrowStmt, _: &dyn Node) {
+    self.found.push((throw_stmt.span, "Throw"));
+  }
+
+  fn visit_break_stmt(&mut self, break_stmt: &BreakStmt, _: &dyn Node) {
+    let is_safe = match &break_stmt.label {
+      Some(label) => self.labels.contains(&label.sym),
+      None => self.loop_depth > 0 || self.switch_depth > 0,
+    };
+    if !is_safe {
+      self.found.push((break_stmt.span, "Break"));
+    }
+  }
+
+  fn visit_continue_stmt(
+    &mut self,
+    continue_stmt: &ContinueStmt,
+    _: &dyn Node,
+  ) {
+    let is_safe = match &continue_stmt.label {
+      Some(label) => self.labels.contains(&label.sym),
+      None => self.loop_depth > 0,
+    };
+    if !is_safe {
+      self.found.push((continue_stmt.span, "Continue"));
+    }
+  }
+
+  fn visit_labeled_stmt(
+    &mut self,
+    labeled_stmt: &LabeledStmt,
+    parent: &dyn Node,
+  ) {
+    self.labels.push(labeled_stmt.label.sym.clone());
+    visit::visit_labeled_stmt(self, labeled_stmt, parent);
+    self.labels.pop();
+  }
+
+  fn visit_switch_stmt(&mut self, switch_stmt: &SwitchStmt, parent: &dyn Node) {
+    self.switch_depth += 1;
+    visit::visit_switch_stmt(self, switch_stmt, parent);
+    self.switch_depth -= 1;
+  }
+
+  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, parent: &dyn Node) {
+    self.visit_loop(|v| visit::visit_for_stmt(v, for_stmt, parent));
+  }
+
+  fn visit_for_in_stmt(&mut self, for_in_stmt: &ForInStmt, parent: &dyn Node) {
+    self.visit_loop(|v| visit::visit_for_in_stmt(v, for_in_stmt, parent));
+  }
+
+  fn visit_for_of_stmt(&mut self, for_of_stmt: &ForOfStmt, parent: &dyn Node) {
+    self.visit_loop(|v| visit::visit_for_of_stmt(v, for_of_stmt, parent));
+  }
+
+  fn visit_while_stmt(&mut self, while_stmt: &WhileStmt, parent: &dyn Node) {
+    self.visit_loop(|v| visit::visit_while_stmt(v, while_stmt, parent));
+  }
+
+  fn visit_do_while_stmt(
+    &mut self,
+    do_while_stmt: &DoWhileStmt,
+    parent: &dyn Node,
+  ) {
+    self.visit_loop(|v| visit::visit_do_while_stmt(v, do_while_stmt, parent));
   }
 }
 
@@ -140,8 +268,8 @@ let foo = function() {
   }
 };
      "#,
-      7,
-      12,
+      8,
+      4,
     );
   }
 
@@ -159,8 +287,8 @@ let foo = function() {
   }
 };
      "#,
-      7,
-      12,
+      8,
+      4,
     );
   }
 
@@ -178,8 +306,8 @@ let foo = function() {
   }
 };
           "#,
-      7,
-      12,
+      8,
+      4,
     );
   }
 
@@ -197,8 +325,8 @@ let foo = function() {
   }
 };
      "#,
-      7,
-      12,
+      8,
+      4,
     );
   }
 
@@ -214,8 +342,57 @@ finally {
   }
 }
      "#,
-      5,
-      10,
+      6,
+      4,
+    );
+  }
+
+  #[test]
+  fn it_passes_for_statements_targeting_inside_the_finally_block() {
+    assert_lint_ok_n::<NoUnsafeFinally>(vec![
+      "try {} finally { while (a) { break; } }",
+      "try {} finally { for (const a of b) { if (a) continue; } }",
+      "try {} finally { do { break; } while (a); }",
+      "outer: while (a) { try {} finally { inner: for (;;) { break inner; } } }",
+      "try {} finally { block: { break block; } }",
+      "try {} finally { switch (a) { case 1: break; } }",
+      "try {} finally { const f = () => { throw e; }; }",
+      "try {} finally { class A { foo() { return 1; } } }",
+      "try {} finally { const o = { get x() { return 1; } }; }",
+      "try {} finally { const o = { set x(v) { return; } }; }",
+      "try {} catch (e) { throw e; } finally { foo(); }",
+    ]);
+  }
+
+  #[test]
+  fn it_fails_for_nested_statements_leaving_the_finally_block() {
+    assert_lint_err::<NoUnsafeFinally>(
+      "try {} finally { if (a) { throw e; } }",
+      26,
+    );
+    assert_lint_err::<NoUnsafeFinally>(
+      "function foo() { try {} finally { { return 1; } } }",
+      36,
+    );
+    assert_lint_err::<NoUnsafeFinally>(
+      "outer: while (a) { try {} finally { while (b) { break outer; } } }",
+      48,
+    );
+    assert_lint_err::<NoUnsafeFinally>(
+      "outer: while (a) { try {} finally { for (;;) { continue outer; } } }",
+      47,
+    );
+    assert_lint_err::<NoUnsafeFinally>(
+      "while (a) { try {} finally { switch (b) { case 1: continue; } } }",
+      50,
+    );
+    assert_lint_err::<NoUnsafeFinally>(
+      "function foo() { try {} finally { try { return 1; } catch (e) {} } }",
+      40,
+    );
+    assert_lint_err_n::<NoUnsafeFinally>(
+      "function foo() { try {} finally { if (a) return 1; else throw e; } }",
+      vec![41, 56],
     );
   }
 }
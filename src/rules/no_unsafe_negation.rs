@@ -1,13 +1,11 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_ecmascript::ast::BinExpr;
 use swc_ecmascript::ast::BinaryOp;
 use swc_ecmascript::ast::Expr;
 use swc_ecmascript::ast::UnaryOp;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -22,13 +20,19 @@ impl LintRule for NoUnsafeNegation {
     "no-unsafe-negation"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows negating the left operand of relational operators."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoUnsafeNegationVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoUnsafeNegationVisitor::new(context)))
   }
 }
 
@@ -42,17 +46,24 @@ impl NoUnsafeNegationVisitor {
   }
 }
 
-impl Visit for NoUnsafeNegationVisitor {
-  noop_visit_type!();
-
-  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, _parent: &dyn Node) {
+impl Handler for NoUnsafeNegationVisitor {
+  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, _traversal: &mut Traversal) {
     if bin_expr.op == BinaryOp::In || bin_expr.op == BinaryOp::InstanceOf {
-      if let Expr::Unary(unary_expr) = &*bin_expr.left {
+      // `(!key) in object` is as likely to be a mistake as `!key in object`.
+      let mut left = &*bin_expr.left;
+      while let Expr::Paren(paren) = left {
+        left = &*paren.expr;
+      }
+      if let Expr::Unary(unary_expr) = left {
         if unary_expr.op == UnaryOp::Bang {
-          self.context.add_diagnostic(
+          self.context.add_diagnostic_with_hint(
             bin_expr.span,
             "no-unsafe-negation",
             "Unexpected negation of left operand",
+            &format!(
+              "Negate the whole expression with `!(a {} b)` instead",
+              bin_expr.op
+            ),
           );
         }
       }
@@ -74,6 +85,8 @@ mod tests {
       "!(1 in [1, 2, 3])",
       "!(key in object)",
       "!(foo instanceof Date)",
+      "(!foo) === bar",
+      "!foo && bar in baz",
     ]);
   }
 
@@ -82,5 +95,31 @@ mod tests {
     assert_lint_err::<NoUnsafeNegation>("!1 in [1, 2, 3]", 0);
     assert_lint_err::<NoUnsafeNegation>("!key in object", 0);
     assert_lint_err::<NoUnsafeNegation>("!foo instanceof Date", 0);
+    assert_lint_err::<NoUnsafeNegation>("(!key) in object", 0);
+    assert_lint_err::<NoUnsafeNegation>("((!foo)) instanceof Date", 0);
+    assert_lint_err::<NoUnsafeNegation>("if (!key in object) {}", 4);
+    assert_lint_err::<NoUnsafeNegation>("const a = !(!key in object);", 12);
+    assert_lint_err!(
+      NoUnsafeNegation,
+      "function foo(a) {\n  return !a instanceof Foo;\n}",
+      2,
+      9
+    );
+  }
+
+  #[test]
+  fn no_unsafe_negation_hint() {
+    assert_lint_err_with_hint::<NoUnsafeNegation>(
+      "!key in object",
+      1,
+      0,
+      "Negate the whole expression with `!(a in b)` instead",
+    );
+    assert_lint_err_with_hint::<NoUnsafeNegation>(
+      "!foo instanceof Date",
+      1,
+      0,
+      "Negate the whole expression with `!(a instanceof b)` instead",
+    );
   }
 }
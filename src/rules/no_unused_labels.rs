@@ -1,13 +1,11 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_ecmascript::ast::BreakStmt;
 use swc_ecmascript::ast::ContinueStmt;
 use swc_ecmascript::ast::Ident;
 use swc_ecmascript::ast::LabeledStmt;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -22,13 +20,19 @@ impl LintRule for NoUnusedLabels {
     "no-unused-labels"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows labels that are never used."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoUnusedLabelsVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoUnusedLabelsVisitor::new(context)))
   }
 }
 
@@ -64,13 +68,11 @@ impl NoUnusedLabelsVisitor {
   }
 }
 
-impl Visit for NoUnusedLabelsVisitor {
-  noop_visit_type!();
-
+impl Handler for NoUnusedLabelsVisitor {
   fn visit_labeled_stmt(
     &mut self,
     labeled_stmt: &LabeledStmt,
-    parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     let name = labeled_stmt.label.sym.as_ref();
     let label_scope = LabelScope {
@@ -78,7 +80,10 @@ impl Visit for NoUnusedLabelsVisitor {
       used: false,
     };
     self.label_scopes.push(label_scope);
-    swc_ecmascript::visit::visit_labeled_stmt(self, labeled_stmt, parent);
+  }
+
+  fn exit_labeled_stmt(&mut self, labeled_stmt: &LabeledStmt) {
+    let name = labeled_stmt.label.sym.as_ref();
     let scope = self.label_scopes.pop().expect("self.label_scopes is empty");
     if !scope.used {
       self.context.add_diagnostic(
@@ -92,12 +97,16 @@ impl Visit for NoUnusedLabelsVisitor {
   fn visit_continue_stmt(
     &mut self,
     continue_stmt: &ContinueStmt,
-    _parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     self.maybe_check_label(continue_stmt.label.as_ref());
   }
 
-  fn visit_break_stmt(&mut self, break_stmt: &BreakStmt, _parent: &dyn Node) {
+  fn visit_break_stmt(
+    &mut self,
+    break_stmt: &BreakStmt,
+    _traversal: &mut Traversal,
+  ) {
     self.maybe_check_label(break_stmt.label.as_ref());
   }
 }
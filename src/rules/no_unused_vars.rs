@@ -1,6 +1,7 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{walk, Handler, Traversal};
 use swc_ecmascript::utils::find_ids;
 use swc_ecmascript::utils::ident::IdentLike;
 use swc_ecmascript::utils::Id;
@@ -33,20 +34,15 @@ impl LintRule for NoUnusedVars {
     "no-unused-vars"
   }
 
-  fn lint_module(&self, context: Arc<Context>, module: &Module) {
-    let mut collector = Collector {
-      used_vars: Default::default(),
-      cur_defining: Default::default(),
-      used_types: Default::default(),
-    };
-    module.visit_with(module, &mut collector);
+  fn docs(&self) -> &'static str {
+    "Disallows variables that are declared but never used."
+  }
 
-    let mut visitor = NoUnusedVarVisitor::new(
-      context,
-      collector.used_vars,
-      collector.used_types,
-    );
-    module.visit_with(module, &mut visitor);
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoUnusedVarVisitor::new(context)))
   }
 }
 
@@ -246,15 +242,11 @@ struct NoUnusedVarVisitor {
 }
 
 impl NoUnusedVarVisitor {
-  fn new(
-    context: Arc<Context>,
-    used_vars: HashSet<Id>,
-    used_types: HashSet<Id>,
-  ) -> Self {
+  fn new(context: Arc<Context>) -> Self {
     Self {
       context,
-      used_vars,
-      used_types,
+      used_vars: Default::default(),
+      used_types: Default::default(),
     }
   }
 }
@@ -276,91 +268,125 @@ impl NoUnusedVarVisitor {
   }
 }
 
-impl Visit for NoUnusedVarVisitor {
-  fn visit_arrow_expr(&mut self, expr: &ArrowExpr, _: &dyn Node) {
+impl Handler for NoUnusedVarVisitor {
+  fn visit_types(&self) -> bool {
+    true
+  }
+
+  fn visit_module(&mut self, module: &Module, _traversal: &mut Traversal) {
+    // Variables may be used before they are declared.
+    let mut collector = Collector {
+      used_vars: Default::default(),
+      cur_defining: Default::default(),
+      used_types: Default::default(),
+    };
+    module.visit_with(module, &mut collector);
+    self.used_vars = collector.used_vars;
+    self.used_types = collector.used_types;
+  }
+
+  fn visit_arrow_expr(&mut self, expr: &ArrowExpr, traversal: &mut Traversal) {
+    traversal.skip_children();
     let declared_idents: Vec<Ident> = find_ids(&expr.params);
 
     for ident in declared_idents {
       self.handle_id(&ident);
     }
-    expr.body.visit_with(expr, self)
+    walk(self, &expr.body);
   }
 
-  fn visit_fn_decl(&mut self, decl: &FnDecl, _: &dyn Node) {
+  fn visit_fn_decl(&mut self, decl: &FnDecl, traversal: &mut Traversal) {
+    traversal.skip_children();
     if decl.declare {
       return;
     }
     self.handle_id(&decl.ident);
-    decl.function.visit_with(decl, self);
+    walk(self, &decl.function);
   }
 
-  fn visit_var_decl(&mut self, n: &VarDecl, _: &dyn Node) {
+  fn visit_var_decl(&mut self, n: &VarDecl, traversal: &mut Traversal) {
     if n.declare {
-      return;
+      traversal.skip_children();
     }
-
-    n.decls.visit_with(n, self);
   }
 
-  fn visit_var_declarator(&mut self, declarator: &VarDeclarator, _: &dyn Node) {
+  fn visit_var_declarator(
+    &mut self,
+    declarator: &VarDeclarator,
+    _traversal: &mut Traversal,
+  ) {
     let declared_idents: Vec<Ident> = find_ids(&declarator.name);
 
     for ident in declared_idents {
       self.handle_id(&ident);
     }
-    declarator.name.visit_with(declarator, self);
-    declarator.init.visit_with(declarator, self);
   }
 
-  fn visit_class_decl(&mut self, n: &ClassDecl, _: &dyn Node) {
+  fn visit_class_decl(&mut self, n: &ClassDecl, traversal: &mut Traversal) {
     if n.declare {
-      return;
+      traversal.skip_children();
     }
-
-    n.visit_children_with(self);
   }
 
-  fn visit_catch_clause(&mut self, clause: &CatchClause, _: &dyn Node) {
+  fn visit_catch_clause(
+    &mut self,
+    clause: &CatchClause,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
     let declared_idents: Vec<Ident> = find_ids(&clause.param);
 
     for ident in declared_idents {
       self.handle_id(&ident);
     }
 
-    clause.body.visit_with(clause, self);
+    walk(self, &clause.body);
   }
 
-  fn visit_setter_prop(&mut self, prop: &SetterProp, _: &dyn Node) {
-    prop.key.visit_with(prop, self);
-    prop.body.visit_with(prop, self);
+  fn visit_setter_prop(
+    &mut self,
+    prop: &SetterProp,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
+    walk(self, &prop.key);
+    walk(self, &prop.body);
   }
 
-  fn visit_class_method(&mut self, method: &ClassMethod, _: &dyn Node) {
-    method.function.decorators.visit_with(method, self);
-    method.key.visit_with(method, self);
+  fn visit_class_method(
+    &mut self,
+    method: &ClassMethod,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
+    walk(self, &method.function.decorators);
+    walk(self, &method.key);
 
     match method.kind {
-      MethodKind::Method => method.function.params.visit_children_with(self),
+      MethodKind::Method => {
+        for param in &method.function.params {
+          walk(self, param);
+        }
+      }
       MethodKind::Getter => {}
       MethodKind::Setter => {}
     }
 
-    method.function.body.visit_with(method, self);
+    walk(self, &method.function.body);
   }
 
-  fn visit_param(&mut self, param: &Param, _: &dyn Node) {
+  fn visit_param(&mut self, param: &Param, _traversal: &mut Traversal) {
     let declared_idents: Vec<Ident> = find_ids(&param.pat);
 
     for ident in declared_idents {
       self.handle_id(&ident);
     }
-    param.visit_children_with(self)
   }
 
   fn visit_import_named_specifier(
     &mut self,
     import: &ImportNamedSpecifier,
-    _: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     if self.used_types.contains(&import.local.to_id()) {
       return;
@@ -371,7 +397,7 @@ impl Visit for NoUnusedVarVisitor {
   fn visit_import_default_specifier(
     &mut self,
     import: &ImportDefaultSpecifier,
-    _: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     if self.used_types.contains(&import.local.to_id()) {
       return;
@@ -382,7 +408,7 @@ impl Visit for NoUnusedVarVisitor {
   fn visit_import_star_as_specifier(
     &mut self,
     import: &ImportStarAsSpecifier,
-    _: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     if self.used_types.contains(&import.local.to_id()) {
       return;
@@ -391,39 +417,45 @@ impl Visit for NoUnusedVarVisitor {
   }
 
   /// No error as export is kind of usage
-  fn visit_export_decl(&mut self, export: &ExportDecl, _: &dyn Node) {
+  fn visit_export_decl(
+    &mut self,
+    export: &ExportDecl,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
     match &export.decl {
       Decl::Class(c) => {
-        c.class.visit_with(c, self);
+        walk(self, &c.class);
       }
       Decl::Fn(f) => {
-        f.function.visit_with(f, self);
+        walk(self, &f.function);
       }
       Decl::Var(v) => {
         for decl in &v.decls {
-          decl.name.visit_with(decl, self);
-          decl.init.visit_with(decl, self);
+          walk(self, &decl.name);
+          walk(self, &decl.init);
         }
       }
       _ => {}
     }
   }
 
-  fn visit_params(&mut self, params: &[Param], parent: &dyn Node) {
-    match params.first() {
-      Some(Param {
-        pat: Pat::Ident(i), ..
-      }) if i.sym == *"this" => params
-        .iter()
-        .skip(1)
-        .for_each(|param| param.visit_with(parent, self)),
-      _ => params
-        .iter()
-        .for_each(|param| param.visit_with(parent, self)),
+  fn visit_params(&mut self, params: &[Param], traversal: &mut Traversal) {
+    if let Some(Param {
+      pat: Pat::Ident(i), ..
+    }) = params.first()
+    {
+      if i.sym == *"this" {
+        traversal.skip_children();
+        for param in params.iter().skip(1) {
+          walk(self, param);
+        }
+      }
     }
   }
 
-  fn visit_ts_enum_decl(&mut self, n: &TsEnumDecl, _: &dyn Node) {
+  fn visit_ts_enum_decl(&mut self, n: &TsEnumDecl, traversal: &mut Traversal) {
+    traversal.skip_children();
     if n.declare {
       return;
     }
@@ -434,24 +466,34 @@ impl Visit for NoUnusedVarVisitor {
     self.handle_id(&n.id);
   }
 
-  fn visit_ts_module_decl(&mut self, n: &TsModuleDecl, _: &dyn Node) {
+  fn visit_ts_module_decl(
+    &mut self,
+    n: &TsModuleDecl,
+    traversal: &mut Traversal,
+  ) {
     if n.declare {
-      return;
+      traversal.skip_children();
     }
-
-    n.body.visit_with(n, self);
   }
 
-  fn visit_ts_namespace_decl(&mut self, n: &TsNamespaceDecl, _: &dyn Node) {
+  fn visit_ts_namespace_decl(
+    &mut self,
+    n: &TsNamespaceDecl,
+    traversal: &mut Traversal,
+  ) {
     if n.declare {
-      return;
+      traversal.skip_children();
     }
-
-    n.body.visit_with(n, self);
   }
 
   /// no-op as export is kind of usage
-  fn visit_named_export(&mut self, _: &NamedExport, _: &dyn Node) {}
+  fn visit_named_export(
+    &mut self,
+    _export: &NamedExport,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
+  }
 }
 
 #[cfg(test)]
@@ -1,11 +1,10 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
+use swc_common::BytePos;
 use swc_ecmascript::ast::VarDecl;
 use swc_ecmascript::ast::VarDeclKind;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -20,13 +19,15 @@ impl LintRule for NoVar {
     "no-var"
   }
 
-  fn lint_module(
-    &self,
+  fn docs(&self) -> &'static str {
+    "Disallows the use of the `var` keyword."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoVarVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoVarVisitor::new(context)))
   }
 }
 
@@ -40,16 +41,34 @@ impl NoVarVisitor {
   }
 }
 
-impl Visit for NoVarVisitor {
-  noop_visit_type!();
-
-  fn visit_var_decl(&mut self, var_decl: &VarDecl, _parent: &dyn Node) {
+impl Handler for NoVarVisitor {
+  fn visit_var_decl(&mut self, var_decl: &VarDecl, _traversal: &mut Traversal) {
     if var_decl.kind == VarDeclKind::Var {
-      self.context.add_diagnostic(
-        var_decl.span,
-        "no-var",
-        "`var` keyword is not allowed",
-      );
+      // Only the keyword is reported, if it's where the declaration starts.
+      let lo = var_decl.span.lo();
+      let keyword_span = var_decl.span.with_hi(lo + BytePos(3));
+      let mut diagnostic = if self.context.span_text(keyword_span) == "var" {
+        let mut diagnostic = self.context.create_default_diagnostic(
+          keyword_span,
+          "no-var",
+          "`var` keyword is not allowed",
+        );
+        // Not a fix, as `let` is block scoped and not hoisted.
+        diagnostic.suggestions = vec![self.context.create_suggestion(
+          "Replace `var` with `let`",
+          keyword_span,
+          "let",
+        )];
+        diagnostic
+      } else {
+        self.context.create_default_diagnostic(
+          var_decl.span,
+          "no-var",
+          "`var` keyword is not allowed",
+        )
+      };
+      diagnostic.hint = Some("Use `let` or `const` instead".to_string());
+      self.context.push_diagnostic(diagnostic);
     }
   }
 }
@@ -66,4 +85,59 @@ mod tests {
       0,
     );
   }
+
+  #[test]
+  fn no_var_valid() {
+    assert_lint_ok! {
+      NoVar,
+      "let a = 1; const b = 2;",
+      "for (let i = 0; i < 10; i++) {}",
+      "for (const key in obj) {}",
+      "for (const item of items) {}",
+      "function f() { let variable = 1; return variable; }",
+      "const vars = { var: 1 };",
+    };
+  }
+
+  #[test]
+  fn no_var_invalid() {
+    assert_lint_err!(NoVar, "var a = 1, b = 2;", 1, 0);
+    assert_lint_err!(NoVar, "for (var i = 0; i < 10; i++) {}", 1, 5);
+    assert_lint_err!(NoVar, "for (var key in obj) {}", 1, 5);
+    assert_lint_err!(NoVar, "for (var item of items) {}", 1, 5);
+    assert_lint_err!(NoVar, "function f() {\n  var a = 1;\n}", 2, 2);
+    assert_lint_err!(NoVar, "export var a = 1;", 1, 7);
+    assert_lint_err!(NoVar, "var a = () => { var b = 1; };", [(1, 0), (1, 16)]);
+    assert_lint_err_on_range::<NoVar>("var a = 1;", (1, 0), (1, 3));
+  }
+
+  #[test]
+  fn suggestion() {
+    use crate::diagnostic::apply_suggestion;
+
+    let source = "for (var i = 0; i < 3; i++) {}";
+    let diagnostics = lint_with_rule(NoVar::new(), source);
+    assert!(diagnostics[0].fix.is_none());
+    assert_eq!(
+      apply_suggestion(source, &diagnostics[0], 0).unwrap(),
+      "for (let i = 0; i < 3; i++) {}"
+    );
+
+    let source = "declare var a: number;";
+    let diagnostics = lint_with_rule(NoVar::new(), source);
+    assert_eq!(
+      apply_suggestion(source, &diagnostics[0], 0).unwrap(),
+      "declare let a: number;"
+    );
+  }
+
+  #[test]
+  fn hint() {
+    assert_lint_err_with_hint::<NoVar>(
+      "var a = 1;",
+      1,
+      0,
+      "Use `let` or `const` instead",
+    );
+  }
 }
@@ -1,10 +1,8 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_ecmascript::ast::WithStmt;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -19,13 +17,19 @@ impl LintRule for NoWith {
     "no-with"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows the use of the `with` statement."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoWithVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(NoWithVisitor::new(context)))
   }
 }
 
@@ -39,10 +43,12 @@ impl NoWithVisitor {
   }
 }
 
-impl Visit for NoWithVisitor {
-  noop_visit_type!();
-
-  fn visit_with_stmt(&mut self, with_stmt: &WithStmt, _parent: &dyn Node) {
+impl Handler for NoWithVisitor {
+  fn visit_with_stmt(
+    &mut self,
+    with_stmt: &WithStmt,
+    _traversal: &mut Traversal,
+  ) {
     self.context.add_diagnostic(
       with_stmt.span,
       "no-with",
@@ -54,10 +60,39 @@ impl Visit for NoWithVisitor {
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::linter::LinterBuilder;
+  use crate::swc_util::{self, ModuleKind, SyntaxConfig};
   use crate::test_util::*;
 
+  /// `with` statements are only valid in scripts, as modules are strict.
+  fn lint_script(source: &str) -> Vec<(usize, usize)> {
+    LinterBuilder::default()
+      .syntax_config(SyntaxConfig {
+        kind: ModuleKind::Script,
+        syntax: swc_util::get_default_es_config(),
+        ..SyntaxConfig::default()
+      })
+      .rules(vec![NoWith::new()])
+      .build()
+      .lint("script.js".to_string(), source.to_string())
+      .unwrap()
+      .iter()
+      .map(|d| (d.range.start.line, d.range.start.col))
+      .collect()
+  }
+
   #[test]
   fn no_with() {
     assert_lint_err::<NoWith>("with (someVar) { console.log('asdf'); }", 0)
   }
+
+  #[test]
+  fn no_with_script() {
+    assert_eq!(lint_script("with (a) { b(); }"), vec![(1, 0)]);
+    assert_eq!(
+      lint_script("function f() {\n  with (a) {\n    with (b) {}\n  }\n}"),
+      vec![(2, 2), (3, 4)]
+    );
+    assert!(lint_script("const withValue = a.with;\nobj.with(1);").is_empty());
+  }
 }
@@ -1,13 +1,12 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{walk, Handler, Traversal};
 use std::sync::Arc;
 use swc_ecmascript::ast::{
   ArrayPat, Expr, Ident, Lit, ObjectPat, Pat, TsAsExpr, TsLit, TsType,
   TsTypeAssertion, VarDecl,
 };
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 pub struct PreferAsConst;
 
@@ -20,13 +19,19 @@ impl LintRule for PreferAsConst {
     "prefer-as-const"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Recommends using `as const` over literal type annotations and assertions."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = PreferAsConstVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(PreferAsConstVisitor::new(context)))
   }
 }
 
@@ -69,16 +74,26 @@ impl PreferAsConstVisitor {
   }
 }
 
-impl Visit for PreferAsConstVisitor {
-  fn visit_ts_as_expr(&mut self, as_expr: &TsAsExpr, _parent: &dyn Node) {
+impl Handler for PreferAsConstVisitor {
+  fn visit_types(&self) -> bool {
+    true
+  }
+
+  fn visit_ts_as_expr(
+    &mut self,
+    as_expr: &TsAsExpr,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
     self.compare(&as_expr.type_ann, &as_expr.expr, as_expr.span);
   }
 
   fn visit_ts_type_assertion(
     &mut self,
     type_assertion: &TsTypeAssertion,
-    _parent: &dyn Node,
+    traversal: &mut Traversal,
   ) {
+    traversal.skip_children();
     self.compare(
       &type_assertion.type_ann,
       &type_assertion.expr,
@@ -86,19 +101,20 @@ impl Visit for PreferAsConstVisitor {
     );
   }
 
-  fn visit_var_decl(&mut self, var_decl: &VarDecl, _parent: &dyn Node) {
+  fn visit_var_decl(&mut self, var_decl: &VarDecl, traversal: &mut Traversal) {
+    traversal.skip_children();
     if let Some(init) = &var_decl.decls[0].init {
       match &**init {
         Expr::TsAs(as_expr) => {
-          self.visit_ts_as_expr(&as_expr, _parent);
+          self.visit_ts_as_expr(as_expr, traversal);
           return;
         }
         Expr::Object(object) => {
-          self.visit_object_lit(&object, _parent);
+          walk(self, object);
           return;
         }
         Expr::TsTypeAssertion(type_assert) => {
-          self.visit_ts_type_assertion(&type_assert, _parent);
+          self.visit_ts_type_assertion(type_assert, traversal);
           return;
         }
         _ => {}
@@ -1,6 +1,7 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{walk, Handler, Traversal};
 use std::collections::BTreeMap;
 use std::mem;
 use std::sync::{Arc, Mutex};
@@ -28,17 +29,19 @@ impl LintRule for PreferConst {
     "prefer-const"
   }
 
-  fn lint_module(
-    &self,
-    context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut collector = VariableCollector::new();
-    collector.visit_module(module, module);
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
 
-    let mut visitor =
-      PreferConstVisitor::new(context, mem::take(&mut collector.scopes));
-    visitor.visit_module(module, module);
+  fn docs(&self) -> &'static str {
+    "Recommends declaring variables that are never reassigned with `const`."
+  }
+
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(PreferConstVisitor::new(context)))
   }
 }
 
@@ -476,10 +479,10 @@ struct PreferConstVisitor {
 }
 
 impl PreferConstVisitor {
-  fn new(context: Arc<Context>, scopes: BTreeMap<ScopeRange, Scope>) -> Self {
+  fn new(context: Arc<Context>) -> Self {
     Self {
       context,
-      scopes,
+      scopes: BTreeMap::new(),
       cur_scope: ScopeRange::Global,
     }
   }
@@ -601,8 +604,16 @@ impl PreferConstVisitor {
     // If the declaration isn't found, most likely it means the ident is declared with `var`
     false
   }
+}
+
+impl Handler for PreferConstVisitor {
+  fn visit_module(&mut self, module: &Module, _traversal: &mut Traversal) {
+    let mut collector = VariableCollector::new();
+    collector.visit_module(module, module);
+    self.scopes = mem::take(&mut collector.scopes);
+  }
 
-  fn exit_module(&mut self) {
+  fn exit_module(&mut self, _module: &Module) {
     let mut for_init_vars = BTreeMap::new();
 
     for scope in self.scopes.values() {
@@ -634,23 +645,20 @@ impl PreferConstVisitor {
       self.report(sym, var.span);
     }
   }
-}
-
-impl Visit for PreferConstVisitor {
-  noop_visit_type!();
 
-  fn visit_module(&mut self, module: &Module, _: &dyn Node) {
-    module.visit_children_with(self);
-    self.exit_module();
-  }
-
-  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, _: &dyn Node) {
+  fn visit_assign_expr(
+    &mut self,
+    assign_expr: &AssignExpr,
+    traversal: &mut Traversal,
+  ) {
     // This only handles _nested_ `AssignmentExpression` since not nested `AssignExpression` (i.e. the direct child of
     // `ExpressionStatement`) is already handled by `visit_expr_stmt`. The variables within nested
     // `AssignmentExpression` should be marked as "reassigned" even if it's not been yet initialized, otherwise it
     // would result in false positives.
     // See https://github.com/denoland/deno_lint/issues/358
-    assign_expr.visit_children_with(self);
+    traversal.skip_children();
+    walk(self, &assign_expr.left);
+    walk(self, &assign_expr.right);
 
     let idents: Vec<Ident> = match &assign_expr.left {
       PatOrExpr::Pat(pat) => find_ids(pat), // find_ids doesn't work for Expression
@@ -666,7 +674,12 @@ impl Visit for PreferConstVisitor {
     }
   }
 
-  fn visit_expr_stmt(&mut self, expr_stmt: &ExprStmt, _: &dyn Node) {
+  fn visit_expr_stmt(
+    &mut self,
+    expr_stmt: &ExprStmt,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
     let mut expr = &*expr_stmt.expr;
 
     // Unwrap parentheses
@@ -683,17 +696,23 @@ impl Visit for PreferConstVisitor {
               self.mark_reassigned(ident, false);
             }
             otherwise => {
-              otherwise.visit_children_with(self);
+              walk(self, otherwise);
             }
           },
         };
-        assign_expr.visit_children_with(self);
+        walk(self, &assign_expr.left);
+        walk(self, &assign_expr.right);
       }
-      _ => expr_stmt.visit_children_with(self),
+      _ => walk(self, &*expr_stmt.expr),
     }
   }
 
-  fn visit_update_expr(&mut self, update_expr: &UpdateExpr, _: &dyn Node) {
+  fn visit_update_expr(
+    &mut self,
+    update_expr: &UpdateExpr,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
     match &*update_expr.arg {
       Expr::Ident(ident) => {
         self.mark_reassigned(
@@ -701,174 +720,223 @@ impl Visit for PreferConstVisitor {
           self.declared_outer_scope_or_param_var(ident),
         );
       }
-      otherwise => otherwise.visit_children_with(self),
+      otherwise => walk(self, otherwise),
     }
   }
 
-  fn visit_function(&mut self, function: &Function, _: &dyn Node) {
+  fn visit_function(&mut self, function: &Function, traversal: &mut Traversal) {
+    traversal.skip_children();
     self.with_child_scope(function, |a| {
       if let Some(body) = &function.body {
-        body.visit_children_with(a);
+        walk(a, &body.stmts);
       }
     });
   }
 
-  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, _: &dyn Node) {
+  fn visit_arrow_expr(
+    &mut self,
+    arrow_expr: &ArrowExpr,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
     self.with_child_scope(arrow_expr, |a| match &arrow_expr.body {
       BlockStmtOrExpr::BlockStmt(block_stmt) => {
-        block_stmt.visit_children_with(a);
+        walk(a, &block_stmt.stmts);
       }
       BlockStmtOrExpr::Expr(expr) => {
-        expr.visit_children_with(a);
+        walk(a, &**expr);
       }
     });
   }
 
-  fn visit_block_stmt(&mut self, block_stmt: &BlockStmt, _: &dyn Node) {
-    self.with_child_scope(block_stmt, |a| block_stmt.visit_children_with(a));
+  fn visit_block_stmt(
+    &mut self,
+    block_stmt: &BlockStmt,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
+    self.with_child_scope(block_stmt, |a| walk(a, &block_stmt.stmts));
   }
 
-  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, _: &dyn Node) {
+  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, traversal: &mut Traversal) {
+    traversal.skip_children();
     self.with_child_scope(for_stmt, |a| {
-      for_stmt.init.visit_children_with(a);
-      for_stmt.test.visit_children_with(a);
-      for_stmt.update.visit_children_with(a);
+      walk(a, &for_stmt.init);
+      walk(a, &for_stmt.test);
+      walk(a, &for_stmt.update);
 
       if let Stmt::Block(block_stmt) = &*for_stmt.body {
-        block_stmt.visit_children_with(a);
+        walk(a, &block_stmt.stmts);
       } else {
-        for_stmt.body.visit_children_with(a);
+        walk(a, &*for_stmt.body);
       }
     });
   }
 
-  fn visit_for_of_stmt(&mut self, for_of_stmt: &ForOfStmt, _: &dyn Node) {
+  fn visit_for_of_stmt(
+    &mut self,
+    for_of_stmt: &ForOfStmt,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
     self.with_child_scope(for_of_stmt, |a| {
       match &for_of_stmt.left {
         VarDeclOrPat::VarDecl(var_decl) => {
-          var_decl.visit_with(&for_of_stmt.left, a);
+          walk(a, var_decl);
         }
         VarDeclOrPat::Pat(pat) => {
           a.extract_assign_idents(pat);
         }
       }
 
-      for_of_stmt.right.visit_children_with(a);
+      walk(a, &*for_of_stmt.right);
 
       if let Stmt::Block(block_stmt) = &*for_of_stmt.body {
-        block_stmt.visit_children_with(a);
+        walk(a, &block_stmt.stmts);
       } else {
-        for_of_stmt.body.visit_children_with(a);
+        walk(a, &*for_of_stmt.body);
       }
     });
   }
 
-  fn visit_for_in_stmt(&mut self, for_in_stmt: &ForInStmt, _: &dyn Node) {
+  fn visit_for_in_stmt(
+    &mut self,
+    for_in_stmt: &ForInStmt,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
     self.with_child_scope(for_in_stmt, |a| {
       match &for_in_stmt.left {
         VarDeclOrPat::VarDecl(var_decl) => {
-          var_decl.visit_with(&for_in_stmt.left, a);
+          walk(a, var_decl);
         }
         VarDeclOrPat::Pat(pat) => {
           a.extract_assign_idents(pat);
         }
       }
 
-      for_in_stmt.right.visit_children_with(a);
+      walk(a, &*for_in_stmt.right);
 
       if let Stmt::Block(block_stmt) = &*for_in_stmt.body {
-        block_stmt.visit_children_with(a);
+        walk(a, &block_stmt.stmts);
       } else {
-        for_in_stmt.body.visit_children_with(a);
+        walk(a, &*for_in_stmt.body);
       }
     });
   }
 
-  fn visit_if_stmt(&mut self, if_stmt: &IfStmt, _: &dyn Node) {
+  fn visit_if_stmt(&mut self, if_stmt: &IfStmt, traversal: &mut Traversal) {
+    traversal.skip_children();
     self.with_child_scope(if_stmt, |a| {
-      if_stmt.test.visit_children_with(a);
+      walk(a, &*if_stmt.test);
       // BlockStmt needs special handling to avoid creating a duplicate scope
       if let Stmt::Block(body) = &*if_stmt.cons {
-        body.visit_children_with(a);
+        walk(a, &body.stmts);
       } else {
-        if_stmt.cons.visit_children_with(a);
+        walk(a, &*if_stmt.cons);
       }
     });
 
     if let Some(alt) = &if_stmt.alt {
       self.with_child_scope(alt, |a| {
-        alt.visit_children_with(a);
+        walk(a, &**alt);
       });
     }
   }
 
-  fn visit_while_stmt(&mut self, while_stmt: &WhileStmt, _: &dyn Node) {
+  fn visit_while_stmt(
+    &mut self,
+    while_stmt: &WhileStmt,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
     self.with_child_scope(while_stmt, |a| {
-      while_stmt.test.visit_children_with(a);
+      walk(a, &*while_stmt.test);
       // BlockStmt needs special handling to avoid creating a duplicate scope
       if let Stmt::Block(body) = &*while_stmt.body {
-        body.visit_children_with(a);
+        walk(a, &body.stmts);
       } else {
-        while_stmt.body.visit_children_with(a);
+        walk(a, &*while_stmt.body);
       }
     });
   }
 
-  fn visit_do_while_stmt(&mut self, do_while_stmt: &DoWhileStmt, _: &dyn Node) {
+  fn visit_do_while_stmt(
+    &mut self,
+    do_while_stmt: &DoWhileStmt,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
     self.with_child_scope(do_while_stmt, |a| {
       // BlockStmt needs special handling to avoid creating a duplicate scope
       if let Stmt::Block(body) = &*do_while_stmt.body {
-        body.visit_children_with(a);
+        walk(a, &body.stmts);
       } else {
-        do_while_stmt.body.visit_children_with(a);
+        walk(a, &*do_while_stmt.body);
       }
-      do_while_stmt.test.visit_children_with(a);
+      walk(a, &*do_while_stmt.test);
     });
   }
 
-  fn visit_with_stmt(&mut self, with_stmt: &WithStmt, _: &dyn Node) {
+  fn visit_with_stmt(
+    &mut self,
+    with_stmt: &WithStmt,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
     self.with_child_scope(with_stmt, |a| {
-      with_stmt.obj.visit_children_with(a);
+      walk(a, &*with_stmt.obj);
       // BlockStmt needs special handling to avoid creating a duplicate scope
       if let Stmt::Block(body) = &*with_stmt.body {
-        body.visit_children_with(a);
+        walk(a, &body.stmts);
       } else {
-        with_stmt.body.visit_children_with(a);
+        walk(a, &*with_stmt.body);
       }
     });
   }
 
-  fn visit_catch_clause(&mut self, catch_clause: &CatchClause, _: &dyn Node) {
+  fn visit_catch_clause(
+    &mut self,
+    catch_clause: &CatchClause,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
     self.with_child_scope(catch_clause, |a| {
       if let Some(param) = &catch_clause.param {
-        param.visit_children_with(a);
+        walk(a, param);
       }
-      catch_clause.body.visit_children_with(a);
+      walk(a, &catch_clause.body.stmts);
     });
   }
 
-  fn visit_class(&mut self, class: &Class, _: &dyn Node) {
+  fn visit_class(&mut self, class: &Class, traversal: &mut Traversal) {
+    traversal.skip_children();
     for decorator in &class.decorators {
-      decorator.visit_children_with(self);
+      walk(self, decorator);
     }
     if let Some(super_class) = &class.super_class {
-      super_class.visit_children_with(self);
+      walk(self, &**super_class);
     }
     self.with_child_scope(class, |a| {
       for member in &class.body {
-        member.visit_children_with(a);
+        walk(a, member);
       }
     });
   }
 
-  fn visit_constructor(&mut self, constructor: &Constructor, _: &dyn Node) {
+  fn visit_constructor(
+    &mut self,
+    constructor: &Constructor,
+    traversal: &mut Traversal,
+  ) {
+    traversal.skip_children();
     self.with_child_scope(constructor, |a| {
       for param in &constructor.params {
-        param.visit_children_with(a);
+        walk(a, param);
       }
 
       if let Some(body) = &constructor.body {
-        body.visit_children_with(a);
+        walk(a, &body.stmts);
       }
     });
   }
@@ -1787,7 +1855,7 @@ var foo = function() {
       r#"/*eslint use-x:error*/ { let x = 1 }"#,
       29,
     );
-    assert_lint_err_n::<PreferConst>(r#"let { foo, bar } = baz;"#, vec![11, 6]);
+    assert_lint_err_n::<PreferConst>(r#"let { foo, bar } = baz;"#, vec![6, 11]);
     assert_lint_err::<PreferConst>(r#"const x = [1,2]; let [,y] = x;"#, 23);
     assert_lint_err_n::<PreferConst>(
       r#"const x = [1,2,3]; let [y,,z] = x;"#,
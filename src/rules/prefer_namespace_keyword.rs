@@ -1,10 +1,9 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use regex::Regex;
 use swc_ecmascript::ast::{TsModuleDecl, TsModuleName};
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -19,13 +18,19 @@ impl LintRule for PreferNamespaceKeyword {
     "prefer-namespace-keyword"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Recommends using the `namespace` keyword over `module` to declare custom TypeScript modules."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = PreferNamespaceKeywordVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(PreferNamespaceKeywordVisitor::new(context)))
   }
 }
 
@@ -39,13 +44,18 @@ impl PreferNamespaceKeywordVisitor {
   }
 }
 
-impl Visit for PreferNamespaceKeywordVisitor {
+impl Handler for PreferNamespaceKeywordVisitor {
+  fn visit_types(&self) -> bool {
+    true
+  }
+
   fn visit_ts_module_decl(
     &mut self,
     mod_decl: &TsModuleDecl,
-    parent: &dyn Node,
+    traversal: &mut Traversal,
   ) {
     if let TsModuleName::Str(_) = &mod_decl.id {
+      traversal.skip_children();
       return;
     }
     lazy_static! {
@@ -69,9 +79,6 @@ impl Visit for PreferNamespaceKeywordVisitor {
         )
       }
     }
-    for stmt in &mod_decl.body {
-      self.visit_ts_namespace_body(stmt, parent)
-    }
   }
 }
 
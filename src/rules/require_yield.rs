@@ -1,6 +1,7 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_ecmascript::ast::ClassMethod;
 use swc_ecmascript::ast::FnDecl;
 use swc_ecmascript::ast::FnExpr;
@@ -8,9 +9,6 @@ use swc_ecmascript::ast::Function;
 use swc_ecmascript::ast::MethodProp;
 use swc_ecmascript::ast::PrivateMethod;
 use swc_ecmascript::ast::YieldExpr;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -25,13 +23,19 @@ impl LintRule for RequireYield {
     "require-yield"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Disallows generator functions that don't contain `yield`."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = RequireYieldVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(RequireYieldVisitor::new(context)))
   }
 }
 
@@ -73,50 +77,66 @@ impl RequireYieldVisitor {
   }
 }
 
-impl Visit for RequireYieldVisitor {
-  noop_visit_type!();
-
-  fn visit_yield_expr(&mut self, _yield_expr: &YieldExpr, _parent: &dyn Node) {
+impl Handler for RequireYieldVisitor {
+  fn visit_yield_expr(
+    &mut self,
+    _yield_expr: &YieldExpr,
+    _traversal: &mut Traversal,
+  ) {
     if let Some(last) = self.yield_stack.last_mut() {
       *last += 1;
     }
   }
 
-  fn visit_fn_decl(&mut self, fn_decl: &FnDecl, parent: &dyn Node) {
+  fn visit_fn_decl(&mut self, fn_decl: &FnDecl, _traversal: &mut Traversal) {
     self.enter_function(&fn_decl.function);
-    swc_ecmascript::visit::visit_fn_decl(self, fn_decl, parent);
+  }
+
+  fn exit_fn_decl(&mut self, fn_decl: &FnDecl) {
     self.exit_function(&fn_decl.function);
   }
 
-  fn visit_fn_expr(&mut self, fn_expr: &FnExpr, parent: &dyn Node) {
+  fn visit_fn_expr(&mut self, fn_expr: &FnExpr, _traversal: &mut Traversal) {
     self.enter_function(&fn_expr.function);
-    swc_ecmascript::visit::visit_fn_expr(self, fn_expr, parent);
+  }
+
+  fn exit_fn_expr(&mut self, fn_expr: &FnExpr) {
     self.exit_function(&fn_expr.function);
   }
 
   fn visit_class_method(
     &mut self,
     class_method: &ClassMethod,
-    parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     self.enter_function(&class_method.function);
-    swc_ecmascript::visit::visit_class_method(self, class_method, parent);
+  }
+
+  fn exit_class_method(&mut self, class_method: &ClassMethod) {
     self.exit_function(&class_method.function);
   }
 
   fn visit_private_method(
     &mut self,
     private_method: &PrivateMethod,
-    parent: &dyn Node,
+    _traversal: &mut Traversal,
   ) {
     self.enter_function(&private_method.function);
-    swc_ecmascript::visit::visit_private_method(self, private_method, parent);
+  }
+
+  fn exit_private_method(&mut self, private_method: &PrivateMethod) {
     self.exit_function(&private_method.function);
   }
 
-  fn visit_method_prop(&mut self, method_prop: &MethodProp, parent: &dyn Node) {
+  fn visit_method_prop(
+    &mut self,
+    method_prop: &MethodProp,
+    _traversal: &mut Traversal,
+  ) {
     self.enter_function(&method_prop.function);
-    swc_ecmascript::visit::visit_method_prop(self, method_prop, parent);
+  }
+
+  fn exit_method_prop(&mut self, method_prop: &MethodProp) {
     self.exit_function(&method_prop.function);
   }
 }
@@ -1,10 +1,8 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::handler::{Handler, Traversal};
 use swc_ecmascript::ast::VarDecl;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
@@ -19,13 +17,15 @@ impl LintRule for SingleVarDeclarator {
     "single-var-declarator"
   }
 
-  fn lint_module(
-    &self,
+  fn docs(&self) -> &'static str {
+    "Disallows multiple variable declarations in a single statement."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = SingleVarDeclaratorVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(SingleVarDeclaratorVisitor::new(context)))
   }
 }
 
@@ -39,10 +39,9 @@ impl SingleVarDeclaratorVisitor {
   }
 }
 
-impl Visit for SingleVarDeclaratorVisitor {
-  noop_visit_type!();
-
-  fn visit_var_decl(&mut self, var_decl: &VarDecl, _parent: &dyn Node) {
+impl Handler for SingleVarDeclaratorVisitor {
+  fn visit_var_decl(&mut self, var_decl: &VarDecl, traversal: &mut Traversal) {
+    traversal.skip_children();
     if var_decl.decls.len() > 1 {
       self.context.add_diagnostic(
         var_decl.span,
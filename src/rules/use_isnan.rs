@@ -1,9 +1,8 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
+use crate::handler::{Handler, Traversal};
+use swc_ecmascript::ast::{BinExpr, BinaryOp, Expr, ExprOrSuper, SwitchStmt};
 
 use std::sync::Arc;
 
@@ -18,13 +17,19 @@ impl LintRule for UseIsNaN {
     "use-isnan"
   }
 
-  fn lint_module(
-    &self,
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    "Requires calls to `isNaN()` when checking for `NaN`."
+  }
+
+  fn handler<'a>(
+    &'a self,
     context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = UseIsNaNVisitor::new(context);
-    visitor.visit_module(module, module);
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(UseIsNaNVisitor::new(context)))
   }
 }
 
@@ -38,74 +43,73 @@ impl UseIsNaNVisitor {
   }
 }
 
-fn is_nan_identifier(ident: &swc_ecmascript::ast::Ident) -> bool {
-  ident.sym == *"NaN"
+/// Whether `expr` is `NaN` or `Number.NaN`.
+fn is_nan(expr: &Expr) -> bool {
+  match expr {
+    Expr::Ident(ident) => ident.sym == *"NaN",
+    Expr::Member(member) if !member.computed => {
+      let is_number = match &member.obj {
+        ExprOrSuper::Expr(obj) => match &**obj {
+          Expr::Ident(obj) => obj.sym == *"Number",
+          _ => false,
+        },
+        ExprOrSuper::Super(_) => false,
+      };
+      let is_nan_prop = match &*member.prop {
+        Expr::Ident(prop) => prop.sym == *"NaN",
+        _ => false,
+      };
+      is_number && is_nan_prop
+    }
+    Expr::Paren(paren) => is_nan(&paren.expr),
+    _ => false,
+  }
 }
 
-impl Visit for UseIsNaNVisitor {
-  noop_visit_type!();
-
-  fn visit_bin_expr(
-    &mut self,
-    bin_expr: &swc_ecmascript::ast::BinExpr,
-    _parent: &dyn Node,
-  ) {
-    if bin_expr.op == swc_ecmascript::ast::BinaryOp::EqEq
-      || bin_expr.op == swc_ecmascript::ast::BinaryOp::NotEq
-      || bin_expr.op == swc_ecmascript::ast::BinaryOp::EqEqEq
-      || bin_expr.op == swc_ecmascript::ast::BinaryOp::NotEqEq
-      || bin_expr.op == swc_ecmascript::ast::BinaryOp::Lt
-      || bin_expr.op == swc_ecmascript::ast::BinaryOp::LtEq
-      || bin_expr.op == swc_ecmascript::ast::BinaryOp::Gt
-      || bin_expr.op == swc_ecmascript::ast::BinaryOp::GtEq
-    {
-      if let swc_ecmascript::ast::Expr::Ident(ident) = &*bin_expr.left {
-        if is_nan_identifier(&ident) {
-          self.context.add_diagnostic(
-            bin_expr.span,
-            "use-isnan",
-            "Use the isNaN function to compare with NaN",
-          );
-        }
-      }
-      if let swc_ecmascript::ast::Expr::Ident(ident) = &*bin_expr.right {
-        if is_nan_identifier(&ident) {
-          self.context.add_diagnostic(
-            bin_expr.span,
-            "use-isnan",
-            "Use the isNaN function to compare with NaN",
-          );
-        }
-      }
+impl Handler for UseIsNaNVisitor {
+  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, _traversal: &mut Traversal) {
+    let is_comparison = matches!(
+      bin_expr.op,
+      BinaryOp::EqEq
+        | BinaryOp::NotEq
+        | BinaryOp::EqEqEq
+        | BinaryOp::NotEqEq
+        | BinaryOp::Lt
+        | BinaryOp::LtEq
+        | BinaryOp::Gt
+        | BinaryOp::GtEq
+    );
+    if is_comparison && (is_nan(&bin_expr.left) || is_nan(&bin_expr.right)) {
+      self.context.add_diagnostic_with_hint(
+        bin_expr.span,
+        "use-isnan",
+        "Use the isNaN function to compare with NaN",
+        "Use `Number.isNaN(value)` instead, as `NaN` is not equal to anything",
+      );
     }
   }
 
   fn visit_switch_stmt(
     &mut self,
-    switch_stmt: &swc_ecmascript::ast::SwitchStmt,
-    _parent: &dyn Node,
+    switch_stmt: &SwitchStmt,
+    _traversal: &mut Traversal,
   ) {
-    if let swc_ecmascript::ast::Expr::Ident(ident) = &*switch_stmt.discriminant
-    {
-      if is_nan_identifier(&ident) {
-        self.context.add_diagnostic(
-          switch_stmt.span,
-          "use-isnan",
-          "'switch(NaN)' can never match a case clause. Use Number.isNaN instead of the switch",
-        );
-      }
+    if is_nan(&switch_stmt.discriminant) {
+      self.context.add_diagnostic(
+        switch_stmt.span,
+        "use-isnan",
+        "'switch(NaN)' can never match a case clause. Use Number.isNaN instead of the switch",
+      );
     }
 
     for case in &switch_stmt.cases {
       if let Some(expr) = &case.test {
-        if let swc_ecmascript::ast::Expr::Ident(ident) = &**expr {
-          if is_nan_identifier(ident) {
-            self.context.add_diagnostic(
-              case.span,
-              "use-isnan",
-              "'case NaN' can never match. Use Number.isNaN before the switch",
-            );
-          }
+        if is_nan(expr) {
+          self.context.add_diagnostic(
+            case.span,
+            "use-isnan",
+            "'case NaN' can never match. Use Number.isNaN before the switch",
+          );
         }
       }
     }
@@ -132,4 +136,52 @@ switch (NaN) {
       vec![(2, 0), (3, 2)],
     );
   }
+
+  #[test]
+  fn use_isnan_valid() {
+    assert_lint_ok! {
+      UseIsNaN,
+      "Number.isNaN(x);",
+      "isNaN(x);",
+      "x === Number.NaNValue;",
+      "x === foo.NaN;",
+      "x + NaN;",
+      "switch (x) { case 1: break; }",
+    };
+  }
+
+  #[test]
+  fn use_isnan_invalid() {
+    assert_lint_err::<UseIsNaN>("x === NaN", 0);
+    assert_lint_err::<UseIsNaN>("NaN == x", 0);
+    assert_lint_err::<UseIsNaN>("x !== Number.NaN", 0);
+    assert_lint_err::<UseIsNaN>("Number.NaN != x", 0);
+    assert_lint_err::<UseIsNaN>("x < (NaN)", 0);
+    assert_lint_err::<UseIsNaN>("NaN === NaN", 0);
+    assert_lint_err::<UseIsNaN>("const isNaN = x === NaN;", 14);
+    assert_lint_err::<UseIsNaN>("switch (Number.NaN) {}", 0);
+    assert_lint_err::<UseIsNaN>("switch (x) { case Number.NaN: break; }", 13);
+    assert_lint_err!(
+      UseIsNaN,
+      "function foo(x) {\n  if (x == NaN) {}\n}",
+      2,
+      6
+    );
+    assert_lint_err!(
+      UseIsNaN,
+      "switch (x) {\n  case 1:\n    switch (NaN) {}\n}",
+      3,
+      4
+    );
+  }
+
+  #[test]
+  fn use_isnan_hint() {
+    assert_lint_err_with_hint::<UseIsNaN>(
+      "x === NaN",
+      1,
+      0,
+      "Use `Number.isNaN(value)` instead, as `NaN` is not equal to anything",
+    );
+  }
 }
@@ -1,29 +1,74 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
-use super::{Context, LintRule};
+use super::{Context, LintRule, RuleConfigError};
+use crate::handler::Handler;
 use swc_common::Spanned;
+use swc_ecmascript::ast::BinExpr;
 use swc_ecmascript::ast::BinaryOp::{EqEq, EqEqEq, NotEq, NotEqEq};
-use swc_ecmascript::ast::Expr::{Lit, Unary};
+use swc_ecmascript::ast::Expr::{Lit, Tpl, Unary};
 use swc_ecmascript::ast::Lit::Str;
 use swc_ecmascript::ast::UnaryOp::TypeOf;
-use swc_ecmascript::ast::{BinExpr, Module};
-use swc_ecmascript::visit::{noop_visit_type, Node, Visit};
 
 use std::sync::Arc;
 
-pub struct ValidTypeof;
+pub struct ValidTypeof {
+  require_string_literals: bool,
+}
 
 impl LintRule for ValidTypeof {
   fn new() -> Box<Self> {
-    Box::new(ValidTypeof)
+    Box::new(ValidTypeof {
+      require_string_literals: false,
+    })
+  }
+
+  fn new_with_config(
+    &self,
+    config: serde_json::Value,
+  ) -> Result<Box<dyn LintRule>, RuleConfigError> {
+    let require_string_literals = match config.get("requireStringLiterals") {
+      None => false,
+      Some(serde_json::Value::Bool(require)) => *require,
+      Some(value) => {
+        return Err(RuleConfigError {
+          code: "valid-typeof".to_string(),
+          message: format!(
+            "expected requireStringLiterals to be a boolean, got {}",
+            value
+          ),
+        })
+      }
+    };
+    Ok(Box::new(ValidTypeof {
+      require_string_literals,
+    }))
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(serde_json::json!({
+      "type": "object",
+      "properties": {
+        "requireStringLiterals": { "type": "boolean" }
+      },
+      "additionalProperties": false
+    }))
   }
 
   fn code(&self) -> &'static str {
     "valid-typeof"
   }
 
-  fn lint_module(&self, context: Arc<Context>, module: &Module) {
-    let mut visitor = ValidTypeofVisitor::new(context);
-    visitor.visit_module(module, module);
+  fn tags(&self) -> &[&'static str] {
+    &["recommended"]
+  }
+
+  fn handler<'a>(
+    &'a self,
+    context: Arc<Context>,
+  ) -> Option<Box<dyn Handler + 'a>> {
+    Some(Box::new(ValidTypeofVisitor::new(
+      context,
+      self.require_string_literals,
+    )))
   }
 
   fn docs(&self) -> &'static str {
@@ -39,7 +84,9 @@ When used with a value the `typeof` operator returns one of the following string
 - `"symbol"`
 - `"bigint"`
 
-This rule disallows comparison with anything other than one of these string literals when using the `typeof` operator, as this likely represents a typing mistake in the string. The rule also disallows comparing the result of a `typeof` operation with any non-string literal value, such as `undefined`, which can represent an inadvertent use of a keyword instead of a string. This includes comparing against string variables even if they contain one of the above values as this cannot be guaranteed. An exception to this is comparing the results of two `typeof` operations as these are both guaranteed to return on of the above strings.
+This rule disallows comparison with any other string literal when using the `typeof` operator, as this likely represents a typing mistake in the string.
+
+With `{ "requireStringLiterals": true }`, the rule also disallows comparing the result of a `typeof` operation with any non-string literal value, such as `undefined`, which can represent an inadvertent use of a keyword instead of a string. This includes comparing against string variables even if they contain one of the above values as this cannot be guaranteed. An exception to this is comparing the results of two `typeof` operations as these are both guaranteed to return on of the above strings.
 
 ### Invalid:
 ```typescript
@@ -54,6 +101,7 @@ typeof bar != "nunber"
 ```typescript
 typeof bar !== "fucntion"
 ```
+With `requireStringLiterals`:
 ```typescript
 typeof foo === undefined
 ```
@@ -85,18 +133,20 @@ typeof bar === typeof qux
 
 struct ValidTypeofVisitor {
   context: Arc<Context>,
+  require_string_literals: bool,
 }
 
 impl ValidTypeofVisitor {
-  fn new(context: Arc<Context>) -> Self {
-    Self { context }
+  fn new(context: Arc<Context>, require_string_literals: bool) -> Self {
+    Self {
+      context,
+      require_string_literals,
+    }
   }
 }
 
-impl Visit for ValidTypeofVisitor {
-  noop_visit_type!();
-
-  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, _parent: &dyn Node) {
+impl Handler for ValidTypeofVisitor {
+  fn exit_bin_expr(&mut self, bin_expr: &BinExpr) {
     if !bin_expr.is_eq_expr() {
       return;
     }
@@ -105,24 +155,21 @@ impl Visit for ValidTypeofVisitor {
       (Unary(unary), operand) | (operand, Unary(unary))
         if unary.op == TypeOf =>
       {
-        match operand {
-          Unary(unary) if unary.op == TypeOf => {}
-          Lit(Str(str)) => {
-            if !is_valid_typeof_string(&str.value) {
-              self.context.add_diagnostic(
-                str.span,
-                "valid-typeof",
-                "Invalid typeof comparison value",
-              );
-            }
-          }
-          _ => {
-            self.context.add_diagnostic(
-              operand.span(),
-              "valid-typeof",
-              "Invalid typeof comparison value",
-            );
+        let is_valid = match operand {
+          Unary(unary) if unary.op == TypeOf => true,
+          Lit(Str(str)) => is_valid_typeof_string(&str.value),
+          // Templates without expressions have a single quasi.
+          Tpl(tpl) if tpl.exprs.is_empty() => {
+            is_valid_typeof_string(&tpl.quasis[0].raw.value)
           }
+          _ => !self.require_string_literals,
+        };
+        if !is_valid {
+          self.context.add_diagnostic(
+            operand.span(),
+            "valid-typeof",
+            "Invalid typeof comparison value",
+          );
         }
       }
       _ => {}
@@ -180,9 +227,58 @@ typeof bar == "undefined"
   }
 
   #[test]
-  fn it_fails_not_using_strings() {
-    assert_lint_err::<ValidTypeof>(r#"typeof foo === undefined"#, 15);
-    assert_lint_err::<ValidTypeof>(r#"typeof bar == Object"#, 14);
-    assert_lint_err::<ValidTypeof>(r#"typeof baz === anotherVariable"#, 15);
+  fn it_passes_not_using_strings_by_default() {
+    assert_lint_ok! {
+      ValidTypeof,
+      "typeof foo === undefined",
+      "typeof bar == Object",
+      "typeof baz === anotherVariable",
+    };
+  }
+
+  #[test]
+  fn it_checks_template_literals_and_nested_comparisons() {
+    assert_lint_ok::<ValidTypeof>("typeof foo === `string`");
+    assert_lint_err::<ValidTypeof>("typeof foo === `strnig`", 15);
+    assert_lint_err::<ValidTypeof>(r#""nunber" !== typeof foo"#, 0);
+    assert_lint_err::<ValidTypeof>(
+      r#"const isString = (typeof foo === "strnig") === true;"#,
+      33,
+    );
+    assert_lint_err!(
+      ValidTypeof,
+      "function f(x) {\n  return typeof x == \"obejct\";\n}",
+      2,
+      21
+    );
+  }
+
+  #[test]
+  fn it_fails_not_using_strings_with_require_string_literals() {
+    let config = || serde_json::json!({ "requireStringLiterals": true });
+    assert_lint_err_with_config::<ValidTypeof>(
+      r#"typeof foo === undefined"#,
+      config(),
+      15,
+    );
+    assert_lint_err_with_config::<ValidTypeof>(
+      r#"typeof bar == Object"#,
+      config(),
+      14,
+    );
+    assert_lint_err_with_config::<ValidTypeof>(
+      r#"typeof baz === anotherVariable"#,
+      config(),
+      15,
+    );
+    assert_lint_err_with_config::<ValidTypeof>(
+      r#"typeof foo == 5"#,
+      config(),
+      14,
+    );
+    assert_lint_ok_with_config::<ValidTypeof>(
+      "typeof bar === typeof qux;\ntypeof foo === \"string\";",
+      config(),
+    );
   }
 }
@@ -1,11 +1,13 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use swc_atoms::JsWord;
-use swc_common::DUMMY_SP;
+use swc_common::{Span, DUMMY_SP};
 use swc_ecmascript::ast::{
-  ArrowExpr, BlockStmt, BlockStmtOrExpr, CatchClause, ClassDecl, ClassExpr,
-  DoWhileStmt, Expr, FnDecl, ForInStmt, ForOfStmt, ForStmt, Function, Ident,
-  ImportDefaultSpecifier, ImportNamedSpecifier, ImportStarAsSpecifier, Invalid,
-  Module, Param, Pat, SwitchStmt, VarDecl, VarDeclKind, WhileStmt, WithStmt,
+  ArrowExpr, AssignExpr, BlockStmt, BlockStmtOrExpr, CatchClause, ClassDecl,
+  ClassExpr, ClassProp, DoWhileStmt, ExportSpecifier, Expr, FnDecl, ForInStmt,
+  ForOfStmt, ForStmt, Function, Ident, ImportDefaultSpecifier,
+  ImportNamedSpecifier, ImportStarAsSpecifier, Invalid, MemberExpr, Module,
+  NamedExport, ObjectPatProp, Param, Pat, PatOrExpr, Prop, PropName,
+  SwitchStmt, VarDecl, VarDeclKind, WhileStmt, WithStmt,
 };
 use swc_ecmascript::utils::find_ids;
 use swc_ecmascript::utils::ident::IdentLike;
@@ -18,6 +20,8 @@ use swc_ecmascript::visit::VisitWith;
 pub struct Scope {
   vars: HashMap<Id, Var>,
   symbols: HashMap<JsWord, Vec<Id>>,
+  references: Vec<Reference>,
+  globals: HashSet<JsWord>,
 }
 
 impl Scope {
@@ -30,15 +34,70 @@ impl Scope {
   pub fn var(&self, id: &Id) -> Option<&Var> {
     self.vars.get(id)
   }
+
+  /// All references to variables, in the order they are visited.
+  #[allow(dead_code)]
+  pub fn references(&self) -> &[Reference] {
+    &self.references
+  }
+
+  /// References resolved to the variable with given id.
+  #[allow(dead_code)]
+  pub fn references_to<'a>(
+    &'a self,
+    id: &'a Id,
+  ) -> impl Iterator<Item = &'a Reference> {
+    self.references.iter().filter(move |r| &r.id == id)
+  }
+
+  /// True if `sym` is a predefined global. Note that it may still be
+  /// shadowed by a declared variable.
+  pub fn is_global(&self, sym: &JsWord) -> bool {
+    self.globals.contains(sym)
+  }
+
+  /// References not resolved to any declared variable, but to a global.
+  #[allow(dead_code)]
+  pub fn global_references(&self) -> impl Iterator<Item = &Reference> {
+    self.references.iter().filter(move |r| {
+      !self.vars.contains_key(&r.id) && self.is_global(&r.id.0)
+    })
+  }
+
+  /// References not resolved to any declared variable or global.
+  #[allow(dead_code)]
+  pub fn unresolved_references(&self) -> impl Iterator<Item = &Reference> {
+    self.references.iter().filter(move |r| {
+      !self.vars.contains_key(&r.id) && !self.is_global(&r.id.0)
+    })
+  }
 }
 
 #[derive(Debug)]
 pub struct Var {
   path: Vec<ScopeKind>,
   kind: BindingKind,
+  span: Span,
+}
+
+/// Usage of a variable. As the module is processed by the resolver,
+/// a reference points to its variable by having the same id.
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct Reference {
+  pub id: Id,
+  pub span: Span,
+  /// True if the variable is assigned to.
+  pub is_write: bool,
 }
 
 impl Var {
+  /// Span of the declared identifier.
+  #[allow(dead_code)]
+  pub fn span(&self) -> Span {
+    self.span
+  }
+
   /// Empty path means root scope.
   #[allow(dead_code)]
   pub fn path(&self) -> &[ScopeKind] {
@@ -75,10 +134,13 @@ pub enum ScopeKind {
   Catch,
 }
 
-pub fn analyze(module: &Module) -> Scope {
+/// Analyzes `module` with `globals` as the predefined bindings.
+pub fn analyze(module: &Module, globals: HashSet<JsWord>) -> Scope {
   let mut scope = Scope {
     vars: Default::default(),
     symbols: Default::default(),
+    references: Default::default(),
+    globals,
   };
   let mut path = vec![];
 
@@ -99,26 +161,66 @@ struct Analyzer<'a> {
 }
 
 impl Analyzer<'_> {
-  fn declare_id(&mut self, kind: BindingKind, i: Id) {
+  fn declare(&mut self, kind: BindingKind, i: &Ident) {
+    let id = i.to_id();
     self.scope.vars.insert(
-      i.clone(),
+      id.clone(),
       Var {
         kind,
         path: self.path.clone(),
+        span: i.span,
       },
     );
-    self.scope.symbols.entry(i.0.clone()).or_default().push(i);
+    self.scope.symbols.entry(id.0.clone()).or_default().push(id);
   }
 
-  fn declare(&mut self, kind: BindingKind, i: &Ident) {
-    self.declare_id(kind, i.to_id());
+  fn declare_pat(&mut self, kind: BindingKind, pat: &Pat) {
+    let idents: Vec<Ident> = find_ids(pat);
+
+    for ident in idents {
+      self.declare(kind, &ident);
+    }
+    self.visit_pat_exprs(pat);
   }
 
-  fn declare_pat(&mut self, kind: BindingKind, pat: &Pat) {
-    let ids: Vec<Id> = find_ids(pat);
+  fn reference(&mut self, i: &Ident, is_write: bool) {
+    self.scope.references.push(Reference {
+      id: i.to_id(),
+      span: i.span,
+      is_write,
+    });
+  }
 
-    for id in ids {
-      self.declare_id(kind, id);
+  /// Visits default values and computed keys of a binding pattern,
+  /// without treating the bound names as references.
+  fn visit_pat_exprs(&mut self, pat: &Pat) {
+    match pat {
+      Pat::Assign(assign) => {
+        self.visit_pat_exprs(&assign.left);
+        assign.right.visit_with(pat, self);
+      }
+      Pat::Array(array) => {
+        for elem in array.elems.iter().flatten() {
+          self.visit_pat_exprs(elem);
+        }
+      }
+      Pat::Object(object) => {
+        for prop in &object.props {
+          match prop {
+            ObjectPatProp::KeyValue(kv) => {
+              kv.key.visit_with(pat, self);
+              self.visit_pat_exprs(&kv.value);
+            }
+            ObjectPatProp::Assign(assign) => {
+              assign.value.visit_with(pat, self);
+            }
+            ObjectPatProp::Rest(rest) => self.visit_pat_exprs(&rest.arg),
+          }
+        }
+      }
+      Pat::Rest(rest) => self.visit_pat_exprs(&rest.arg),
+      Pat::Expr(expr) => expr.visit_with(pat, self),
+      Pat::Ident(_) | Pat::Invalid(_) => {}
     }
   }
 
@@ -143,7 +245,79 @@ impl Analyzer<'_> {
 
 impl Visit for Analyzer<'_> {
   fn visit_arrow_expr(&mut self, n: &ArrowExpr, _: &dyn Node) {
-    self.with(ScopeKind::Arrow, |a| n.visit_children_with(a))
+    self.with(ScopeKind::Arrow, |a| {
+      for param in &n.params {
+        a.declare_pat(BindingKind::Param, param);
+      }
+      n.body.visit_with(n, a);
+    })
+  }
+
+  fn visit_expr(&mut self, n: &Expr, _: &dyn Node) {
+    match n {
+      Expr::Ident(i) => self.reference(i, false),
+      _ => n.visit_children_with(self),
+    }
+  }
+
+  /// Assignment targets are written, not read.
+  fn visit_pat(&mut self, n: &Pat, _: &dyn Node) {
+    let idents: Vec<Ident> = find_ids(n);
+    for ident in idents {
+      self.reference(&ident, true);
+    }
+    self.visit_pat_exprs(n);
+  }
+
+  fn visit_assign_expr(&mut self, n: &AssignExpr, _: &dyn Node) {
+    match &n.left {
+      PatOrExpr::Pat(pat) => pat.visit_with(n, self),
+      PatOrExpr::Expr(expr) => match &**expr {
+        Expr::Ident(i) => self.reference(i, true),
+        expr => expr.visit_with(n, self),
+      },
+    }
+    n.right.visit_with(n, self);
+  }
+
+  fn visit_member_expr(&mut self, n: &MemberExpr, _: &dyn Node) {
+    n.obj.visit_with(n, self);
+    if n.computed {
+      n.prop.visit_with(n, self);
+    }
+  }
+
+  fn visit_prop(&mut self, n: &Prop, _: &dyn Node) {
+    match n {
+      Prop::Shorthand(i) => self.reference(i, false),
+      _ => n.visit_children_with(self),
+    }
+  }
+
+  fn visit_prop_name(&mut self, n: &PropName, _: &dyn Node) {
+    if let PropName::Computed(computed) = n {
+      computed.expr.visit_with(n, self);
+    }
+  }
+
+  fn visit_class_prop(&mut self, n: &ClassProp, _: &dyn Node) {
+    n.decorators.visit_with(n, self);
+    if n.computed {
+      n.key.visit_with(n, self);
+    }
+    n.value.visit_with(n, self);
+  }
+
+  fn visit_named_export(&mut self, n: &NamedExport, _: &dyn Node) {
+    // Specifiers of `export { a } from "./mod.ts"` aren't local.
+    if n.src.is_some() {
+      return;
+    }
+    for specifier in &n.specifiers {
+      if let ExportSpecifier::Named(named) = specifier {
+        self.reference(&named.orig, false);
+      }
+    }
   }
 
   /// Overriden not to add ScopeKind::Block
@@ -222,6 +396,7 @@ impl Visit for Analyzer<'_> {
   }
 
   fn visit_param(&mut self, n: &Param, _: &dyn Node) {
+    n.decorators.visit_with(n, self);
     self.declare_pat(BindingKind::Param, &n.pat);
   }
 
@@ -308,7 +483,11 @@ mod tests {
       ast_parser.parse_module("file_name.ts", syntax, source_code);
     let module = parse_result.unwrap();
 
-    analyze(&module)
+    let globals = ["console"]
+      .iter()
+      .map(|g| swc_atoms::JsWord::from(*g))
+      .collect();
+    analyze(&module, globals)
   }
 
   fn id(scope: &Scope, s: &str) -> Id {
@@ -370,4 +549,138 @@ try {
     assert_eq!(var(&scope, "e").kind(), BindingKind::CatchClause);
     assert_eq!(var(&scope, "e").path(), &[]);
   }
+
+  fn reference_ids(scope: &Scope, s: &str) -> Vec<Id> {
+    scope
+      .references()
+      .iter()
+      .filter(|r| &*r.id.0 == s)
+      .map(|r| r.id.clone())
+      .collect()
+  }
+
+  #[test]
+  fn shadowing() {
+    let scope = test_scope(
+      r#"
+const a = 1;
+function foo() {
+  const a = 2;
+  return a;
+}
+a;
+"#,
+    );
+    let ids = scope.ids_with_symbol(&"a".into()).unwrap();
+    assert_eq!(ids.len(), 2);
+    let refs = reference_ids(&scope, "a");
+    assert_eq!(refs.len(), 2);
+    assert_eq!(scope.var(&refs[0]).unwrap().path(), &[ScopeKind::Function]);
+    assert_eq!(scope.var(&refs[1]).unwrap().path(), &[]);
+  }
+
+  #[test]
+  fn let_used_before_declaration() {
+    let scope = test_scope("a; let a = 1;");
+    let refs = reference_ids(&scope, "a");
+    assert_eq!(refs, vec![id(&scope, "a")]);
+    let var = var(&scope, "a");
+    assert_eq!(var.kind(), BindingKind::Let);
+    assert_eq!(var.span().lo.0, 7);
+  }
+
+  #[test]
+  fn hoisting() {
+    let scope = test_scope("foo(); b = 1; function foo() {} var b;");
+    assert_eq!(reference_ids(&scope, "foo"), vec![id(&scope, "foo")]);
+    let b = id(&scope, "b");
+    let writes: Vec<_> = scope.references_to(&b).map(|r| r.is_write).collect();
+    assert_eq!(writes, vec![true]);
+  }
+
+  #[test]
+  fn catch_param() {
+    let scope = test_scope("try {} catch ({ message }) { message; }");
+    let message = id(&scope, "message");
+    assert_eq!(var(&scope, "message").kind(), BindingKind::CatchClause);
+    assert_eq!(scope.references_to(&message).count(), 1);
+    assert_eq!(scope.unresolved_references().count(), 0);
+  }
+
+  #[test]
+  fn nested_arrow_captures() {
+    let scope = test_scope(
+      r#"
+let count = 0;
+const inc = (step) => () => () => { count += step; };
+"#,
+    );
+    assert_eq!(var(&scope, "step").kind(), BindingKind::Param);
+    assert_eq!(var(&scope, "step").path(), &[ScopeKind::Arrow]);
+    let count = id(&scope, "count");
+    let writes: Vec<_> =
+      scope.references_to(&count).map(|r| r.is_write).collect();
+    assert_eq!(writes, vec![true]);
+    assert_eq!(scope.references_to(&id(&scope, "step")).count(), 1);
+  }
+
+  #[test]
+  fn destructuring_and_default_params() {
+    let scope = test_scope(
+      r#"
+function foo({ a, b: [c = d] }, e = a, ...rest) {}
+const { f = c, ...g } = obj;
+"#,
+    );
+    for name in &["a", "c", "e", "rest"] {
+      assert_eq!(var(&scope, name).kind(), BindingKind::Param);
+    }
+    assert_eq!(var(&scope, "f").kind(), BindingKind::Const);
+    assert_eq!(var(&scope, "g").kind(), BindingKind::Const);
+    assert_eq!(reference_ids(&scope, "a"), vec![id(&scope, "a")]);
+    let unresolved: Vec<_> = scope
+      .unresolved_references()
+      .map(|r| r.id.0.to_string())
+      .collect();
+    assert_eq!(unresolved, vec!["d", "obj", "c"]);
+  }
+
+  #[test]
+  fn global_references() {
+    let scope = test_scope(
+      r#"
+console.log(a);
+function f(console) { console.log(b); }
+"#,
+    );
+    let globals: Vec<_> = scope
+      .global_references()
+      .map(|r| r.id.0.to_string())
+      .collect();
+    assert_eq!(globals, vec!["console"]);
+    let unresolved: Vec<_> = scope
+      .unresolved_references()
+      .map(|r| r.id.0.to_string())
+      .collect();
+    assert_eq!(unresolved, vec!["a", "b"]);
+    assert!(scope.is_global(&"console".into()));
+  }
+
+  #[test]
+  fn member_and_property_names_are_not_references() {
+    let scope = test_scope(
+      r#"
+const a = { b: 1, c };
+a.b;
+a[c];
+class Foo { b = 1; [c] = 2; }
+"#,
+    );
+    let names: Vec<_> = scope
+      .references()
+      .iter()
+      .map(|r| r.id.0.to_string())
+      .collect();
+    assert_eq!(names, vec!["c", "a", "a", "c", "c"]);
+  }
 }
@@ -1,7 +1,9 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use crate::diagnostic::{Position, Range};
 use crate::scopes::Scope;
 use std::error::Error;
 use std::fmt;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::RwLock;
 use swc_common::comments::SingleThreadedComments;
@@ -17,8 +19,8 @@ use swc_common::Span;
 use swc_common::DUMMY_SP;
 use swc_common::{Mark, GLOBALS};
 use swc_ecmascript::ast::{
-  ComputedPropName, Expr, ExprOrSpread, Ident, Lit, MemberExpr, PatOrExpr,
-  Prop, PropName, PropOrSpread, Str, Tpl,
+  ComputedPropName, Expr, ExprOrSpread, Ident, Lit, MemberExpr, Module,
+  ModuleItem, PatOrExpr, Prop, PropName, PropOrSpread, Str, Tpl,
 };
 use swc_ecmascript::parser::lexer::Lexer;
 use swc_ecmascript::parser::EsConfig;
@@ -34,7 +36,6 @@ use swc_ecmascript::{
   visit::FoldWith,
 };
 
-#[allow(unused)]
 pub fn get_default_es_config() -> Syntax {
   let mut config = EsConfig::default();
   config.num_sep = true;
@@ -58,9 +59,86 @@ pub fn get_default_ts_config() -> Syntax {
   Syntax::Typescript(ts_config)
 }
 
+/// Whether a file is parsed as an ES module or as a script.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ModuleKind {
+  Module,
+  Script,
+}
+
+/// Options of the parser used by the linter.
+#[derive(Clone, Copy, Debug)]
+pub struct SyntaxConfig {
+  /// Defaults to `ModuleKind::Module`. Scripts are not strict mode code,
+  /// so they may e.g. contain `with` statements, and can't contain imports
+  /// or exports.
+  pub kind: ModuleKind,
+  pub target: JscTarget,
+  /// Language and syntax proposals to parse, e.g. decorators through
+  /// `EsConfig::decorators`.
+  pub syntax: Syntax,
+}
+
+impl Default for SyntaxConfig {
+  fn default() -> Self {
+    Self {
+      kind: ModuleKind::Module,
+      target: JscTarget::Es2019,
+      syntax: get_default_ts_config(),
+    }
+  }
+}
+
+/// Picks the syntax to parse a file with based on its extension.
+/// TypeScript is assumed for unknown extensions.
+pub fn get_syntax_for_file_name(file_name: &str) -> Syntax {
+  adapt_syntax_to_file_name(get_default_ts_config(), file_name)
+}
+
+/// Adapts `syntax` to the extension of `file_name`, toggling JSX. Its
+/// options are kept if it's already the language of the extension,
+/// otherwise the default syntax of that language is used. Unknown
+/// extensions keep `syntax` as is.
+pub fn adapt_syntax_to_file_name(syntax: Syntax, file_name: &str) -> Syntax {
+  let extension = Path::new(file_name)
+    .extension()
+    .and_then(|e| e.to_str())
+    .unwrap_or("");
+  let (is_ts, is_jsx) = match extension {
+    "js" | "mjs" | "cjs" => (false, false),
+    "jsx" => (false, true),
+    "ts" => (true, false),
+    "tsx" => (true, true),
+    _ => return syntax,
+  };
+  let syntax = match (syntax, is_ts) {
+    (Syntax::Es(_), true) => get_default_ts_config(),
+    (Syntax::Typescript(_), false) => get_default_es_config(),
+    (syntax, _) => syntax,
+  };
+  match syntax {
+    Syntax::Es(mut config) => {
+      config.jsx = is_jsx;
+      Syntax::Es(config)
+    }
+    Syntax::Typescript(mut config) => {
+      config.tsx = is_jsx;
+      Syntax::Typescript(config)
+    }
+  }
+}
+
+/// Syntax error reported by the parser.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseDiagnostic {
+  pub message: String,
+  pub range: Range,
+}
+
 #[derive(Clone, Debug)]
 pub struct SwcDiagnosticBuffer {
   pub diagnostics: Vec<String>,
+  pub parse_diagnostics: Vec<ParseDiagnostic>,
 }
 
 impl Error for SwcDiagnosticBuffer {}
@@ -80,6 +158,36 @@ impl SwcDiagnosticBuffer {
   ) -> Self {
     let s = error_buffer.0.read().unwrap().clone();
 
+    let parse_diagnostics = s
+      .iter()
+      .map(|d| {
+        let range = match d.span.primary_span() {
+          Some(span) => Range {
+            start: Position::new(&parser.source_map, span.lo()),
+            end: Position::new(&parser.source_map, span.hi()),
+          },
+          None => Range {
+            start: Position {
+              line: 1,
+              col: 0,
+              col_utf16: 0,
+              byte_pos: 0,
+            },
+            end: Position {
+              line: 1,
+              col: 0,
+              col_utf16: 0,
+              byte_pos: 0,
+            },
+          },
+        };
+        ParseDiagnostic {
+          message: d.message(),
+          range,
+        }
+      })
+      .collect();
+
     let diagnostics = s
       .iter()
       .map(|d| {
@@ -101,7 +209,10 @@ impl SwcDiagnosticBuffer {
       })
       .collect::<Vec<String>>();
 
-    Self { diagnostics }
+    Self {
+      diagnostics,
+      parse_diagnostics,
+    }
   }
 }
 
@@ -161,6 +272,8 @@ impl AstParser {
     }
   }
 
+  /// Parses a module with the default target.
+  #[cfg(test)]
   pub(crate) fn parse_module(
     &self,
     file_name: &str,
@@ -169,6 +282,24 @@ impl AstParser {
   ) -> (
     Result<swc_ecmascript::ast::Module, SwcDiagnosticBuffer>,
     SingleThreadedComments,
+  ) {
+    let config = SyntaxConfig {
+      syntax,
+      ..SyntaxConfig::default()
+    };
+    self.parse(file_name, config, source_code)
+  }
+
+  /// Parses the file according to `config`. Scripts are returned as modules
+  /// containing only statements, so that rules handle both alike.
+  pub(crate) fn parse(
+    &self,
+    file_name: &str,
+    config: SyntaxConfig,
+    source_code: &str,
+  ) -> (
+    Result<swc_ecmascript::ast::Module, SwcDiagnosticBuffer>,
+    SingleThreadedComments,
   ) {
     let swc_source_file = self.source_map.new_source_file(
       FileName::Custom(file_name.to_string()),
@@ -179,15 +310,29 @@ impl AstParser {
 
     let comments = SingleThreadedComments::default();
     let lexer = Lexer::new(
-      syntax,
-      JscTarget::Es2019,
+      config.syntax,
+      config.target,
       StringInput::from(&*swc_source_file),
       Some(&comments),
     );
 
     let mut parser = Parser::new_from(lexer);
 
-    let parse_result = parser.parse_module().map_err(move |err| {
+    let parse_result = match config.kind {
+      ModuleKind::Module => parser.parse_module(),
+      ModuleKind::Script => parser.parse_script().map(|script| Module {
+        span: script.span,
+        body: script.body.into_iter().map(ModuleItem::Stmt).collect(),
+        shebang: script.shebang,
+      }),
+    };
+    // Errors the parser recovered from are kept in the buffer, next to the
+    // fatal one if any.
+    for err in parser.take_errors() {
+      err.into_diagnostic(&self.handler).emit();
+    }
+
+    let parse_result = parse_result.map_err(move |err| {
       let mut diagnostic_builder = err.into_diagnostic(&self.handler);
       diagnostic_builder.emit();
       SwcDiagnosticBuffer::from_swc_error(buffered_err, self)
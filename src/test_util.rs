@@ -1,12 +1,47 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 
-use crate::diagnostic::LintDiagnostic;
+use crate::diagnostic::{LintDiagnostic, Severity};
 use crate::linter::LinterBuilder;
 use crate::rules::LintRule;
 use crate::swc_util;
 use swc_ecmascript::ast::Module;
 
-fn lint(rule: Box<dyn LintRule>, source: &str) -> Vec<LintDiagnostic> {
+/// Asserts that none of the sources produce diagnostics of the rule.
+///
+/// ```ignore
+/// assert_lint_ok!(NoDebugger, "foo();", "bar();");
+/// ```
+macro_rules! assert_lint_ok {
+  ($rule:ty, $($source:expr),+ $(,)?) => {
+    $(
+      $crate::test_util::assert_lint_ok::<$rule>($source);
+    )+
+  };
+}
+
+/// Asserts that the source produces diagnostics of the rule at the given
+/// positions. Lines are 1-based and columns are 0-based.
+///
+/// ```ignore
+/// assert_lint_err!(NoDebugger, "debugger;", 1, 0);
+/// assert_lint_err!(NoDebugger, "debugger;\ndebugger;", [(1, 0), (2, 0)]);
+/// ```
+macro_rules! assert_lint_err {
+  ($rule:ty, $source:expr, [$(($line:expr, $col:expr)),* $(,)?] $(,)?) => {
+    $crate::test_util::assert_lint_err_on_line_n::<$rule>(
+      $source,
+      vec![$(($line, $col)),*],
+    )
+  };
+  ($rule:ty, $source:expr, $line:expr, $col:expr $(,)?) => {
+    $crate::test_util::assert_lint_err_on_line::<$rule>($source, $line, $col)
+  };
+}
+
+pub fn lint_with_rule(
+  rule: Box<dyn LintRule>,
+  source: &str,
+) -> Vec<LintDiagnostic> {
   let mut linter = LinterBuilder::default()
     .lint_unused_ignore_directives(false)
     .lint_unknown_rules(false)
@@ -19,6 +54,28 @@ fn lint(rule: Box<dyn LintRule>, source: &str) -> Vec<LintDiagnostic> {
     .expect("Failed to lint")
 }
 
+/// Like `lint_with_rule`, but parses the source as a non-strict script, for
+/// code that's invalid in modules like `with` statements or legacy octals.
+pub fn lint_script_with_rule(
+  rule: Box<dyn LintRule>,
+  source: &str,
+) -> Vec<LintDiagnostic> {
+  let mut linter = LinterBuilder::default()
+    .lint_unused_ignore_directives(false)
+    .lint_unknown_rules(false)
+    .syntax_config(swc_util::SyntaxConfig {
+      kind: swc_util::ModuleKind::Script,
+      syntax: swc_util::get_default_es_config(),
+      ..swc_util::SyntaxConfig::default()
+    })
+    .rules(vec![rule])
+    .build();
+
+  linter
+    .lint("deno_lint_test.js".to_string(), source.to_string())
+    .expect("Failed to lint")
+}
+
 pub fn assert_diagnostic(
   diagnostic: &LintDiagnostic,
   code: &str,
@@ -46,7 +103,7 @@ pub fn assert_diagnostic(
 
 pub fn assert_lint_ok<T: LintRule + 'static>(source: &str) {
   let rule = T::new();
-  let diagnostics = lint(rule, source);
+  let diagnostics = lint_with_rule(rule, source);
   if !diagnostics.is_empty() {
     panic!(
       "Unexpected diagnostics found:\n{:#?}\n\nsource:\n{}\n",
@@ -61,6 +118,42 @@ pub fn assert_lint_ok_n<T: LintRule + 'static>(cases: Vec<&str>) {
   }
 }
 
+pub fn assert_lint_ok_with_config<T: LintRule + 'static>(
+  source: &str,
+  config: serde_json::Value,
+) {
+  let rule = T::new()
+    .new_with_config(config)
+    .expect("Invalid rule config");
+  let diagnostics = lint_with_rule(rule, source);
+  if !diagnostics.is_empty() {
+    panic!(
+      "Unexpected diagnostics found:\n{:#?}\n\nsource:\n{}\n",
+      diagnostics, source
+    );
+  }
+}
+
+pub fn assert_lint_err_with_config<T: LintRule + 'static>(
+  source: &str,
+  config: serde_json::Value,
+  col: usize,
+) {
+  let rule = T::new()
+    .new_with_config(config)
+    .expect("Invalid rule config");
+  let rule_code = rule.code();
+  let diagnostics = lint_with_rule(rule, source);
+  assert_eq!(
+    diagnostics.len(),
+    1,
+    "1 diagnostic expected, but got {}.\n\nsource:\n{}\n",
+    diagnostics.len(),
+    source
+  );
+  assert_diagnostic(&diagnostics[0], rule_code, 1, col, source);
+}
+
 pub fn assert_lint_err<T: LintRule + 'static>(source: &str, col: usize) {
   assert_lint_err_on_line::<T>(source, 1, col)
 }
@@ -72,7 +165,7 @@ pub fn assert_lint_err_on_line<T: LintRule + 'static>(
 ) {
   let rule = T::new();
   let rule_code = rule.code();
-  let diagnostics = lint(rule, source);
+  let diagnostics = lint_with_rule(rule, source);
   assert_eq!(
     diagnostics.len(),
     1,
@@ -83,6 +176,82 @@ pub fn assert_lint_err_on_line<T: LintRule + 'static>(
   assert_diagnostic(&diagnostics[0], rule_code, line, col, source);
 }
 
+pub fn assert_lint_err_on_range<T: LintRule + 'static>(
+  source: &str,
+  start: (usize, usize),
+  end: (usize, usize),
+) {
+  let rule = T::new();
+  let rule_code = rule.code();
+  let diagnostics = lint_with_rule(rule, source);
+  assert_eq!(
+    diagnostics.len(),
+    1,
+    "1 diagnostic expected, but got {}.\n\nsource:\n{}\n",
+    diagnostics.len(),
+    source
+  );
+  let diagnostic = &diagnostics[0];
+  assert_diagnostic(diagnostic, rule_code, start.0, start.1, source);
+  let actual_end = (diagnostic.range.end.line, diagnostic.range.end.col);
+  assert_eq!(
+    actual_end, end,
+    "expect diagnostic {} to end at {:?}\n\nsource:\n{}\n",
+    rule_code, end, source
+  );
+}
+
+/// Lints `source` expecting exactly one diagnostic at `line`:`col`.
+fn lint_single<T: LintRule + 'static>(
+  source: &str,
+  line: usize,
+  col: usize,
+) -> LintDiagnostic {
+  let rule = T::new();
+  let rule_code = rule.code();
+  let mut diagnostics = lint_with_rule(rule, source);
+  assert_eq!(
+    diagnostics.len(),
+    1,
+    "1 diagnostic expected, but got {}.\n\nsource:\n{}\n",
+    diagnostics.len(),
+    source
+  );
+  assert_diagnostic(&diagnostics[0], rule_code, line, col, source);
+  diagnostics.remove(0)
+}
+
+pub fn assert_lint_err_with_severity<T: LintRule + 'static>(
+  source: &str,
+  line: usize,
+  col: usize,
+  severity: Severity,
+) {
+  let diagnostic = lint_single::<T>(source, line, col);
+  assert_eq!(
+    diagnostic.severity, severity,
+    "expect diagnostic {} to have severity {:?}\n\nsource:\n{}\n",
+    diagnostic.code, severity, source
+  );
+}
+
+pub fn assert_lint_err_with_hint<T: LintRule + 'static>(
+  source: &str,
+  line: usize,
+  col: usize,
+  hint: &str,
+) {
+  let diagnostic = lint_single::<T>(source, line, col);
+  assert_eq!(
+    diagnostic.hint.as_deref(),
+    Some(hint),
+    "expect diagnostic {} to have hint {:?}\n\nsource:\n{}\n",
+    diagnostic.code,
+    hint,
+    source
+  );
+}
+
 pub fn assert_lint_err_n<T: LintRule + 'static>(
   source: &str,
   expected: Vec<usize>,
@@ -100,7 +269,7 @@ pub fn assert_lint_err_on_line_n<T: LintRule + 'static>(
 ) {
   let rule = T::new();
   let rule_code = rule.code();
-  let diagnostics = lint(rule, source);
+  let diagnostics = lint_with_rule(rule, source);
   assert_eq!(
     diagnostics.len(),
     expected.len(),
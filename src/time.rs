@@ -0,0 +1,47 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+//! `Instant` used for timings and timeouts. `std::time::Instant` panics on
+//! `wasm32-unknown-unknown`, so with the `wasm` feature the clock of the
+//! JavaScript host is used there instead.
+
+#[cfg(not(all(target_arch = "wasm32", feature = "wasm")))]
+pub use std::time::Instant;
+
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+pub use self::js::Instant;
+
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+mod js {
+  use std::ops::{Add, Sub};
+  use std::time::Duration;
+
+  /// Milliseconds since the Unix epoch, as returned by `Date.now()`.
+  #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+  pub struct Instant(f64);
+
+  impl Instant {
+    pub fn now() -> Self {
+      Instant(js_sys::Date::now())
+    }
+
+    pub fn elapsed(&self) -> Duration {
+      Self::now() - *self
+    }
+  }
+
+  impl Add<Duration> for Instant {
+    type Output = Instant;
+
+    fn add(self, duration: Duration) -> Instant {
+      Instant(self.0 + duration.as_secs_f64() * 1000.0)
+    }
+  }
+
+  impl Sub for Instant {
+    type Output = Duration;
+
+    /// Saturates at zero as `Date.now()` isn't monotonic.
+    fn sub(self, earlier: Instant) -> Duration {
+      Duration::from_secs_f64((self.0 - earlier.0).max(0.0) / 1000.0)
+    }
+  }
+}
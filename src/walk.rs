@@ -0,0 +1,223 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Extensions of the files discovered by `Linter::lint_paths`.
+const EXTENSIONS: &[&str] = &["js", "jsx", "mjs", "ts", "tsx"];
+
+/// Directories skipped unless `WalkOptions::skip_default_dirs` is unset,
+/// in addition to hidden ones.
+const DEFAULT_SKIPPED_DIRS: &[&str] = &["node_modules"];
+
+/// Options of `Linter::lint_paths`.
+///
+/// Patterns are matched against paths relative to the root they were found
+/// in, using `/` as separator. `*` and `?` don't match `/`, while `**`
+/// matches any number of directories. Patterns without a `/` are matched
+/// against file and directory names only.
+#[derive(Clone, Debug)]
+pub struct WalkOptions {
+  /// If not empty, only files matching one of these patterns are linted.
+  pub include: Vec<String>,
+  /// Files and directories matching one of these patterns are skipped.
+  pub exclude: Vec<String>,
+  /// Skip `node_modules` and hidden directories.
+  pub skip_default_dirs: bool,
+}
+
+impl Default for WalkOptions {
+  fn default() -> Self {
+    Self {
+      include: vec![],
+      exclude: vec![],
+      skip_default_dirs: true,
+    }
+  }
+}
+
+/// Files found under `roots`, sorted by path, together with the paths
+/// that couldn't be read. Roots that are files are always included.
+pub(crate) fn collect_files(
+  roots: &[PathBuf],
+  options: &WalkOptions,
+) -> (Vec<PathBuf>, Vec<(PathBuf, io::Error)>) {
+  let mut walker = Walker {
+    options,
+    visited: HashSet::new(),
+    files: vec![],
+    errors: vec![],
+  };
+  for root in roots {
+    match fs::metadata(root) {
+      Ok(metadata) if metadata.is_dir() => walker.walk_dir(root, root),
+      Ok(_) => walker.files.push(root.clone()),
+      Err(err) => walker.errors.push((root.clone(), err)),
+    }
+  }
+
+  let mut files = walker.files;
+  files.sort();
+  files.dedup();
+  (files, walker.errors)
+}
+
+struct Walker<'a> {
+  options: &'a WalkOptions,
+  /// Canonical paths of the visited directories, so that symlink cycles
+  /// are only walked once.
+  visited: HashSet<PathBuf>,
+  files: Vec<PathBuf>,
+  errors: Vec<(PathBuf, io::Error)>,
+}
+
+impl<'a> Walker<'a> {
+  fn walk_dir(&mut self, root: &Path, dir: &Path) {
+    match fs::canonicalize(dir) {
+      Ok(canonical) => {
+        if !self.visited.insert(canonical) {
+          return;
+        }
+      }
+      Err(err) => return self.errors.push((dir.to_path_buf(), err)),
+    }
+    let entries = match fs::read_dir(dir) {
+      Ok(entries) => entries,
+      Err(err) => return self.errors.push((dir.to_path_buf(), err)),
+    };
+
+    for entry in entries {
+      let path = match entry {
+        Ok(entry) => entry.path(),
+        Err(err) => {
+          self.errors.push((dir.to_path_buf(), err));
+          continue;
+        }
+      };
+      let relative = relative_path(root, &path);
+      if self.matches_any(&self.options.exclude, &relative) {
+        continue;
+      }
+      // Follows symlinks, which is why cycles are checked above.
+      let metadata = match fs::metadata(&path) {
+        Ok(metadata) => metadata,
+        Err(err) => {
+          self.errors.push((path, err));
+          continue;
+        }
+      };
+
+      if metadata.is_dir() {
+        if !(self.options.skip_default_dirs && is_skipped_dir(&path)) {
+          self.walk_dir(root, &path);
+        }
+      } else if has_lintable_extension(&path)
+        && (self.options.include.is_empty()
+          || self.matches_any(&self.options.include, &relative))
+      {
+        self.files.push(path);
+      }
+    }
+  }
+
+  fn matches_any(&self, patterns: &[String], relative: &str) -> bool {
+    let name = relative.rsplit('/').next().unwrap_or(relative);
+    patterns.iter().any(|pattern| {
+      let path = if pattern.contains('/') {
+        relative
+      } else {
+        name
+      };
+      let pattern = pattern.trim_start_matches("./");
+      glob_match(
+        &pattern.chars().collect::<Vec<_>>(),
+        &path.chars().collect::<Vec<_>>(),
+      )
+    })
+  }
+}
+
+fn relative_path(root: &Path, path: &Path) -> String {
+  let relative = path.strip_prefix(root).unwrap_or(path);
+  relative
+    .components()
+    .map(|c| c.as_os_str().to_string_lossy())
+    .collect::<Vec<_>>()
+    .join("/")
+}
+
+fn is_skipped_dir(path: &Path) -> bool {
+  match path.file_name().and_then(|name| name.to_str()) {
+    Some(name) => name.starts_with('.') || DEFAULT_SKIPPED_DIRS.contains(&name),
+    None => false,
+  }
+}
+
+fn has_lintable_extension(path: &Path) -> bool {
+  match path.extension().and_then(|e| e.to_str()) {
+    Some(extension) => EXTENSIONS.contains(&extension),
+    None => false,
+  }
+}
+
+fn glob_match(pattern: &[char], path: &[char]) -> bool {
+  match pattern.first() {
+    None => path.is_empty(),
+    Some('*') if pattern.get(1) == Some(&'*') => {
+      let rest = &pattern[2..];
+      if rest.is_empty() {
+        return true;
+      }
+      let rest = if rest[0] == '/' { &rest[1..] } else { rest };
+      (0..=path.len()).any(|i| {
+        let at_segment_start = i == 0 || path[i - 1] == '/';
+        at_segment_start && glob_match(rest, &path[i..])
+      })
+    }
+    Some('*') => {
+      for i in 0..=path.len() {
+        if glob_match(&pattern[1..], &path[i..]) {
+          return true;
+        }
+        if i < path.len() && path[i] == '/' {
+          break;
+        }
+      }
+      false
+    }
+    Some('?') => {
+      !path.is_empty()
+        && path[0] != '/'
+        && glob_match(&pattern[1..], &path[1..])
+    }
+    Some(c) => path.first() == Some(c) && glob_match(&pattern[1..], &path[1..]),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn matches(pattern: &str, path: &str) -> bool {
+    glob_match(
+      &pattern.chars().collect::<Vec<_>>(),
+      &path.chars().collect::<Vec<_>>(),
+    )
+  }
+
+  #[test]
+  fn glob_patterns() {
+    assert!(matches("*.ts", "a.ts"));
+    assert!(!matches("*.ts", "a/b.ts"));
+    assert!(!matches("*.ts", "a.tsx"));
+    assert!(matches("a?.js", "ab.js"));
+    assert!(!matches("a?.js", "a/.js"));
+    assert!(matches("src/**/*.ts", "src/a.ts"));
+    assert!(matches("src/**/*.ts", "src/a/b/c.ts"));
+    assert!(!matches("src/**/*.ts", "lib/a.ts"));
+    assert!(matches("**/test/**", "a/test/b.js"));
+    assert!(matches("**", "a/b/c"));
+    assert!(!matches("**/x.js", "ax.js"));
+  }
+}
@@ -0,0 +1,55 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use deno_lint::diagnostic::LintDiagnostic;
+use deno_lint::fmt::{DiagnosticFormatter, FormatterRegistry, SourceProvider};
+use deno_lint::linter::{Linter, LinterBuilder};
+use deno_lint::rules::get_recommended_rules;
+
+/// Prints the code and the offending source line of each diagnostic.
+struct LineFormatter;
+
+impl DiagnosticFormatter for LineFormatter {
+  fn format_diagnostics(
+    &self,
+    diagnostics: &[LintDiagnostic],
+    sources: &dyn SourceProvider,
+  ) -> String {
+    diagnostics
+      .iter()
+      .map(|d| {
+        let line = sources
+          .source(&d.filename)
+          .and_then(|source| source.lines().nth(d.range.start.line - 1))
+          .unwrap_or("");
+        format!("{} {}: {}", d.filename, d.code, line.trim())
+      })
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
+}
+
+#[test]
+fn select_custom_formatter_by_name() {
+  let mut registry = FormatterRegistry::with_builtin_formatters();
+  registry.register("line", Box::new(LineFormatter)).unwrap();
+  assert!(registry.register("line", Box::new(LineFormatter)).is_err());
+
+  let inputs = vec![
+    (
+      "a.ts".to_string(),
+      "const a = 1;\n  debugger;\n".to_string(),
+    ),
+    ("b.ts".to_string(), "const = ;".to_string()),
+    ("c.ts".to_string(), "const a = 1;\nif (a) {}\n".to_string()),
+  ];
+  let results = Linter::lint_files(inputs.clone(), || {
+    LinterBuilder::default()
+      .rules(get_recommended_rules())
+      .build()
+  });
+
+  let formatter = registry.get("line").unwrap();
+  assert_eq!(
+    formatter.format(&results, &inputs),
+    "a.ts no-debugger: debugger;\nc.ts no-empty: if (a) {}"
+  );
+}
@@ -0,0 +1,134 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use deno_lint::diagnostic::{LintDiagnostic, Position};
+use deno_lint::linter::{Context, LinterBuilder};
+use deno_lint::rules::{LintRule, RuleRegistry};
+use deno_lint::swc_ecmascript::ast::{Ident, Module};
+use deno_lint::swc_ecmascript::visit::{noop_visit_type, Node, Visit};
+use std::sync::Arc;
+
+/// Reports identifiers named `foo`.
+struct NoFoo;
+
+impl LintRule for NoFoo {
+  fn new() -> Box<Self> {
+    Box::new(NoFoo)
+  }
+
+  fn code(&self) -> &'static str {
+    "no-foo"
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoFooVisitor { context };
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoFooVisitor {
+  context: Arc<Context>,
+}
+
+impl Visit for NoFooVisitor {
+  noop_visit_type!();
+
+  fn visit_ident(&mut self, ident: &Ident, _parent: &dyn Node) {
+    if &*ident.sym == "foo" {
+      self
+        .context
+        .add_diagnostic(ident.span, "no-foo", "`foo` is not allowed");
+    }
+  }
+}
+
+fn lint(source: &str) -> Vec<LintDiagnostic> {
+  let mut registry = RuleRegistry::new();
+  registry
+    .register(deno_lint::rules::no_debugger::NoDebugger::new())
+    .unwrap();
+  registry.register(NoFoo::new()).unwrap();
+  LinterBuilder::default()
+    .rules(registry.into_rules())
+    .build()
+    .lint("custom.ts".to_string(), source.to_string())
+    .unwrap()
+}
+
+fn codes_and_lines(diagnostics: &[LintDiagnostic]) -> Vec<(&str, usize)> {
+  diagnostics
+    .iter()
+    .map(|d| (d.code.as_str(), d.range.start.line))
+    .collect()
+}
+
+#[test]
+fn diagnostics_are_ordered_with_builtin_rules() {
+  let diagnostics = lint("debugger;\nfoo();\ndebugger; foo;\n");
+  assert_eq!(
+    codes_and_lines(&diagnostics),
+    vec![
+      ("no-debugger", 1),
+      ("no-foo", 2),
+      ("no-debugger", 3),
+      ("no-foo", 3),
+    ]
+  );
+  assert_eq!(
+    diagnostics[3].range.start,
+    Position {
+      line: 3,
+      col: 10,
+      col_utf16: 10,
+      byte_pos: 27
+    }
+  );
+  assert_eq!(diagnostics[3].message, "`foo` is not allowed");
+}
+
+#[test]
+fn ignore_directives_apply_to_custom_rules() {
+  let diagnostics = lint(
+    r#"// deno-lint-ignore no-foo
+foo();
+// deno-lint-ignore no-foo
+bar();
+// deno-lint-ignore no-bar
+foo();
+"#,
+  );
+  assert_eq!(
+    codes_and_lines(&diagnostics),
+    vec![
+      ("ban-unused-ignore", 3),
+      ("ban-unknown-rule-code", 5),
+      ("no-foo", 6),
+    ]
+  );
+}
+
+#[test]
+fn add_rule_rejects_duplicate_codes() {
+  let mut linter = LinterBuilder::default().rules(vec![NoFoo::new()]).build();
+  let err = linter.add_rule(NoFoo::new()).unwrap_err();
+  assert_eq!(err.code, "no-foo");
+  linter
+    .add_rule(deno_lint::rules::no_debugger::NoDebugger::new())
+    .unwrap();
+
+  let diagnostics = linter
+    .lint("custom.ts".to_string(), "foo;\ndebugger;\n".to_string())
+    .unwrap();
+  assert_eq!(
+    codes_and_lines(&diagnostics),
+    vec![("no-foo", 1), ("no-debugger", 2)]
+  );
+}
+
+#[test]
+fn json_output() {
+  let json = serde_json::to_value(lint("foo;\n")).unwrap();
+  assert_eq!(json[0]["code"], "no-foo");
+  assert_eq!(
+    json[0]["range"]["start"],
+    serde_json::json!({ "line": 1, "col": 0, "col_utf16": 0, "byte_pos": 0 })
+  );
+}
@@ -0,0 +1,161 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use deno_lint::linter::{Linter, LinterBuilder, PathDiagnostics};
+use deno_lint::rules::get_recommended_rules;
+use deno_lint::walk::WalkOptions;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Temporary directory removed when dropped.
+struct TempDir(PathBuf);
+
+impl TempDir {
+  fn new(name: &str) -> Self {
+    let path = std::env::temp_dir().join(format!(
+      "deno_lint_{}_{}",
+      name,
+      std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    TempDir(path)
+  }
+
+  fn write(&self, relative: &str, contents: &str) {
+    let path = self.0.join(relative);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+  }
+}
+
+impl Drop for TempDir {
+  fn drop(&mut self) {
+    let _ = fs::remove_dir_all(&self.0);
+  }
+}
+
+fn lint(root: &Path, options: WalkOptions) -> Vec<PathDiagnostics> {
+  Linter::lint_paths(&[root.to_path_buf()], options, || {
+    LinterBuilder::default()
+      .rules(get_recommended_rules())
+      .build()
+  })
+}
+
+fn relative_paths(root: &Path, results: &[PathDiagnostics]) -> Vec<String> {
+  results
+    .iter()
+    .map(|r| {
+      let relative = r.path.strip_prefix(root).unwrap();
+      relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+    })
+    .collect()
+}
+
+fn create_tree(name: &str) -> TempDir {
+  let dir = TempDir::new(name);
+  dir.write("a.ts", "debugger;\n");
+  dir.write("b.jsx", "const a = <div />;\n");
+  dir.write("README.md", "debugger;\n");
+  dir.write("src/c.mjs", "export const c = 1;\n");
+  dir.write("src/d.tsx", "export const d = <div />;\n");
+  dir.write("src/gen/e.generated.ts", "debugger;\n");
+  dir.write("node_modules/pkg/index.js", "debugger;\n");
+  dir.write(".git/hooks/f.js", "debugger;\n");
+  dir
+}
+
+#[test]
+fn default_skip_list() {
+  let dir = create_tree("default_skip_list");
+  let results = lint(&dir.0, WalkOptions::default());
+  assert_eq!(
+    relative_paths(&dir.0, &results),
+    vec![
+      "a.ts",
+      "b.jsx",
+      "src/c.mjs",
+      "src/d.tsx",
+      "src/gen/e.generated.ts"
+    ]
+  );
+  let diagnostics = results[0].diagnostics.as_ref().unwrap();
+  assert_eq!(diagnostics.len(), 1);
+  assert_eq!(diagnostics[0].code, "no-debugger");
+  for result in &results[1..4] {
+    assert_eq!(result.diagnostics.as_ref().unwrap().len(), 0);
+  }
+
+  let options = WalkOptions {
+    skip_default_dirs: false,
+    ..WalkOptions::default()
+  };
+  let results = lint(&dir.0, options);
+  let paths = relative_paths(&dir.0, &results);
+  assert!(paths.contains(&".git/hooks/f.js".to_string()));
+  assert!(paths.contains(&"node_modules/pkg/index.js".to_string()));
+}
+
+#[test]
+fn include_and_exclude_globs() {
+  let dir = create_tree("include_and_exclude_globs");
+  let options = WalkOptions {
+    exclude: vec!["*.generated.ts".to_string(), "b.jsx".to_string()],
+    ..WalkOptions::default()
+  };
+  assert_eq!(
+    relative_paths(&dir.0, &lint(&dir.0, options)),
+    vec!["a.ts", "src/c.mjs", "src/d.tsx"]
+  );
+
+  let options = WalkOptions {
+    exclude: vec!["src/gen".to_string()],
+    include: vec!["src/**/*.ts*".to_string()],
+    ..WalkOptions::default()
+  };
+  assert_eq!(
+    relative_paths(&dir.0, &lint(&dir.0, options)),
+    vec!["src/d.tsx"]
+  );
+}
+
+#[test]
+fn missing_root() {
+  let dir = TempDir::new("missing_root");
+  let results = lint(&dir.0.join("missing"), WalkOptions::default());
+  assert_eq!(results.len(), 1);
+  assert!(results[0].diagnostics.is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn symlink_cycle() {
+  let dir = TempDir::new("symlink_cycle");
+  dir.write("sub/a.ts", "debugger;\n");
+  std::os::unix::fs::symlink(&dir.0, dir.0.join("sub/loop")).unwrap();
+  let results = lint(&dir.0, WalkOptions::default());
+  assert_eq!(relative_paths(&dir.0, &results), vec!["sub/a.ts"]);
+}
+
+#[test]
+fn keeps_configured_syntax() {
+  use deno_lint::swc_ecmascript::parser::{EsConfig, Syntax};
+
+  let dir = TempDir::new("keeps_configured_syntax");
+  dir.write("a.js", "@dec\nclass A {}\n");
+  let results =
+    Linter::lint_paths(&[dir.0.clone()], WalkOptions::default(), || {
+      LinterBuilder::default()
+        .syntax(Syntax::Es(EsConfig {
+          decorators: true,
+          ..EsConfig::default()
+        }))
+        .rules(get_recommended_rules())
+        .build()
+    });
+  assert_eq!(results.len(), 1);
+  assert!(results[0].diagnostics.is_ok());
+}